@@ -0,0 +1,1486 @@
+//! Support for the [declarative configuration] file selected with
+//! `OTEL_CONFIG_FILE`.
+//!
+//! The file is parsed into the model below, which mirrors the subset of the
+//! schema this crate supports, and then turned into the SDK providers. Unknown
+//! or unsupported properties are rejected rather than silently ignored, so a
+//! file written for another SDK fails loudly if it relies on something this
+//! crate can't configure.
+//!
+//! [declarative configuration]: https://opentelemetry.io/docs/specs/otel/configuration/data-model/
+
+// Depending on the enabled signals, parts of the model are never read.
+#![cfg_attr(
+    not(all(feature = "trace", feature = "metrics", feature = "logs")),
+    allow(dead_code)
+)]
+
+use std::collections::HashMap;
+use std::env;
+#[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
+use std::time::Duration;
+
+use opentelemetry::{otel_warn, Array, KeyValue, StringValue, Value};
+#[cfg(feature = "logs")]
+use opentelemetry_sdk::logs::{
+    self, BatchLogProcessor, LogExporter, LogRecordLimits, SdkLoggerProvider,
+};
+#[cfg(feature = "metrics")]
+use opentelemetry_sdk::metrics::{
+    exporter::PushMetricExporter, Aggregation, InstrumentKind, InstrumentSelector, PeriodicReader,
+    SdkMeterProvider, Stream, Temporality, View,
+};
+#[cfg(feature = "trace")]
+use opentelemetry_sdk::trace::{
+    self, BatchSpanProcessor, Sampler, SdkTracerProvider, SpanExporter, SpanLimits,
+};
+use opentelemetry_sdk::{resource::TelemetryResourceDetector, Resource};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Deserializer};
+
+use crate::{AutoConfigureError, Providers};
+
+/// Major versions of the configuration schema this crate understands.
+const SUPPORTED_FILE_FORMATS: [&str; 2] = ["0", "1"];
+
+// Defaults of the declarative configuration schema, which are used instead of
+// the `OTEL_*` environment variables the SDK falls back to otherwise.
+const DEFAULT_BSP_SCHEDULE_DELAY: u64 = 5_000;
+const DEFAULT_BLRP_SCHEDULE_DELAY: u64 = 1_000;
+const DEFAULT_MAX_QUEUE_SIZE: usize = 2_048;
+const DEFAULT_MAX_EXPORT_BATCH_SIZE: usize = 512;
+const DEFAULT_METRIC_EXPORT_INTERVAL: u64 = 60_000;
+const DEFAULT_HISTOGRAM_BOUNDARIES: [f64; 15] = [
+    0.0, 5.0, 10.0, 25.0, 50.0, 75.0, 100.0, 250.0, 500.0, 750.0, 1_000.0, 2_500.0, 5_000.0,
+    7_500.0, 10_000.0,
+];
+const DEFAULT_EXPO_MAX_SIZE: u32 = 160;
+const DEFAULT_EXPO_MAX_SCALE: i8 = 20;
+
+/// The root of a configuration file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Configuration {
+    file_format: String,
+    #[serde(default)]
+    disabled: bool,
+    #[serde(default)]
+    attribute_limits: AttributeLimitsConfig,
+    resource: Option<ResourceConfig>,
+    propagator: Option<PropagatorConfig>,
+    tracer_provider: Option<TracerProviderConfig>,
+    meter_provider: Option<MeterProviderConfig>,
+    logger_provider: Option<LoggerProviderConfig>,
+    // Configures instrumentation libraries, which are not part of the SDK.
+    #[serde(rename = "instrumentation")]
+    _instrumentation: Option<IgnoredAny>,
+    #[serde(rename = "instrumentation/development")]
+    _instrumentation_development: Option<IgnoredAny>,
+}
+
+/// Limits shared by all signals, which the signal specific limits override.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AttributeLimitsConfig {
+    attribute_value_length_limit: Option<u32>,
+    attribute_count_limit: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ResourceConfig {
+    #[serde(default)]
+    attributes: Vec<AttributeConfig>,
+    attributes_list: Option<String>,
+    schema_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AttributeConfig {
+    name: String,
+    value: AttributeValueConfig,
+    #[serde(rename = "type")]
+    value_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AttributeValueConfig {
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+    String(String),
+    BoolArray(Vec<bool>),
+    IntArray(Vec<i64>),
+    DoubleArray(Vec<f64>),
+    StringArray(Vec<String>),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PropagatorConfig {
+    #[serde(default)]
+    composite: Vec<HashMap<String, IgnoredAny>>,
+    composite_list: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TracerProviderConfig {
+    #[serde(default)]
+    processors: Vec<ProcessorConfig>,
+    sampler: Option<SamplerConfig>,
+    limits: Option<SpanLimitsConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LoggerProviderConfig {
+    #[serde(default)]
+    processors: Vec<ProcessorConfig>,
+    limits: Option<LogRecordLimitsConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ProcessorConfig {
+    Batch(BatchProcessorConfig),
+    Simple(SimpleProcessorConfig),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchProcessorConfig {
+    schedule_delay: Option<u64>,
+    export_timeout: Option<u64>,
+    max_queue_size: Option<usize>,
+    max_export_batch_size: Option<usize>,
+    exporter: ExporterConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SimpleProcessorConfig {
+    exporter: ExporterConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ExporterConfig {
+    OtlpHttp(#[serde(deserialize_with = "nullable")] OtlpConfig),
+    OtlpGrpc(#[serde(deserialize_with = "nullable")] OtlpConfig),
+    Console(#[serde(deserialize_with = "nullable")] ConsoleConfig),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OtlpConfig {
+    endpoint: Option<String>,
+    #[serde(default)]
+    headers: Vec<NameValueConfig>,
+    headers_list: Option<String>,
+    compression: Option<String>,
+    timeout: Option<u64>,
+    encoding: Option<String>,
+    temporality_preference: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConsoleConfig {
+    temporality_preference: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NameValueConfig {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SamplerConfig {
+    AlwaysOn(#[serde(deserialize_with = "nullable")] EmptyConfig),
+    AlwaysOff(#[serde(deserialize_with = "nullable")] EmptyConfig),
+    TraceIdRatioBased(#[serde(deserialize_with = "nullable")] RatioConfig),
+    ParentBased(#[serde(deserialize_with = "nullable")] ParentBasedConfig),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EmptyConfig {}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RatioConfig {
+    ratio: Option<f64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ParentBasedConfig {
+    root: Option<Box<SamplerConfig>>,
+    remote_parent_sampled: Option<Box<SamplerConfig>>,
+    remote_parent_not_sampled: Option<Box<SamplerConfig>>,
+    local_parent_sampled: Option<Box<SamplerConfig>>,
+    local_parent_not_sampled: Option<Box<SamplerConfig>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SpanLimitsConfig {
+    attribute_value_length_limit: Option<u32>,
+    attribute_count_limit: Option<u32>,
+    event_count_limit: Option<u32>,
+    link_count_limit: Option<u32>,
+    event_attribute_count_limit: Option<u32>,
+    link_attribute_count_limit: Option<u32>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LogRecordLimitsConfig {
+    attribute_value_length_limit: Option<u32>,
+    attribute_count_limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MeterProviderConfig {
+    #[serde(default)]
+    readers: Vec<ReaderConfig>,
+    #[serde(default)]
+    views: Vec<ViewConfig>,
+    exemplar_filter: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ReaderConfig {
+    Periodic(PeriodicReaderConfig),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PeriodicReaderConfig {
+    interval: Option<u64>,
+    timeout: Option<u64>,
+    exporter: ExporterConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ViewConfig {
+    selector: SelectorConfig,
+    stream: StreamConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SelectorConfig {
+    instrument_name: Option<String>,
+    instrument_type: Option<String>,
+    unit: Option<String>,
+    meter_name: Option<String>,
+    meter_version: Option<String>,
+    meter_schema_url: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StreamConfig {
+    name: Option<String>,
+    description: Option<String>,
+    aggregation: Option<AggregationConfig>,
+    aggregation_cardinality_limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum AggregationConfig {
+    Default(#[serde(deserialize_with = "nullable")] EmptyConfig),
+    Drop(#[serde(deserialize_with = "nullable")] EmptyConfig),
+    Sum(#[serde(deserialize_with = "nullable")] EmptyConfig),
+    LastValue(#[serde(deserialize_with = "nullable")] EmptyConfig),
+    ExplicitBucketHistogram(#[serde(deserialize_with = "nullable")] ExplicitBucketConfig),
+    Base2ExponentialBucketHistogram(
+        #[serde(deserialize_with = "nullable")] ExponentialBucketConfig,
+    ),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExplicitBucketConfig {
+    boundaries: Option<Vec<f64>>,
+    record_min_max: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExponentialBucketConfig {
+    max_size: Option<u32>,
+    max_scale: Option<i8>,
+    record_min_max: Option<bool>,
+}
+
+/// Deserializes a missing (`null`) value as the default, so that properties
+/// like `console:` can be written without a body.
+fn nullable<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+fn invalid(message: impl Into<String>) -> AutoConfigureError {
+    AutoConfigureError::InvalidConfig {
+        message: message.into(),
+    }
+}
+
+/// Parses a configuration file after substituting environment variables.
+pub(crate) fn parse(text: &str) -> Result<Configuration, AutoConfigureError> {
+    // The schema selects exporters, samplers and the like with single key
+    // maps, like `console:`, instead of YAML tags.
+    let config: Configuration = serde_norway::with::singleton_map_recursive::deserialize(
+        serde_norway::Deserializer::from_str(&substitute_env_vars(text)?),
+    )
+    .map_err(|err| invalid(err.to_string()))?;
+
+    let major = config.file_format.split('.').next().unwrap_or_default();
+    if !SUPPORTED_FILE_FORMATS.contains(&major) {
+        return Err(invalid(format!(
+            "unsupported file_format '{}'",
+            config.file_format
+        )));
+    }
+    Ok(config)
+}
+
+/// Replaces `${NAME}`, `${env:NAME}` and `${NAME:-default}` with the value of
+/// the environment variable `NAME`, and `$$` with `$`.
+///
+/// Unset variables without a default are replaced by an empty string.
+fn substitute_env_vars(text: &str) -> Result<String, AutoConfigureError> {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("$$") {
+            result.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("${") {
+            let end = after
+                .find('}')
+                .ok_or_else(|| invalid("unterminated environment variable substitution"))?;
+            let reference = &after[..end];
+            let (name, default) = match reference.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (reference, None),
+            };
+            let name = name.strip_prefix("env:").unwrap_or(name);
+            if !is_env_var_name(name) {
+                return Err(invalid(format!(
+                    "invalid environment variable substitution '${{{reference}}}'"
+                )));
+            }
+            match env::var(name).ok().filter(|value| !value.is_empty()) {
+                Some(value) => result.push_str(&value),
+                None => result.push_str(default.unwrap_or_default()),
+            }
+            rest = &after[end + 1..];
+        } else {
+            result.push('$');
+            rest = &rest[1..];
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn is_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Parses a comma-separated list of `key=value` pairs.
+fn parse_key_value_list(
+    list: &str,
+    property: &str,
+) -> Result<Vec<(String, String)>, AutoConfigureError> {
+    list.split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_owned(), value.trim().to_owned()))
+            }
+            _ => Err(invalid(format!("invalid entry '{pair}' in {property}"))),
+        })
+        .collect()
+}
+
+/// Builds the providers described by the configuration.
+pub(crate) fn build(config: Configuration) -> Result<Providers, AutoConfigureError> {
+    if config.disabled {
+        return Ok(Providers::default());
+    }
+
+    #[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
+    let resource = build_resource(
+        config
+            .resource
+            .as_ref()
+            .unwrap_or(&ResourceConfig::default()),
+    )?;
+
+    Ok(Providers {
+        #[cfg(feature = "trace")]
+        tracer_provider: config
+            .tracer_provider
+            .as_ref()
+            .map(|tracer_provider| {
+                build_tracer_provider(tracer_provider, &config.attribute_limits, resource.clone())
+            })
+            .transpose()?,
+        #[cfg(feature = "trace")]
+        propagators: Some(
+            config
+                .propagator
+                .as_ref()
+                .map(PropagatorConfig::names)
+                .unwrap_or_default(),
+        ),
+        #[cfg(feature = "metrics")]
+        meter_provider: config
+            .meter_provider
+            .as_ref()
+            .map(|meter_provider| build_meter_provider(meter_provider, resource.clone()))
+            .transpose()?,
+        #[cfg(feature = "logs")]
+        logger_provider: config
+            .logger_provider
+            .as_ref()
+            .map(|logger_provider| {
+                build_logger_provider(logger_provider, &config.attribute_limits, resource.clone())
+            })
+            .transpose()?,
+    })
+}
+
+fn build_resource(config: &ResourceConfig) -> Result<Resource, AutoConfigureError> {
+    let mut builder = Resource::builder_empty()
+        .with_service_name("unknown_service")
+        .with_detector(Box::new(TelemetryResourceDetector));
+
+    if let Some(list) = &config.attributes_list {
+        builder = builder.with_attributes(
+            parse_key_value_list(list, "resource.attributes_list")?
+                .into_iter()
+                .map(|(key, value)| KeyValue::new(key, value)),
+        );
+    }
+    builder = builder.with_attributes(
+        config
+            .attributes
+            .iter()
+            .map(AttributeConfig::key_value)
+            .collect::<Result<Vec<_>, _>>()?,
+    );
+    if let Some(schema_url) = &config.schema_url {
+        builder = builder.with_schema_url([], schema_url.clone());
+    }
+    Ok(builder.build())
+}
+
+impl AttributeConfig {
+    fn key_value(&self) -> Result<KeyValue, AutoConfigureError> {
+        use AttributeValueConfig as V;
+
+        let value_type = match &self.value_type {
+            Some(value_type) => value_type.as_str(),
+            None => self.value.type_name(),
+        };
+        let value = match (value_type, &self.value) {
+            ("string", V::String(v)) => Value::from(v.clone()),
+            ("string", V::Bool(v)) => Value::from(v.to_string()),
+            ("string", V::Int(v)) => Value::from(v.to_string()),
+            ("string", V::Double(v)) => Value::from(v.to_string()),
+            ("bool", V::Bool(v)) => Value::Bool(*v),
+            ("int", V::Int(v)) => Value::I64(*v),
+            ("double", V::Double(v)) => Value::F64(*v),
+            ("double", V::Int(v)) => Value::F64(*v as f64),
+            ("bool_array", V::BoolArray(v)) => Value::Array(Array::Bool(v.clone())),
+            ("int_array", V::IntArray(v)) => Value::Array(Array::I64(v.clone())),
+            ("double_array", V::DoubleArray(v)) => Value::Array(Array::F64(v.clone())),
+            ("double_array", V::IntArray(v)) => {
+                Value::Array(Array::F64(v.iter().map(|v| *v as f64).collect()))
+            }
+            ("string_array", V::StringArray(v)) => Value::Array(Array::String(
+                v.iter().cloned().map(StringValue::from).collect(),
+            )),
+            // An empty list is parsed as the first array variant.
+            ("int_array", V::BoolArray(v)) if v.is_empty() => Value::Array(Array::I64(Vec::new())),
+            ("double_array", V::BoolArray(v)) if v.is_empty() => {
+                Value::Array(Array::F64(Vec::new()))
+            }
+            ("string_array", V::BoolArray(v)) if v.is_empty() => {
+                Value::Array(Array::String(Vec::new()))
+            }
+            _ => {
+                return Err(invalid(format!(
+                    "resource attribute '{}' is not a valid {value_type}",
+                    self.name
+                )))
+            }
+        };
+        Ok(KeyValue::new(self.name.clone(), value))
+    }
+}
+
+impl AttributeValueConfig {
+    fn type_name(&self) -> &'static str {
+        match self {
+            AttributeValueConfig::Bool(_) => "bool",
+            AttributeValueConfig::Int(_) => "int",
+            AttributeValueConfig::Double(_) => "double",
+            AttributeValueConfig::String(_) => "string",
+            AttributeValueConfig::BoolArray(_) => "bool_array",
+            AttributeValueConfig::IntArray(_) => "int_array",
+            AttributeValueConfig::DoubleArray(_) => "double_array",
+            AttributeValueConfig::StringArray(_) => "string_array",
+        }
+    }
+}
+
+impl PropagatorConfig {
+    /// The propagator names of `composite` followed by the ones of
+    /// `composite_list`.
+    fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .composite
+            .iter()
+            .flat_map(|propagator| propagator.keys().cloned())
+            .collect();
+        if let Some(list) = &self.composite_list {
+            names.extend(list.split(',').map(|name| name.trim().to_owned()));
+        }
+        names
+    }
+}
+
+impl ProcessorConfig {
+    fn exporter(&self) -> &ExporterConfig {
+        match self {
+            ProcessorConfig::Batch(batch) => &batch.exporter,
+            ProcessorConfig::Simple(simple) => &simple.exporter,
+        }
+    }
+}
+
+impl BatchProcessorConfig {
+    fn warn_unsupported(&self) {
+        if self.export_timeout.is_some() {
+            otel_warn!(
+                name: "AutoConfigure.UnsupportedExportTimeout",
+                message = "export_timeout of batch processors is not supported and is ignored.",
+            );
+        }
+    }
+}
+
+impl ExporterConfig {
+    fn name(&self) -> &'static str {
+        match self {
+            ExporterConfig::OtlpHttp(_) => "otlp_http",
+            ExporterConfig::OtlpGrpc(_) => "otlp_grpc",
+            ExporterConfig::Console(_) => "console",
+        }
+    }
+
+    fn feature_required(&self) -> AutoConfigureError {
+        AutoConfigureError::FeatureRequired {
+            exporter: self.name(),
+            feature: match self {
+                ExporterConfig::OtlpHttp(_) => "otlp-http",
+                ExporterConfig::OtlpGrpc(_) => "otlp-grpc",
+                ExporterConfig::Console(_) => "stdout",
+            },
+        }
+    }
+
+    fn temporality_preference(&self) -> Option<&str> {
+        match self {
+            ExporterConfig::OtlpHttp(otlp) | ExporterConfig::OtlpGrpc(otlp) => {
+                otlp.temporality_preference.as_deref()
+            }
+            ExporterConfig::Console(console) => console.temporality_preference.as_deref(),
+        }
+    }
+
+    /// Rejects metric specific options on span and log exporters.
+    fn check_not_metrics(&self) -> Result<(), AutoConfigureError> {
+        if self.temporality_preference().is_some() {
+            return Err(invalid(format!(
+                "temporality_preference is only supported by metric exporters, not by the {} span or log exporter",
+                self.name()
+            )));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "metrics")]
+    fn temporality(&self) -> Result<Temporality, AutoConfigureError> {
+        match self.temporality_preference() {
+            None | Some("cumulative") => Ok(Temporality::Cumulative),
+            Some("delta") => Ok(Temporality::Delta),
+            Some("low_memory") => Ok(Temporality::LowMemory),
+            Some(other) => Err(invalid(format!(
+                "unsupported temporality_preference '{other}'"
+            ))),
+        }
+    }
+}
+
+impl OtlpConfig {
+    /// The headers of `headers_list`, overridden by the ones of `headers`.
+    fn headers(&self) -> Result<HashMap<String, String>, AutoConfigureError> {
+        let mut headers: HashMap<String, String> = match &self.headers_list {
+            Some(list) => parse_key_value_list(list, "headers_list")?
+                .into_iter()
+                .collect(),
+            None => HashMap::new(),
+        };
+        headers.extend(
+            self.headers
+                .iter()
+                .map(|header| (header.name.clone(), header.value.clone())),
+        );
+        Ok(headers)
+    }
+
+    #[cfg(any(feature = "otlp-http", feature = "otlp-grpc"))]
+    fn compression(&self) -> Result<Option<opentelemetry_otlp::Compression>, AutoConfigureError> {
+        match self.compression.as_deref() {
+            None | Some("none") => Ok(None),
+            Some(compression) => compression
+                .parse()
+                .map(Some)
+                .map_err(crate::otlp_build_error),
+        }
+    }
+}
+
+#[cfg(any(feature = "otlp-http", feature = "otlp-grpc"))]
+fn with_export_config<B>(mut builder: B, config: &OtlpConfig) -> B
+where
+    B: opentelemetry_otlp::WithExportConfig,
+{
+    if let Some(endpoint) = &config.endpoint {
+        builder = builder.with_endpoint(endpoint.clone());
+    }
+    if let Some(timeout) = config.timeout {
+        builder = builder.with_timeout(Duration::from_millis(timeout));
+    }
+    builder
+}
+
+#[cfg(feature = "otlp-http")]
+fn with_otlp_http_config<B>(builder: B, config: &OtlpConfig) -> Result<B, AutoConfigureError>
+where
+    B: opentelemetry_otlp::WithExportConfig + opentelemetry_otlp::WithHttpConfig,
+{
+    match config.encoding.as_deref() {
+        None | Some("protobuf") => {}
+        Some(encoding) => {
+            return Err(invalid(format!(
+                "unsupported otlp_http encoding '{encoding}'"
+            )))
+        }
+    }
+
+    let mut builder =
+        with_export_config(builder, config).with_protocol(opentelemetry_otlp::Protocol::HttpBinary);
+    let headers = config.headers()?;
+    if !headers.is_empty() {
+        builder = builder.with_headers(headers);
+    }
+    if let Some(compression) = config.compression()? {
+        builder = builder.with_compression(compression);
+    }
+    Ok(builder)
+}
+
+#[cfg(feature = "otlp-grpc")]
+fn with_otlp_grpc_config<B>(builder: B, config: &OtlpConfig) -> Result<B, AutoConfigureError>
+where
+    B: opentelemetry_otlp::WithExportConfig + opentelemetry_otlp::WithTonicConfig,
+{
+    use tonic::metadata::{MetadataKey, MetadataMap};
+
+    if config.encoding.is_some() {
+        return Err(invalid(
+            "encoding is only supported by the otlp_http exporter",
+        ));
+    }
+
+    let mut builder = with_export_config(builder, config);
+    let headers = config.headers()?;
+    if !headers.is_empty() {
+        let mut metadata = MetadataMap::with_capacity(headers.len());
+        for (name, value) in headers {
+            let key = MetadataKey::from_bytes(name.as_bytes())
+                .map_err(|_| invalid(format!("invalid otlp_grpc header name '{name}'")))?;
+            let value = value
+                .parse()
+                .map_err(|_| invalid(format!("invalid value for otlp_grpc header '{name}'")))?;
+            metadata.insert(key, value);
+        }
+        builder = builder.with_metadata(metadata);
+    }
+    if let Some(compression) = config.compression()? {
+        builder = builder.with_compression(compression);
+    }
+    Ok(builder)
+}
+
+#[cfg(feature = "trace")]
+fn build_tracer_provider(
+    config: &TracerProviderConfig,
+    attribute_limits: &AttributeLimitsConfig,
+    resource: Resource,
+) -> Result<SdkTracerProvider, AutoConfigureError> {
+    let mut builder = SdkTracerProvider::builder()
+        .with_resource(resource)
+        .with_sampler(match &config.sampler {
+            Some(sampler) => sampler.build()?,
+            None => Sampler::ParentBased(Box::new(Sampler::AlwaysOn)),
+        })
+        .with_span_limits(
+            config
+                .limits
+                .as_ref()
+                .unwrap_or(&SpanLimitsConfig::default())
+                .build(attribute_limits),
+        );
+    for processor in &config.processors {
+        builder = with_span_processor(builder, processor)?;
+    }
+    Ok(builder.build())
+}
+
+#[cfg(feature = "trace")]
+fn with_span_processor(
+    builder: trace::TracerProviderBuilder,
+    processor: &ProcessorConfig,
+) -> Result<trace::TracerProviderBuilder, AutoConfigureError> {
+    fn add<E: SpanExporter + 'static>(
+        builder: trace::TracerProviderBuilder,
+        processor: &ProcessorConfig,
+        exporter: E,
+    ) -> trace::TracerProviderBuilder {
+        match processor {
+            ProcessorConfig::Batch(batch) => builder.with_span_processor(
+                BatchSpanProcessor::builder(exporter)
+                    .with_batch_config(
+                        trace::BatchConfigBuilder::default()
+                            .with_scheduled_delay(Duration::from_millis(
+                                batch.schedule_delay.unwrap_or(DEFAULT_BSP_SCHEDULE_DELAY),
+                            ))
+                            .with_max_queue_size(
+                                batch.max_queue_size.unwrap_or(DEFAULT_MAX_QUEUE_SIZE),
+                            )
+                            .with_max_export_batch_size(
+                                batch
+                                    .max_export_batch_size
+                                    .unwrap_or(DEFAULT_MAX_EXPORT_BATCH_SIZE),
+                            )
+                            .build(),
+                    )
+                    .build(),
+            ),
+            ProcessorConfig::Simple(_) => builder.with_simple_exporter(exporter),
+        }
+    }
+
+    if let ProcessorConfig::Batch(batch) = processor {
+        batch.warn_unsupported();
+    }
+    let exporter = processor.exporter();
+    exporter.check_not_metrics()?;
+    Ok(match exporter {
+        #[cfg(feature = "otlp-http")]
+        ExporterConfig::OtlpHttp(otlp) => add(
+            builder,
+            processor,
+            with_otlp_http_config(
+                opentelemetry_otlp::SpanExporter::builder().with_http(),
+                otlp,
+            )?
+            .build()
+            .map_err(crate::otlp_build_error)?,
+        ),
+        #[cfg(feature = "otlp-grpc")]
+        ExporterConfig::OtlpGrpc(otlp) => add(
+            builder,
+            processor,
+            with_otlp_grpc_config(
+                opentelemetry_otlp::SpanExporter::builder().with_tonic(),
+                otlp,
+            )?
+            .build()
+            .map_err(crate::otlp_build_error)?,
+        ),
+        #[cfg(feature = "stdout")]
+        ExporterConfig::Console(_) => add(
+            builder,
+            processor,
+            opentelemetry_stdout::SpanExporter::default(),
+        ),
+        #[allow(unreachable_patterns)]
+        exporter => return Err(exporter.feature_required()),
+    })
+}
+
+impl SamplerConfig {
+    #[cfg(feature = "trace")]
+    fn build(&self) -> Result<Sampler, AutoConfigureError> {
+        Ok(match self {
+            SamplerConfig::AlwaysOn(_) => Sampler::AlwaysOn,
+            SamplerConfig::AlwaysOff(_) => Sampler::AlwaysOff,
+            SamplerConfig::TraceIdRatioBased(ratio) => {
+                Sampler::TraceIdRatioBased(ratio.ratio.unwrap_or(1.0))
+            }
+            SamplerConfig::ParentBased(parent_based) => {
+                // The SDK always follows the decision of the parent, which
+                // matches the defaults of these properties.
+                let follows_parent = |sampler: &Option<Box<SamplerConfig>>, sampled: bool| {
+                    match sampler.as_deref() {
+                        None => true,
+                        Some(SamplerConfig::AlwaysOn(_)) => sampled,
+                        Some(SamplerConfig::AlwaysOff(_)) => !sampled,
+                        Some(_) => false,
+                    }
+                };
+                if !(follows_parent(&parent_based.remote_parent_sampled, true)
+                    && follows_parent(&parent_based.remote_parent_not_sampled, false)
+                    && follows_parent(&parent_based.local_parent_sampled, true)
+                    && follows_parent(&parent_based.local_parent_not_sampled, false))
+                {
+                    return Err(invalid(
+                        "parent_based samplers only support the default samplers for sampled and not sampled parents",
+                    ));
+                }
+                let root = match &parent_based.root {
+                    Some(root) => root.build()?,
+                    None => Sampler::AlwaysOn,
+                };
+                Sampler::ParentBased(Box::new(root))
+            }
+        })
+    }
+}
+
+impl SpanLimitsConfig {
+    #[cfg(feature = "trace")]
+    fn build(&self, attribute_limits: &AttributeLimitsConfig) -> SpanLimits {
+        let defaults = SpanLimits::default();
+        SpanLimits {
+            max_attributes_per_span: self
+                .attribute_count_limit
+                .or(attribute_limits.attribute_count_limit)
+                .unwrap_or(defaults.max_attributes_per_span),
+            max_events_per_span: self
+                .event_count_limit
+                .unwrap_or(defaults.max_events_per_span),
+            max_links_per_span: self.link_count_limit.unwrap_or(defaults.max_links_per_span),
+            max_attributes_per_event: self
+                .event_attribute_count_limit
+                .unwrap_or(defaults.max_attributes_per_event),
+            max_attributes_per_link: self
+                .link_attribute_count_limit
+                .unwrap_or(defaults.max_attributes_per_link),
+            max_attribute_value_length: self
+                .attribute_value_length_limit
+                .or(attribute_limits.attribute_value_length_limit),
+        }
+    }
+}
+
+impl LogRecordLimitsConfig {
+    #[cfg(feature = "logs")]
+    fn build(&self, attribute_limits: &AttributeLimitsConfig) -> LogRecordLimits {
+        let mut limits = LogRecordLimits::default();
+        if let Some(max_attributes) = self
+            .attribute_count_limit
+            .or(attribute_limits.attribute_count_limit)
+        {
+            limits = limits.with_max_attributes_per_log_record(max_attributes);
+        }
+        if let Some(max_length) = self
+            .attribute_value_length_limit
+            .or(attribute_limits.attribute_value_length_limit)
+        {
+            limits = limits.with_max_attribute_value_length(max_length);
+        }
+        limits
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn build_meter_provider(
+    config: &MeterProviderConfig,
+    resource: Resource,
+) -> Result<SdkMeterProvider, AutoConfigureError> {
+    let mut builder = SdkMeterProvider::builder().with_resource(resource);
+    if let Some(filter) = &config.exemplar_filter {
+        builder = builder.with_exemplar_filter(
+            filter
+                .parse()
+                .map_err(|_| invalid(format!("unsupported exemplar_filter '{filter}'")))?,
+        );
+    }
+    for view in &config.views {
+        builder = builder.with_declarative_view(view.build()?);
+    }
+    for reader in &config.readers {
+        builder = with_reader(builder, reader)?;
+    }
+    Ok(builder.build())
+}
+
+#[cfg(feature = "metrics")]
+fn with_reader(
+    builder: opentelemetry_sdk::metrics::MeterProviderBuilder,
+    reader: &ReaderConfig,
+) -> Result<opentelemetry_sdk::metrics::MeterProviderBuilder, AutoConfigureError> {
+    fn add<E: PushMetricExporter>(
+        builder: opentelemetry_sdk::metrics::MeterProviderBuilder,
+        periodic: &PeriodicReaderConfig,
+        exporter: E,
+    ) -> opentelemetry_sdk::metrics::MeterProviderBuilder {
+        builder.with_reader(
+            PeriodicReader::builder(exporter)
+                .with_interval(Duration::from_millis(
+                    periodic.interval.unwrap_or(DEFAULT_METRIC_EXPORT_INTERVAL),
+                ))
+                .build(),
+        )
+    }
+
+    let ReaderConfig::Periodic(periodic) = reader;
+    if periodic.timeout.is_some() {
+        otel_warn!(
+            name: "AutoConfigure.UnsupportedReaderTimeout",
+            message = "timeout of periodic readers is not supported and is ignored.",
+        );
+    }
+    let exporter = &periodic.exporter;
+    let temporality = exporter.temporality()?;
+    Ok(match exporter {
+        #[cfg(feature = "otlp-http")]
+        ExporterConfig::OtlpHttp(otlp) => add(
+            builder,
+            periodic,
+            with_otlp_http_config(
+                opentelemetry_otlp::MetricExporter::builder()
+                    .with_temporality(temporality)
+                    .with_http(),
+                otlp,
+            )?
+            .build()
+            .map_err(crate::otlp_build_error)?,
+        ),
+        #[cfg(feature = "otlp-grpc")]
+        ExporterConfig::OtlpGrpc(otlp) => add(
+            builder,
+            periodic,
+            with_otlp_grpc_config(
+                opentelemetry_otlp::MetricExporter::builder()
+                    .with_temporality(temporality)
+                    .with_tonic(),
+                otlp,
+            )?
+            .build()
+            .map_err(crate::otlp_build_error)?,
+        ),
+        #[cfg(feature = "stdout")]
+        ExporterConfig::Console(_) => add(
+            builder,
+            periodic,
+            opentelemetry_stdout::MetricExporter::builder()
+                .with_temporality(temporality)
+                .build(),
+        ),
+        #[allow(unreachable_patterns)]
+        exporter => return Err(exporter.feature_required()),
+    })
+}
+
+impl ViewConfig {
+    #[cfg(feature = "metrics")]
+    fn build(&self) -> Result<View, AutoConfigureError> {
+        let selector = &self.selector;
+        let mut instrument_selector = InstrumentSelector::new();
+        if let Some(name) = &selector.instrument_name {
+            instrument_selector = instrument_selector.with_name(name.clone());
+        }
+        if let Some(kind) = &selector.instrument_type {
+            let kind: InstrumentKind = kind
+                .parse()
+                .map_err(|_| invalid(format!("unsupported instrument_type '{kind}'")))?;
+            instrument_selector = instrument_selector.with_kind(kind);
+        }
+        if let Some(unit) = &selector.unit {
+            instrument_selector = instrument_selector.with_unit(unit.clone());
+        }
+        if let Some(name) = &selector.meter_name {
+            instrument_selector = instrument_selector.with_meter_name(name.clone());
+        }
+        if let Some(version) = &selector.meter_version {
+            instrument_selector = instrument_selector.with_meter_version(version.clone());
+        }
+        if let Some(schema_url) = &selector.meter_schema_url {
+            instrument_selector = instrument_selector.with_meter_schema_url(schema_url.clone());
+        }
+
+        let config = &self.stream;
+        let mut stream = Stream::builder();
+        if let Some(name) = &config.name {
+            stream = stream.with_name(name.clone());
+        }
+        if let Some(description) = &config.description {
+            stream = stream.with_description(description.clone());
+        }
+        if let Some(aggregation) = &config.aggregation {
+            stream = stream.with_aggregation(aggregation.build());
+        }
+        if let Some(limit) = config.aggregation_cardinality_limit {
+            stream = stream.with_cardinality_limit(limit);
+        }
+        let stream = stream
+            .build()
+            .map_err(|err| invalid(format!("invalid view stream: {err}")))?;
+
+        View::new(instrument_selector, stream)
+            .map_err(|err| invalid(format!("invalid view: {err}")))
+    }
+}
+
+impl AggregationConfig {
+    #[cfg(feature = "metrics")]
+    fn build(&self) -> Aggregation {
+        match self {
+            AggregationConfig::Default(_) => Aggregation::Default,
+            AggregationConfig::Drop(_) => Aggregation::Drop,
+            AggregationConfig::Sum(_) => Aggregation::Sum,
+            AggregationConfig::LastValue(_) => Aggregation::LastValue,
+            AggregationConfig::ExplicitBucketHistogram(config) => {
+                Aggregation::ExplicitBucketHistogram {
+                    boundaries: config
+                        .boundaries
+                        .clone()
+                        .unwrap_or_else(|| DEFAULT_HISTOGRAM_BOUNDARIES.to_vec()),
+                    record_min_max: config.record_min_max.unwrap_or(true),
+                }
+            }
+            AggregationConfig::Base2ExponentialBucketHistogram(config) => {
+                Aggregation::Base2ExponentialHistogram {
+                    max_size: config.max_size.unwrap_or(DEFAULT_EXPO_MAX_SIZE),
+                    max_scale: config.max_scale.unwrap_or(DEFAULT_EXPO_MAX_SCALE),
+                    record_min_max: config.record_min_max.unwrap_or(true),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "logs")]
+fn build_logger_provider(
+    config: &LoggerProviderConfig,
+    attribute_limits: &AttributeLimitsConfig,
+    resource: Resource,
+) -> Result<SdkLoggerProvider, AutoConfigureError> {
+    let mut builder = SdkLoggerProvider::builder()
+        .with_resource(resource)
+        .with_log_record_limits(
+            config
+                .limits
+                .as_ref()
+                .unwrap_or(&LogRecordLimitsConfig::default())
+                .build(attribute_limits),
+        );
+    for processor in &config.processors {
+        builder = with_log_processor(builder, processor)?;
+    }
+    Ok(builder.build())
+}
+
+#[cfg(feature = "logs")]
+fn with_log_processor(
+    builder: logs::LoggerProviderBuilder,
+    processor: &ProcessorConfig,
+) -> Result<logs::LoggerProviderBuilder, AutoConfigureError> {
+    fn add<E: LogExporter + 'static>(
+        builder: logs::LoggerProviderBuilder,
+        processor: &ProcessorConfig,
+        exporter: E,
+    ) -> logs::LoggerProviderBuilder {
+        match processor {
+            ProcessorConfig::Batch(batch) => builder.with_log_processor(
+                BatchLogProcessor::builder(exporter)
+                    .with_batch_config(
+                        logs::BatchConfigBuilder::default()
+                            .with_scheduled_delay(Duration::from_millis(
+                                batch.schedule_delay.unwrap_or(DEFAULT_BLRP_SCHEDULE_DELAY),
+                            ))
+                            .with_max_queue_size(
+                                batch.max_queue_size.unwrap_or(DEFAULT_MAX_QUEUE_SIZE),
+                            )
+                            .with_max_export_batch_size(
+                                batch
+                                    .max_export_batch_size
+                                    .unwrap_or(DEFAULT_MAX_EXPORT_BATCH_SIZE),
+                            )
+                            .build(),
+                    )
+                    .build(),
+            ),
+            ProcessorConfig::Simple(_) => builder.with_simple_exporter(exporter),
+        }
+    }
+
+    if let ProcessorConfig::Batch(batch) = processor {
+        batch.warn_unsupported();
+    }
+    let exporter = processor.exporter();
+    exporter.check_not_metrics()?;
+    Ok(match exporter {
+        #[cfg(feature = "otlp-http")]
+        ExporterConfig::OtlpHttp(otlp) => add(
+            builder,
+            processor,
+            with_otlp_http_config(opentelemetry_otlp::LogExporter::builder().with_http(), otlp)?
+                .build()
+                .map_err(crate::otlp_build_error)?,
+        ),
+        #[cfg(feature = "otlp-grpc")]
+        ExporterConfig::OtlpGrpc(otlp) => add(
+            builder,
+            processor,
+            with_otlp_grpc_config(
+                opentelemetry_otlp::LogExporter::builder().with_tonic(),
+                otlp,
+            )?
+            .build()
+            .map_err(crate::otlp_build_error)?,
+        ),
+        #[cfg(feature = "stdout")]
+        ExporterConfig::Console(_) => add(
+            builder,
+            processor,
+            opentelemetry_stdout::LogExporter::default(),
+        ),
+        #[allow(unreachable_patterns)]
+        exporter => return Err(exporter.feature_required()),
+    })
+}
+
+#[cfg(all(
+    test,
+    feature = "trace",
+    feature = "metrics",
+    feature = "logs",
+    feature = "otlp-http",
+    feature = "stdout"
+))]
+mod tests {
+    use super::*;
+    use opentelemetry::Key;
+
+    fn invalid_message(text: &str) -> String {
+        match parse(text).and_then(build) {
+            Err(AutoConfigureError::InvalidConfig { message }) => message,
+            other => panic!("expected an invalid configuration, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn substitutes_environment_variables() {
+        temp_env::with_vars(
+            [
+                ("CONFIG_TEST_SET", Some("value")),
+                ("CONFIG_TEST_EMPTY", Some("")),
+            ],
+            || {
+                assert_eq!(
+                    substitute_env_vars(
+                        "a: ${CONFIG_TEST_SET}, b: ${env:CONFIG_TEST_SET}, c: ${CONFIG_TEST_UNSET}"
+                    )
+                    .unwrap(),
+                    "a: value, b: value, c: "
+                );
+                assert_eq!(
+                    substitute_env_vars(
+                        "${CONFIG_TEST_EMPTY:-default} ${CONFIG_TEST_SET:-default}"
+                    )
+                    .unwrap(),
+                    "default value"
+                );
+                assert_eq!(substitute_env_vars("$$ $${X} $5").unwrap(), "$ ${X} $5");
+            },
+        );
+
+        assert!(substitute_env_vars("${1NVALID}").is_err());
+        assert!(substitute_env_vars("${UNTERMINATED").is_err());
+    }
+
+    #[test]
+    fn resource_attributes_are_typed() {
+        let config: Configuration = parse(
+            r#"
+file_format: "0.3"
+resource:
+  schema_url: https://opentelemetry.io/schemas/1.16.0
+  attributes_list: service.name=from-list,deployment.environment=prod
+  attributes:
+    - name: service.name
+      value: checkout
+    - name: port
+      value: 8080
+      type: string
+    - name: ratio
+      value: 1
+      type: double
+    - name: tags
+      value: []
+      type: string_array
+    - name: enabled
+      value: true
+"#,
+        )
+        .unwrap();
+        let resource = build_resource(config.resource.as_ref().unwrap()).unwrap();
+
+        let get = |key: &'static str| resource.get(&Key::from_static_str(key));
+        assert_eq!(get("service.name"), Some(Value::from("checkout")));
+        assert_eq!(get("deployment.environment"), Some(Value::from("prod")));
+        assert_eq!(get("port"), Some(Value::from("8080")));
+        assert_eq!(get("ratio"), Some(Value::F64(1.0)));
+        assert_eq!(get("tags"), Some(Value::Array(Array::String(Vec::new()))));
+        assert_eq!(get("enabled"), Some(Value::Bool(true)));
+        assert!(get("telemetry.sdk.name").is_some());
+        assert_eq!(
+            resource.schema_url(),
+            Some("https://opentelemetry.io/schemas/1.16.0")
+        );
+
+        let message = invalid_message(
+            r#"
+file_format: "0.3"
+resource:
+  attributes:
+    - name: port
+      value: http
+      type: int
+"#,
+        );
+        assert_eq!(message, "resource attribute 'port' is not a valid int");
+    }
+
+    #[test]
+    fn builds_configured_providers() {
+        let providers = from_config_str_for_test(
+            r#"
+file_format: "0.3"
+propagator:
+  composite:
+    - tracecontext:
+  composite_list: baggage
+tracer_provider:
+  processors:
+    - simple:
+        exporter:
+          console:
+    - batch:
+        schedule_delay: 100
+        export_timeout: 1000
+        exporter:
+          otlp_http:
+            endpoint: http://localhost:4318/v1/traces
+            headers:
+              - name: api-key
+                value: secret
+            headers_list: tenant=a
+  sampler:
+    parent_based:
+      root:
+        trace_id_ratio_based:
+          ratio: 0.5
+      remote_parent_sampled:
+        always_on:
+  limits:
+    attribute_count_limit: 10
+meter_provider:
+  exemplar_filter: always_off
+  readers:
+    - periodic:
+        interval: 1000
+        exporter:
+          console:
+            temporality_preference: delta
+  views:
+    - selector:
+        instrument_name: "http.*"
+        instrument_type: histogram
+      stream:
+        aggregation:
+          explicit_bucket_histogram:
+            boundaries: [1, 5, 10]
+logger_provider:
+  processors:
+    - batch:
+        exporter:
+          console:
+  limits:
+    attribute_count_limit: 64
+"#,
+        );
+        assert!(providers.tracer_provider().is_some());
+        assert!(providers.meter_provider().is_some());
+        assert!(providers.logger_provider().is_some());
+        assert_eq!(
+            providers.propagators,
+            Some(vec!["tracecontext".to_owned(), "baggage".to_owned()])
+        );
+        assert!(providers.shutdown().is_ok());
+    }
+
+    #[test]
+    fn attribute_limits_are_overridden_per_signal() {
+        let config = parse(
+            r#"
+file_format: "0.3"
+attribute_limits:
+  attribute_value_length_limit: 4096
+  attribute_count_limit: 64
+tracer_provider:
+  limits:
+    attribute_value_length_limit: 1024
+logger_provider:
+  limits:
+    attribute_count_limit: 32
+"#,
+        )
+        .unwrap();
+
+        let span_limits = config
+            .tracer_provider
+            .as_ref()
+            .and_then(|tracer_provider| tracer_provider.limits.as_ref())
+            .unwrap()
+            .build(&config.attribute_limits);
+        assert_eq!(span_limits.max_attribute_value_length, Some(1024));
+        assert_eq!(span_limits.max_attributes_per_span, 64);
+        assert_eq!(span_limits.max_attributes_per_event, 128);
+
+        let log_record_limits = config
+            .logger_provider
+            .as_ref()
+            .and_then(|logger_provider| logger_provider.limits.as_ref())
+            .unwrap()
+            .build(&config.attribute_limits);
+        assert_eq!(log_record_limits.max_attribute_value_length, Some(4096));
+        assert_eq!(log_record_limits.max_attributes_per_log_record, 32);
+    }
+
+    fn from_config_str_for_test(text: &str) -> Providers {
+        parse(text).and_then(build).unwrap()
+    }
+
+    #[test]
+    fn missing_sections_build_no_providers() {
+        let providers =
+            from_config_str_for_test(r#"{"file_format": "1.0", "tracer_provider": {}}"#);
+        assert!(providers.tracer_provider().is_some());
+        assert!(providers.meter_provider().is_none());
+        assert!(providers.logger_provider().is_none());
+        assert_eq!(providers.propagators, Some(Vec::new()));
+
+        let providers =
+            from_config_str_for_test("file_format: \"0.3\"\ndisabled: true\ntracer_provider:\n");
+        assert!(providers.tracer_provider().is_none());
+        assert!(providers.propagators.is_none());
+    }
+
+    #[test]
+    fn unsupported_configuration_is_rejected() {
+        assert_eq!(
+            invalid_message("file_format: \"2.0\""),
+            "unsupported file_format '2.0'"
+        );
+        assert!(invalid_message("file_format: \"0.3\"\nunknown: 1").contains("unknown field"));
+        assert!(invalid_message(
+            "file_format: \"0.3\"\ntracer_provider:\n  processors:\n    - simple:\n        exporter:\n          zipkin:\n"
+        )
+        .contains("unknown variant `zipkin`"));
+        assert!(invalid_message(
+            "file_format: \"0.3\"\ntracer_provider:\n  processors:\n    - simple:\n        exporter:\n          console:\n            temporality_preference: delta\n"
+        )
+        .starts_with("temporality_preference is only supported by metric exporters"));
+        assert!(invalid_message(
+            "file_format: \"0.3\"\ntracer_provider:\n  sampler:\n    parent_based:\n      remote_parent_sampled:\n        always_off:\n"
+        )
+        .starts_with("parent_based samplers only support"));
+        assert_eq!(
+            invalid_message(
+                "file_format: \"0.3\"\nmeter_provider:\n  views:\n    - selector:\n        instrument_type: timer\n      stream:\n"
+            ),
+            "unsupported instrument_type 'timer'"
+        );
+        assert!(invalid_message(
+            "file_format: \"0.3\"\nmeter_provider:\n  views:\n    - selector:\n        instrument_name: \"*\"\n      stream:\n        name: renamed\n"
+        )
+        .starts_with("invalid view"));
+    }
+
+    #[test]
+    fn config_file_is_used_from_env() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"file_format: \"0.3\"\nmeter_provider:\n  readers: []\n",
+        )
+        .unwrap();
+
+        temp_env::with_vars(
+            [
+                (crate::OTEL_CONFIG_FILE, Some(file.path().to_str().unwrap())),
+                (crate::OTEL_TRACES_EXPORTER, Some("unsupported")),
+            ],
+            || {
+                let providers = crate::from_env().unwrap();
+                assert!(providers.tracer_provider().is_none());
+                assert!(providers.meter_provider().is_some());
+            },
+        );
+
+        temp_env::with_var(
+            crate::OTEL_CONFIG_FILE,
+            Some("/does/not/exist.yaml"),
+            || {
+                assert!(matches!(
+                    crate::from_env(),
+                    Err(AutoConfigureError::ConfigFileRead { .. })
+                ));
+            },
+        );
+    }
+}
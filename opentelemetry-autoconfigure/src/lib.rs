@@ -0,0 +1,738 @@
+//! # OpenTelemetry Autoconfigure
+//!
+//! Builds the OpenTelemetry SDK providers for traces, metrics and logs from the
+//! [environment variables] defined by the OpenTelemetry specification, so
+//! applications don't need to repeat the same bootstrap code.
+//!
+//! The exporters of each signal are selected with a comma-separated list in
+//! the following environment variables:
+//!
+//! | Variable                | Default | Supported values           |
+//! |-------------------------|---------|----------------------------|
+//! | `OTEL_TRACES_EXPORTER`  | `otlp`  | `otlp`, `console`, `none`  |
+//! | `OTEL_METRICS_EXPORTER` | `otlp`  | `otlp`, `console`, `none`  |
+//! | `OTEL_LOGS_EXPORTER`    | `otlp`  | `otlp`, `console`, `none`  |
+//!
+//! `otlp` uses [`opentelemetry-otlp`] with a batching processor for spans and
+//! logs and a periodic reader for metrics, `console` uses
+//! [`opentelemetry-stdout`] with a simple processor for spans and logs. With
+//! `none`, the provider of that signal is built without any exporter. If
+//! `OTEL_SDK_DISABLED` is `true`, no providers are built at all and the
+//! OpenTelemetry API stays a no-op.
+//!
+//! When installed globally, the text map propagator is built from
+//! `OTEL_PROPAGATORS`, defaulting to `tracecontext,baggage`.
+//!
+//! Everything else is configured by the SDK and exporters themselves, for
+//! example the resource with `OTEL_SERVICE_NAME` and
+//! `OTEL_RESOURCE_ATTRIBUTES`, the sampler with `OTEL_TRACES_SAMPLER`, the
+//! processors with `OTEL_BSP_*`, `OTEL_BLRP_*` and
+//! `OTEL_METRIC_EXPORT_INTERVAL`, and the exporters with
+//! `OTEL_EXPORTER_OTLP_*`.
+//!
+//! # Configuration file
+//!
+//! If `OTEL_CONFIG_FILE` is set, the providers are built from the
+//! [declarative configuration] file at that path instead, and the variables
+//! above are ignored. See [`from_config_file`] for the supported options.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use opentelemetry::global;
+//! use opentelemetry::trace::Tracer;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // Build the providers from the environment and install them globally.
+//! let providers = opentelemetry_autoconfigure::init()?;
+//!
+//! global::tracer("my-component").in_span("do-work", |_cx| {
+//!     // ...
+//! });
+//!
+//! providers.shutdown()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Crate Feature Flags
+//!
+//! * `trace`, `metrics`, `logs`: build the provider of the signal. All are
+//!   enabled by default.
+//! * `otlp-http`: support the `otlp` exporter using HTTP with protobuf
+//!   encoding. Enabled by default.
+//! * `otlp-grpc`: support the `otlp` exporter using gRPC. The transport is
+//!   selected with `OTEL_EXPORTER_OTLP_PROTOCOL` if both are enabled.
+//! * `stdout`: support the `console` exporter. Enabled by default.
+//! * `config-file`: support declarative configuration files selected with
+//!   `OTEL_CONFIG_FILE`. Enabled by default.
+//! * `jaeger-remote-sampler`: support the `jaeger_remote` and
+//!   `parentbased_jaeger_remote` samplers of `OTEL_TRACES_SAMPLER`, polling
+//!   the sampling strategy with a blocking reqwest client.
+//!
+//! [environment variables]: https://opentelemetry.io/docs/specs/otel/configuration/sdk-environment-variables/
+//! [`opentelemetry-otlp`]: https://docs.rs/opentelemetry-otlp
+//! [`opentelemetry-stdout`]: https://docs.rs/opentelemetry-stdout
+//! [declarative configuration]: https://opentelemetry.io/docs/specs/otel/configuration/data-model/
+#![warn(missing_debug_implementations, missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg), deny(rustdoc::broken_intra_doc_links))]
+// Without any exporter feature, every configured exporter is an error and
+// the code adding exporters to the providers can't be reached.
+#![cfg_attr(
+    not(any(feature = "otlp-http", feature = "otlp-grpc", feature = "stdout")),
+    allow(
+        dead_code,
+        unreachable_code,
+        unused_mut,
+        unused_variables,
+        clippy::never_loop
+    )
+)]
+
+#[cfg(feature = "config-file")]
+mod config;
+
+use std::env;
+#[cfg(feature = "config-file")]
+use std::path::Path;
+
+use opentelemetry::otel_warn;
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+#[cfg(feature = "logs")]
+use opentelemetry_sdk::logs::SdkLoggerProvider;
+#[cfg(feature = "metrics")]
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+#[cfg(feature = "trace")]
+use opentelemetry_sdk::trace::SdkTracerProvider;
+#[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
+use opentelemetry_sdk::Resource;
+
+/// Path of a declarative configuration file, which replaces all other
+/// environment variables if set.
+pub const OTEL_CONFIG_FILE: &str = "OTEL_CONFIG_FILE";
+/// Disables the SDK for all signals if set to `true`.
+pub const OTEL_SDK_DISABLED: &str = "OTEL_SDK_DISABLED";
+/// The exporters used for traces.
+pub const OTEL_TRACES_EXPORTER: &str = "OTEL_TRACES_EXPORTER";
+/// The exporters used for metrics.
+pub const OTEL_METRICS_EXPORTER: &str = "OTEL_METRICS_EXPORTER";
+/// The exporters used for logs.
+pub const OTEL_LOGS_EXPORTER: &str = "OTEL_LOGS_EXPORTER";
+
+#[cfg(feature = "jaeger-remote-sampler")]
+const JAEGER_REMOTE_HTTP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Errors that can occur while configuring the SDK from the environment.
+#[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
+pub enum AutoConfigureError {
+    /// The exporter name is not known.
+    #[error("unsupported exporter '{value}' in {variable}")]
+    UnsupportedExporter {
+        /// The environment variable the exporter was read from.
+        variable: &'static str,
+        /// The exporter name.
+        value: String,
+    },
+
+    /// The exporter is known but the crate feature providing it is disabled.
+    #[error("exporter '{exporter}' requires the '{feature}' feature")]
+    FeatureRequired {
+        /// The exporter name.
+        exporter: &'static str,
+        /// The crate feature that must be enabled.
+        feature: &'static str,
+    },
+
+    /// The exporter failed to build.
+    #[error("failed to build the {exporter} exporter: {message}")]
+    ExporterBuild {
+        /// The exporter name.
+        exporter: &'static str,
+        /// The reason the exporter failed to build.
+        message: String,
+    },
+
+    /// The configuration file can't be read.
+    #[error("failed to read the configuration file '{path}': {message}")]
+    ConfigFileRead {
+        /// The path of the configuration file.
+        path: String,
+        /// The reason the file can't be read.
+        message: String,
+    },
+
+    /// The configuration file is invalid or uses options that are not
+    /// supported.
+    #[error("invalid configuration: {message}")]
+    InvalidConfig {
+        /// The reason the configuration is invalid.
+        message: String,
+    },
+}
+
+/// The exporters that can be selected with `OTEL_*_EXPORTER`, limited to the
+/// ones whose crate feature is enabled.
+#[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExporterKind {
+    #[cfg(any(feature = "otlp-http", feature = "otlp-grpc"))]
+    Otlp,
+    #[cfg(feature = "stdout")]
+    Console,
+}
+
+/// The SDK providers built from the environment.
+///
+/// A provider is `None` if its signal is disabled, either with
+/// `OTEL_SDK_DISABLED` or because the crate feature of the signal is not
+/// enabled.
+#[derive(Debug, Default)]
+pub struct Providers {
+    #[cfg(feature = "trace")]
+    tracer_provider: Option<SdkTracerProvider>,
+    #[cfg(feature = "metrics")]
+    meter_provider: Option<SdkMeterProvider>,
+    #[cfg(feature = "logs")]
+    logger_provider: Option<SdkLoggerProvider>,
+    /// The propagators set by a configuration file, `OTEL_PROPAGATORS` is
+    /// used if `None`.
+    #[cfg(feature = "trace")]
+    propagators: Option<Vec<String>>,
+}
+
+impl Providers {
+    /// The tracer provider, unless the SDK is disabled.
+    #[cfg(feature = "trace")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "trace")))]
+    pub fn tracer_provider(&self) -> Option<&SdkTracerProvider> {
+        self.tracer_provider.as_ref()
+    }
+
+    /// The meter provider, unless the SDK is disabled.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn meter_provider(&self) -> Option<&SdkMeterProvider> {
+        self.meter_provider.as_ref()
+    }
+
+    /// The logger provider, unless the SDK is disabled.
+    ///
+    /// Log records are only emitted to it through a log appender, such as
+    /// `opentelemetry-appender-tracing`.
+    #[cfg(feature = "logs")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "logs")))]
+    pub fn logger_provider(&self) -> Option<&SdkLoggerProvider> {
+        self.logger_provider.as_ref()
+    }
+
+    /// Installs the providers as the global providers of the
+    /// [`opentelemetry::global`] module.
+    ///
+    /// Along with the tracer provider, the global text map propagator is set
+    /// to the propagators of the configuration file or, without one, to the
+    /// propagators listed in `OTEL_PROPAGATORS`, see
+    /// [`text_map_propagator_from_env`]. Propagators from other crates, like
+    /// `b3` from `opentelemetry-zipkin`, must be registered before.
+    ///
+    /// [`text_map_propagator_from_env`]: opentelemetry_sdk::propagation::text_map_propagator_from_env
+    pub fn set_global(&self) {
+        #[cfg(feature = "trace")]
+        if let Some(provider) = &self.tracer_provider {
+            use opentelemetry_sdk::propagation::{
+                text_map_propagator_from_env, text_map_propagator_from_names,
+            };
+
+            opentelemetry::global::set_tracer_provider(provider.clone());
+            opentelemetry::global::set_text_map_propagator(match &self.propagators {
+                Some(names) => text_map_propagator_from_names(names),
+                None => text_map_propagator_from_env(),
+            });
+        }
+        #[cfg(feature = "metrics")]
+        if let Some(provider) = &self.meter_provider {
+            opentelemetry::global::set_meter_provider(provider.clone());
+        }
+        #[cfg(feature = "logs")]
+        if let Some(provider) = &self.logger_provider {
+            opentelemetry::global::set_logger_provider(provider.clone());
+        }
+    }
+
+    /// Flushes and shuts down all providers.
+    ///
+    /// All providers are shut down even if one of them fails.
+    pub fn shutdown(&self) -> OTelSdkResult {
+        #[allow(unused_mut)]
+        let mut errs: Vec<OTelSdkError> = vec![];
+        #[cfg(feature = "trace")]
+        if let Some(Err(err)) = self.tracer_provider.as_ref().map(|p| p.shutdown()) {
+            errs.push(err);
+        }
+        #[cfg(feature = "metrics")]
+        if let Some(Err(err)) = self.meter_provider.as_ref().map(|p| p.shutdown()) {
+            errs.push(err);
+        }
+        #[cfg(feature = "logs")]
+        if let Some(Err(err)) = self.logger_provider.as_ref().map(|p| p.shutdown()) {
+            errs.push(err);
+        }
+
+        if errs.is_empty() {
+            Ok(())
+        } else {
+            Err(OTelSdkError::InternalFailure(format!("{errs:?}")))
+        }
+    }
+}
+
+/// Builds the SDK providers from the environment.
+///
+/// See the [crate documentation](crate) for the supported environment
+/// variables. If `OTEL_CONFIG_FILE` is set, the providers are built from that
+/// file with [`from_config_file`] instead.
+///
+/// # Errors
+///
+/// Returns an error if an `OTEL_*_EXPORTER` variable names an unknown
+/// exporter or one whose crate feature is disabled, if an exporter fails to
+/// build, or if the configuration file can't be used.
+pub fn from_env() -> Result<Providers, AutoConfigureError> {
+    if let Some(path) = env::var(OTEL_CONFIG_FILE)
+        .ok()
+        .filter(|path| !path.trim().is_empty())
+    {
+        #[cfg(feature = "config-file")]
+        return from_config_file(path.trim());
+        #[cfg(not(feature = "config-file"))]
+        return Err(AutoConfigureError::InvalidConfig {
+            message: format!(
+                "{OTEL_CONFIG_FILE} is set to '{path}' but the 'config-file' feature is disabled"
+            ),
+        });
+    }
+
+    if sdk_disabled() {
+        return Ok(Providers::default());
+    }
+
+    #[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
+    let resource = Resource::builder().build();
+
+    Ok(Providers {
+        #[cfg(feature = "trace")]
+        tracer_provider: Some(tracer_provider(resource.clone())?),
+        #[cfg(feature = "metrics")]
+        meter_provider: Some(meter_provider(resource.clone())?),
+        #[cfg(feature = "logs")]
+        logger_provider: Some(logger_provider(resource)?),
+        #[cfg(feature = "trace")]
+        propagators: None,
+    })
+}
+
+/// Builds the SDK providers from a [declarative configuration] file.
+///
+/// The file is written in YAML, or JSON which is a subset of it. References to
+/// environment variables like `${OTEL_SERVICE_NAME}` or
+/// `${OTLP_ENDPOINT:-http://localhost:4318/v1/traces}` are replaced by their
+/// value before the file is parsed, and `$$` escapes a `$`. Apart from these
+/// references, the `OTEL_*` environment variables of the SDK aren't used, with
+/// the exception of the `OTEL_EXPORTER_OTLP_*` variables for the OTLP exporter
+/// options that are not set in the file.
+///
+/// The `resource`, `propagator`, `tracer_provider`, `meter_provider` and
+/// `logger_provider` sections are supported, with the `otlp_http`,
+/// `otlp_grpc` and `console` exporters. A provider is only built if its
+/// section is present. Properties that are not supported are rejected.
+///
+/// ```yaml
+/// file_format: "0.3"
+/// resource:
+///   attributes:
+///     - name: service.name
+///       value: ${SERVICE_NAME:-checkout}
+/// propagator:
+///   composite_list: tracecontext,baggage
+/// tracer_provider:
+///   processors:
+///     - batch:
+///         schedule_delay: 1000
+///         exporter:
+///           otlp_http:
+///             endpoint: http://localhost:4318/v1/traces
+///   sampler:
+///     parent_based:
+///       root:
+///         trace_id_ratio_based:
+///           ratio: 0.25
+/// meter_provider:
+///   readers:
+///     - periodic:
+///         interval: 10000
+///         exporter:
+///           console:
+///   views:
+///     - selector:
+///         instrument_name: http.server.request.duration
+///       stream:
+///         aggregation:
+///           explicit_bucket_histogram:
+///             boundaries: [0.1, 0.5, 1.0]
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file can't be read or is invalid, uses options that
+/// are not supported, or an exporter fails to build.
+///
+/// [declarative configuration]: https://opentelemetry.io/docs/specs/otel/configuration/data-model/
+#[cfg(feature = "config-file")]
+#[cfg_attr(docsrs, doc(cfg(feature = "config-file")))]
+pub fn from_config_file(path: impl AsRef<Path>) -> Result<Providers, AutoConfigureError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|err| AutoConfigureError::ConfigFileRead {
+        path: path.display().to_string(),
+        message: err.to_string(),
+    })?;
+    from_config_str(&text)
+}
+
+/// Builds the SDK providers from the content of a declarative configuration
+/// file, see [`from_config_file`].
+#[cfg(feature = "config-file")]
+#[cfg_attr(docsrs, doc(cfg(feature = "config-file")))]
+pub fn from_config_str(config: &str) -> Result<Providers, AutoConfigureError> {
+    config::build(config::parse(config)?)
+}
+
+/// Builds the SDK providers from the environment and installs them as the
+/// global providers.
+///
+/// This is a shorthand for [`from_env`] followed by
+/// [`Providers::set_global`]. Keep the returned [`Providers`] to shut them
+/// down before the application exits.
+pub fn init() -> Result<Providers, AutoConfigureError> {
+    let providers = from_env()?;
+    providers.set_global();
+    Ok(providers)
+}
+
+fn sdk_disabled() -> bool {
+    match env::var(OTEL_SDK_DISABLED) {
+        Ok(value) if value.trim().eq_ignore_ascii_case("true") => true,
+        Ok(value) if !value.trim().is_empty() && !value.trim().eq_ignore_ascii_case("false") => {
+            otel_warn!(
+                name: "AutoConfigure.InvalidSdkDisabled",
+                message = "Invalid value for OTEL_SDK_DISABLED, the SDK stays enabled.",
+                value = value.as_str(),
+            );
+            false
+        }
+        _ => false,
+    }
+}
+
+/// Reads the exporters of a signal from `variable`, defaulting to `otlp`.
+#[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
+fn exporters_from_env(variable: &'static str) -> Result<Vec<ExporterKind>, AutoConfigureError> {
+    parse_exporters(variable, &env::var(variable).unwrap_or_default())
+}
+
+#[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
+fn parse_exporters(
+    variable: &'static str,
+    value: &str,
+) -> Result<Vec<ExporterKind>, AutoConfigureError> {
+    let mut names: Vec<String> = value
+        .split(',')
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    if names.is_empty() {
+        names.push("otlp".into());
+    }
+    if names.iter().any(|name| name == "none") {
+        if names.len() > 1 {
+            otel_warn!(
+                name: "AutoConfigure.NoneWithOtherExporters",
+                message = "Exporter 'none' is combined with other exporters, no exporter is configured.",
+                variable = variable,
+                value = value,
+            );
+        }
+        return Ok(Vec::new());
+    }
+
+    let mut exporters = Vec::with_capacity(names.len());
+    for name in names {
+        let exporter = match name.as_str() {
+            #[cfg(any(feature = "otlp-http", feature = "otlp-grpc"))]
+            "otlp" => ExporterKind::Otlp,
+            #[cfg(not(any(feature = "otlp-http", feature = "otlp-grpc")))]
+            "otlp" => {
+                return Err(AutoConfigureError::FeatureRequired {
+                    exporter: "otlp",
+                    feature: "otlp-http",
+                })
+            }
+            #[cfg(feature = "stdout")]
+            "console" => ExporterKind::Console,
+            #[cfg(not(feature = "stdout"))]
+            "console" => {
+                return Err(AutoConfigureError::FeatureRequired {
+                    exporter: "console",
+                    feature: "stdout",
+                })
+            }
+            _ => {
+                return Err(AutoConfigureError::UnsupportedExporter {
+                    variable,
+                    value: name,
+                })
+            }
+        };
+        if !exporters.contains(&exporter) {
+            exporters.push(exporter);
+        }
+    }
+    Ok(exporters)
+}
+
+#[cfg(all(
+    any(feature = "otlp-http", feature = "otlp-grpc"),
+    any(feature = "trace", feature = "metrics", feature = "logs")
+))]
+fn otlp_build_error(err: opentelemetry_otlp::ExporterBuildError) -> AutoConfigureError {
+    AutoConfigureError::ExporterBuild {
+        exporter: "otlp",
+        message: err.to_string(),
+    }
+}
+
+#[cfg(feature = "trace")]
+fn tracer_provider(resource: Resource) -> Result<SdkTracerProvider, AutoConfigureError> {
+    let mut builder = SdkTracerProvider::builder().with_resource(resource);
+    #[cfg(feature = "jaeger-remote-sampler")]
+    if let Some(client) = jaeger_remote_http_client() {
+        builder = builder.with_jaeger_remote_http_client(client);
+    }
+    for exporter in exporters_from_env(OTEL_TRACES_EXPORTER)? {
+        builder = match exporter {
+            #[cfg(any(feature = "otlp-http", feature = "otlp-grpc"))]
+            ExporterKind::Otlp => builder.with_batch_exporter(
+                opentelemetry_otlp::SpanExporter::builder()
+                    .build()
+                    .map_err(otlp_build_error)?,
+            ),
+            #[cfg(feature = "stdout")]
+            ExporterKind::Console => {
+                builder.with_simple_exporter(opentelemetry_stdout::SpanExporter::default())
+            }
+        };
+    }
+    Ok(builder.build())
+}
+
+/// The client polling the strategy of the Jaeger remote sampler, if it is
+/// selected with `OTEL_TRACES_SAMPLER`.
+#[cfg(feature = "jaeger-remote-sampler")]
+fn jaeger_remote_http_client() -> Option<reqwest::blocking::Client> {
+    let sampler = env::var("OTEL_TRACES_SAMPLER").ok()?;
+    if !matches!(
+        sampler.as_str(),
+        "jaeger_remote" | "parentbased_jaeger_remote"
+    ) {
+        return None;
+    }
+    // Like the OTLP exporter, build the blocking client on its own thread so
+    // that the providers can be built from an async context.
+    std::thread::spawn(|| {
+        reqwest::blocking::Client::builder()
+            .timeout(JAEGER_REMOTE_HTTP_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| reqwest::blocking::Client::new())
+    })
+    .join()
+    .ok()
+}
+
+#[cfg(feature = "metrics")]
+fn meter_provider(resource: Resource) -> Result<SdkMeterProvider, AutoConfigureError> {
+    let mut builder = SdkMeterProvider::builder().with_resource(resource);
+    for exporter in exporters_from_env(OTEL_METRICS_EXPORTER)? {
+        builder = match exporter {
+            #[cfg(any(feature = "otlp-http", feature = "otlp-grpc"))]
+            ExporterKind::Otlp => builder.with_periodic_exporter(
+                opentelemetry_otlp::MetricExporter::builder()
+                    .build()
+                    .map_err(otlp_build_error)?,
+            ),
+            #[cfg(feature = "stdout")]
+            ExporterKind::Console => {
+                builder.with_periodic_exporter(opentelemetry_stdout::MetricExporter::default())
+            }
+        };
+    }
+    Ok(builder.build())
+}
+
+#[cfg(feature = "logs")]
+fn logger_provider(resource: Resource) -> Result<SdkLoggerProvider, AutoConfigureError> {
+    let mut builder = SdkLoggerProvider::builder().with_resource(resource);
+    for exporter in exporters_from_env(OTEL_LOGS_EXPORTER)? {
+        builder = match exporter {
+            #[cfg(any(feature = "otlp-http", feature = "otlp-grpc"))]
+            ExporterKind::Otlp => builder.with_batch_exporter(
+                opentelemetry_otlp::LogExporter::builder()
+                    .build()
+                    .map_err(otlp_build_error)?,
+            ),
+            #[cfg(feature = "stdout")]
+            ExporterKind::Console => {
+                builder.with_simple_exporter(opentelemetry_stdout::LogExporter::default())
+            }
+        };
+    }
+    Ok(builder.build())
+}
+
+#[cfg(all(
+    test,
+    feature = "trace",
+    feature = "metrics",
+    feature = "logs",
+    feature = "otlp-http",
+    feature = "stdout"
+))]
+mod tests {
+    use super::*;
+
+    const ALL_EXPORTERS: [&str; 3] = [
+        OTEL_TRACES_EXPORTER,
+        OTEL_METRICS_EXPORTER,
+        OTEL_LOGS_EXPORTER,
+    ];
+
+    fn with_exporters<F: FnOnce()>(value: Option<&str>, f: F) {
+        let vars: Vec<(&str, Option<&str>)> = ALL_EXPORTERS
+            .iter()
+            .map(|var| (*var, value))
+            .chain([(OTEL_SDK_DISABLED, None)])
+            .collect();
+        temp_env::with_vars(vars, f);
+    }
+
+    #[test]
+    fn parse_exporter_lists() {
+        let var = OTEL_TRACES_EXPORTER;
+        assert_eq!(parse_exporters(var, "").unwrap(), vec![ExporterKind::Otlp]);
+        assert_eq!(
+            parse_exporters(var, "console").unwrap(),
+            vec![ExporterKind::Console]
+        );
+        assert_eq!(
+            parse_exporters(var, " OTLP , console,otlp").unwrap(),
+            vec![ExporterKind::Otlp, ExporterKind::Console]
+        );
+        assert!(parse_exporters(var, "none").unwrap().is_empty());
+        assert!(parse_exporters(var, "otlp,none").unwrap().is_empty());
+
+        let err = parse_exporters(var, "otlp,zipkin").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unsupported exporter 'zipkin' in OTEL_TRACES_EXPORTER"
+        );
+    }
+
+    #[test]
+    fn sdk_disabled_builds_no_providers() {
+        temp_env::with_var(OTEL_SDK_DISABLED, Some("TRUE"), || {
+            let providers = from_env().unwrap();
+            assert!(providers.tracer_provider().is_none());
+            assert!(providers.meter_provider().is_none());
+            assert!(providers.logger_provider().is_none());
+        });
+    }
+
+    #[test]
+    fn invalid_sdk_disabled_keeps_sdk_enabled() {
+        temp_env::with_var(OTEL_SDK_DISABLED, Some("yes"), || {
+            assert!(!sdk_disabled());
+        });
+        temp_env::with_var(OTEL_SDK_DISABLED, Some("false"), || {
+            assert!(!sdk_disabled());
+        });
+    }
+
+    #[test]
+    fn none_exporter_builds_providers_without_exporters() {
+        with_exporters(Some("none"), || {
+            let providers = from_env().unwrap();
+            assert!(providers.tracer_provider().is_some());
+            assert!(providers.meter_provider().is_some());
+            assert!(providers.logger_provider().is_some());
+            assert!(providers.shutdown().is_ok());
+        });
+    }
+
+    #[test]
+    fn console_exporter_builds_providers() {
+        with_exporters(Some("console"), || {
+            let providers = from_env().unwrap();
+            assert!(providers.tracer_provider().is_some());
+            assert!(providers.shutdown().is_ok());
+        });
+    }
+
+    #[test]
+    fn otlp_exporter_is_the_default() {
+        with_exporters(None, || {
+            let providers = from_env().unwrap();
+            assert!(providers.tracer_provider().is_some());
+            assert!(providers.meter_provider().is_some());
+            assert!(providers.logger_provider().is_some());
+        });
+    }
+
+    #[test]
+    fn unsupported_exporter_is_an_error() {
+        temp_env::with_vars(
+            [
+                (OTEL_SDK_DISABLED, None),
+                (OTEL_TRACES_EXPORTER, Some("none")),
+                (OTEL_METRICS_EXPORTER, Some("prometheus")),
+                (OTEL_LOGS_EXPORTER, Some("none")),
+            ],
+            || {
+                assert!(matches!(
+                    from_env(),
+                    Err(AutoConfigureError::UnsupportedExporter {
+                        variable: OTEL_METRICS_EXPORTER,
+                        ..
+                    })
+                ));
+            },
+        );
+    }
+
+    #[cfg(feature = "jaeger-remote-sampler")]
+    #[test]
+    fn jaeger_remote_sampler_gets_an_http_client() {
+        for (sampler, has_client) in [
+            (Some("parentbased_jaeger_remote"), true),
+            (Some("jaeger_remote"), true),
+            (Some("always_on"), false),
+            (None, false),
+        ] {
+            temp_env::with_var("OTEL_TRACES_SAMPLER", sampler, || {
+                assert_eq!(
+                    jaeger_remote_http_client().is_some(),
+                    has_client,
+                    "{sampler:?}"
+                );
+            });
+        }
+    }
+}
@@ -95,4 +95,6 @@
 ///  [jaeger propagation format]: https://www.jaegertracing.io/docs/1.18/client-libraries/#propagation-format
 pub mod propagator;
 
+#[cfg(feature = "registry")]
+pub use propagator::register_propagator;
 pub use propagator::Propagator;
@@ -219,6 +219,27 @@ impl TextMapPropagator for Propagator {
     }
 }
 
+/// Registers the Jaeger propagator with the SDK as `jaeger`, so it can be
+/// selected with the `OTEL_PROPAGATORS` environment variable.
+///
+/// Requires the `registry` feature.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::global;
+/// use opentelemetry_sdk::propagation::text_map_propagator_from_env;
+///
+/// opentelemetry_jaeger_propagator::register_propagator();
+/// // With OTEL_PROPAGATORS=tracecontext,jaeger
+/// global::set_text_map_propagator(text_map_propagator_from_env());
+/// ```
+#[cfg(feature = "registry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+pub fn register_propagator() {
+    opentelemetry_sdk::propagation::register_text_map_propagator("jaeger", Propagator::new);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -734,4 +755,18 @@ mod tests {
         assert_eq!(fields.len(), 1);
         assert_eq!(fields.first().unwrap(), &JAEGER_HEADER);
     }
+
+    #[test]
+    #[cfg(feature = "registry")]
+    fn test_registered_propagator() {
+        use opentelemetry_sdk::propagation::text_map_propagator_from_names;
+
+        register_propagator();
+
+        let propagator = text_map_propagator_from_names(["jaeger"]);
+        assert_eq!(
+            propagator.fields().collect::<Vec<&str>>(),
+            vec![JAEGER_HEADER]
+        );
+    }
 }
@@ -1,24 +1,43 @@
 use super::OtlpHttpClient;
+use crate::persistent_queue::SendError;
+use crate::retry::RetryErrorType;
 use crate::Protocol;
 use opentelemetry::{otel_debug, otel_warn};
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
 use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
 use opentelemetry_sdk::logs::{LogBatch, LogExporter};
 #[cfg(feature = "http-proto")]
 use prost::Message;
 use std::time;
 
+impl OtlpHttpClient {
+    pub(crate) async fn export_logs_request(
+        &self,
+        request: ExportLogsServiceRequest,
+        items: usize,
+    ) -> Result<(), SendError> {
+        self.send_http_with_retry(
+            request,
+            items,
+            OtlpHttpClient::build_logs_export_body,
+            handle_partial_success,
+            "HttpLogsClient.Export",
+        )
+        .await
+        .map_err(|(error, error_type)| SendError {
+            error,
+            retryable: error_type != RetryErrorType::NonRetryable,
+        })
+    }
+}
+
 impl LogExporter for OtlpHttpClient {
     async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
-        let response_body = self
-            .export_http_with_retry(
-                batch,
-                OtlpHttpClient::build_logs_export_body,
-                "HttpLogsClient.Export",
-            )
-            .await?;
-
-        handle_partial_success(&response_body, self.protocol);
-        Ok(())
+        let items = batch.iter().count();
+        let request = self.build_logs_request(&batch);
+        self.export_logs_request(request, items)
+            .await
+            .map_err(|e| e.error)
     }
 
     fn shutdown_with_timeout(&self, _timeout: time::Duration) -> OTelSdkResult {
@@ -39,8 +58,8 @@ impl LogExporter for OtlpHttpClient {
 }
 
 /// Handles partial success returned by OTLP endpoints. We log the rejected log records,
-/// as well as the error message returned.
-fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
+/// as well as the error message returned, and return the number of rejected log records.
+fn handle_partial_success(response_body: &[u8], protocol: Protocol) -> u64 {
     use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceResponse;
 
     let response: ExportLogsServiceResponse = match protocol {
@@ -49,7 +68,7 @@ fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
             Ok(r) => r,
             Err(e) => {
                 otel_debug!(name: "HttpLogsClient.ResponseParseError", error = e.to_string());
-                return;
+                return 0;
             }
         },
         #[cfg(feature = "http-proto")]
@@ -57,7 +76,7 @@ fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
             Ok(r) => r,
             Err(e) => {
                 otel_debug!(name: "HttpLogsClient.ResponseParseError", error = e.to_string());
-                return;
+                return 0;
             }
         },
         #[cfg(feature = "grpc-tonic")]
@@ -66,14 +85,19 @@ fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
         }
     };
 
-    if let Some(partial_success) = response.partial_success {
-        if partial_success.rejected_log_records > 0 || !partial_success.error_message.is_empty() {
-            otel_warn!(
-                name: "HttpLogsClient.PartialSuccess",
-                rejected_log_records = partial_success.rejected_log_records,
-                error_message = partial_success.error_message.as_str(),
-            );
+    match response.partial_success {
+        Some(partial_success) => {
+            if partial_success.rejected_log_records > 0 || !partial_success.error_message.is_empty()
+            {
+                otel_warn!(
+                    name: "HttpLogsClient.PartialSuccess",
+                    rejected_log_records = partial_success.rejected_log_records,
+                    error_message = partial_success.error_message.as_str(),
+                );
+            }
+            partial_success.rejected_log_records.max(0) as u64
         }
+        None => 0,
     }
 }
 
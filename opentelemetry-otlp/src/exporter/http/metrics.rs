@@ -7,19 +7,18 @@ use opentelemetry_sdk::metrics::data::ResourceMetrics;
 use prost::Message;
 
 use super::OtlpHttpClient;
+use crate::exporter::self_diagnostics::data_point_count;
 
 impl MetricsClient for OtlpHttpClient {
     async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
-        let response_body = self
-            .export_http_with_retry(
-                metrics,
-                OtlpHttpClient::build_metrics_export_body,
-                "HttpMetricsClient.Export",
-            )
-            .await?;
-
-        handle_partial_success(&response_body, self.protocol);
-        Ok(())
+        self.export_http_with_retry(
+            metrics,
+            data_point_count(metrics),
+            OtlpHttpClient::build_metrics_export_body,
+            handle_partial_success,
+            "HttpMetricsClient.Export",
+        )
+        .await
     }
 
     fn shutdown(&self) -> OTelSdkResult {
@@ -33,8 +32,8 @@ impl MetricsClient for OtlpHttpClient {
 }
 
 /// Handles partial success returned by OTLP endpoints. We log the rejected data points,
-/// as well as the error message returned.
-fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
+/// as well as the error message returned, and return the number of rejected data points.
+fn handle_partial_success(response_body: &[u8], protocol: Protocol) -> u64 {
     use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceResponse;
 
     let response: ExportMetricsServiceResponse = match protocol {
@@ -43,7 +42,7 @@ fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
             Ok(r) => r,
             Err(e) => {
                 otel_debug!(name: "HttpMetricsClient.ResponseParseError", error = e.to_string());
-                return;
+                return 0;
             }
         },
         #[cfg(feature = "http-proto")]
@@ -51,7 +50,7 @@ fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
             Ok(r) => r,
             Err(e) => {
                 otel_debug!(name: "HttpMetricsClient.ResponseParseError", error = e.to_string());
-                return;
+                return 0;
             }
         },
         #[cfg(feature = "grpc-tonic")]
@@ -60,14 +59,19 @@ fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
         }
     };
 
-    if let Some(partial_success) = response.partial_success {
-        if partial_success.rejected_data_points > 0 || !partial_success.error_message.is_empty() {
-            otel_warn!(
-                name: "HttpMetricsClient.PartialSuccess",
-                rejected_data_points = partial_success.rejected_data_points,
-                error_message = partial_success.error_message.as_str(),
-            );
+    match response.partial_success {
+        Some(partial_success) => {
+            if partial_success.rejected_data_points > 0 || !partial_success.error_message.is_empty()
+            {
+                otel_warn!(
+                    name: "HttpMetricsClient.PartialSuccess",
+                    rejected_data_points = partial_success.rejected_data_points,
+                    error_message = partial_success.error_message.as_str(),
+                );
+            }
+            partial_success.rejected_data_points.max(0) as u64
         }
+        None => 0,
     }
 }
 
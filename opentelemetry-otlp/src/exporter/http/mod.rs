@@ -1,3 +1,4 @@
+use super::self_diagnostics::{http_status_code, ExportedItems, ExporterMetrics};
 use super::{
     default_headers, parse_header_string, resolve_timeout, ExporterBuildError,
     OTEL_EXPORTER_OTLP_HTTP_ENDPOINT_DEFAULT,
@@ -159,6 +160,7 @@ impl Default for HttpExporterBuilder {
 }
 
 impl HttpExporterBuilder {
+    #[allow(clippy::too_many_arguments)]
     fn build_client(
         &mut self,
         signal_endpoint_var: &str,
@@ -167,6 +169,7 @@ impl HttpExporterBuilder {
         signal_http_headers_var: &str,
         signal_compression_var: &str,
         signal_protocol_var: &str,
+        items: ExportedItems,
     ) -> Result<OtlpHttpClient, ExporterBuildError> {
         let protocol = super::resolve_protocol(signal_protocol_var, self.exporter_config.protocol);
 
@@ -282,6 +285,21 @@ impl HttpExporterBuilder {
             add_header_from_string(&input, &mut headers);
         }
 
+        let transport = match protocol {
+            #[cfg(feature = "http-json")]
+            Protocol::HttpJson => "http_json",
+            #[cfg(feature = "http-proto")]
+            Protocol::HttpBinary => "http",
+            #[cfg(feature = "grpc-tonic")]
+            Protocol::Grpc => unreachable!("gRPC protocol is rejected above"),
+        };
+        let metrics = ExporterMetrics::new(
+            std::mem::take(&mut self.exporter_config.meter),
+            items,
+            transport,
+            Some(&endpoint),
+        );
+
         let mut client = OtlpHttpClient::new(
             http_client,
             endpoint,
@@ -294,6 +312,7 @@ impl HttpExporterBuilder {
         if let Some(max_request_body_size) = self.http_config.max_request_body_size {
             client.max_request_body_size = max_request_body_size;
         }
+        client.metrics = metrics;
         Ok(client)
     }
 
@@ -320,6 +339,7 @@ impl HttpExporterBuilder {
             OTEL_EXPORTER_OTLP_TRACES_HEADERS,
             OTEL_EXPORTER_OTLP_TRACES_COMPRESSION,
             OTEL_EXPORTER_OTLP_TRACES_PROTOCOL,
+            super::self_diagnostics::SPANS,
         )?;
 
         Ok(crate::SpanExporter::from_http(client))
@@ -341,6 +361,7 @@ impl HttpExporterBuilder {
             OTEL_EXPORTER_OTLP_LOGS_HEADERS,
             OTEL_EXPORTER_OTLP_LOGS_COMPRESSION,
             OTEL_EXPORTER_OTLP_LOGS_PROTOCOL,
+            super::self_diagnostics::LOG_RECORDS,
         )?;
 
         Ok(crate::LogExporter::from_http(client))
@@ -365,6 +386,7 @@ impl HttpExporterBuilder {
             OTEL_EXPORTER_OTLP_METRICS_HEADERS,
             OTEL_EXPORTER_OTLP_METRICS_COMPRESSION,
             OTEL_EXPORTER_OTLP_METRICS_PROTOCOL,
+            super::self_diagnostics::DATA_POINTS,
         )?;
 
         Ok(crate::MetricExporter::from_http(client, temporality))
@@ -381,6 +403,7 @@ pub(crate) struct OtlpHttpClient {
     compression: Option<crate::Compression>,
     retry_policy: RetryPolicy,
     max_request_body_size: usize,
+    metrics: ExporterMetrics,
     #[allow(dead_code)]
     // <allow dead> would be removed once we support set_resource for metrics and traces.
     resource: opentelemetry_proto::transform::common::tonic::ResourceAttributesWithSchema,
@@ -393,20 +416,54 @@ impl OtlpHttpClient {
     /// Delays between retries adapt to the calling context: cooperative
     /// `tokio::time::sleep` inside a Tokio runtime, or `std::thread::sleep`
     /// on bare OS threads (the SDK's default batch processors).
+    ///
+    /// `items` is the number of items in `data`, and `handle_response` returns
+    /// the number of items rejected by the endpoint from the response body.
     async fn export_http_with_retry<F, T>(
         &self,
         data: T,
+        items: usize,
+        build_body_fn: F,
+        handle_response: fn(&[u8], Protocol) -> u64,
+        operation_name: &'static str,
+    ) -> opentelemetry_sdk::error::OTelSdkResult
+    where
+        F: Fn(&Self, T) -> Result<(Vec<u8>, &'static str, Option<&'static str>), String>,
+    {
+        self.send_http_with_retry(data, items, build_body_fn, handle_response, operation_name)
+            .await
+            .map_err(|(error, _)| error)
+    }
+
+    /// Same as [`Self::export_http_with_retry`], also returning the
+    /// classification of the last error, which tells whether the data can be
+    /// exported later.
+    async fn send_http_with_retry<F, T>(
+        &self,
+        data: T,
+        items: usize,
         build_body_fn: F,
+        handle_response: fn(&[u8], Protocol) -> u64,
         operation_name: &'static str,
-    ) -> Result<Bytes, opentelemetry_sdk::error::OTelSdkError>
+    ) -> Result<(), (opentelemetry_sdk::error::OTelSdkError, RetryErrorType)>
     where
         F: Fn(&Self, T) -> Result<(Vec<u8>, &'static str, Option<&'static str>), String>,
     {
-        use crate::retry::retry_with_backoff;
+        use crate::retry::retry_with_backoff_and_observer;
+
+        let operation = self.metrics.start_export(items);
 
         // Build request body once before retry loop
-        let (body, content_type, content_encoding) = build_body_fn(self, data)
-            .map_err(opentelemetry_sdk::error::OTelSdkError::InternalFailure)?;
+        let (body, content_type, content_encoding) = match build_body_fn(self, data) {
+            Ok(body) => body,
+            Err(e) => {
+                operation.failed("internal_failure", None);
+                return Err((
+                    opentelemetry_sdk::error::OTelSdkError::InternalFailure(e),
+                    RetryErrorType::NonRetryable,
+                ));
+            }
+        };
 
         let retry_data = Arc::new(HttpRetryData {
             body,
@@ -414,7 +471,7 @@ impl OtlpHttpClient {
             endpoint: self.collector_endpoint.to_string(),
         });
 
-        let response_body = retry_with_backoff(
+        let result = retry_with_backoff_and_observer(
             &self.retry_policy,
             self.timeout,
             classify_http_export_error,
@@ -423,21 +480,40 @@ impl OtlpHttpClient {
                 self.export_http_once(&retry_data, content_type, content_encoding, operation_name)
                     .await
             },
+            |error_type| self.metrics.record_retry(error_type),
         )
-        .await
-        .map_err(|e| opentelemetry_sdk::error::OTelSdkError::InternalFailure(e.message))?;
+        .await;
 
-        Ok(response_body)
+        match result {
+            Ok((status_code, response_body)) => {
+                let rejected = handle_response(&response_body, self.protocol);
+                operation.succeeded(rejected, Some(http_status_code(status_code)));
+                Ok(())
+            }
+            Err(e) => {
+                match e.status_code {
+                    0 => operation.failed("transport_error", None),
+                    status_code => operation
+                        .failed(status_code.to_string(), Some(http_status_code(status_code))),
+                }
+                let error_type = classify_http_export_error(&e);
+                Err((
+                    opentelemetry_sdk::error::OTelSdkError::InternalFailure(e.message),
+                    error_type,
+                ))
+            }
+        }
     }
 
-    /// Single HTTP export attempt - shared between retry and no-retry paths
+    /// Single HTTP export attempt - shared between retry and no-retry paths.
+    /// Returns the status code and the body of the response.
     async fn export_http_once(
         &self,
         retry_data: &HttpRetryData,
         content_type: &'static str,
         content_encoding: Option<&'static str>,
         _operation_name: &'static str,
-    ) -> Result<Bytes, HttpExportError> {
+    ) -> Result<(u16, Bytes), HttpExportError> {
         // Get client
         let client = self
             .client
@@ -514,7 +590,7 @@ impl OtlpHttpClient {
         otel_debug!(name: "HttpClient.ExportSucceeded");
 
         // Return the response, consuming the body to save a copy
-        Ok(response.into_body())
+        Ok((status_code, response.into_body()))
     }
 
     /// Compress data using gzip or zstd if the user has requested it and the relevant feature
@@ -522,6 +598,7 @@ impl OtlpHttpClient {
     /// we should catch this at exporter build time and never get here.
     fn process_body(&self, body: Vec<u8>) -> Result<(Vec<u8>, Option<&'static str>), String> {
         self.validate_request_body_size(&body, "uncompressed")?;
+        let uncompressed_size = body.len();
 
         let (processed_body, content_encoding) = match self.compression {
             #[cfg(feature = "gzip-http")]
@@ -556,6 +633,10 @@ impl OtlpHttpClient {
 
         if content_encoding.is_some() {
             self.validate_request_body_size(&processed_body, "compressed")?;
+            self.metrics
+                .record_payload(uncompressed_size, Some(processed_body.len()));
+        } else {
+            self.metrics.record_payload(uncompressed_size, None);
         }
 
         Ok((processed_body, content_encoding))
@@ -594,19 +675,26 @@ impl OtlpHttpClient {
             compression,
             retry_policy: retry_policy.unwrap_or_default(),
             max_request_body_size: DEFAULT_MAX_REQUEST_BODY_SIZE,
+            metrics: ExporterMetrics::default(),
             resource: ResourceAttributesWithSchema::default(),
         }
     }
 
     #[cfg(feature = "trace")]
-    fn build_trace_export_body(
+    pub(crate) fn build_trace_request(
         &self,
         spans: Vec<SpanData>,
-    ) -> Result<(Vec<u8>, &'static str, Option<&'static str>), String> {
+    ) -> opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest {
         use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
         let resource_spans = group_spans_by_resource_and_scope(spans, &self.resource);
+        ExportTraceServiceRequest { resource_spans }
+    }
 
-        let req = ExportTraceServiceRequest { resource_spans };
+    #[cfg(feature = "trace")]
+    fn build_trace_export_body(
+        &self,
+        req: opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest,
+    ) -> Result<(Vec<u8>, &'static str, Option<&'static str>), String> {
         let (body, content_type) = match self.protocol {
             #[cfg(feature = "http-json")]
             Protocol::HttpJson => match serde_json::to_string_pretty(&req) {
@@ -628,14 +716,20 @@ impl OtlpHttpClient {
     }
 
     #[cfg(feature = "logs")]
-    fn build_logs_export_body(
+    pub(crate) fn build_logs_request(
         &self,
-        logs: LogBatch<'_>,
-    ) -> Result<(Vec<u8>, &'static str, Option<&'static str>), String> {
+        logs: &LogBatch<'_>,
+    ) -> opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest {
         use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
-        let resource_logs = group_logs_by_resource_and_scope(&logs, &self.resource);
-        let req = ExportLogsServiceRequest { resource_logs };
+        let resource_logs = group_logs_by_resource_and_scope(logs, &self.resource);
+        ExportLogsServiceRequest { resource_logs }
+    }
 
+    #[cfg(feature = "logs")]
+    fn build_logs_export_body(
+        &self,
+        req: opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest,
+    ) -> Result<(Vec<u8>, &'static str, Option<&'static str>), String> {
         let (body, content_type) = match self.protocol {
             #[cfg(feature = "http-json")]
             Protocol::HttpJson => match serde_json::to_string_pretty(&req) {
@@ -1406,7 +1500,9 @@ mod tests {
             let client = create_test_client(crate::Protocol::HttpBinary, None);
             let span_data = create_test_span_data();
 
-            let result = client.build_trace_export_body(vec![span_data]).unwrap();
+            let result = client
+                .build_trace_export_body(client.build_trace_request(vec![span_data]))
+                .unwrap();
             let (_body, content_type, content_encoding) = result;
 
             assert_eq!(content_type, "application/x-protobuf");
@@ -1419,7 +1515,9 @@ mod tests {
             let client = create_test_client(crate::Protocol::HttpJson, None);
             let span_data = create_test_span_data();
 
-            let result = client.build_trace_export_body(vec![span_data]).unwrap();
+            let result = client
+                .build_trace_export_body(client.build_trace_request(vec![span_data]))
+                .unwrap();
             let (_body, content_type, content_encoding) = result;
 
             assert_eq!(content_type, "application/json");
@@ -1433,7 +1531,9 @@ mod tests {
                 create_test_client(crate::Protocol::HttpBinary, Some(crate::Compression::Gzip));
             let span_data = create_test_span_data();
 
-            let result = client.build_trace_export_body(vec![span_data]).unwrap();
+            let result = client
+                .build_trace_export_body(client.build_trace_request(vec![span_data]))
+                .unwrap();
             let (_body, content_type, content_encoding) = result;
 
             assert_eq!(content_type, "application/x-protobuf");
@@ -1454,7 +1554,9 @@ mod tests {
             let client = create_test_client(crate::Protocol::HttpBinary, None);
             let batch = create_test_log_batch();
 
-            let result = client.build_logs_export_body(batch).unwrap();
+            let result = client
+                .build_logs_export_body(client.build_logs_request(&batch))
+                .unwrap();
             let (_body, content_type, content_encoding) = result;
 
             assert_eq!(content_type, "application/x-protobuf");
@@ -1467,7 +1569,9 @@ mod tests {
             let client = create_test_client(crate::Protocol::HttpJson, None);
             let batch = create_test_log_batch();
 
-            let result = client.build_logs_export_body(batch).unwrap();
+            let result = client
+                .build_logs_export_body(client.build_logs_request(&batch))
+                .unwrap();
             let (_body, content_type, content_encoding) = result;
 
             assert_eq!(content_type, "application/json");
@@ -1481,7 +1585,9 @@ mod tests {
                 create_test_client(crate::Protocol::HttpBinary, Some(crate::Compression::Gzip));
             let batch = create_test_log_batch();
 
-            let result = client.build_logs_export_body(batch).unwrap();
+            let result = client
+                .build_logs_export_body(client.build_logs_request(&batch))
+                .unwrap();
             let (_body, content_type, content_encoding) = result;
 
             assert_eq!(content_type, "application/x-protobuf");
@@ -1815,7 +1921,9 @@ mod tests {
 
             let result = futures_executor::block_on(client.export_http_with_retry(
                 (),
+                1,
                 build_processed_test_body,
+                |_, _| 0,
                 "test",
             ));
 
@@ -1840,7 +1948,9 @@ mod tests {
 
             let result = futures_executor::block_on(client.export_http_with_retry(
                 (),
+                1,
                 build_test_body,
+                |_, _| 0,
                 "test",
             ));
 
@@ -1858,7 +1968,9 @@ mod tests {
 
             let result = futures_executor::block_on(client.export_http_with_retry(
                 (),
+                1,
                 build_test_body,
+                |_, _| 0,
                 "test",
             ));
 
@@ -1876,7 +1988,9 @@ mod tests {
 
             let result = futures_executor::block_on(client.export_http_with_retry(
                 (),
+                1,
                 build_test_body,
+                |_, _| 0,
                 "test",
             ));
 
@@ -1902,7 +2016,9 @@ mod tests {
             let start = std::time::Instant::now();
             let result = futures_executor::block_on(client.export_http_with_retry(
                 (),
+                1,
                 build_test_body,
+                |_, _| 0,
                 "test",
             ));
 
@@ -1930,7 +2046,9 @@ mod tests {
             let start = std::time::Instant::now();
             let result = futures_executor::block_on(client.export_http_with_retry(
                 (),
+                1,
                 build_test_body,
+                |_, _| 0,
                 "test",
             ));
 
@@ -1939,6 +2057,56 @@ mod tests {
             assert!(start.elapsed() >= std::time::Duration::from_millis(900));
         }
 
+        #[test]
+        fn export_metrics_are_recorded() {
+            use crate::exporter::self_diagnostics::{
+                tests::metric_value, DiagnosticsMeter, ExporterMetrics, SPANS,
+            };
+            use opentelemetry_sdk::metrics::{InMemoryMetricExporter, SdkMeterProvider};
+
+            let exporter = InMemoryMetricExporter::default();
+            let provider = SdkMeterProvider::builder()
+                .with_periodic_exporter(exporter.clone())
+                .build();
+            let mock = Arc::new(SequencedMockClient::new(vec![
+                http::Response::builder()
+                    .status(503)
+                    .body(Bytes::new())
+                    .unwrap(),
+                http::Response::builder()
+                    .status(200)
+                    .body(Bytes::new())
+                    .unwrap(),
+            ]));
+            let mut client = make_client(mock.clone(), retry_policy());
+            client.metrics = ExporterMetrics::new(
+                DiagnosticsMeter::new(&provider),
+                SPANS,
+                "http",
+                Some(&client.collector_endpoint),
+            );
+
+            let result = futures_executor::block_on(client.export_http_with_retry(
+                (),
+                4,
+                build_processed_test_body,
+                |_, _| 1,
+                "test",
+            ));
+            provider.force_flush().unwrap();
+
+            assert!(result.is_ok());
+            let exported = "otel.sdk.exporter.span.exported";
+            assert_eq!(metric_value(&exporter, exported, None), 3);
+            assert_eq!(metric_value(&exporter, exported, Some("rejected")), 1);
+            let retries = "otel.sdk.exporter.operation.retries";
+            assert_eq!(metric_value(&exporter, retries, None), 1);
+            let duration = "otel.sdk.exporter.operation.duration";
+            assert_eq!(metric_value(&exporter, duration, None), 1);
+            let uncompressed_size = "otel.sdk.exporter.payload.uncompressed_size";
+            assert_eq!(metric_value(&exporter, uncompressed_size, None), 3);
+        }
+
         #[test]
         fn retries_on_network_error() {
             let mock = Arc::new(NetworkFailureMockClient::new(2));
@@ -1946,7 +2114,9 @@ mod tests {
 
             let result = futures_executor::block_on(client.export_http_with_retry(
                 (),
+                1,
                 build_test_body,
+                |_, _| 0,
                 "test",
             ));
 
@@ -1,6 +1,9 @@
 use super::OtlpHttpClient;
+use crate::persistent_queue::SendError;
+use crate::retry::RetryErrorType;
 use crate::Protocol;
 use opentelemetry::{otel_debug, otel_warn};
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
 use opentelemetry_sdk::{
     error::{OTelSdkError, OTelSdkResult},
     trace::{SpanData, SpanExporter},
@@ -8,18 +11,34 @@ use opentelemetry_sdk::{
 #[cfg(feature = "http-proto")]
 use prost::Message;
 
+impl OtlpHttpClient {
+    pub(crate) async fn export_trace_request(
+        &self,
+        request: ExportTraceServiceRequest,
+        items: usize,
+    ) -> Result<(), SendError> {
+        self.send_http_with_retry(
+            request,
+            items,
+            OtlpHttpClient::build_trace_export_body,
+            handle_partial_success,
+            "HttpTracesClient.Export",
+        )
+        .await
+        .map_err(|(error, error_type)| SendError {
+            error,
+            retryable: error_type != RetryErrorType::NonRetryable,
+        })
+    }
+}
+
 impl SpanExporter for OtlpHttpClient {
     async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
-        let response_body = self
-            .export_http_with_retry(
-                batch,
-                OtlpHttpClient::build_trace_export_body,
-                "HttpTracesClient.Export",
-            )
-            .await?;
-
-        handle_partial_success(&response_body, self.protocol);
-        Ok(())
+        let items = batch.len();
+        let request = self.build_trace_request(batch);
+        self.export_trace_request(request, items)
+            .await
+            .map_err(|e| e.error)
     }
 
     fn shutdown(&self) -> OTelSdkResult {
@@ -40,8 +59,8 @@ impl SpanExporter for OtlpHttpClient {
 }
 
 /// Handles partial success returned by OTLP endpoints. We log the rejected spans,
-/// as well as the error message returned.
-fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
+/// as well as the error message returned, and return the number of rejected spans.
+fn handle_partial_success(response_body: &[u8], protocol: Protocol) -> u64 {
     use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceResponse;
 
     let response: ExportTraceServiceResponse = match protocol {
@@ -50,7 +69,7 @@ fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
             Ok(r) => r,
             Err(e) => {
                 otel_debug!(name: "HttpTraceClient.ResponseParseError", error = e.to_string());
-                return;
+                return 0;
             }
         },
         #[cfg(feature = "http-proto")]
@@ -58,7 +77,7 @@ fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
             Ok(r) => r,
             Err(e) => {
                 otel_debug!(name: "HttpTraceClient.ResponseParseError", error = e.to_string());
-                return;
+                return 0;
             }
         },
         #[cfg(feature = "grpc-tonic")]
@@ -67,14 +86,18 @@ fn handle_partial_success(response_body: &[u8], protocol: Protocol) {
         }
     };
 
-    if let Some(partial_success) = response.partial_success {
-        if partial_success.rejected_spans > 0 || !partial_success.error_message.is_empty() {
-            otel_warn!(
-                name: "HttpTraceClient.PartialSuccess",
-                rejected_spans = partial_success.rejected_spans,
-                error_message = partial_success.error_message.as_str(),
-            );
+    match response.partial_success {
+        Some(partial_success) => {
+            if partial_success.rejected_spans > 0 || !partial_success.error_message.is_empty() {
+                otel_warn!(
+                    name: "HttpTraceClient.PartialSuccess",
+                    rejected_spans = partial_success.rejected_spans,
+                    error_message = partial_success.error_message.as_str(),
+                );
+            }
+            partial_success.rejected_spans.max(0) as u64
         }
+        None => 0,
     }
 }
 
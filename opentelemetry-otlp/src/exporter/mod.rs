@@ -57,6 +57,8 @@ const OTEL_EXPORTER_OTLP_HTTP_ENDPOINT_DEFAULT: &str = "http://localhost:4318";
 
 #[cfg(any(feature = "http-proto", feature = "http-json"))]
 pub(crate) mod http;
+#[cfg(any(feature = "grpc-tonic", feature = "http-proto", feature = "http-json"))]
+pub(crate) mod self_diagnostics;
 #[cfg(feature = "grpc-tonic")]
 pub(crate) mod tonic;
 
@@ -79,6 +81,10 @@ pub(crate) struct ExportConfig {
     ///
     /// Note: Programmatically setting this will override any value set via the environment variable.
     pub timeout: Option<Duration>,
+
+    /// The meter of the self-diagnostics metrics of the exporter.
+    #[cfg(any(feature = "grpc-tonic", feature = "http-proto", feature = "http-json"))]
+    pub meter: self_diagnostics::DiagnosticsMeter,
 }
 
 /// Resolve protocol with priority:
@@ -302,6 +308,31 @@ pub trait WithExportConfig {
     ///
     /// Note: Programmatically setting this will override any value set via the environment variable.
     fn with_timeout(self, timeout: Duration) -> Self;
+    /// Report the self-diagnostics metrics of the exporter to `meter_provider`.
+    ///
+    /// The metrics follow the [semantic conventions for SDK metrics]:
+    /// `otel.sdk.exporter.{span,log,metric_data_point}.exported` and
+    /// `otel.sdk.exporter.{span,log,metric_data_point}.inflight` count the
+    /// exported items, the items rejected by the endpoint being reported with
+    /// `error.type=rejected`, and `otel.sdk.exporter.operation.duration`
+    /// records the duration of the exports. In addition,
+    /// `otel.sdk.exporter.operation.retries` counts the retried requests by
+    /// `otel.sdk.exporter.retry.type` (`retryable` or `throttled`), and
+    /// `otel.sdk.exporter.payload.uncompressed_size` and
+    /// `otel.sdk.exporter.payload.compressed_size` the bytes of the request
+    /// payloads. The compressed size is only reported by the HTTP exporters.
+    ///
+    /// No metric is reported by default.
+    ///
+    /// [semantic conventions for SDK metrics]: https://github.com/open-telemetry/semantic-conventions/blob/main/docs/otel/sdk-metrics.md
+    #[cfg(all(
+        feature = "metrics",
+        any(feature = "grpc-tonic", feature = "http-proto", feature = "http-json")
+    ))]
+    fn with_meter_provider(
+        self,
+        meter_provider: &impl opentelemetry::metrics::MeterProvider,
+    ) -> Self;
 }
 
 impl<B: HasExportConfig> WithExportConfig for B {
@@ -319,6 +350,18 @@ impl<B: HasExportConfig> WithExportConfig for B {
         self.export_config().timeout = Some(timeout);
         self
     }
+
+    #[cfg(all(
+        feature = "metrics",
+        any(feature = "grpc-tonic", feature = "http-proto", feature = "http-json")
+    ))]
+    fn with_meter_provider(
+        mut self,
+        meter_provider: &impl opentelemetry::metrics::MeterProvider,
+    ) -> Self {
+        self.export_config().meter = self_diagnostics::DiagnosticsMeter::new(meter_provider);
+        self
+    }
 }
 
 #[cfg(any(feature = "grpc-tonic", feature = "http-proto", feature = "http-json"))]
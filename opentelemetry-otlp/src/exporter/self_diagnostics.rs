@@ -0,0 +1,565 @@
+//! Self-diagnostics metrics of the OTLP exporters, following the
+//! [semantic conventions for SDK metrics].
+//!
+//! The metrics are only recorded when a `MeterProvider` is passed to
+//! [`WithExportConfig::with_meter_provider`], and require the `metrics`
+//! feature.
+//!
+//! [semantic conventions for SDK metrics]: https://github.com/open-telemetry/semantic-conventions/blob/main/docs/otel/sdk-metrics.md
+//! [`WithExportConfig::with_meter_provider`]: crate::WithExportConfig::with_meter_provider
+use crate::retry::RetryErrorType;
+#[cfg(feature = "metrics")]
+use opentelemetry::metrics::{Counter, Histogram, Meter, UpDownCounter};
+use opentelemetry::KeyValue;
+#[cfg(feature = "metrics")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
+/// Bucket boundaries of `otel.sdk.exporter.operation.duration`, in seconds.
+#[cfg(feature = "metrics")]
+const OPERATION_DURATION_BOUNDARIES: [f64; 14] = [
+    0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0,
+];
+
+/// The telemetry items exported by an exporter.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+pub(crate) struct ExportedItems {
+    /// The item in the metric names, e.g. `otel.sdk.exporter.span.exported`.
+    name: &'static str,
+    /// The unit of the item metrics.
+    unit: &'static str,
+    /// The signal in the component type, e.g. `otlp_grpc_span_exporter`.
+    signal: &'static str,
+}
+
+#[cfg(feature = "trace")]
+pub(crate) const SPANS: ExportedItems = ExportedItems {
+    name: "span",
+    unit: "{span}",
+    signal: "span",
+};
+
+#[cfg(feature = "logs")]
+pub(crate) const LOG_RECORDS: ExportedItems = ExportedItems {
+    name: "log",
+    unit: "{log_record}",
+    signal: "log",
+};
+
+#[cfg(feature = "metrics")]
+pub(crate) const DATA_POINTS: ExportedItems = ExportedItems {
+    name: "metric_data_point",
+    unit: "{data_point}",
+    signal: "metric",
+};
+
+/// The meter of the self-diagnostics metrics, set with
+/// [`WithExportConfig::with_meter_provider`].
+///
+/// [`WithExportConfig::with_meter_provider`]: crate::WithExportConfig::with_meter_provider
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DiagnosticsMeter(#[cfg(feature = "metrics")] Option<Meter>);
+
+#[cfg(feature = "metrics")]
+impl DiagnosticsMeter {
+    pub(crate) fn new(meter_provider: &impl opentelemetry::metrics::MeterProvider) -> Self {
+        let scope = opentelemetry::InstrumentationScope::builder("opentelemetry-otlp")
+            .with_version(env!("CARGO_PKG_VERSION"))
+            .build();
+        DiagnosticsMeter(Some(meter_provider.meter_with_scope(scope)))
+    }
+}
+
+/// The self-diagnostics metrics of an exporter.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ExporterMetrics {
+    #[cfg(feature = "metrics")]
+    instruments: Option<Arc<Instruments>>,
+}
+
+#[cfg(feature = "metrics")]
+#[derive(Debug)]
+struct Instruments {
+    attributes: Vec<KeyValue>,
+    exported: Counter<u64>,
+    inflight: UpDownCounter<i64>,
+    duration: Histogram<f64>,
+    retries: Counter<u64>,
+    uncompressed_size: Counter<u64>,
+    compressed_size: Counter<u64>,
+}
+
+impl ExporterMetrics {
+    /// Creates the metrics of an exporter of `items` using `transport`
+    /// (`grpc`, `http` or `http_json`) to send them to `endpoint`, or no-op
+    /// metrics without meter.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn new(
+        meter: DiagnosticsMeter,
+        items: ExportedItems,
+        transport: &'static str,
+        endpoint: Option<&http::Uri>,
+    ) -> Self {
+        #[cfg(feature = "metrics")]
+        if let Some(meter) = meter.0 {
+            return ExporterMetrics::with_meter(&meter, items, transport, endpoint);
+        }
+        ExporterMetrics::default()
+    }
+
+    #[cfg(feature = "metrics")]
+    fn with_meter(
+        meter: &Meter,
+        items: ExportedItems,
+        transport: &'static str,
+        endpoint: Option<&http::Uri>,
+    ) -> Self {
+        let component_type = format!("otlp_{transport}_{}_exporter", items.signal);
+        let component_name = format!("{component_type}/{}", next_component_id(&component_type));
+        let mut attributes = vec![
+            KeyValue::new("otel.component.type", component_type),
+            KeyValue::new("otel.component.name", component_name),
+        ];
+        if let Some(host) = endpoint.and_then(|endpoint| endpoint.host()) {
+            attributes.push(KeyValue::new("server.address", host.to_string()));
+            let port = endpoint.and_then(|endpoint| {
+                endpoint.port_u16().or_else(|| match endpoint.scheme_str() {
+                    Some("https") => Some(443),
+                    Some("http") => Some(80),
+                    _ => None,
+                })
+            });
+            if let Some(port) = port {
+                attributes.push(KeyValue::new("server.port", i64::from(port)));
+            }
+        }
+
+        let instruments = Instruments {
+            attributes,
+            exported: meter
+                .u64_counter(format!("otel.sdk.exporter.{}.exported", items.name))
+                .with_description(
+                    "The number of items for which the export has finished, either successful or failed.",
+                )
+                .with_unit(items.unit)
+                .build(),
+            inflight: meter
+                .i64_up_down_counter(format!("otel.sdk.exporter.{}.inflight", items.name))
+                .with_description(
+                    "The number of items in the export requests that have been sent but have not finished yet.",
+                )
+                .with_unit(items.unit)
+                .build(),
+            duration: meter
+                .f64_histogram("otel.sdk.exporter.operation.duration")
+                .with_description("The duration of exporting a batch of items, retries included.")
+                .with_unit("s")
+                .with_boundaries(OPERATION_DURATION_BOUNDARIES.to_vec())
+                .build(),
+            retries: meter
+                .u64_counter("otel.sdk.exporter.operation.retries")
+                .with_description("The number of export requests that have been retried.")
+                .with_unit("{retry}")
+                .build(),
+            uncompressed_size: meter
+                .u64_counter("otel.sdk.exporter.payload.uncompressed_size")
+                .with_description("The size of the export request payloads before compression.")
+                .with_unit("By")
+                .build(),
+            compressed_size: meter
+                .u64_counter("otel.sdk.exporter.payload.compressed_size")
+                .with_description("The size of the compressed export request payloads.")
+                .with_unit("By")
+                .build(),
+        };
+        ExporterMetrics {
+            instruments: Some(Arc::new(instruments)),
+        }
+    }
+
+    /// Starts tracking the export of `items` items.
+    pub(crate) fn start_export(&self, items: usize) -> ExportOperation<'_> {
+        #[cfg(feature = "metrics")]
+        if let Some(instruments) = &self.instruments {
+            instruments
+                .inflight
+                .add(items as i64, &instruments.attributes);
+        }
+        ExportOperation {
+            metrics: self,
+            items,
+            #[cfg(feature = "metrics")]
+            start: Instant::now(),
+            finished: false,
+        }
+    }
+
+    /// Records a retry of an export request that failed with an error
+    /// classified as `error_type`.
+    pub(crate) fn record_retry(&self, error_type: &RetryErrorType) {
+        #[cfg(feature = "metrics")]
+        if let Some(instruments) = &self.instruments {
+            let retry_type = match error_type {
+                RetryErrorType::Throttled(_) => "throttled",
+                _ => "retryable",
+            };
+            instruments.retries.add(
+                1,
+                &with_attributes(
+                    &instruments.attributes,
+                    [Some(KeyValue::new(
+                        "otel.sdk.exporter.retry.type",
+                        retry_type,
+                    ))],
+                ),
+            );
+        }
+        #[cfg(not(feature = "metrics"))]
+        let _ = error_type;
+    }
+
+    /// Records the size of an export request payload, before and after
+    /// compression if it is compressed.
+    pub(crate) fn record_payload(&self, uncompressed_size: usize, compressed_size: Option<usize>) {
+        #[cfg(feature = "metrics")]
+        if let Some(instruments) = &self.instruments {
+            instruments
+                .uncompressed_size
+                .add(uncompressed_size as u64, &instruments.attributes);
+            if let Some(compressed_size) = compressed_size {
+                instruments
+                    .compressed_size
+                    .add(compressed_size as u64, &instruments.attributes);
+            }
+        }
+        #[cfg(not(feature = "metrics"))]
+        let _ = (uncompressed_size, compressed_size);
+    }
+}
+
+/// Returns the number of data points in `metrics`.
+#[cfg(feature = "metrics")]
+pub(crate) fn data_point_count(
+    metrics: &opentelemetry_sdk::metrics::data::ResourceMetrics,
+) -> usize {
+    use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+
+    fn count<T>(data: &MetricData<T>) -> usize {
+        match data {
+            MetricData::Gauge(gauge) => gauge.data_points().count(),
+            MetricData::Sum(sum) => sum.data_points().count(),
+            MetricData::Histogram(histogram) => histogram.data_points().count(),
+            MetricData::ExponentialHistogram(histogram) => histogram.data_points().count(),
+        }
+    }
+
+    metrics
+        .scope_metrics()
+        .flat_map(|scope_metrics| scope_metrics.metrics())
+        .map(|metric| match metric.data() {
+            AggregatedMetrics::F64(data) => count(data),
+            AggregatedMetrics::U64(data) => count(data),
+            AggregatedMetrics::I64(data) => count(data),
+        })
+        .sum()
+}
+
+/// Runs `export` with the telemetry of the current thread suppressed while it
+/// is polled. The telemetry of the export itself, e.g. the logs of the HTTP
+/// client, must not be exported again.
+#[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
+pub(crate) async fn suppress_telemetry<F: std::future::Future>(export: F) -> F::Output {
+    let mut export = std::pin::pin!(export);
+    std::future::poll_fn(|cx| {
+        let _guard = opentelemetry::Context::enter_telemetry_suppressed_scope();
+        export.as_mut().poll(cx)
+    })
+    .await
+}
+
+/// Returns the next id of the exporters of `component_type`.
+#[cfg(feature = "metrics")]
+fn next_component_id(component_type: &str) -> usize {
+    static NEXT_IDS: Mutex<Vec<(String, usize)>> = Mutex::new(Vec::new());
+    let mut next_ids = NEXT_IDS.lock().unwrap_or_else(|e| e.into_inner());
+    match next_ids.iter_mut().find(|(ty, _)| ty == component_type) {
+        Some((_, next_id)) => {
+            *next_id += 1;
+            *next_id - 1
+        }
+        None => {
+            next_ids.push((component_type.to_string(), 1));
+            0
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn with_attributes<const N: usize>(
+    attributes: &[KeyValue],
+    extra: [Option<KeyValue>; N],
+) -> Vec<KeyValue> {
+    attributes
+        .iter()
+        .cloned()
+        .chain(extra.into_iter().flatten())
+        .collect()
+}
+
+/// An export in progress.
+///
+/// An export dropped before it finishes, e.g. because the export future was
+/// cancelled, is recorded as failed with `error.type=cancelled`.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+pub(crate) struct ExportOperation<'a> {
+    metrics: &'a ExporterMetrics,
+    items: usize,
+    #[cfg(feature = "metrics")]
+    start: Instant,
+    finished: bool,
+}
+
+impl ExportOperation<'_> {
+    /// Records the export as successful, except for the `rejected` items the
+    /// endpoint rejected. `status` is the status code of the response.
+    pub(crate) fn succeeded(mut self, rejected: u64, status: Option<KeyValue>) {
+        self.finish(None, status, rejected);
+    }
+
+    /// Records the export as failed with `error_type`. `status` is the status
+    /// code of the response, if one was received.
+    pub(crate) fn failed(mut self, error_type: impl Into<String>, status: Option<KeyValue>) {
+        self.finish(Some(error_type.into()), status, 0);
+    }
+
+    /// Records the export as failed with the status of a gRPC response.
+    #[cfg(feature = "grpc-tonic")]
+    pub(crate) fn failed_with_grpc_status(self, status: &tonic::Status) {
+        self.failed(
+            grpc_code_name(status.code()),
+            Some(grpc_status_code(status.code())),
+        )
+    }
+
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn finish(&mut self, error_type: Option<String>, status: Option<KeyValue>, rejected: u64) {
+        self.finished = true;
+        #[cfg(feature = "metrics")]
+        if let Some(instruments) = &self.metrics.instruments {
+            let items = self.items as u64;
+            let attributes = &instruments.attributes;
+            instruments.inflight.add(-(self.items as i64), attributes);
+            let error_type = error_type.map(|error_type| KeyValue::new("error.type", error_type));
+            match &error_type {
+                Some(error_type) => instruments.exported.add(
+                    items,
+                    &with_attributes(attributes, [Some(error_type.clone())]),
+                ),
+                None => {
+                    let rejected = rejected.min(items);
+                    if rejected < items {
+                        instruments.exported.add(items - rejected, attributes);
+                    }
+                    if rejected > 0 {
+                        instruments.exported.add(
+                            rejected,
+                            &with_attributes(
+                                attributes,
+                                [Some(KeyValue::new("error.type", "rejected"))],
+                            ),
+                        );
+                    }
+                }
+            }
+            instruments.duration.record(
+                self.start.elapsed().as_secs_f64(),
+                &with_attributes(attributes, [error_type, status]),
+            );
+        }
+    }
+}
+
+impl Drop for ExportOperation<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.finish(Some("cancelled".into()), None, 0);
+        }
+    }
+}
+
+/// The `rpc.grpc.status_code` attribute of a gRPC response.
+#[cfg(feature = "grpc-tonic")]
+pub(crate) fn grpc_status_code(code: tonic::Code) -> KeyValue {
+    KeyValue::new("rpc.grpc.status_code", code as i64)
+}
+
+/// The `http.response.status_code` attribute of an HTTP response.
+#[cfg(any(feature = "http-proto", feature = "http-json"))]
+pub(crate) fn http_status_code(status_code: u16) -> KeyValue {
+    KeyValue::new("http.response.status_code", i64::from(status_code))
+}
+
+/// The name of a gRPC status code, as used for `error.type`.
+#[cfg(feature = "grpc-tonic")]
+fn grpc_code_name(code: tonic::Code) -> &'static str {
+    match code {
+        tonic::Code::Ok => "OK",
+        tonic::Code::Cancelled => "CANCELLED",
+        tonic::Code::Unknown => "UNKNOWN",
+        tonic::Code::InvalidArgument => "INVALID_ARGUMENT",
+        tonic::Code::DeadlineExceeded => "DEADLINE_EXCEEDED",
+        tonic::Code::NotFound => "NOT_FOUND",
+        tonic::Code::AlreadyExists => "ALREADY_EXISTS",
+        tonic::Code::PermissionDenied => "PERMISSION_DENIED",
+        tonic::Code::ResourceExhausted => "RESOURCE_EXHAUSTED",
+        tonic::Code::FailedPrecondition => "FAILED_PRECONDITION",
+        tonic::Code::Aborted => "ABORTED",
+        tonic::Code::OutOfRange => "OUT_OF_RANGE",
+        tonic::Code::Unimplemented => "UNIMPLEMENTED",
+        tonic::Code::Internal => "INTERNAL",
+        tonic::Code::Unavailable => "UNAVAILABLE",
+        tonic::Code::DataLoss => "DATA_LOSS",
+        tonic::Code::Unauthenticated => "UNAUTHENTICATED",
+    }
+}
+
+#[cfg(all(test, feature = "metrics"))]
+pub(crate) mod tests {
+    use super::*;
+    use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+    use opentelemetry_sdk::metrics::{InMemoryMetricExporter, SdkMeterProvider};
+    use std::time::Duration;
+
+    /// Returns the value of the sums, or the count of the histograms, named
+    /// `name` exported to `exporter`, summed over the data points with
+    /// `error.type=error_type`.
+    pub(crate) fn metric_value(
+        exporter: &InMemoryMetricExporter,
+        name: &str,
+        error_type: Option<&str>,
+    ) -> i64 {
+        fn has_error_type<'a>(
+            mut attributes: impl Iterator<Item = &'a KeyValue>,
+            error_type: Option<&str>,
+        ) -> bool {
+            attributes
+                .find(|kv| kv.key.as_str() == "error.type")
+                .map(|kv| kv.value.to_string())
+                .as_deref()
+                == error_type
+        }
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let Some(metric) = metrics
+            .last()
+            .into_iter()
+            .flat_map(|metrics| metrics.scope_metrics())
+            .flat_map(|scope_metrics| scope_metrics.metrics())
+            .find(|metric| metric.name() == name)
+        else {
+            return 0;
+        };
+        match metric.data() {
+            AggregatedMetrics::U64(MetricData::Sum(sum)) => sum
+                .data_points()
+                .filter(|dp| has_error_type(dp.attributes(), error_type))
+                .map(|dp| dp.value() as i64)
+                .sum(),
+            AggregatedMetrics::I64(MetricData::Sum(sum)) => sum
+                .data_points()
+                .filter(|dp| has_error_type(dp.attributes(), error_type))
+                .map(|dp| dp.value())
+                .sum(),
+            AggregatedMetrics::F64(MetricData::Histogram(histogram)) => histogram
+                .data_points()
+                .filter(|dp| has_error_type(dp.attributes(), error_type))
+                .map(|dp| dp.count() as i64)
+                .sum(),
+            data => panic!("unexpected data for {name}: {data:?}"),
+        }
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn export_operations_are_recorded() {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(exporter.clone())
+            .build();
+        let endpoint: http::Uri = "https://collector.example.com/v1/traces".parse().unwrap();
+        let metrics = ExporterMetrics::new(
+            DiagnosticsMeter::new(&provider),
+            SPANS,
+            "http",
+            Some(&endpoint),
+        );
+
+        metrics.start_export(10).succeeded(3, None);
+        metrics.start_export(5).failed("503", None);
+        let cancelled = metrics.start_export(2);
+        metrics.record_retry(&RetryErrorType::Retryable);
+        metrics.record_retry(&RetryErrorType::Throttled(Duration::from_secs(1)));
+        metrics.record_payload(100, Some(40));
+        metrics.record_payload(50, None);
+        provider.force_flush().unwrap();
+
+        let exported = "otel.sdk.exporter.span.exported";
+        let inflight = "otel.sdk.exporter.span.inflight";
+        let duration = "otel.sdk.exporter.operation.duration";
+        assert_eq!(metric_value(&exporter, exported, None), 7);
+        assert_eq!(metric_value(&exporter, exported, Some("rejected")), 3);
+        assert_eq!(metric_value(&exporter, exported, Some("503")), 5);
+        assert_eq!(metric_value(&exporter, inflight, None), 2);
+        assert_eq!(metric_value(&exporter, duration, None), 1);
+        assert_eq!(metric_value(&exporter, duration, Some("503")), 1);
+        let retries = "otel.sdk.exporter.operation.retries";
+        assert_eq!(metric_value(&exporter, retries, None), 2);
+        let uncompressed_size = "otel.sdk.exporter.payload.uncompressed_size";
+        assert_eq!(metric_value(&exporter, uncompressed_size, None), 150);
+        let compressed_size = "otel.sdk.exporter.payload.compressed_size";
+        assert_eq!(metric_value(&exporter, compressed_size, None), 40);
+
+        drop(cancelled);
+        provider.force_flush().unwrap();
+        assert_eq!(metric_value(&exporter, inflight, None), 0);
+        assert_eq!(metric_value(&exporter, exported, Some("cancelled")), 2);
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let attributes: Vec<(String, String)> = metrics
+            .last()
+            .into_iter()
+            .flat_map(|metrics| metrics.scope_metrics())
+            .flat_map(|scope_metrics| scope_metrics.metrics())
+            .find(|metric| metric.name() == retries)
+            .map(|metric| match metric.data() {
+                AggregatedMetrics::U64(MetricData::Sum(sum)) => sum
+                    .data_points()
+                    .flat_map(|dp| dp.attributes())
+                    .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .unwrap_or_default();
+        for (key, value) in [
+            ("otel.component.type", "otlp_http_span_exporter"),
+            ("server.address", "collector.example.com"),
+            ("server.port", "443"),
+            ("otel.sdk.exporter.retry.type", "retryable"),
+            ("otel.sdk.exporter.retry.type", "throttled"),
+        ] {
+            assert!(
+                attributes.contains(&(key.to_string(), value.to_string())),
+                "missing {key}={value} in {attributes:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn no_metrics_without_meter() {
+        let metrics = ExporterMetrics::new(DiagnosticsMeter::default(), DATA_POINTS, "grpc", None);
+        assert!(metrics.instruments.is_none());
+        metrics.start_export(1).succeeded(0, None);
+    }
+}
@@ -5,20 +5,25 @@ use opentelemetry_proto::tonic::collector::logs::v1::{
 };
 use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
 use opentelemetry_sdk::logs::{LogBatch, LogExporter};
-use std::sync::{Arc, Mutex};
+use prost::Message;
+use std::sync::Mutex;
 use std::time;
 use tonic::{codegen::CompressionEncoding, service::Interceptor, transport::Channel, Request};
 
 use opentelemetry_proto::transform::logs::tonic::group_logs_by_resource_and_scope;
 
 use super::BoxInterceptor;
+use crate::exporter::self_diagnostics::{grpc_status_code, ExporterMetrics};
 
-use crate::retry::RetryPolicy;
+use crate::persistent_queue::SendError;
+use crate::retry::{RetryErrorType, RetryPolicy};
+use crate::retry_classification::grpc::classify_tonic_status;
 
 pub(crate) struct TonicLogsClient {
     inner: Mutex<Option<ClientInner>>,
     retry_policy: RetryPolicy,
     timeout: std::time::Duration,
+    metrics: ExporterMetrics,
     #[allow(dead_code)]
     // <allow dead> would be removed once we support set_resource for metrics.
     resource: opentelemetry_proto::transform::common::tonic::ResourceAttributesWithSchema,
@@ -42,6 +47,7 @@ impl TonicLogsClient {
         compression: Option<CompressionEncoding>,
         retry_policy: Option<RetryPolicy>,
         timeout: std::time::Duration,
+        metrics: ExporterMetrics,
     ) -> Self {
         let mut client = LogsServiceClient::new(channel);
         if let Some(compression) = compression {
@@ -59,23 +65,33 @@ impl TonicLogsClient {
             })),
             retry_policy: retry_policy.unwrap_or_default(),
             timeout,
+            metrics,
             resource: Default::default(),
         }
     }
 }
 
-impl LogExporter for TonicLogsClient {
-    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
-        let batch = Arc::new(batch);
+impl TonicLogsClient {
+    pub(crate) fn build_request(&self, batch: &LogBatch<'_>) -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: group_logs_by_resource_and_scope(batch, &self.resource),
+        }
+    }
+
+    pub(crate) async fn export_request(
+        &self,
+        request: ExportLogsServiceRequest,
+        items: usize,
+    ) -> Result<(), SendError> {
+        let operation = self.metrics.start_export(items);
 
         match super::tonic_retry_with_backoff(
             &self.retry_policy,
             self.timeout,
-            crate::retry_classification::grpc::classify_tonic_status,
+            classify_tonic_status,
             "TonicLogsClient.Export",
+            &self.metrics,
             || async {
-                let batch_clone = Arc::clone(&batch);
-
                 // Execute the export operation
                 let (mut client, metadata, extensions) = self
                     .inner
@@ -97,43 +113,62 @@ impl LogExporter for TonicLogsClient {
                         )),
                     })?;
 
-                let resource_logs = group_logs_by_resource_and_scope(&batch_clone, &self.resource);
-
                 otel_debug!(name: "TonicLogsClient.ExportStarted");
 
+                let request = request.clone();
+                self.metrics.record_payload(request.encoded_len(), None);
+
                 client
-                    .export(Request::from_parts(
-                        metadata,
-                        extensions,
-                        ExportLogsServiceRequest { resource_logs },
-                    ))
+                    .export(Request::from_parts(metadata, extensions, request))
                     .await
                     .map(|response| {
                         otel_debug!(name: "TonicLogsClient.ExportSucceeded");
 
                         // Handle partial success. As per spec, we log and _do not_ retry.
-                        if let Some(partial_success) = response.into_inner().partial_success {
-                            if partial_success.rejected_log_records > 0
-                                || !partial_success.error_message.is_empty()
-                            {
-                                otel_warn!(
-                                    name: "TonicLogsClient.PartialSuccess",
-                                    rejected_log_records = partial_success.rejected_log_records,
-                                    error_message = partial_success.error_message.as_str(),
-                                );
+                        match response.into_inner().partial_success {
+                            Some(partial_success) => {
+                                if partial_success.rejected_log_records > 0
+                                    || !partial_success.error_message.is_empty()
+                                {
+                                    otel_warn!(
+                                        name: "TonicLogsClient.PartialSuccess",
+                                        rejected_log_records = partial_success.rejected_log_records,
+                                        error_message = partial_success.error_message.as_str(),
+                                    );
+                                }
+                                partial_success.rejected_log_records.max(0) as u64
                             }
+                            None => 0,
                         }
                     })
             },
         )
         .await
         {
-            Ok(_) => Ok(()),
+            Ok(rejected) => {
+                operation.succeeded(rejected, Some(grpc_status_code(tonic::Code::Ok)));
+                Ok(())
+            }
             Err(tonic_status) => {
-                super::handle_tonic_export_error!("TonicLogsClient", tonic_status)
+                operation.failed_with_grpc_status(&tonic_status);
+                let retryable =
+                    classify_tonic_status(&tonic_status) != RetryErrorType::NonRetryable;
+                let result: OTelSdkResult =
+                    super::handle_tonic_export_error!("TonicLogsClient", tonic_status);
+                result.map_err(|error| SendError { error, retryable })
             }
         }
     }
+}
+
+impl LogExporter for TonicLogsClient {
+    async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        let items = batch.iter().count();
+        let request = self.build_request(&batch);
+        self.export_request(request, items)
+            .await
+            .map_err(|e| e.error)
+    }
 
     fn shutdown_with_timeout(&self, _timeout: time::Duration) -> OTelSdkResult {
         self.inner
@@ -1,4 +1,5 @@
 use core::fmt;
+use prost::Message;
 use std::sync::Mutex;
 
 use opentelemetry::{otel_debug, otel_warn};
@@ -10,6 +11,7 @@ use opentelemetry_sdk::metrics::data::ResourceMetrics;
 use tonic::{codegen::CompressionEncoding, service::Interceptor, transport::Channel, Request};
 
 use super::BoxInterceptor;
+use crate::exporter::self_diagnostics::{data_point_count, grpc_status_code, ExporterMetrics};
 use crate::metric::MetricsClient;
 
 use crate::retry::RetryPolicy;
@@ -18,6 +20,7 @@ pub(crate) struct TonicMetricsClient {
     inner: Mutex<Option<ClientInner>>,
     retry_policy: RetryPolicy,
     timeout: std::time::Duration,
+    metrics: ExporterMetrics,
 }
 
 struct ClientInner {
@@ -38,6 +41,7 @@ impl TonicMetricsClient {
         compression: Option<CompressionEncoding>,
         retry_policy: Option<RetryPolicy>,
         timeout: std::time::Duration,
+        metrics: ExporterMetrics,
     ) -> Self {
         let mut client = MetricsServiceClient::new(channel);
         if let Some(compression) = compression {
@@ -55,17 +59,21 @@ impl TonicMetricsClient {
             })),
             retry_policy: retry_policy.unwrap_or_default(),
             timeout,
+            metrics,
         }
     }
 }
 
 impl MetricsClient for TonicMetricsClient {
     async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+        let operation = self.metrics.start_export(data_point_count(metrics));
+
         match super::tonic_retry_with_backoff(
             &self.retry_policy,
             self.timeout,
             crate::retry_classification::grpc::classify_tonic_status,
             "TonicMetricsClient.Export",
+            &self.metrics,
             || async {
                 // Execute the export operation
                 let (mut client, metadata, extensions) = self
@@ -90,35 +98,42 @@ impl MetricsClient for TonicMetricsClient {
 
                 otel_debug!(name: "TonicMetricsClient.ExportStarted");
 
+                let request = ExportMetricsServiceRequest::from(metrics);
+                self.metrics.record_payload(request.encoded_len(), None);
+
                 client
-                    .export(Request::from_parts(
-                        metadata,
-                        extensions,
-                        ExportMetricsServiceRequest::from(metrics),
-                    ))
+                    .export(Request::from_parts(metadata, extensions, request))
                     .await
                     .map(|response| {
                         otel_debug!(name: "TonicMetricsClient.ExportSucceeded");
 
                         // Handle partial success. As per spec, we log and _do not_ retry.
-                        if let Some(partial_success) = response.into_inner().partial_success {
-                            if partial_success.rejected_data_points > 0
-                                || !partial_success.error_message.is_empty()
-                            {
-                                otel_warn!(
-                                    name: "TonicMetricsClient.PartialSuccess",
-                                    rejected_data_points = partial_success.rejected_data_points,
-                                    error_message = partial_success.error_message.as_str(),
-                                );
+                        match response.into_inner().partial_success {
+                            Some(partial_success) => {
+                                if partial_success.rejected_data_points > 0
+                                    || !partial_success.error_message.is_empty()
+                                {
+                                    otel_warn!(
+                                        name: "TonicMetricsClient.PartialSuccess",
+                                        rejected_data_points = partial_success.rejected_data_points,
+                                        error_message = partial_success.error_message.as_str(),
+                                    );
+                                }
+                                partial_success.rejected_data_points.max(0) as u64
                             }
+                            None => 0,
                         }
                     })
             },
         )
         .await
         {
-            Ok(_) => Ok(()),
+            Ok(rejected) => {
+                operation.succeeded(rejected, Some(grpc_status_code(tonic::Code::Ok)));
+                Ok(())
+            }
             Err(tonic_status) => {
+                operation.failed_with_grpc_status(&tonic_status);
                 super::handle_tonic_export_error!("TonicMetricsClient", tonic_status)
             }
         }
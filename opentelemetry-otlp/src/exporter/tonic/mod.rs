@@ -16,6 +16,7 @@ use tonic::transport::Channel;
 ))]
 use tonic::transport::ClientTlsConfig;
 
+use super::self_diagnostics::{ExportedItems, ExporterMetrics};
 use super::{default_headers, parse_header_string, OTEL_EXPORTER_OTLP_GRPC_ENDPOINT_DEFAULT};
 use super::{resolve_timeout, ExporterBuildError};
 use crate::exporter::Compression;
@@ -25,7 +26,7 @@ use crate::{exporter::ExportConfig, OTEL_EXPORTER_OTLP_ENDPOINT, OTEL_EXPORTER_O
     feature = "grpc-tonic",
     any(feature = "trace", feature = "metrics", feature = "logs")
 ))]
-use crate::retry::retry_with_backoff;
+use crate::retry::retry_with_backoff_and_observer;
 use crate::retry::RetryPolicy;
 #[cfg(all(
     feature = "grpc-tonic",
@@ -174,6 +175,7 @@ impl Default for TonicExporterBuilder {
 impl TonicExporterBuilder {
     // This is for clippy to work with only the grpc-tonic feature enabled
     #[allow(unused, clippy::type_complexity)]
+    #[allow(clippy::too_many_arguments)]
     fn build_channel(
         self,
         signal_endpoint_var: &str,
@@ -182,6 +184,7 @@ impl TonicExporterBuilder {
         signal_headers_var: &str,
         signal_protocol_var: &str,
         signal_insecure_var: &str,
+        items: ExportedItems,
     ) -> Result<
         (
             Channel,
@@ -189,6 +192,7 @@ impl TonicExporterBuilder {
             Option<CompressionEncoding>,
             Option<RetryPolicy>,
             std::time::Duration,
+            ExporterMetrics,
         ),
         ExporterBuildError,
     > {
@@ -262,7 +266,15 @@ impl TonicExporterBuilder {
 
         // If a custom channel was provided, use that channel instead of creating one
         if let Some(channel) = self.tonic_config.channel {
-            return Ok((channel, interceptor, compression, retry_policy, timeout));
+            let metrics = ExporterMetrics::new(self.exporter_config.meter, items, "grpc", None);
+            return Ok((
+                channel,
+                interceptor,
+                compression,
+                retry_policy,
+                timeout,
+                metrics,
+            ));
         }
 
         let config = self.exporter_config;
@@ -278,6 +290,8 @@ impl TonicExporterBuilder {
         let endpoint = tonic::transport::Endpoint::from_shared(endpoint_str)
             .map_err(|op| ExporterBuildError::InvalidUri(endpoint_clone.clone(), op.to_string()))?;
 
+        let metrics = ExporterMetrics::new(config.meter, items, "grpc", Some(endpoint.uri()));
+
         let is_https = endpoint
             .uri()
             .scheme()
@@ -326,7 +340,14 @@ impl TonicExporterBuilder {
         let channel = endpoint.timeout(timeout).connect_lazy();
 
         otel_debug!(name: "TonicChannelBuilt", endpoint = endpoint_clone, timeout_in_millisecs = timeout.as_millis(), compression = format!("{:?}", compression), headers = format!("{:?}", headers_for_logging));
-        Ok((channel, interceptor, compression, retry_policy, timeout))
+        Ok((
+            channel,
+            interceptor,
+            compression,
+            retry_policy,
+            timeout,
+            metrics,
+        ))
     }
 
     fn resolve_endpoint(default_endpoint_var: &str, provided_endpoint: Option<String>) -> String {
@@ -364,16 +385,25 @@ impl TonicExporterBuilder {
 
         otel_debug!(name: "LogsTonicChannelBuilding");
 
-        let (channel, interceptor, compression, retry_policy, timeout) = self.build_channel(
-            crate::logs::OTEL_EXPORTER_OTLP_LOGS_ENDPOINT,
-            crate::logs::OTEL_EXPORTER_OTLP_LOGS_TIMEOUT,
-            crate::logs::OTEL_EXPORTER_OTLP_LOGS_COMPRESSION,
-            crate::logs::OTEL_EXPORTER_OTLP_LOGS_HEADERS,
-            crate::logs::OTEL_EXPORTER_OTLP_LOGS_PROTOCOL,
-            crate::logs::OTEL_EXPORTER_OTLP_LOGS_INSECURE,
-        )?;
-
-        let client = TonicLogsClient::new(channel, interceptor, compression, retry_policy, timeout);
+        let (channel, interceptor, compression, retry_policy, timeout, metrics) = self
+            .build_channel(
+                crate::logs::OTEL_EXPORTER_OTLP_LOGS_ENDPOINT,
+                crate::logs::OTEL_EXPORTER_OTLP_LOGS_TIMEOUT,
+                crate::logs::OTEL_EXPORTER_OTLP_LOGS_COMPRESSION,
+                crate::logs::OTEL_EXPORTER_OTLP_LOGS_HEADERS,
+                crate::logs::OTEL_EXPORTER_OTLP_LOGS_PROTOCOL,
+                crate::logs::OTEL_EXPORTER_OTLP_LOGS_INSECURE,
+                super::self_diagnostics::LOG_RECORDS,
+            )?;
+
+        let client = TonicLogsClient::new(
+            channel,
+            interceptor,
+            compression,
+            retry_policy,
+            timeout,
+            metrics,
+        );
 
         Ok(crate::logs::LogExporter::from_tonic(client))
     }
@@ -389,17 +419,25 @@ impl TonicExporterBuilder {
 
         otel_debug!(name: "MetricsTonicChannelBuilding");
 
-        let (channel, interceptor, compression, retry_policy, timeout) = self.build_channel(
-            crate::metric::OTEL_EXPORTER_OTLP_METRICS_ENDPOINT,
-            crate::metric::OTEL_EXPORTER_OTLP_METRICS_TIMEOUT,
-            crate::metric::OTEL_EXPORTER_OTLP_METRICS_COMPRESSION,
-            crate::metric::OTEL_EXPORTER_OTLP_METRICS_HEADERS,
-            crate::metric::OTEL_EXPORTER_OTLP_METRICS_PROTOCOL,
-            crate::metric::OTEL_EXPORTER_OTLP_METRICS_INSECURE,
-        )?;
-
-        let client =
-            TonicMetricsClient::new(channel, interceptor, compression, retry_policy, timeout);
+        let (channel, interceptor, compression, retry_policy, timeout, metrics) = self
+            .build_channel(
+                crate::metric::OTEL_EXPORTER_OTLP_METRICS_ENDPOINT,
+                crate::metric::OTEL_EXPORTER_OTLP_METRICS_TIMEOUT,
+                crate::metric::OTEL_EXPORTER_OTLP_METRICS_COMPRESSION,
+                crate::metric::OTEL_EXPORTER_OTLP_METRICS_HEADERS,
+                crate::metric::OTEL_EXPORTER_OTLP_METRICS_PROTOCOL,
+                crate::metric::OTEL_EXPORTER_OTLP_METRICS_INSECURE,
+                super::self_diagnostics::DATA_POINTS,
+            )?;
+
+        let client = TonicMetricsClient::new(
+            channel,
+            interceptor,
+            compression,
+            retry_policy,
+            timeout,
+            metrics,
+        );
 
         Ok(MetricExporter::from_tonic(client, temporality))
     }
@@ -411,17 +449,25 @@ impl TonicExporterBuilder {
 
         otel_debug!(name: "TracesTonicChannelBuilding");
 
-        let (channel, interceptor, compression, retry_policy, timeout) = self.build_channel(
-            crate::span::OTEL_EXPORTER_OTLP_TRACES_ENDPOINT,
-            crate::span::OTEL_EXPORTER_OTLP_TRACES_TIMEOUT,
-            crate::span::OTEL_EXPORTER_OTLP_TRACES_COMPRESSION,
-            crate::span::OTEL_EXPORTER_OTLP_TRACES_HEADERS,
-            crate::span::OTEL_EXPORTER_OTLP_TRACES_PROTOCOL,
-            crate::span::OTEL_EXPORTER_OTLP_TRACES_INSECURE,
-        )?;
-
-        let client =
-            TonicTracesClient::new(channel, interceptor, compression, retry_policy, timeout);
+        let (channel, interceptor, compression, retry_policy, timeout, metrics) = self
+            .build_channel(
+                crate::span::OTEL_EXPORTER_OTLP_TRACES_ENDPOINT,
+                crate::span::OTEL_EXPORTER_OTLP_TRACES_TIMEOUT,
+                crate::span::OTEL_EXPORTER_OTLP_TRACES_COMPRESSION,
+                crate::span::OTEL_EXPORTER_OTLP_TRACES_HEADERS,
+                crate::span::OTEL_EXPORTER_OTLP_TRACES_PROTOCOL,
+                crate::span::OTEL_EXPORTER_OTLP_TRACES_INSECURE,
+                super::self_diagnostics::SPANS,
+            )?;
+
+        let client = TonicTracesClient::new(
+            channel,
+            interceptor,
+            compression,
+            retry_policy,
+            timeout,
+            metrics,
+        );
 
         Ok(crate::SpanExporter::from_tonic(client))
     }
@@ -441,13 +487,22 @@ async fn tonic_retry_with_backoff<F, Fut, T>(
     timeout: std::time::Duration,
     classify_fn: fn(&tonic::Status) -> crate::retry::RetryErrorType,
     operation_name: &'static str,
+    metrics: &ExporterMetrics,
     operation: F,
 ) -> Result<T, tonic::Status>
 where
     F: Fn() -> Fut,
     Fut: Future<Output = Result<T, tonic::Status>>,
 {
-    retry_with_backoff(policy, timeout, classify_fn, operation_name, operation).await
+    retry_with_backoff_and_observer(
+        policy,
+        timeout,
+        classify_fn,
+        operation_name,
+        operation,
+        |error_type| metrics.record_retry(error_type),
+    )
+    .await
 }
 
 #[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
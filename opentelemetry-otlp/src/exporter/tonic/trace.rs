@@ -1,5 +1,6 @@
 use core::fmt;
-use std::sync::{Arc, Mutex};
+use prost::Message;
+use std::sync::Mutex;
 
 use opentelemetry::{otel_debug, otel_warn};
 use opentelemetry_proto::tonic::collector::trace::v1::{
@@ -14,13 +15,17 @@ use opentelemetry_sdk::{
 use tonic::{codegen::CompressionEncoding, service::Interceptor, transport::Channel, Request};
 
 use super::BoxInterceptor;
+use crate::exporter::self_diagnostics::{grpc_status_code, ExporterMetrics};
 
-use crate::retry::RetryPolicy;
+use crate::persistent_queue::SendError;
+use crate::retry::{RetryErrorType, RetryPolicy};
+use crate::retry_classification::grpc::classify_tonic_status;
 
 pub(crate) struct TonicTracesClient {
     inner: Mutex<Option<ClientInner>>,
     retry_policy: RetryPolicy,
     timeout: std::time::Duration,
+    metrics: ExporterMetrics,
     #[allow(dead_code)]
     // <allow dead> would be removed once we support set_resource for metrics.
     resource: opentelemetry_proto::transform::common::tonic::ResourceAttributesWithSchema,
@@ -44,6 +49,7 @@ impl TonicTracesClient {
         compression: Option<CompressionEncoding>,
         retry_policy: Option<RetryPolicy>,
         timeout: std::time::Duration,
+        metrics: ExporterMetrics,
     ) -> Self {
         let mut client = TraceServiceClient::new(channel);
         if let Some(compression) = compression {
@@ -61,23 +67,33 @@ impl TonicTracesClient {
             })),
             retry_policy: retry_policy.unwrap_or_default(),
             timeout,
+            metrics,
             resource: Default::default(),
         }
     }
 }
 
-impl SpanExporter for TonicTracesClient {
-    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
-        let batch = Arc::new(batch);
+impl TonicTracesClient {
+    pub(crate) fn build_request(&self, batch: Vec<SpanData>) -> ExportTraceServiceRequest {
+        ExportTraceServiceRequest {
+            resource_spans: group_spans_by_resource_and_scope(batch, &self.resource),
+        }
+    }
+
+    pub(crate) async fn export_request(
+        &self,
+        request: ExportTraceServiceRequest,
+        items: usize,
+    ) -> Result<(), SendError> {
+        let operation = self.metrics.start_export(items);
 
         match super::tonic_retry_with_backoff(
             &self.retry_policy,
             self.timeout,
-            crate::retry_classification::grpc::classify_tonic_status,
+            classify_tonic_status,
             "TonicTracesClient.Export",
+            &self.metrics,
             || async {
-                let batch_clone = Arc::clone(&batch);
-
                 // Execute the export operation
                 let (mut client, metadata, extensions) = self
                     .inner
@@ -99,44 +115,62 @@ impl SpanExporter for TonicTracesClient {
                         )),
                     })?;
 
-                let resource_spans =
-                    group_spans_by_resource_and_scope((*batch_clone).clone(), &self.resource);
-
                 otel_debug!(name: "TonicTracesClient.ExportStarted");
 
+                let request = request.clone();
+                self.metrics.record_payload(request.encoded_len(), None);
+
                 client
-                    .export(Request::from_parts(
-                        metadata,
-                        extensions,
-                        ExportTraceServiceRequest { resource_spans },
-                    ))
+                    .export(Request::from_parts(metadata, extensions, request))
                     .await
                     .map(|response| {
                         otel_debug!(name: "TonicTracesClient.ExportSucceeded");
 
                         // Handle partial success. As per spec, we log and _do not_ retry.
-                        if let Some(partial_success) = response.into_inner().partial_success {
-                            if partial_success.rejected_spans > 0
-                                || !partial_success.error_message.is_empty()
-                            {
-                                otel_warn!(
-                                    name: "TonicTracesClient.PartialSuccess",
-                                    rejected_spans = partial_success.rejected_spans,
-                                    error_message = partial_success.error_message.as_str(),
-                                );
+                        match response.into_inner().partial_success {
+                            Some(partial_success) => {
+                                if partial_success.rejected_spans > 0
+                                    || !partial_success.error_message.is_empty()
+                                {
+                                    otel_warn!(
+                                        name: "TonicTracesClient.PartialSuccess",
+                                        rejected_spans = partial_success.rejected_spans,
+                                        error_message = partial_success.error_message.as_str(),
+                                    );
+                                }
+                                partial_success.rejected_spans.max(0) as u64
                             }
+                            None => 0,
                         }
                     })
             },
         )
         .await
         {
-            Ok(_) => Ok(()),
+            Ok(rejected) => {
+                operation.succeeded(rejected, Some(grpc_status_code(tonic::Code::Ok)));
+                Ok(())
+            }
             Err(tonic_status) => {
-                super::handle_tonic_export_error!("TonicTracesClient", tonic_status)
+                operation.failed_with_grpc_status(&tonic_status);
+                let retryable =
+                    classify_tonic_status(&tonic_status) != RetryErrorType::NonRetryable;
+                let result: OTelSdkResult =
+                    super::handle_tonic_export_error!("TonicTracesClient", tonic_status);
+                result.map_err(|error| SendError { error, retryable })
             }
         }
     }
+}
+
+impl SpanExporter for TonicTracesClient {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let items = batch.len();
+        let request = self.build_request(batch);
+        self.export_request(request, items)
+            .await
+            .map_err(|e| e.error)
+    }
 
     fn shutdown(&self) -> OTelSdkResult {
         let mut inner_guard = self
@@ -303,6 +303,9 @@
 //! Failed exports are automatically retried according to the configured retry policy,
 //! with proper classification of retryable vs non-retryable errors and support for
 //! server-provided throttling hints (HTTP Retry-After, gRPC RetryInfo).
+//! The spans and logs of an export failing after the retries are dropped, unless
+//! the exporter is built with a [`PersistentQueue`], which keeps them on disk
+//! until a later export succeeds, including across restarts.
 //!
 //! # Full Configuration Reference
 //!
@@ -653,6 +656,9 @@ mod logs;
 #[cfg(feature = "metrics")]
 #[cfg(any(feature = "http-proto", feature = "http-json", feature = "grpc-tonic"))]
 mod metric;
+#[cfg(any(feature = "trace", feature = "logs"))]
+#[cfg(any(feature = "http-proto", feature = "http-json", feature = "grpc-tonic"))]
+mod persistent_queue;
 #[cfg(feature = "trace")]
 #[cfg(any(feature = "http-proto", feature = "http-json", feature = "grpc-tonic"))]
 mod span;
@@ -693,6 +699,10 @@ pub use crate::logs::{
     OTEL_EXPORTER_OTLP_LOGS_TIMEOUT,
 };
 
+#[cfg(any(feature = "trace", feature = "logs"))]
+#[cfg(any(feature = "http-proto", feature = "http-json", feature = "grpc-tonic"))]
+pub use crate::persistent_queue::PersistentQueue;
+
 #[cfg(any(feature = "http-proto", feature = "http-json"))]
 pub use crate::exporter::http::WithHttpConfig;
 
@@ -2,12 +2,15 @@
 //!
 //! Defines a [LogExporter] to send logs via the OpenTelemetry Protocol (OTLP)
 
+use crate::exporter::self_diagnostics::suppress_telemetry;
 #[cfg(feature = "grpc-tonic")]
 use opentelemetry::otel_debug;
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
 use opentelemetry_sdk::{error::OTelSdkResult, logs::LogBatch};
 use std::fmt::Debug;
 use std::time;
 
+use crate::persistent_queue::{open_queue, DiskQueue, PersistentQueue};
 use crate::{exporter::HasExportConfig, ExporterBuildError, NoExporterBuilderSet};
 
 #[cfg(feature = "grpc-tonic")]
@@ -41,6 +44,16 @@ pub const OTEL_EXPORTER_OTLP_LOGS_INSECURE: &str = "OTEL_EXPORTER_OTLP_LOGS_INSE
 pub struct LogExporterBuilder<C> {
     client: C,
     endpoint: Option<String>,
+    persistent_queue: Option<PersistentQueue>,
+}
+
+impl<C> LogExporterBuilder<C> {
+    /// Keep the log records on disk until they are exported, see
+    /// [PersistentQueue].
+    pub fn with_persistent_queue(mut self, persistent_queue: PersistentQueue) -> Self {
+        self.persistent_queue = Some(persistent_queue);
+        self
+    }
 }
 
 impl LogExporterBuilder<NoExporterBuilderSet> {
@@ -55,6 +68,7 @@ impl LogExporterBuilder<NoExporterBuilderSet> {
         LogExporterBuilder {
             client: TonicExporterBuilderSet(TonicExporterBuilder::default()),
             endpoint: self.endpoint,
+            persistent_queue: self.persistent_queue,
         }
     }
 
@@ -64,6 +78,7 @@ impl LogExporterBuilder<NoExporterBuilderSet> {
         LogExporterBuilder {
             client: HttpExporterBuilderSet(HttpExporterBuilder::default()),
             endpoint: self.endpoint,
+            persistent_queue: self.persistent_queue,
         }
     }
 
@@ -98,7 +113,11 @@ impl LogExporterBuilder<NoExporterBuilderSet> {
 impl LogExporterBuilder<TonicExporterBuilderSet> {
     /// Build the [LogExporter] with the gRPC Tonic transport.
     pub fn build(self) -> Result<LogExporter, ExporterBuildError> {
-        let result = self.client.0.build_log_exporter();
+        let queue = open_queue(self.persistent_queue.as_ref(), "logs")?;
+        let result = self.client.0.build_log_exporter().map(|mut exporter| {
+            exporter.queue = queue;
+            exporter
+        });
         otel_debug!(name: "LogExporterBuilt", result = format!("{:?}", &result));
         result
     }
@@ -108,7 +127,10 @@ impl LogExporterBuilder<TonicExporterBuilderSet> {
 impl LogExporterBuilder<HttpExporterBuilderSet> {
     /// Build the [LogExporter] with the HTTP transport.
     pub fn build(self) -> Result<LogExporter, ExporterBuildError> {
-        self.client.0.build_log_exporter()
+        let queue = open_queue(self.persistent_queue.as_ref(), "logs")?;
+        let mut exporter = self.client.0.build_log_exporter()?;
+        exporter.queue = queue;
+        Ok(exporter)
     }
 }
 
@@ -144,6 +166,7 @@ impl HasHttpConfig for LogExporterBuilder<HttpExporterBuilderSet> {
 #[derive(Debug)]
 pub struct LogExporter {
     client: SupportedTransportClient,
+    queue: Option<DiskQueue>,
 }
 
 #[derive(Debug)]
@@ -164,6 +187,7 @@ impl LogExporter {
     pub(crate) fn from_http(client: crate::exporter::http::OtlpHttpClient) -> Self {
         LogExporter {
             client: SupportedTransportClient::Http(client),
+            queue: None,
         }
     }
 
@@ -171,17 +195,58 @@ impl LogExporter {
     pub(crate) fn from_tonic(client: crate::exporter::tonic::logs::TonicLogsClient) -> Self {
         LogExporter {
             client: SupportedTransportClient::Tonic(client),
+            queue: None,
         }
     }
+
+    async fn export_queued(&self, queue: &DiskQueue, batch: LogBatch<'_>) -> OTelSdkResult {
+        let request = match &self.client {
+            #[cfg(feature = "grpc-tonic")]
+            SupportedTransportClient::Tonic(client) => client.build_request(&batch),
+            #[cfg(any(feature = "http-proto", feature = "http-json"))]
+            SupportedTransportClient::Http(client) => client.build_logs_request(&batch),
+        };
+        queue
+            .export(request, |request| async move {
+                let items = log_record_count(&request);
+                match &self.client {
+                    #[cfg(feature = "grpc-tonic")]
+                    SupportedTransportClient::Tonic(client) => {
+                        client.export_request(request, items).await
+                    }
+                    #[cfg(any(feature = "http-proto", feature = "http-json"))]
+                    SupportedTransportClient::Http(client) => {
+                        client.export_logs_request(request, items).await
+                    }
+                }
+            })
+            .await
+    }
+}
+
+fn log_record_count(request: &ExportLogsServiceRequest) -> usize {
+    request
+        .resource_logs
+        .iter()
+        .flat_map(|resource_logs| &resource_logs.scope_logs)
+        .map(|scope_logs| scope_logs.log_records.len())
+        .sum()
 }
 
 impl opentelemetry_sdk::logs::LogExporter for LogExporter {
     async fn export(&self, batch: LogBatch<'_>) -> OTelSdkResult {
+        if let Some(queue) = &self.queue {
+            return suppress_telemetry(self.export_queued(queue, batch)).await;
+        }
         match &self.client {
             #[cfg(feature = "grpc-tonic")]
-            SupportedTransportClient::Tonic(client) => client.export(batch).await,
+            SupportedTransportClient::Tonic(client) => {
+                suppress_telemetry(client.export(batch)).await
+            }
             #[cfg(any(feature = "http-proto", feature = "http-json"))]
-            SupportedTransportClient::Http(client) => client.export(batch).await,
+            SupportedTransportClient::Http(client) => {
+                suppress_telemetry(client.export(batch)).await
+            }
         }
     }
 
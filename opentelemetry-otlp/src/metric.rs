@@ -20,6 +20,7 @@ use crate::{
 
 use crate::{ExporterBuildError, NoExporterBuilderSet};
 
+use crate::exporter::self_diagnostics::suppress_telemetry;
 use core::fmt;
 use opentelemetry_sdk::error::OTelSdkResult;
 
@@ -223,9 +224,13 @@ impl PushMetricExporter for MetricExporter {
     async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
         match &self.client {
             #[cfg(feature = "grpc-tonic")]
-            SupportedTransportClient::Tonic(client) => client.export(metrics).await,
+            SupportedTransportClient::Tonic(client) => {
+                suppress_telemetry(client.export(metrics)).await
+            }
             #[cfg(any(feature = "http-proto", feature = "http-json"))]
-            SupportedTransportClient::Http(client) => client.export(metrics).await,
+            SupportedTransportClient::Http(client) => {
+                suppress_telemetry(client.export(metrics)).await
+            }
         }
     }
 
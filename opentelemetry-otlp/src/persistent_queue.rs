@@ -0,0 +1,692 @@
+//! # OTLP - Persistent Queue
+//!
+//! Keeps the requests of the span and log exporters on disk until the
+//! endpoint accepts them, so that they survive an unreachable endpoint and a
+//! restart of the process.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::future::Future;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+use std::thread;
+
+use futures_channel::oneshot;
+use opentelemetry::{otel_debug, otel_warn, Context};
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+use prost::Message;
+
+use crate::ExporterBuildError;
+
+const DEFAULT_MAX_SIZE: u64 = 64 * 1024 * 1024;
+const DEFAULT_MAX_REQUESTS_PER_EXPORT: usize = 10;
+const SEGMENT_EXTENSION: &str = "pb";
+const TEMPORARY_EXTENSION: &str = "tmp";
+
+/// Configuration of a disk-backed queue of the requests of an exporter.
+///
+/// Every batch exported with a persistent queue is first written to the
+/// directory, as a file holding the OTLP protobuf request, then the queued
+/// requests are sent, oldest first, and each file is deleted once the
+/// endpoint has accepted its request, or has rejected it with an error that is
+/// not retryable. A request that fails with a retryable error, e.g. because the
+/// endpoint is unreachable, stays on disk and is sent again, before the newer
+/// ones, by the next export, including the first export after a restart of the
+/// process.
+///
+/// An export sends at most 10 queued requests, so that a long backlog is
+/// replayed over several exports instead of blocking one of them. The files
+/// are written, read and deleted on a thread of the queue, not on the thread
+/// polling the export.
+///
+/// When the queued files exceed the maximum size, the oldest ones are deleted.
+/// When a request cannot be written, it is sent without being queued.
+///
+/// The span and log exporters use the `traces` and `logs` subdirectories of
+/// the directory, so a configuration can be shared between them, but two
+/// exporters of the same signal must not share a directory.
+///
+/// ```no_run
+/// # #[cfg(all(feature = "trace", feature = "grpc-tonic"))]
+/// # {
+/// use opentelemetry_otlp::{PersistentQueue, SpanExporter};
+///
+/// let exporter = SpanExporter::builder()
+///     .with_tonic()
+///     .with_persistent_queue(
+///         PersistentQueue::new("/var/lib/my-app/otlp").with_max_size(16 * 1024 * 1024),
+///     )
+///     .build()?;
+/// # }
+/// # Ok::<(), opentelemetry_otlp::ExporterBuildError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct PersistentQueue {
+    directory: PathBuf,
+    max_size: u64,
+    max_requests_per_export: usize,
+}
+
+impl PersistentQueue {
+    /// Create a configuration keeping the requests in `directory`, which is
+    /// created if it doesn't exist, up to 64 MiB per signal.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        PersistentQueue {
+            directory: directory.into(),
+            max_size: DEFAULT_MAX_SIZE,
+            max_requests_per_export: DEFAULT_MAX_REQUESTS_PER_EXPORT,
+        }
+    }
+
+    /// Keep up to `max_size` bytes of requests per signal instead of 64 MiB.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Send at most `max_requests` queued requests per export instead of 10.
+    /// The remaining ones are sent by the next exports.
+    pub fn with_max_requests_per_export(mut self, max_requests: usize) -> Self {
+        self.max_requests_per_export = max_requests.max(1);
+        self
+    }
+
+    /// Open the queue of a signal, keeping the requests left by a previous
+    /// process.
+    pub(crate) fn open(&self, signal: &str) -> io::Result<DiskQueue> {
+        let directory = self.directory.join(signal);
+        fs::create_dir_all(&directory)?;
+
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            let extension = path.extension().and_then(|e| e.to_str());
+            if extension == Some(TEMPORARY_EXTENSION) {
+                // A request whose writing was interrupted.
+                fs::remove_file(&path)?;
+                continue;
+            }
+            let sequence = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.parse::<u64>().ok());
+            if let (Some(SEGMENT_EXTENSION), Some(sequence)) = (extension, sequence) {
+                segments.push(Segment {
+                    sequence,
+                    size: fs::metadata(&path)?.len(),
+                    in_flight: false,
+                });
+            }
+        }
+        segments.sort_by_key(|segment| segment.sequence);
+
+        let state = QueueState {
+            next_sequence: segments.last().map_or(0, |s| s.sequence + 1),
+            size: segments.iter().map(|s| s.size).sum(),
+            segments: segments.into(),
+        };
+        let files = Arc::new(QueueFiles {
+            directory,
+            max_size: self.max_size,
+            state: Mutex::new(state),
+            dropped_requests: FailureCount::default(),
+            write_failures: FailureCount::default(),
+            delete_failures: FailureCount::default(),
+        });
+
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let thread_files = files.clone();
+        thread::Builder::new()
+            .name(format!("OpenTelemetry.Otlp.PersistentQueue.{signal}"))
+            .spawn(move || {
+                // The file operations of the queue are part of the export.
+                let _suppress_guard = Context::enter_telemetry_suppressed_scope();
+                for job in receiver {
+                    job(&thread_files);
+                }
+                otel_debug!(name: "PersistentQueue.ThreadStopped");
+            })?;
+
+        Ok(DiskQueue {
+            files,
+            jobs,
+            max_requests_per_export: self.max_requests_per_export,
+        })
+    }
+}
+
+/// Open the queue of a signal if the exporter is configured with one.
+pub(crate) fn open_queue(
+    persistent_queue: Option<&PersistentQueue>,
+    signal: &str,
+) -> Result<Option<DiskQueue>, ExporterBuildError> {
+    persistent_queue
+        .map(|persistent_queue| persistent_queue.open(signal))
+        .transpose()
+        .map_err(|e| ExporterBuildError::InvalidConfig {
+            name: "persistent_queue".to_string(),
+            reason: format!("failed to open the queue directory: {e}"),
+        })
+}
+
+/// A failed send of a queued request.
+#[derive(Debug)]
+pub(crate) struct SendError {
+    pub(crate) error: OTelSdkError,
+    /// Whether the request can succeed later, in which case it stays queued.
+    pub(crate) retryable: bool,
+}
+
+/// The requests of a signal, one file per request named after its sequence
+/// number.
+#[derive(Debug)]
+pub(crate) struct DiskQueue {
+    files: Arc<QueueFiles>,
+    /// The file operations run by the thread of the queue.
+    jobs: mpsc::Sender<Job>,
+    max_requests_per_export: usize,
+}
+
+type Job = Box<dyn FnOnce(&Arc<QueueFiles>) + Send>;
+
+/// The files of a queue, only written, read and deleted by the thread of the
+/// queue.
+#[derive(Debug)]
+struct QueueFiles {
+    directory: PathBuf,
+    max_size: u64,
+    state: Mutex<QueueState>,
+    /// The requests deleted before being sent: evicted, unreadable or
+    /// corrupted.
+    dropped_requests: FailureCount,
+    /// The requests sent without being queued since they couldn't be written.
+    write_failures: FailureCount,
+    delete_failures: FailureCount,
+}
+
+#[derive(Debug)]
+struct QueueState {
+    /// The segments on disk, oldest first.
+    segments: VecDeque<Segment>,
+    next_sequence: u64,
+    size: u64,
+}
+
+#[derive(Debug)]
+struct Segment {
+    sequence: u64,
+    size: u64,
+    /// Whether an export is sending the request, so that concurrent exports
+    /// don't send it too.
+    in_flight: bool,
+}
+
+/// A segment claimed by an export, released for a later export when dropped
+/// unless it was removed, so that it isn't left claimed by an export future
+/// dropped before finishing.
+#[derive(Debug)]
+struct Claim {
+    files: Arc<QueueFiles>,
+    sequence: u64,
+    removed: bool,
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        if !self.removed {
+            self.files.release(self.sequence);
+        }
+    }
+}
+
+/// The number of times a failure happened, which is only logged the first
+/// time, then in total when the queue is dropped.
+#[derive(Debug, Default)]
+struct FailureCount(AtomicU64);
+
+impl FailureCount {
+    /// Count a failure, returning whether it is the first one.
+    fn increment(&self) -> bool {
+        self.0.fetch_add(1, Ordering::Relaxed) == 0
+    }
+
+    fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl DiskQueue {
+    /// Queue `request`, then send the queued requests with `send`, oldest
+    /// first, until one of them fails or `max_requests_per_export` of them
+    /// are sent.
+    pub(crate) async fn export<R, F, Fut>(&self, request: R, send: F) -> OTelSdkResult
+    where
+        R: Message + Default,
+        F: Fn(R) -> Fut,
+        Fut: Future<Output = Result<(), SendError>>,
+    {
+        let payload = request.encode_to_vec();
+        if let Err(e) = self.run(move |files| files.push(&payload)).await {
+            if self.files.write_failures.increment() {
+                otel_warn!(
+                    name: "PersistentQueue.WriteFailed",
+                    directory = self.files.directory.display().to_string(),
+                    error = e.to_string(),
+                    message = "A request could not be queued and was sent without being queued. No further log will be emitted for further write failures until the exporter is dropped.",
+                );
+            }
+            return send(request).await.map_err(|e| e.error);
+        }
+
+        for _ in 0..self.max_requests_per_export {
+            let Ok(Some((claim, payload))) = self.run(QueueFiles::claim).await else {
+                break;
+            };
+            let request = match R::decode(payload.as_slice()) {
+                Ok(request) => request,
+                Err(e) => {
+                    self.files
+                        .drop_request("corrupted", claim.sequence, &e.to_string());
+                    self.remove(claim).await;
+                    continue;
+                }
+            };
+            if let Err(e) = send(request).await {
+                // A retryable request is released when `claim` is dropped.
+                if !e.retryable {
+                    self.remove(claim).await;
+                }
+                return Err(e.error);
+            }
+            self.remove(claim).await;
+        }
+        Ok(())
+    }
+
+    /// Run `job` on the thread of the queue.
+    async fn run<T, J>(&self, job: J) -> io::Result<T>
+    where
+        T: Send + 'static,
+        J: FnOnce(&Arc<QueueFiles>) -> io::Result<T> + Send + 'static,
+    {
+        let (sender, receiver) = oneshot::channel();
+        let stopped = || io::Error::other("the queue thread has stopped");
+        self.jobs
+            .send(Box::new(move |files| {
+                let _ = sender.send(job(files));
+            }))
+            .map_err(|_| stopped())?;
+        receiver.await.map_err(|_| stopped())?
+    }
+
+    /// Delete the claimed segment.
+    async fn remove(&self, mut claim: Claim) {
+        claim.removed = true;
+        let sequence = claim.sequence;
+        let _ = self
+            .run(move |files| {
+                files.remove(sequence);
+                Ok(())
+            })
+            .await;
+    }
+}
+
+impl Drop for DiskQueue {
+    fn drop(&mut self) {
+        let directory = self.files.directory.display().to_string();
+        for (count, name) in [
+            (&self.files.dropped_requests, "dropped"),
+            (&self.files.write_failures, "sent without being queued"),
+            (&self.files.delete_failures, "not deleted"),
+        ] {
+            if count.get() > 0 {
+                otel_warn!(
+                    name: "PersistentQueue.FailureCount",
+                    directory = directory.clone(),
+                    requests = name,
+                    count = count.get(),
+                );
+            }
+        }
+    }
+}
+
+impl QueueFiles {
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        // The state is left consistent by every operation, so a poisoned lock
+        // is still usable.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn path(&self, sequence: u64, extension: &str) -> PathBuf {
+        self.directory.join(format!("{sequence:020}.{extension}"))
+    }
+
+    /// Count a request deleted before being sent.
+    fn drop_request(&self, reason: &str, sequence: u64, error: &str) {
+        if self.dropped_requests.increment() {
+            otel_warn!(
+                name: "PersistentQueue.RequestDroppingStarted",
+                directory = self.directory.display().to_string(),
+                reason = reason,
+                sequence = sequence,
+                error = error,
+                message = "A queued request was dropped before being sent. No further log will be emitted for further dropped requests until the exporter is dropped, when the total count is logged.",
+            );
+        }
+    }
+
+    /// Write `payload` as the newest segment, deleting the oldest ones that
+    /// aren't being sent to stay under the maximum size.
+    fn push(&self, payload: &[u8]) -> io::Result<()> {
+        let size = payload.len() as u64;
+        if size > self.max_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "request of {size} bytes exceeds the maximum size of {} bytes",
+                    self.max_size
+                ),
+            ));
+        }
+
+        let mut state = self.lock();
+        while state.size + size > self.max_size {
+            let Some(index) = state.segments.iter().position(|s| !s.in_flight) else {
+                break;
+            };
+            let evicted = state.segments.remove(index).expect("index is in bounds");
+            state.size -= evicted.size;
+            self.drop_request("evicted", evicted.sequence, "the queue is full");
+            self.remove_file(&self.path(evicted.sequence, SEGMENT_EXTENSION));
+        }
+
+        let sequence = state.next_sequence;
+        let temporary = self.path(sequence, TEMPORARY_EXTENSION);
+        write_file(&temporary, payload)
+            .and_then(|_| fs::rename(&temporary, self.path(sequence, SEGMENT_EXTENSION)))
+            .map_err(|e| {
+                self.remove_file(&temporary);
+                e
+            })?;
+
+        state.next_sequence += 1;
+        state.size += size;
+        state.segments.push_back(Segment {
+            sequence,
+            size,
+            in_flight: false,
+        });
+        Ok(())
+    }
+
+    /// Mark the oldest segment that isn't being sent as being sent, and read
+    /// it.
+    fn claim(self: &Arc<Self>) -> io::Result<Option<(Claim, Vec<u8>)>> {
+        loop {
+            let sequence = {
+                let mut state = self.lock();
+                let Some(segment) = state.segments.iter_mut().find(|s| !s.in_flight) else {
+                    return Ok(None);
+                };
+                segment.in_flight = true;
+                segment.sequence
+            };
+            match fs::read(self.path(sequence, SEGMENT_EXTENSION)) {
+                Ok(payload) => {
+                    let claim = Claim {
+                        files: self.clone(),
+                        sequence,
+                        removed: false,
+                    };
+                    return Ok(Some((claim, payload)));
+                }
+                Err(e) => {
+                    self.drop_request("unreadable", sequence, &e.to_string());
+                    self.remove(sequence);
+                }
+            }
+        }
+    }
+
+    /// Let a later export send the segment again.
+    fn release(&self, sequence: u64) {
+        if let Some(segment) = self
+            .lock()
+            .segments
+            .iter_mut()
+            .find(|s| s.sequence == sequence)
+        {
+            segment.in_flight = false;
+        }
+    }
+
+    /// Delete the segment.
+    fn remove(&self, sequence: u64) {
+        let mut state = self.lock();
+        if let Some(index) = state.segments.iter().position(|s| s.sequence == sequence) {
+            let removed = state.segments.remove(index).expect("index is in bounds");
+            state.size -= removed.size;
+        }
+        self.remove_file(&self.path(sequence, SEGMENT_EXTENSION));
+    }
+
+    fn remove_file(&self, path: &Path) {
+        if let Err(e) = fs::remove_file(path) {
+            if e.kind() != io::ErrorKind::NotFound && self.delete_failures.increment() {
+                otel_warn!(
+                    name: "PersistentQueue.DeleteFailed",
+                    path = path.display().to_string(),
+                    error = e.to_string(),
+                    message = "A queued file could not be deleted. No further log will be emitted for further delete failures until the exporter is dropped.",
+                );
+            }
+        }
+    }
+}
+
+fn write_file(path: &Path, payload: &[u8]) -> io::Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(payload)?;
+    // The request must be on disk before it replaces the temporary file, or a
+    // power loss could leave an empty segment.
+    file.sync_all()
+}
+
+#[cfg(all(test, feature = "trace"))]
+mod tests {
+    use super::*;
+    use futures_executor::block_on;
+    use futures_util::future;
+    use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
+    use opentelemetry_proto::tonic::trace::v1::ResourceSpans;
+
+    struct TestDirectory(PathBuf);
+
+    impl TestDirectory {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir()
+                .join(format!("opentelemetry-otlp-{name}-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            TestDirectory(path)
+        }
+    }
+
+    impl Drop for TestDirectory {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn request(id: &str) -> ExportTraceServiceRequest {
+        ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                schema_url: id.to_string(),
+                ..Default::default()
+            }],
+        }
+    }
+
+    fn id(request: &ExportTraceServiceRequest) -> String {
+        request.resource_spans[0].schema_url.clone()
+    }
+
+    fn segment_count(queue: &DiskQueue) -> usize {
+        fs::read_dir(&queue.files.directory).unwrap().count()
+    }
+
+    /// Export `id` with an endpoint failing with `failure`, returning the ids
+    /// of the requests the endpoint received.
+    fn export(queue: &DiskQueue, id: &str, failure: Option<bool>) -> (OTelSdkResult, Vec<String>) {
+        let sent = Mutex::new(Vec::new());
+        let result = block_on(queue.export(request(id), |request| {
+            sent.lock().unwrap().push(self::id(&request));
+            async move {
+                match failure {
+                    Some(retryable) => Err(SendError {
+                        error: OTelSdkError::InternalFailure("unavailable".into()),
+                        retryable,
+                    }),
+                    None => Ok(()),
+                }
+            }
+        }));
+        (result, sent.into_inner().unwrap())
+    }
+
+    #[test]
+    fn requests_are_kept_until_sent() {
+        let directory = TestDirectory::new("requests-are-kept-until-sent");
+        let queue = PersistentQueue::new(&directory.0).open("traces").unwrap();
+
+        let (result, sent) = export(&queue, "a", Some(true));
+        assert!(result.is_err());
+        assert_eq!(sent, ["a"]);
+        let (result, sent) = export(&queue, "b", Some(true));
+        assert!(result.is_err());
+        assert_eq!(sent, ["a"]);
+        assert_eq!(segment_count(&queue), 2);
+
+        let (result, sent) = export(&queue, "c", None);
+        assert!(result.is_ok());
+        assert_eq!(sent, ["a", "b", "c"]);
+        assert_eq!(segment_count(&queue), 0);
+        assert_eq!(queue.files.lock().size, 0);
+    }
+
+    #[test]
+    fn requests_are_replayed_after_restart() {
+        let directory = TestDirectory::new("requests-are-replayed-after-restart");
+        let config = PersistentQueue::new(&directory.0);
+
+        let queue = config.open("traces").unwrap();
+        assert!(export(&queue, "a", Some(true)).0.is_err());
+        assert!(export(&queue, "b", Some(true)).0.is_err());
+        drop(queue);
+        // Left by a process that stopped while writing a request.
+        fs::write(
+            directory.0.join("traces").join("00000000000000000002.tmp"),
+            b"",
+        )
+        .unwrap();
+
+        let queue = config.open("traces").unwrap();
+        assert_eq!(segment_count(&queue), 2);
+        let (result, sent) = export(&queue, "c", None);
+        assert!(result.is_ok());
+        assert_eq!(sent, ["a", "b", "c"]);
+        assert_eq!(segment_count(&queue), 0);
+    }
+
+    #[test]
+    fn rejected_requests_are_dropped() {
+        let directory = TestDirectory::new("rejected-requests-are-dropped");
+        let queue = PersistentQueue::new(&directory.0).open("traces").unwrap();
+
+        let (result, sent) = export(&queue, "a", Some(false));
+        assert!(result.is_err());
+        assert_eq!(sent, ["a"]);
+        assert_eq!(segment_count(&queue), 0);
+    }
+
+    #[test]
+    fn oldest_requests_are_evicted() {
+        let directory = TestDirectory::new("oldest-requests-are-evicted");
+        let size = request("a").encoded_len() as u64;
+        let queue = PersistentQueue::new(&directory.0)
+            .with_max_size(2 * size)
+            .open("traces")
+            .unwrap();
+
+        for id in ["a", "b", "c"] {
+            assert!(export(&queue, id, Some(true)).0.is_err());
+        }
+        assert_eq!(segment_count(&queue), 2);
+        assert_eq!(queue.files.dropped_requests.get(), 1);
+        let (result, sent) = export(&queue, "d", None);
+        assert!(result.is_ok());
+        assert_eq!(sent, ["c", "d"]);
+    }
+
+    #[test]
+    fn replayed_requests_are_bounded_per_export() {
+        let directory = TestDirectory::new("replayed-requests-are-bounded-per-export");
+        let queue = PersistentQueue::new(&directory.0)
+            .with_max_requests_per_export(2)
+            .open("traces")
+            .unwrap();
+
+        for id in ["a", "b", "c"] {
+            assert!(export(&queue, id, Some(true)).0.is_err());
+        }
+        let (result, sent) = export(&queue, "d", None);
+        assert!(result.is_ok());
+        assert_eq!(sent, ["a", "b"]);
+        assert_eq!(segment_count(&queue), 2);
+        let (result, sent) = export(&queue, "e", None);
+        assert!(result.is_ok());
+        assert_eq!(sent, ["c", "d"]);
+        assert_eq!(segment_count(&queue), 1);
+    }
+
+    #[test]
+    fn dropped_exports_release_their_requests() {
+        let directory = TestDirectory::new("dropped-exports-release-their-requests");
+        let queue = PersistentQueue::new(&directory.0).open("traces").unwrap();
+        assert!(export(&queue, "a", Some(true)).0.is_err());
+
+        // Drop the export while it is sending "a".
+        let (sending, sent) = oneshot::channel();
+        let sending = Mutex::new(Some(sending));
+        let pending = queue.export(request("b"), |_| {
+            if let Some(sending) = sending.lock().unwrap().take() {
+                let _ = sending.send(());
+            }
+            future::pending::<Result<(), SendError>>()
+        });
+        block_on(future::select(Box::pin(pending), sent));
+        assert!(queue.files.lock().segments.iter().all(|s| !s.in_flight));
+
+        let (result, sent) = export(&queue, "c", None);
+        assert!(result.is_ok());
+        assert_eq!(sent, ["a", "b", "c"]);
+        assert_eq!(segment_count(&queue), 0);
+    }
+
+    #[test]
+    fn oversized_requests_are_sent_without_queueing() {
+        let directory = TestDirectory::new("oversized-requests-are-sent-without-queueing");
+        let queue = PersistentQueue::new(&directory.0)
+            .with_max_size(1)
+            .open("traces")
+            .unwrap();
+
+        let (result, sent) = export(&queue, "a", None);
+        assert!(result.is_ok());
+        assert_eq!(sent, ["a"]);
+        assert_eq!(segment_count(&queue), 0);
+    }
+}
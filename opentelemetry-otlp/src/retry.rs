@@ -134,17 +134,45 @@ fn generate_jitter(max_jitter: u64) -> u64 {
 /// A `Result` containing the operation's result or an error if max retries are reached
 /// or a non-retryable error occurs.
 pub async fn retry_with_backoff<F, Fut, T, E, C>(
+    policy: &RetryPolicy,
+    deadline: Duration,
+    error_classifier: C,
+    operation_name: &str,
+    operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    E: std::fmt::Debug,
+    Fut: Future<Output = Result<T, E>>,
+    C: Fn(&E) -> RetryErrorType,
+{
+    retry_with_backoff_and_observer(
+        policy,
+        deadline,
+        error_classifier,
+        operation_name,
+        operation,
+        |_| {},
+    )
+    .await
+}
+
+/// Same as [`retry_with_backoff`], calling `on_retry` with the classification
+/// of the error before each retry.
+pub(crate) async fn retry_with_backoff_and_observer<F, Fut, T, E, C, O>(
     policy: &RetryPolicy,
     deadline: Duration,
     error_classifier: C,
     operation_name: &str,
     mut operation: F,
+    on_retry: O,
 ) -> Result<T, E>
 where
     F: FnMut() -> Fut,
     E: std::fmt::Debug,
     Fut: Future<Output = Result<T, E>>,
     C: Fn(&E) -> RetryErrorType,
+    O: Fn(&RetryErrorType),
 {
     let start = Instant::now();
     let mut attempt = 0;
@@ -199,6 +227,7 @@ where
                             delay_ms = sleep_duration.as_millis(),
                             message = "OTLP export failed with retryable error - retrying"
                         );
+                        on_retry(&error_type);
                         sleep_for(sleep_duration).await;
                         delay = std::cmp::min(delay * 2, policy.max_delay_ms);
                     }
@@ -225,6 +254,7 @@ where
                             server_requested_ms = server_delay.as_millis(),
                             message = "OTLP export throttled by OTLP endpoint - delaying and retrying"
                         );
+                        on_retry(&error_type);
                         sleep_for(capped_delay).await;
                         // Don't update exponential backoff delay since server provided specific timing
                     }
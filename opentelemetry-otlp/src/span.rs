@@ -4,9 +4,12 @@
 
 use std::fmt::Debug;
 
+use crate::exporter::self_diagnostics::suppress_telemetry;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
 use opentelemetry_sdk::error::OTelSdkResult;
 use opentelemetry_sdk::trace::SpanData;
 
+use crate::persistent_queue::{open_queue, DiskQueue, PersistentQueue};
 use crate::ExporterBuildError;
 #[cfg(feature = "grpc-tonic")]
 use crate::{
@@ -45,6 +48,15 @@ pub const OTEL_EXPORTER_OTLP_TRACES_INSECURE: &str = "OTEL_EXPORTER_OTLP_TRACES_
 #[derive(Debug, Default, Clone)]
 pub struct SpanExporterBuilder<C> {
     client: C,
+    persistent_queue: Option<PersistentQueue>,
+}
+
+impl<C> SpanExporterBuilder<C> {
+    /// Keep the spans on disk until they are exported, see [PersistentQueue].
+    pub fn with_persistent_queue(mut self, persistent_queue: PersistentQueue) -> Self {
+        self.persistent_queue = Some(persistent_queue);
+        self
+    }
 }
 
 impl SpanExporterBuilder<NoExporterBuilderSet> {
@@ -58,6 +70,7 @@ impl SpanExporterBuilder<NoExporterBuilderSet> {
     pub fn with_tonic(self) -> SpanExporterBuilder<TonicExporterBuilderSet> {
         SpanExporterBuilder {
             client: TonicExporterBuilderSet(TonicExporterBuilder::default()),
+            persistent_queue: self.persistent_queue,
         }
     }
 
@@ -66,6 +79,7 @@ impl SpanExporterBuilder<NoExporterBuilderSet> {
     pub fn with_http(self) -> SpanExporterBuilder<HttpExporterBuilderSet> {
         SpanExporterBuilder {
             client: HttpExporterBuilderSet(HttpExporterBuilder::default()),
+            persistent_queue: self.persistent_queue,
         }
     }
 
@@ -100,7 +114,9 @@ impl SpanExporterBuilder<NoExporterBuilderSet> {
 impl SpanExporterBuilder<TonicExporterBuilderSet> {
     /// Build the [SpanExporter] with the gRPC Tonic transport.
     pub fn build(self) -> Result<SpanExporter, ExporterBuildError> {
-        let span_exporter = self.client.0.build_span_exporter()?;
+        let queue = open_queue(self.persistent_queue.as_ref(), "traces")?;
+        let mut span_exporter = self.client.0.build_span_exporter()?;
+        span_exporter.queue = queue;
         opentelemetry::otel_debug!(name: "SpanExporterBuilt");
         Ok(span_exporter)
     }
@@ -110,7 +126,9 @@ impl SpanExporterBuilder<TonicExporterBuilderSet> {
 impl SpanExporterBuilder<HttpExporterBuilderSet> {
     /// Build the [SpanExporter] with the HTTP transport.
     pub fn build(self) -> Result<SpanExporter, ExporterBuildError> {
-        let span_exporter = self.client.0.build_span_exporter()?;
+        let queue = open_queue(self.persistent_queue.as_ref(), "traces")?;
+        let mut span_exporter = self.client.0.build_span_exporter()?;
+        span_exporter.queue = queue;
         Ok(span_exporter)
     }
 }
@@ -147,6 +165,7 @@ impl HasHttpConfig for SpanExporterBuilder<HttpExporterBuilderSet> {
 #[derive(Debug)]
 pub struct SpanExporter {
     client: SupportedTransportClient,
+    queue: Option<DiskQueue>,
 }
 
 #[derive(Debug)]
@@ -167,6 +186,7 @@ impl SpanExporter {
     pub(crate) fn from_http(client: crate::exporter::http::OtlpHttpClient) -> Self {
         SpanExporter {
             client: SupportedTransportClient::Http(client),
+            queue: None,
         }
     }
 
@@ -174,17 +194,58 @@ impl SpanExporter {
     pub(crate) fn from_tonic(client: crate::exporter::tonic::trace::TonicTracesClient) -> Self {
         SpanExporter {
             client: SupportedTransportClient::Tonic(client),
+            queue: None,
         }
     }
+
+    async fn export_queued(&self, queue: &DiskQueue, batch: Vec<SpanData>) -> OTelSdkResult {
+        let request = match &self.client {
+            #[cfg(feature = "grpc-tonic")]
+            SupportedTransportClient::Tonic(client) => client.build_request(batch),
+            #[cfg(any(feature = "http-proto", feature = "http-json"))]
+            SupportedTransportClient::Http(client) => client.build_trace_request(batch),
+        };
+        queue
+            .export(request, |request| async move {
+                let items = span_count(&request);
+                match &self.client {
+                    #[cfg(feature = "grpc-tonic")]
+                    SupportedTransportClient::Tonic(client) => {
+                        client.export_request(request, items).await
+                    }
+                    #[cfg(any(feature = "http-proto", feature = "http-json"))]
+                    SupportedTransportClient::Http(client) => {
+                        client.export_trace_request(request, items).await
+                    }
+                }
+            })
+            .await
+    }
+}
+
+fn span_count(request: &ExportTraceServiceRequest) -> usize {
+    request
+        .resource_spans
+        .iter()
+        .flat_map(|resource_spans| &resource_spans.scope_spans)
+        .map(|scope_spans| scope_spans.spans.len())
+        .sum()
 }
 
 impl opentelemetry_sdk::trace::SpanExporter for SpanExporter {
     async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        if let Some(queue) = &self.queue {
+            return suppress_telemetry(self.export_queued(queue, batch)).await;
+        }
         match &self.client {
             #[cfg(feature = "grpc-tonic")]
-            SupportedTransportClient::Tonic(client) => client.export(batch).await,
+            SupportedTransportClient::Tonic(client) => {
+                suppress_telemetry(client.export(batch)).await
+            }
             #[cfg(any(feature = "http-proto", feature = "http-json"))]
-            SupportedTransportClient::Http(client) => client.export(batch).await,
+            SupportedTransportClient::Http(client) => {
+                suppress_telemetry(client.export(batch)).await
+            }
         }
     }
 
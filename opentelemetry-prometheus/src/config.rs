@@ -3,7 +3,7 @@ use once_cell::sync::OnceCell;
 use opentelemetry_sdk::metrics::ManualReaderBuilder;
 use std::sync::{Arc, Mutex};
 
-use crate::{Collector, PrometheusExporter, ResourceSelector};
+use crate::{Collector, PrometheusExporter, RegisteredCollector, ResourceSelector};
 
 /// [PrometheusExporter] configuration options
 pub struct ExporterBuilder {
@@ -131,7 +131,7 @@ impl ExporterBuilder {
     pub fn build(self) -> Result<PrometheusExporter, opentelemetry_sdk::error::OTelSdkError> {
         let reader = Arc::new(self.reader.build());
 
-        let collector = Collector {
+        let collector = Arc::new(Collector {
             reader: Arc::clone(&reader),
             disable_target_info: self.disable_target_info,
             without_units: self.without_units,
@@ -142,13 +142,13 @@ impl ExporterBuilder {
             inner: Mutex::new(Default::default()),
             resource_selector: self.resource_selector,
             resource_labels_once: OnceCell::new(),
-        };
+        });
 
         let registry = self.registry.unwrap_or_default();
         registry
-            .register(Box::new(collector))
+            .register(Box::new(RegisteredCollector(Arc::clone(&collector))))
             .map_err(|e| opentelemetry_sdk::error::OTelSdkError::InternalFailure(e.to_string()))?;
 
-        Ok(PrometheusExporter { reader })
+        Ok(PrometheusExporter { reader, collector })
     }
 }
@@ -108,6 +108,8 @@ use std::{
 };
 use std::{fmt, sync::Weak};
 
+use open_metrics::SampleExemplar;
+
 const TARGET_INFO_NAME: &str = "target_info";
 const TARGET_INFO_DESCRIPTION: &str = "Target metadata";
 
@@ -126,6 +128,7 @@ const RESERVED_SCOPE_LABELS: [&str; 3] = [
 const COUNTER_SUFFIX: &str = "_total";
 
 mod config;
+mod open_metrics;
 mod resource_selector;
 mod utils;
 
@@ -137,10 +140,41 @@ pub fn exporter() -> ExporterBuilder {
     ExporterBuilder::default()
 }
 
+/// The content type of the metrics encoded by
+/// [`PrometheusExporter::encode_open_metrics`].
+pub const OPEN_METRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
 /// Prometheus metrics exporter
-#[derive(Debug)]
+#[derive(Clone)]
 pub struct PrometheusExporter {
     reader: Arc<ManualReader>,
+    collector: Arc<Collector>,
+}
+
+impl fmt::Debug for PrometheusExporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrometheusExporter")
+            .field("reader", &self.reader)
+            .finish()
+    }
+}
+
+impl PrometheusExporter {
+    /// Collects the metrics of this exporter and writes them in the
+    /// [OpenMetrics] text format, with the exemplars of counters and histogram
+    /// buckets.
+    ///
+    /// The encoders of the `prometheus` crate can't represent exemplars, so
+    /// the metrics gathered from the registry don't have them. Serve the
+    /// output of this method with the [OPEN_METRICS_CONTENT_TYPE] to scrapers
+    /// interested in exemplars. It only contains the metrics of this
+    /// exporter, not those of other collectors of the registry.
+    ///
+    /// [OpenMetrics]: https://github.com/prometheus/OpenMetrics/blob/main/specification/OpenMetrics.md
+    pub fn encode_open_metrics<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        open_metrics::encode(&self.collector.collect_with_exemplars(), writer)
+    }
 }
 
 impl MetricReader for PrometheusExporter {
@@ -257,12 +291,26 @@ impl Collector {
     }
 }
 
-impl prometheus::core::Collector for Collector {
+/// The [Collector] of an exporter, registered in its registry.
+struct RegisteredCollector(Arc<Collector>);
+
+impl prometheus::core::Collector for RegisteredCollector {
     fn desc(&self) -> Vec<&Desc> {
         Vec::new()
     }
 
     fn collect(&self) -> Vec<MetricFamily> {
+        self.0
+            .collect_with_exemplars()
+            .into_iter()
+            .map(|(mf, _)| mf)
+            .collect()
+    }
+}
+
+impl Collector {
+    /// Collects the metric families, with the exemplars of their metric.
+    fn collect_with_exemplars(&self) -> Vec<(MetricFamily, Vec<SampleExemplar>)> {
         let mut inner = match self.inner.lock() {
             Ok(guard) => guard,
             Err(err) => {
@@ -294,7 +342,7 @@ impl prometheus::core::Collector for Collector {
         });
 
         if !self.disable_target_info && !metrics.resource().is_empty() {
-            res.push(target_info.clone())
+            res.push((target_info.clone(), Vec::new()))
         }
 
         let resource_labels = self
@@ -522,15 +570,12 @@ fn validate_metrics(
 }
 
 fn add_histogram_metric<T: Numeric + Copy>(
-    res: &mut Vec<MetricFamily>,
+    res: &mut Vec<(MetricFamily, Vec<SampleExemplar>)>,
     histogram: &data::Histogram<T>,
     description: String,
     extra: &[LabelPair],
     name: Cow<'static, str>,
 ) {
-    // Consider supporting exemplars when `prometheus` crate has the feature
-    // See: https://github.com/tikv/rust-prometheus/issues/393
-
     for dp in histogram.data_points() {
         let kvs = get_attrs(&mut dp.attributes().map(|kv| (&kv.key, &kv.value)), extra);
         let bounds: Vec<f64> = dp.bounds().collect();
@@ -562,12 +607,12 @@ fn add_histogram_metric<T: Numeric + Copy>(
         mf.set_help(description.clone());
         mf.set_field_type(prometheus::proto::MetricType::HISTOGRAM);
         mf.set_metric(vec![pm]);
-        res.push(mf);
+        res.push((mf, open_metrics::histogram_exemplars(dp)));
     }
 }
 
 fn add_sum_metric<T: Numeric + Copy>(
-    res: &mut Vec<MetricFamily>,
+    res: &mut Vec<(MetricFamily, Vec<SampleExemplar>)>,
     sum: &data::Sum<T>,
     description: String,
     extra: &[LabelPair],
@@ -600,12 +645,17 @@ fn add_sum_metric<T: Numeric + Copy>(
         mf.set_help(description.clone());
         mf.set_field_type(metric_type);
         mf.set_metric(vec![pm]);
-        res.push(mf);
+        let exemplars = if sum.is_monotonic() {
+            open_metrics::counter_exemplar(dp).into_iter().collect()
+        } else {
+            Vec::new()
+        };
+        res.push((mf, exemplars));
     }
 }
 
 fn add_gauge_metric<T: Numeric + Copy>(
-    res: &mut Vec<MetricFamily>,
+    res: &mut Vec<(MetricFamily, Vec<SampleExemplar>)>,
     gauge: &data::Gauge<T>,
     description: String,
     extra: &[LabelPair],
@@ -625,7 +675,7 @@ fn add_gauge_metric<T: Numeric + Copy>(
         mf.set_help(description.to_string());
         mf.set_field_type(MetricType::GAUGE);
         mf.set_metric(vec![pm]);
-        res.push(mf);
+        res.push((mf, Vec::new()));
     }
 }
 
@@ -0,0 +1,301 @@
+//! Encoding of collected metrics in the [OpenMetrics] text format, which,
+//! unlike the encoders of the `prometheus` crate, carries exemplars.
+//!
+//! [OpenMetrics]: https://github.com/prometheus/OpenMetrics/blob/main/specification/OpenMetrics.md
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use opentelemetry_sdk::metrics::data;
+use prometheus::proto::{LabelPair, Metric, MetricFamily, MetricType};
+
+use crate::{utils, Numeric, COUNTER_SUFFIX};
+
+/// The maximum number of UTF-8 characters of the names and values of the
+/// labels of an exemplar.
+const MAX_EXEMPLAR_LABELS_LENGTH: usize = 128;
+const TRACE_ID_LABEL: &str = "trace_id";
+const SPAN_ID_LABEL: &str = "span_id";
+
+/// The exemplar of the counter, or of the histogram bucket at index `bucket`,
+/// of a collected metric.
+#[derive(Debug)]
+pub(crate) struct SampleExemplar {
+    bucket: Option<usize>,
+    labels: Vec<LabelPair>,
+    value: f64,
+    time: SystemTime,
+}
+
+/// Returns the exemplar of the counter `dp`, the last one recorded.
+pub(crate) fn counter_exemplar<T: Numeric + Copy>(
+    dp: &data::SumDataPoint<T>,
+) -> Option<SampleExemplar> {
+    dp.exemplars()
+        .max_by_key(|e| e.time())
+        .map(|e| sample_exemplar(e, None))
+}
+
+/// Returns the exemplars of the buckets of the histogram `dp`, the last one
+/// recorded in each bucket.
+pub(crate) fn histogram_exemplars<T: Numeric + Copy>(
+    dp: &data::HistogramDataPoint<T>,
+) -> Vec<SampleExemplar> {
+    let bounds: Vec<f64> = dp.bounds().collect();
+    let mut exemplars: Vec<Option<&data::Exemplar<T>>> = vec![None; bounds.len() + 1];
+    for exemplar in dp.exemplars() {
+        let value = exemplar.value.as_f64();
+        let bucket = bounds.partition_point(|bound| *bound < value);
+        let slot = &mut exemplars[bucket];
+        if slot.map_or(true, |e| e.time() <= exemplar.time()) {
+            *slot = Some(exemplar);
+        }
+    }
+    exemplars
+        .into_iter()
+        .enumerate()
+        .filter_map(|(bucket, e)| e.map(|e| sample_exemplar(e, Some(bucket))))
+        .collect()
+}
+
+fn sample_exemplar<T: Numeric + Copy>(
+    exemplar: &data::Exemplar<T>,
+    bucket: Option<usize>,
+) -> SampleExemplar {
+    let mut labels = Vec::new();
+    let mut length = 0;
+    let mut push = |name: String, value: String| {
+        let label_length = name.chars().count() + value.chars().count();
+        if length + label_length <= MAX_EXEMPLAR_LABELS_LENGTH {
+            length += label_length;
+            let mut label = LabelPair::default();
+            label.set_name(name);
+            label.set_value(value);
+            labels.push(label);
+        }
+    };
+
+    if exemplar.trace_id() != &[0; 16] {
+        push(TRACE_ID_LABEL.to_string(), hex(exemplar.trace_id()));
+        push(SPAN_ID_LABEL.to_string(), hex(exemplar.span_id()));
+    }
+    for kv in exemplar.filtered_attributes() {
+        push(
+            utils::sanitize_prom_kv(kv.key.as_str()),
+            kv.value.to_string(),
+        );
+    }
+
+    SampleExemplar {
+        bucket,
+        labels,
+        value: exemplar.value.as_f64(),
+        time: exemplar.time(),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Writes `families` in the OpenMetrics text format. Metric families with the
+/// same name are merged.
+pub(crate) fn encode<W: Write>(
+    families: &[(MetricFamily, Vec<SampleExemplar>)],
+    writer: &mut W,
+) -> io::Result<()> {
+    let mut order = Vec::new();
+    let mut by_name: HashMap<&str, Vec<&(MetricFamily, Vec<SampleExemplar>)>> = HashMap::new();
+    for family in families {
+        by_name
+            .entry(family.0.name())
+            .or_insert_with(|| {
+                order.push(family.0.name());
+                Vec::new()
+            })
+            .push(family);
+    }
+
+    for name in order {
+        let group = &by_name[name];
+        let first = &group[0].0;
+        let metric_type = first.get_field_type();
+        let family_name = match metric_type {
+            MetricType::COUNTER => name.strip_suffix(COUNTER_SUFFIX).unwrap_or(name),
+            _ => name,
+        };
+        let type_name = match metric_type {
+            MetricType::COUNTER => "counter",
+            MetricType::GAUGE => "gauge",
+            MetricType::HISTOGRAM => "histogram",
+            MetricType::SUMMARY => "summary",
+            MetricType::UNTYPED => "unknown",
+        };
+        writeln!(writer, "# TYPE {family_name} {type_name}")?;
+        if !first.help().is_empty() {
+            writeln!(
+                writer,
+                "# HELP {family_name} {}",
+                escape(first.help(), false)
+            )?;
+        }
+
+        for (family, exemplars) in group {
+            for metric in family.get_metric() {
+                match metric_type {
+                    MetricType::COUNTER => {
+                        let exemplar = exemplars.iter().find(|e| e.bucket.is_none());
+                        write_sample(
+                            writer,
+                            &format!("{family_name}{COUNTER_SUFFIX}"),
+                            metric,
+                            None,
+                            metric.get_counter().get_value(),
+                            exemplar,
+                        )?;
+                    }
+                    MetricType::HISTOGRAM => {
+                        write_histogram(writer, family_name, metric, exemplars)?;
+                    }
+                    _ => write_sample(
+                        writer,
+                        family_name,
+                        metric,
+                        None,
+                        metric.get_gauge().get_value(),
+                        None,
+                    )?,
+                }
+            }
+        }
+    }
+
+    writeln!(writer, "# EOF")
+}
+
+fn write_histogram<W: Write>(
+    writer: &mut W,
+    name: &str,
+    metric: &Metric,
+    exemplars: &[SampleExemplar],
+) -> io::Result<()> {
+    let histogram = metric.get_histogram();
+    let bucket_name = format!("{name}_bucket");
+    let buckets = histogram.get_bucket();
+    for (i, bucket) in buckets.iter().enumerate() {
+        let exemplar = exemplars.iter().find(|e| e.bucket == Some(i));
+        write_sample(
+            writer,
+            &bucket_name,
+            metric,
+            Some(&format_value(bucket.upper_bound())),
+            bucket.cumulative_count() as f64,
+            exemplar,
+        )?;
+    }
+    let exemplar = exemplars.iter().find(|e| e.bucket == Some(buckets.len()));
+    write_sample(
+        writer,
+        &bucket_name,
+        metric,
+        Some("+Inf"),
+        histogram.get_sample_count() as f64,
+        exemplar,
+    )?;
+    write_sample(
+        writer,
+        &format!("{name}_sum"),
+        metric,
+        None,
+        histogram.get_sample_sum(),
+        None,
+    )?;
+    write_sample(
+        writer,
+        &format!("{name}_count"),
+        metric,
+        None,
+        histogram.get_sample_count() as f64,
+        None,
+    )
+}
+
+fn write_sample<W: Write>(
+    writer: &mut W,
+    name: &str,
+    metric: &Metric,
+    le: Option<&str>,
+    value: f64,
+    exemplar: Option<&SampleExemplar>,
+) -> io::Result<()> {
+    write!(writer, "{name}")?;
+    let le = le.map(|le| ("le", le));
+    write_labels(
+        writer,
+        metric.get_label().iter().map(|l| (l.name(), l.value())),
+        le,
+    )?;
+    write!(writer, " {}", format_value(value))?;
+    if let Some(exemplar) = exemplar {
+        write!(writer, " # ")?;
+        if exemplar.labels.is_empty() {
+            write!(writer, "{{}}")?;
+        } else {
+            write_labels(
+                writer,
+                exemplar.labels.iter().map(|l| (l.name(), l.value())),
+                None,
+            )?;
+        }
+        let timestamp = exemplar
+            .time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        write!(writer, " {} {timestamp:.3}", format_value(exemplar.value))?;
+    }
+    writeln!(writer)
+}
+
+fn write_labels<'a, W: Write>(
+    writer: &mut W,
+    labels: impl Iterator<Item = (&'a str, &'a str)>,
+    extra: Option<(&'a str, &'a str)>,
+) -> io::Result<()> {
+    let mut separator = "{";
+    for (name, value) in labels.chain(extra) {
+        write!(writer, "{separator}{name}=\"{}\"", escape(value, true))?;
+        separator = ",";
+    }
+    if separator == "," {
+        write!(writer, "}}")?;
+    }
+    Ok(())
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape(value: &str, quotes: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '"' if quotes => escaped.push_str("\\\""),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
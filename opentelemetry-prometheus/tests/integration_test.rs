@@ -6,7 +6,7 @@ use opentelemetry::metrics::{Meter, MeterProvider as _};
 use opentelemetry::KeyValue;
 use opentelemetry::{InstrumentationScope, Key};
 use opentelemetry_prometheus::{ExporterBuilder, ResourceSelector};
-use opentelemetry_sdk::metrics::SdkMeterProvider;
+use opentelemetry_sdk::metrics::{ExemplarFilter, SdkMeterProvider};
 use opentelemetry_sdk::Resource;
 use opentelemetry_semantic_conventions::resource::{SERVICE_NAME, TELEMETRY_SDK_VERSION};
 use prometheus::{Encoder, TextEncoder};
@@ -486,6 +486,62 @@ fn prometheus_exporter_integration() {
     }
 }
 
+#[test]
+fn exemplars_are_encoded_in_open_metrics() {
+    let exporter = ExporterBuilder::default()
+        .with_registry(prometheus::Registry::new())
+        .without_target_info()
+        .scope_info_enabled(false)
+        .build()
+        .unwrap();
+    let open_metrics_exporter = exporter.clone();
+    let provider = SdkMeterProvider::builder()
+        .with_reader(exporter)
+        .with_exemplar_filter(ExemplarFilter::AlwaysOn)
+        .build();
+    let meter = provider.meter("test");
+    meter
+        .u64_counter("requests")
+        .with_description("Handled \"requests\"")
+        .build()
+        .add(3, &[KeyValue::new("route", "/")]);
+    meter
+        .f64_histogram("latency")
+        .with_boundaries(vec![1.0, 10.0])
+        .build()
+        .record(5.0, &[]);
+
+    let mut output = Vec::new();
+    open_metrics_exporter
+        .encode_open_metrics(&mut output)
+        .unwrap();
+    let output = String::from_utf8(output).unwrap();
+
+    // Exemplar timestamps vary between runs.
+    let output: Vec<&str> = output
+        .lines()
+        .map(|line| match line.contains(" # {") {
+            true => line.rsplit_once(' ').unwrap().0,
+            false => line,
+        })
+        .collect();
+    assert_eq!(
+        output,
+        [
+            "# TYPE requests counter",
+            "# HELP requests Handled \"requests\"",
+            "requests_total{route=\"/\"} 3 # {} 3",
+            "# TYPE latency histogram",
+            "latency_bucket{le=\"1\"} 0",
+            "latency_bucket{le=\"10\"} 1 # {} 5",
+            "latency_bucket{le=\"+Inf\"} 1",
+            "latency_sum 5",
+            "latency_count 1",
+            "# EOF",
+        ]
+    );
+}
+
 fn gather_and_compare(registry: prometheus::Registry, expected: String, name: &'static str) {
     let expected = get_platform_specific_string(expected);
     let output_string = get_platform_specific_string(gather_and_encode(registry));
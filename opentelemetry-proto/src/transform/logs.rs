@@ -110,7 +110,7 @@ pub mod tonic {
                     .map(Into::into)
                     .unwrap_or_default(),
                 body: log_record.body().cloned().map(Into::into),
-                dropped_attributes_count: 0,
+                dropped_attributes_count: log_record.dropped_attributes_count(),
                 flags: trace_context
                     .map(|ctx| {
                         ctx.trace_flags
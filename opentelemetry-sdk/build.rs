@@ -0,0 +1,20 @@
+//! Records the version of the compiler for the `process.runtime.version`
+//! resource attribute.
+use std::{env, process::Command};
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=RUSTC");
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    // e.g. `rustc 1.75.0 (82e1608df 2023-12-21)`
+    if let Some(version) = version
+        .as_deref()
+        .and_then(|version| version.split_whitespace().nth(1))
+    {
+        println!("cargo:rustc-env=OTEL_SDK_RUSTC_VERSION={version}");
+    }
+}
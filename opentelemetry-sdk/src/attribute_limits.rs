@@ -0,0 +1,132 @@
+//! Truncation of attribute values exceeding the configured length limit.
+//!
+//! As defined by the [specification], strings are truncated to the maximum
+//! allowed number of characters, and each element of string arrays is
+//! truncated the same way. Other values are left untouched.
+//!
+//! [specification]: https://github.com/open-telemetry/opentelemetry-specification/blob/main/specification/common/README.md#attribute-limits
+#[cfg(feature = "logs")]
+use opentelemetry::logs::AnyValue;
+use opentelemetry::StringValue;
+#[cfg(feature = "trace")]
+use opentelemetry::{Array, KeyValue, Value};
+
+/// Truncates the value of each attribute to `max_length` characters.
+#[cfg(feature = "trace")]
+pub(crate) fn truncate_attributes(attributes: &mut [KeyValue], max_length: Option<u32>) {
+    if let Some(max_length) = max_length {
+        for attribute in attributes {
+            truncate_value(&mut attribute.value, max_length as usize);
+        }
+    }
+}
+
+/// Truncates strings and arrays of strings to `max_length` characters.
+#[cfg(feature = "trace")]
+pub(crate) fn truncate_value(value: &mut Value, max_length: usize) {
+    match value {
+        Value::String(s) => truncate_string(s, max_length),
+        Value::Array(Array::String(values)) => {
+            for s in values {
+                truncate_string(s, max_length);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Truncates strings to `max_length` characters, looking into lists and maps
+/// for nested strings.
+#[cfg(feature = "logs")]
+pub(crate) fn truncate_any_value(value: &mut AnyValue, max_length: usize) {
+    match value {
+        AnyValue::String(s) => truncate_string(s, max_length),
+        AnyValue::ListAny(values) => {
+            for value in values.iter_mut() {
+                truncate_any_value(value, max_length);
+            }
+        }
+        AnyValue::Map(map) => {
+            for value in map.values_mut() {
+                truncate_any_value(value, max_length);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn truncate_string(s: &mut StringValue, max_length: usize) {
+    if let Some((end, _)) = s.as_str().char_indices().nth(max_length) {
+        *s = StringValue::from(s.as_str()[..end].to_owned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_on_char_boundaries() {
+        let mut s = StringValue::from("héllo wörld");
+        truncate_string(&mut s, 7);
+        assert_eq!(s.as_str(), "héllo w");
+
+        let mut s = StringValue::from("short");
+        truncate_string(&mut s, 5);
+        assert_eq!(s.as_str(), "short");
+
+        truncate_string(&mut s, 0);
+        assert_eq!(s.as_str(), "");
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn truncates_strings_and_string_arrays() {
+        let mut attributes = [
+            KeyValue::new("string", "abcdef"),
+            KeyValue::new(
+                "array",
+                Value::Array(Array::String(vec!["abcdef".into(), "ab".into()])),
+            ),
+            KeyValue::new("int", 123456),
+        ];
+        truncate_attributes(&mut attributes, None);
+        assert_eq!(attributes[0].value, Value::from("abcdef"));
+
+        truncate_attributes(&mut attributes, Some(3));
+        assert_eq!(attributes[0].value, Value::from("abc"));
+        assert_eq!(
+            attributes[1].value,
+            Value::Array(Array::String(vec!["abc".into(), "ab".into()]))
+        );
+        assert_eq!(attributes[2].value, Value::I64(123456));
+    }
+
+    #[cfg(feature = "logs")]
+    #[test]
+    fn truncates_nested_any_values() {
+        use opentelemetry::Key;
+        use std::collections::HashMap;
+
+        let mut value = AnyValue::ListAny(Box::new(vec![
+            AnyValue::from("abcdef"),
+            AnyValue::Map(Box::new(HashMap::from([(
+                Key::new("key"),
+                AnyValue::from("abcdef"),
+            )]))),
+            AnyValue::Bytes(Box::new(vec![1, 2, 3, 4])),
+        ]));
+        truncate_any_value(&mut value, 2);
+        assert_eq!(
+            value,
+            AnyValue::ListAny(Box::new(vec![
+                AnyValue::from("ab"),
+                AnyValue::Map(Box::new(HashMap::from([(
+                    Key::new("key"),
+                    AnyValue::from("ab"),
+                )]))),
+                AnyValue::Bytes(Box::new(vec![1, 2, 3, 4])),
+            ]))
+        );
+    }
+}
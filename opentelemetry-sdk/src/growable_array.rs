@@ -105,6 +105,21 @@ impl<
             .take(self.count)
             .chain(overflow_slice.iter())
     }
+
+    /// Returns an iterator over mutable references to the elements in the
+    /// `GrowableArray`, in the same order as [`GrowableArray::iter`].
+    #[allow(dead_code)]
+    #[inline]
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let overflow_slice = match &mut self.overflow {
+            Some(v) => v.as_mut_slice(),
+            None => &mut [],
+        };
+        self.inline
+            .iter_mut()
+            .take(self.count)
+            .chain(overflow_slice.iter_mut())
+    }
 }
 
 // Implement `IntoIterator` for `GrowableArray`
@@ -227,6 +242,18 @@ mod tests {
         assert_eq!(count, 15);
     }
 
+    #[test]
+    fn test_mut_iter() {
+        let mut collection = GrowableArray::<i32>::new();
+        for i in 0..15 {
+            collection.push(i);
+        }
+        for value in collection.iter_mut() {
+            *value *= 2;
+        }
+        assert!(collection.iter().copied().eq((0..15).map(|i| i * 2)));
+    }
+
     #[test]
     fn test_key_value_pair_storage_growable_array() {
         let mut collection = GrowableArray::<KeyValuePair>::new();
@@ -86,13 +86,14 @@
 //! |---|---|---|
 //! | `OTEL_SERVICE_NAME` | Sets the value of the `service.name` resource attribute. Takes priority over `service.name` in `OTEL_RESOURCE_ATTRIBUTES`. | `unknown_service:<process_name>` |
 //! | `OTEL_RESOURCE_ATTRIBUTES` | Key-value pairs to be used as resource attributes. Format: `key1=value1,key2=value2`. | (none) |
+//! | `OTEL_ATTRIBUTE_VALUE_LENGTH_LIMIT` | Maximum length, in characters, of string attribute values of spans and log records. Used when the signal specific variable is not set. | (no limit) |
 //!
 //! ### Trace: Sampler
 //!
 //! | Variable | Description | Default |
 //! |---|---|---|
-//! | `OTEL_TRACES_SAMPLER` | Sampler to use. Valid values: `always_on`, `always_off`, `traceidratio`, `parentbased_always_on`, `parentbased_always_off`, `parentbased_traceidratio`. | `parentbased_always_on` |
-//! | `OTEL_TRACES_SAMPLER_ARG` | Argument for the sampler. Used when `OTEL_TRACES_SAMPLER` is `traceidratio` or `parentbased_traceidratio`. Must be a float between 0.0 and 1.0. | `1.0` |
+//! | `OTEL_TRACES_SAMPLER` | Sampler to use. Valid values: `always_on`, `always_off`, `traceidratio`, `parentbased_always_on`, `parentbased_always_off`, `parentbased_traceidratio`, `rate_limited`, `parentbased_rate_limited`, and `jaeger_remote` and `parentbased_jaeger_remote` with the `jaeger_remote_sampler` feature. | `parentbased_always_on` |
+//! | `OTEL_TRACES_SAMPLER_ARG` | Argument for the sampler. Used when `OTEL_TRACES_SAMPLER` is `traceidratio` or `parentbased_traceidratio`, where it must be a float between 0.0 and 1.0, `rate_limited` or `parentbased_rate_limited`, where it must be the maximum number of traces sampled per second, or `jaeger_remote` or `parentbased_jaeger_remote`, where it is a comma-separated list of `endpoint`, `pollingIntervalMs` and `initialSamplingRate` settings. | `1.0` |
 //!
 //! ### Trace: Span Limits
 //!
@@ -101,6 +102,7 @@
 //! | `OTEL_SPAN_ATTRIBUTE_COUNT_LIMIT` | Maximum number of attributes allowed on a span. | `128` |
 //! | `OTEL_SPAN_EVENT_COUNT_LIMIT` | Maximum number of events allowed on a span. | `128` |
 //! | `OTEL_SPAN_LINK_COUNT_LIMIT` | Maximum number of links allowed on a span. | `128` |
+//! | `OTEL_SPAN_ATTRIBUTE_VALUE_LENGTH_LIMIT` | Maximum length, in characters, of string attribute values of spans, events and links. | (no limit) |
 //!
 //! ### Trace: Batch Span Processor (BSP)
 //!
@@ -111,6 +113,13 @@
 //! | `OTEL_BSP_MAX_EXPORT_BATCH_SIZE` | Maximum batch size. Must be less than or equal to `OTEL_BSP_MAX_QUEUE_SIZE`. | `512` |
 //! | `OTEL_BSP_MAX_CONCURRENT_EXPORTS` | Maximum number of concurrent exports. Honored by `span_processor_with_async_runtime::BatchSpanProcessor`; thread-based `BatchSpanProcessor` exports serially. For concurrent exports, enable `experimental_trace_batch_span_processor_with_async_runtime` and use the async-runtime processor. | `1` |
 //!
+//! ### Logs: Log Record Limits
+//!
+//! | Variable | Description | Default |
+//! |---|---|---|
+//! | `OTEL_LOGRECORD_ATTRIBUTE_COUNT_LIMIT` | Maximum number of attributes allowed on a log record. | `128` |
+//! | `OTEL_LOGRECORD_ATTRIBUTE_VALUE_LENGTH_LIMIT` | Maximum length, in characters, of string attribute values of log records. | (no limit) |
+//!
 //! ### Logs: Batch Log Record Processor (BLRP)
 //!
 //! | Variable | Description | Default |
@@ -162,6 +171,8 @@
 )]
 #![cfg_attr(test, deny(warnings))]
 
+#[cfg(any(feature = "trace", feature = "logs"))]
+pub(crate) mod attribute_limits;
 pub(crate) mod growable_array;
 
 #[cfg(feature = "logs")]
@@ -173,9 +184,20 @@ pub mod metrics;
 #[cfg(feature = "trace")]
 #[cfg_attr(docsrs, doc(cfg(feature = "trace")))]
 pub mod propagation;
+#[cfg(any(feature = "trace", feature = "logs"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "trace", feature = "logs"))))]
+pub mod redaction;
 pub mod resource;
 #[cfg(feature = "experimental_async_runtime")]
 pub mod runtime;
+#[cfg(any(feature = "trace", feature = "logs", feature = "metrics"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "trace", feature = "logs", feature = "metrics")))
+)]
+pub mod scope_config;
+#[cfg(any(feature = "trace", feature = "logs", feature = "metrics"))]
+pub(crate) mod self_diagnostics;
 #[cfg(any(feature = "testing", test))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "testing", test))))]
 pub mod testing;
@@ -17,6 +17,7 @@
 
 use crate::error::{OTelSdkError, OTelSdkResult};
 use crate::logs::log_processor::LogProcessor;
+use crate::self_diagnostics::{ComponentType, ProcessorMetrics, SdkMeter};
 use crate::{
     logs::{LogBatch, LogExporter, SdkLogRecord},
     Resource,
@@ -25,9 +26,6 @@ use std::sync::mpsc::{self, RecvTimeoutError, SyncSender};
 
 use opentelemetry::{otel_debug, otel_error, otel_warn, Context, InstrumentationScope};
 
-#[cfg(feature = "experimental_metrics_bound_instruments")]
-use opentelemetry::KeyValue;
-
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::{cmp::min, env, sync::Mutex};
 use std::{
@@ -151,14 +149,10 @@ pub struct BatchLogProcessor {
     // Track the maximum queue size that was configured for this processor
     max_queue_size: usize,
 
-    // Self-diagnostics: otel.sdk.processor.log.processed counter.
-    // Gated behind experimental_metrics_bound_instruments so the hot-path
-    // `add` is a single atomic increment (~1.8 ns) with no per-call
-    // attribute resolution.
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    processed_queue_full: opentelemetry::metrics::BoundCounter<u64>,
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    processed_after_shutdown: opentelemetry::metrics::BoundCounter<u64>,
+    // Self-diagnostics. The success count is recorded in the worker thread
+    // when a batch is submitted to the exporter; the drop counts are recorded
+    // here at enqueue time.
+    metrics: Arc<ProcessorMetrics>,
 }
 
 impl Debug for BatchLogProcessor {
@@ -171,6 +165,7 @@ impl Debug for BatchLogProcessor {
 
 impl LogProcessor for BatchLogProcessor {
     fn emit(&self, record: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        self.metrics.register_callbacks();
         // Count the log record before enqueueing it so that a concurrent
         // force_flush()/shutdown() drain never observes an
         // enqueued-but-uncounted record and misses it (issue #3453). If the
@@ -228,8 +223,7 @@ impl LogProcessor for BatchLogProcessor {
                 // The record never entered the channel; revert the increment.
                 self.current_batch_size.fetch_sub(1, Ordering::AcqRel);
                 // Record queue-full drop in self-diagnostics
-                #[cfg(feature = "experimental_metrics_bound_instruments")]
-                self.processed_queue_full.add(1);
+                self.metrics.record_queue_full();
 
                 // Increment dropped logs count. The first time we have to drop
                 // a log, emit a warning.
@@ -242,8 +236,7 @@ impl LogProcessor for BatchLogProcessor {
                 // The record never entered the channel; revert the increment.
                 self.current_batch_size.fetch_sub(1, Ordering::AcqRel);
                 // Record after-shutdown drop in self-diagnostics
-                #[cfg(feature = "experimental_metrics_bound_instruments")]
-                self.processed_after_shutdown.add(1);
+                self.metrics.record_already_shutdown();
 
                 // The following `otel_warn!` may cause an infinite feedback loop of
                 // 'telemetry-induced-telemetry', potentially causing a stack overflow
@@ -296,6 +289,7 @@ impl LogProcessor for BatchLogProcessor {
     }
 
     fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.metrics.unregister_callbacks();
         let dropped_logs = self.dropped_logs_count.load(Ordering::Relaxed);
         let max_queue_size = self.max_queue_size;
         if dropped_logs > 0 {
@@ -374,7 +368,15 @@ impl LogProcessor for BatchLogProcessor {
 }
 
 impl BatchLogProcessor {
-    pub(crate) fn new<E>(mut exporter: E, config: BatchConfig) -> Self
+    #[cfg(all(test, feature = "testing"))]
+    pub(crate) fn new<E>(exporter: E, config: BatchConfig) -> Self
+    where
+        E: LogExporter + Send + Sync + 'static,
+    {
+        Self::new_with_meter(exporter, config, SdkMeter::default())
+    }
+
+    fn new_with_meter<E>(mut exporter: E, config: BatchConfig, meter: SdkMeter) -> Self
     where
         E: LogExporter + Send + Sync + 'static,
     {
@@ -385,77 +387,12 @@ impl BatchLogProcessor {
         let current_batch_size = Arc::new(AtomicUsize::new(0));
         let current_batch_size_for_thread = current_batch_size.clone();
 
-        // Self-diagnostics: create the otel.sdk.processor.log.processed counter.
-        // Created before the worker thread is spawned so the success counter can
-        // be moved into the worker and incremented when a batch is exported.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        let (processed_success, processed_queue_full, processed_after_shutdown) = {
-            static INSTANCE_COUNTER: AtomicUsize = AtomicUsize::new(0);
-            let instance_id = INSTANCE_COUNTER.fetch_add(1, Ordering::Relaxed);
-            let component_name = format!("batching_log_processor/{instance_id}");
-
-            let meter = opentelemetry::global::meter("otel.sdk");
-            let counter = meter
-                .u64_counter("otel.sdk.processor.log.processed")
-                .with_description(
-                    "The number of log records for which the processing has finished, \
-                     either successful or failed.",
-                )
-                .with_unit("{log_record}")
-                .build();
-
-            // Self-diagnostics: otel.sdk.processor.log.queue.capacity. A weak
-            // reference ensures a dropped processor stops reporting, since
-            // observable callbacks live for the meter provider's lifetime and
-            // cannot be individually unregistered.
-            let capacity_attrs = [
-                KeyValue::new("otel.component.type", "batching_log_processor"),
-                KeyValue::new("otel.component.name", component_name.clone()),
-            ];
-            let capacity_state = Arc::downgrade(&current_batch_size);
-            let capacity_value = i64::try_from(max_queue_size).unwrap_or(i64::MAX);
-            let _ = meter
-                .i64_observable_up_down_counter("otel.sdk.processor.log.queue.capacity")
-                .with_description(
-                    "The maximum number of log records the queue of a given instance of \
-                     an SDK log processor can hold.",
-                )
-                .with_unit("{log_record}")
-                .with_callback(move |observer| {
-                    // The capacity value is constant; this is only a liveness
-                    // guard so a dropped processor stops emitting this
-                    // otherwise-unregisterable callback. `strong_count()` is
-                    // sufficient since the callback never reads the state.
-                    if capacity_state.strong_count() > 0 {
-                        observer.observe(capacity_value, &capacity_attrs);
-                    }
-                })
-                .build();
-
-            // Attribute values follow the OTel semantic conventions for SDK metrics:
-            // https://github.com/open-telemetry/semantic-conventions/blob/main/docs/otel/sdk-metrics.md#metric-otelsdkprocessorlogprocessed
-            // https://github.com/open-telemetry/semantic-conventions/blob/main/docs/registry/attributes/otel.md#otel-component-attributes
-            let success_attrs = [
-                KeyValue::new("otel.component.type", "batching_log_processor"),
-                KeyValue::new("otel.component.name", component_name.clone()),
-            ];
-            let queue_full_attrs = [
-                KeyValue::new("error.type", "queue_full"),
-                KeyValue::new("otel.component.type", "batching_log_processor"),
-                KeyValue::new("otel.component.name", component_name.clone()),
-            ];
-            let after_shutdown_attrs = [
-                KeyValue::new("error.type", "already_shutdown"),
-                KeyValue::new("otel.component.type", "batching_log_processor"),
-                KeyValue::new("otel.component.name", component_name),
-            ];
-
-            (
-                counter.bind(&success_attrs),
-                counter.bind(&queue_full_attrs),
-                counter.bind(&after_shutdown_attrs),
-            )
-        };
+        let metrics = ProcessorMetrics::new(
+            &meter,
+            ComponentType::BatchingLogProcessor,
+            Some((max_queue_size, current_batch_size.clone())),
+        );
+        let metrics_for_thread = metrics.clone();
 
         let handle = thread::Builder::new()
             .name("OpenTelemetry.Logs.BatchProcessor".to_string())
@@ -471,29 +408,23 @@ impl BatchLogProcessor {
                 let mut logs = Vec::with_capacity(config.max_export_batch_size);
                 let current_batch_size = current_batch_size_for_thread;
 
-                // Counts records for the otel.sdk.processor.log.processed
-                // metric; a no-op when the self-diagnostics feature is disabled.
-                #[cfg(feature = "experimental_metrics_bound_instruments")]
-                let record_processed_success = move |count: u64| processed_success.add(count);
-                #[cfg(not(feature = "experimental_metrics_bound_instruments"))]
-                let record_processed_success = |_count: u64| {};
+                let metrics = metrics_for_thread;
 
                 // This method gets up to `max_export_batch_size` amount of logs from the channel and exports them.
                 // It returns the result of the export operation.
                 // It expects the logs vec to be empty when it's called.
                 #[inline]
-                fn get_logs_and_export<E, F>(
+                fn get_logs_and_export<E>(
                     logs_receiver: &mpsc::Receiver<LogsData>,
                     exporter: &E,
                     logs: &mut Vec<LogsData>,
                     last_export_time: &mut Instant,
                     current_batch_size: &AtomicUsize,
                     max_export_size: usize,
-                    record_processed_success: &F,
+                    metrics: &ProcessorMetrics,
                 ) -> OTelSdkResult
                 where
                     E: LogExporter + Send + Sync + 'static,
-                    F: Fn(u64),
                 {
                     let target = current_batch_size.load(Ordering::Acquire); // `target` is used to determine the stopping criteria for exporting logs.
                     let mut result = OTelSdkResult::Ok(());
@@ -515,14 +446,16 @@ impl BatchLogProcessor {
                             break;
                         }
                         total_exported_logs += count_of_logs;
+                        // The logs left the queue, which is reported as
+                        // `otel.sdk.processor.log.queue.size`.
+                        current_batch_size.fetch_sub(count_of_logs, Ordering::AcqRel);
 
                         // Count the batch as processed before invoking the
                         // exporter, regardless of the export outcome.
-                        record_processed_success(count_of_logs as u64);
+                        metrics.record_success(count_of_logs);
 
-                        result = export_batch_sync(exporter, logs, last_export_time); // This method clears the logs vec after exporting
-
-                        current_batch_size.fetch_sub(count_of_logs, Ordering::AcqRel);
+                        result = export_batch_sync(exporter, logs, last_export_time);
+                        // This method clears the logs vec after exporting
                     }
                     result
                 }
@@ -549,7 +482,7 @@ impl BatchLogProcessor {
                                 &mut last_export_time,
                                 &current_batch_size,
                                 max_export_batch_size,
-                                &record_processed_success,
+                                &metrics,
                             );
                         }
                         Ok(BatchMessage::ForceFlush(sender)) => {
@@ -561,7 +494,7 @@ impl BatchLogProcessor {
                                 &mut last_export_time,
                                 &current_batch_size,
                                 max_export_batch_size,
-                                &record_processed_success,
+                                &metrics,
                             );
                             let _ = sender.send(result);
                         }
@@ -574,7 +507,7 @@ impl BatchLogProcessor {
                                 &mut last_export_time,
                                 &current_batch_size,
                                 max_export_batch_size,
-                                &record_processed_success,
+                                &metrics,
                             );
                             let _ = exporter.shutdown();
                             let _ = sender.send(result);
@@ -603,7 +536,7 @@ impl BatchLogProcessor {
                                 &mut last_export_time,
                                 &current_batch_size,
                                 max_export_batch_size,
-                                &record_processed_success,
+                                &metrics,
                             );
                         }
                         Err(RecvTimeoutError::Disconnected) => {
@@ -634,10 +567,7 @@ impl BatchLogProcessor {
             export_log_message_sent: Arc::new(AtomicBool::new(false)),
             current_batch_size,
             max_export_batch_size,
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            processed_queue_full,
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            processed_after_shutdown,
+            metrics,
         }
     }
 
@@ -649,6 +579,7 @@ impl BatchLogProcessor {
         BatchLogProcessorBuilder {
             exporter,
             config: Default::default(),
+            meter: SdkMeter::default(),
         }
     }
 }
@@ -693,6 +624,7 @@ where
 pub struct BatchLogProcessorBuilder<E> {
     exporter: E,
     config: BatchConfig,
+    meter: SdkMeter,
 }
 
 impl<E> BatchLogProcessorBuilder<E>
@@ -704,9 +636,26 @@ where
         BatchLogProcessorBuilder { config, ..self }
     }
 
+    /// Report the self-diagnostics metrics of the processor, such as
+    /// `otel.sdk.processor.log.processed`, to `meter_provider`.
+    ///
+    /// By default, they are reported to the global meter provider set when
+    /// the processor receives its first log record.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn with_meter_provider(
+        self,
+        meter_provider: &impl opentelemetry::metrics::MeterProvider,
+    ) -> Self {
+        BatchLogProcessorBuilder {
+            meter: SdkMeter::new(meter_provider),
+            ..self
+        }
+    }
+
     /// Build a batch processor
     pub fn build(self) -> BatchLogProcessor {
-        BatchLogProcessor::new(self.exporter, self.config)
+        BatchLogProcessor::new_with_meter(self.exporter, self.config, self.meter)
     }
 }
 
@@ -1225,12 +1174,12 @@ mod tests {
         }
 
         assert_eq!(processor.dropped_logs_count.load(Ordering::Relaxed), 2);
-        // 4 records in-flight in the blocked export (not yet subtracted)
-        // plus 4 records queued. Without the queue-full revert this would
-        // read 10.
+        // The 4 records in-flight in the blocked export left the queue, 4
+        // records are queued. Without the queue-full revert this would read
+        // 6.
         assert_eq!(
             processor.current_batch_size.load(Ordering::Relaxed),
-            8,
+            4,
             "dropped logs must not remain counted as pending"
         );
 
@@ -1338,308 +1287,284 @@ mod tests {
         );
     }
 
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    mod self_obs {
-        use super::*;
-
-        /// Verifies that `otel.sdk.processor.log.processed` counter records
-        /// successful log processing when `experimental_metrics_bound_instruments`
-        /// is enabled and a real MeterProvider is set as global before creating
-        /// the processor.
-        ///
-        /// This test is `#[ignore]`d because it calls
-        /// `global::set_meter_provider()` which mutates process-wide state.
-        /// CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        #[test]
-        #[ignore]
-        fn self_diagnostics_counter_records_success() {
-            use crate::metrics::data::{AggregatedMetrics, MetricData};
-            use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
-
-            // Setup a real MeterProvider and set it as global BEFORE creating the
-            // BatchLogProcessor, so the processor picks up a real meter.
-            let metric_exporter = InMemoryMetricExporter::default();
-            let meter_provider = SdkMeterProvider::builder()
-                .with_periodic_exporter(metric_exporter.clone())
-                .build();
-            opentelemetry::global::set_meter_provider(meter_provider.clone());
+    /// Verifies that `otel.sdk.processor.log.processed` counts log records
+    /// (with no `error.type`) when the processor submits a batch to the
+    /// exporter.
+    #[test]
+    fn self_diagnostics_counter_records_success() {
+        use crate::metrics::data::{AggregatedMetrics, MetricData};
+        use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
 
-            let log_exporter = InMemoryLogExporter::default();
-            let config = BatchConfigBuilder::default()
-                .with_max_queue_size(256)
-                .with_max_export_batch_size(64)
-                .with_scheduled_delay(Duration::from_secs(60))
-                .build();
-            let processor = BatchLogProcessor::new(log_exporter, config);
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter.clone())
+            .build();
 
-            // Emit 10 logs
-            let instrumentation = InstrumentationScope::default();
-            for _ in 0..10 {
-                let mut record = SdkLogRecord::new();
-                processor.emit(&mut record, &instrumentation);
-            }
+        let log_exporter = InMemoryLogExporter::default();
+        let config = BatchConfigBuilder::default()
+            .with_max_queue_size(256)
+            .with_max_export_batch_size(64)
+            .with_scheduled_delay(Duration::from_secs(60))
+            .build();
+        let processor = BatchLogProcessor::builder(log_exporter)
+            .with_batch_config(config)
+            .with_meter_provider(&meter_provider)
+            .build();
 
-            // Flush so the batch is submitted to the exporter, which is when the
-            // counter is incremented.
-            processor.force_flush().unwrap();
+        // Emit 10 logs
+        let instrumentation = InstrumentationScope::default();
+        for _ in 0..10 {
+            let mut record = SdkLogRecord::new();
+            processor.emit(&mut record, &instrumentation);
+        }
 
-            // Force a metrics collection
-            meter_provider.force_flush().unwrap();
+        // Flush so the batch is submitted to the exporter, which is when the
+        // counter is incremented.
+        processor.force_flush().unwrap();
 
-            // Find the otel.sdk.processor.log.processed metric and sum all data points
-            let metrics = metric_exporter.get_finished_metrics().unwrap();
-            let mut found = false;
-            let mut total_value: u64 = 0;
-            for rm in &metrics {
-                for sm in &rm.scope_metrics {
-                    for metric in &sm.metrics {
-                        if metric.name == "otel.sdk.processor.log.processed" {
-                            found = true;
-                            if let AggregatedMetrics::U64(MetricData::Sum(sum)) = &metric.data {
-                                for dp in sum.data_points() {
-                                    total_value += dp.value();
-                                }
+        // Force a metrics collection
+        meter_provider.force_flush().unwrap();
+
+        // Find the otel.sdk.processor.log.processed metric and sum all data points
+        let metrics = metric_exporter.get_finished_metrics().unwrap();
+        let mut found = false;
+        let mut total_value: u64 = 0;
+        for rm in &metrics {
+            for sm in &rm.scope_metrics {
+                for metric in &sm.metrics {
+                    if metric.name == "otel.sdk.processor.log.processed" {
+                        found = true;
+                        if let AggregatedMetrics::U64(MetricData::Sum(sum)) = &metric.data {
+                            for dp in sum.data_points() {
+                                total_value += dp.value();
                             }
                         }
                     }
                 }
             }
+        }
 
-            assert!(found, "otel.sdk.processor.log.processed metric not found");
-            assert_eq!(
-                total_value, 10,
-                "Expected 10 processed logs, got {total_value}"
-            );
+        assert!(found, "otel.sdk.processor.log.processed metric not found");
+        assert_eq!(
+            total_value, 10,
+            "Expected 10 processed logs, got {total_value}"
+        );
 
-            processor.shutdown().unwrap();
-            meter_provider.shutdown().unwrap();
-        }
+        processor.shutdown().unwrap();
+        meter_provider.shutdown().unwrap();
+    }
 
-        /// Verifies `otel.sdk.processor.log.queue.capacity` through a real
-        /// `SdkLoggerProvider` + `BatchLogProcessor`. The metric reports the
-        /// configured max queue size with the component identity attributes and
-        /// stops reporting after the provider (and processor) is dropped.
-        ///
-        /// `#[ignore]`d because it mutates process-wide state via
-        /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        #[test]
-        #[ignore]
-        fn self_diagnostics_queue_capacity() {
-            use crate::metrics::data::{AggregatedMetrics, MetricData};
-            use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
-
-            let metric_exporter = InMemoryMetricExporter::default();
-            let meter_provider = SdkMeterProvider::builder()
-                .with_periodic_exporter(metric_exporter.clone())
-                .build();
-            opentelemetry::global::set_meter_provider(meter_provider.clone());
+    /// Verifies `otel.sdk.processor.log.queue.capacity` through a real
+    /// `SdkLoggerProvider` + `BatchLogProcessor`. The metric reports the
+    /// configured max queue size with the component identity attributes and
+    /// stops reporting after the provider (and processor) is dropped.
+    #[test]
+    fn self_diagnostics_queue_capacity() {
+        use crate::metrics::data::{AggregatedMetrics, MetricData};
+        use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
 
-            let log_exporter = InMemoryLogExporter::default();
-            let config = BatchConfigBuilder::default()
-                .with_max_queue_size(256)
-                .build();
-            let processor = BatchLogProcessor::new(log_exporter, config);
-            let provider = SdkLoggerProvider::builder()
-                .with_log_processor(processor)
-                .build();
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter.clone())
+            .build();
 
-            // Force a metrics collection so the observable callbacks run. This does
-            // NOT drain the log queue (that only happens on the provider/processor).
-            meter_provider.force_flush().unwrap();
-
-            let read = |name: &str| -> Option<i64> {
-                let metrics = metric_exporter.get_finished_metrics().unwrap();
-                for rm in &metrics {
-                    for sm in &rm.scope_metrics {
-                        for metric in &sm.metrics {
-                            if metric.name == name {
-                                if let AggregatedMetrics::I64(MetricData::Sum(sum)) = &metric.data {
-                                    for dp in sum.data_points() {
-                                        let has_component = dp.attributes().any(|kv| {
-                                            kv.key.as_str() == "otel.component.type"
-                                                && kv.value.as_str() == "batching_log_processor"
-                                        });
-                                        if has_component {
-                                            return Some(dp.value());
-                                        }
+        let log_exporter = InMemoryLogExporter::default();
+        let config = BatchConfigBuilder::default()
+            .with_max_queue_size(256)
+            .build();
+        let processor = BatchLogProcessor::builder(log_exporter)
+            .with_batch_config(config)
+            .with_meter_provider(&meter_provider)
+            .build();
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(processor)
+            .build();
+
+        // Force a metrics collection so the observable callbacks run. This does
+        // NOT drain the log queue (that only happens on the provider/processor).
+        meter_provider.force_flush().unwrap();
+
+        let read = |name: &str| -> Option<i64> {
+            let metrics = metric_exporter.get_finished_metrics().unwrap();
+            for rm in &metrics {
+                for sm in &rm.scope_metrics {
+                    for metric in &sm.metrics {
+                        if metric.name == name {
+                            if let AggregatedMetrics::I64(MetricData::Sum(sum)) = &metric.data {
+                                for dp in sum.data_points() {
+                                    let has_component = dp.attributes().any(|kv| {
+                                        kv.key.as_str() == "otel.component.type"
+                                            && kv.value.as_str() == "batching_log_processor"
+                                    });
+                                    if has_component {
+                                        return Some(dp.value());
                                     }
                                 }
                             }
                         }
                     }
                 }
-                None
-            };
+            }
+            None
+        };
 
-            assert_eq!(
-                read("otel.sdk.processor.log.queue.capacity"),
-                Some(256),
-                "queue.capacity should equal the configured max_queue_size"
-            );
+        assert_eq!(
+            read("otel.sdk.processor.log.queue.capacity"),
+            Some(256),
+            "queue.capacity should equal the configured max_queue_size"
+        );
 
-            // Dropping the provider shuts down and drops the processor, releasing the
-            // Arc<AtomicUsize> the callback holds a Weak to. A subsequent collection
-            // must therefore omit the metric because the Weak upgrade fails.
-            metric_exporter.reset();
-            provider.shutdown().unwrap();
-            drop(provider);
-            meter_provider.force_flush().unwrap();
+        // Dropping the provider shuts down and drops the processor, releasing the
+        // Arc<AtomicUsize> the callback holds a Weak to. A subsequent collection
+        // must therefore omit the metric because the Weak upgrade fails.
+        metric_exporter.reset();
+        provider.shutdown().unwrap();
+        drop(provider);
+        meter_provider.force_flush().unwrap();
 
-            assert_eq!(
-                read("otel.sdk.processor.log.queue.capacity"),
-                None,
-                "queue.capacity must stop being reported after the processor is dropped"
-            );
-
-            meter_provider.shutdown().unwrap();
-        }
+        assert_eq!(
+            read("otel.sdk.processor.log.queue.capacity"),
+            None,
+            "queue.capacity must stop being reported after the processor is dropped"
+        );
 
-        /// Sums the values of `otel.sdk.processor.log.processed` data points whose
-        /// `error.type` attribute equals `error_type`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        fn sum_processed_log_records_with_error_type(
-            metric_exporter: &crate::metrics::InMemoryMetricExporter,
-            error_type: &str,
-        ) -> u64 {
-            use crate::metrics::data::{AggregatedMetrics, MetricData};
+        meter_provider.shutdown().unwrap();
+    }
 
-            let metrics = metric_exporter.get_finished_metrics().unwrap();
-            let mut total: u64 = 0;
-            for rm in &metrics {
-                for sm in &rm.scope_metrics {
-                    for metric in &sm.metrics {
-                        if metric.name == "otel.sdk.processor.log.processed" {
-                            if let AggregatedMetrics::U64(MetricData::Sum(sum)) = &metric.data {
-                                for dp in sum.data_points() {
-                                    let matches = dp.attributes().any(|kv| {
-                                        kv.key.as_str() == "error.type"
-                                            && kv.value.as_str() == error_type
-                                    });
-                                    if matches {
-                                        total += dp.value();
-                                    }
+    /// Sums the values of `otel.sdk.processor.log.processed` data points whose
+    /// `error.type` attribute equals `error_type`.
+    fn sum_processed_log_records_with_error_type(
+        metric_exporter: &crate::metrics::InMemoryMetricExporter,
+        error_type: &str,
+    ) -> u64 {
+        use crate::metrics::data::{AggregatedMetrics, MetricData};
+
+        let metrics = metric_exporter.get_finished_metrics().unwrap();
+        let mut total: u64 = 0;
+        for rm in &metrics {
+            for sm in &rm.scope_metrics {
+                for metric in &sm.metrics {
+                    if metric.name == "otel.sdk.processor.log.processed" {
+                        if let AggregatedMetrics::U64(MetricData::Sum(sum)) = &metric.data {
+                            for dp in sum.data_points() {
+                                let matches = dp.attributes().any(|kv| {
+                                    kv.key.as_str() == "error.type"
+                                        && kv.value.as_str() == error_type
+                                });
+                                if matches {
+                                    total += dp.value();
                                 }
                             }
                         }
                     }
                 }
             }
-            total
         }
+        total
+    }
 
-        /// Verifies that `otel.sdk.processor.log.processed` records queue-full drops
-        /// with `error.type = queue_full` when records overflow the queue while the
-        /// worker is blocked exporting.
-        ///
-        /// `#[ignore]`d because it mutates process-wide state via
-        /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        #[test]
-        #[ignore]
-        fn self_diagnostics_counter_records_queue_full_drops() {
-            use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
-
-            let metric_exporter = InMemoryMetricExporter::default();
-            let meter_provider = SdkMeterProvider::builder()
-                .with_periodic_exporter(metric_exporter.clone())
-                .build();
-            opentelemetry::global::set_meter_provider(meter_provider.clone());
-
-            let (started_sender, started_receiver) = mpsc::sync_channel(8);
-            let (release_sender, release_receiver) = mpsc::sync_channel(8);
-            let exported_count = Arc::new(AtomicUsize::new(0));
-            let exporter = BlockingExporter {
-                exported_count: exported_count.clone(),
-                export_started: started_sender,
-                release: Arc::new(Mutex::new(release_receiver)),
-            };
-            let config = BatchConfigBuilder::default()
-                .with_max_queue_size(4)
-                .with_max_export_batch_size(4)
-                .with_scheduled_delay(Duration::from_secs(60))
-                .build();
-            let processor = BatchLogProcessor::new(exporter, config);
-            let instrumentation = InstrumentationScope::default();
-            let emit = || {
-                let mut record = SdkLogRecord::new();
-                processor.emit(&mut record, &instrumentation);
-            };
-
-            // Fill the queue to the export threshold; the worker drains all four
-            // records and blocks inside export().
-            for _ in 0..4 {
-                emit();
-            }
-            started_receiver
-                .recv_timeout(Duration::from_secs(5))
-                .expect("worker should start exporting the first batch");
-
-            // While the worker is blocked, refill the queue (4) and overflow it by
-            // two records, which must be dropped and counted as queue_full.
-            for _ in 0..6 {
-                emit();
-            }
+    /// Verifies that `otel.sdk.processor.log.processed` records queue-full drops
+    /// with `error.type = queue_full` when records overflow the queue while the
+    /// worker is blocked exporting.
+    #[test]
+    fn self_diagnostics_counter_records_queue_full_drops() {
+        use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
 
-            // Release the in-flight export and the one triggered by force_flush.
-            release_sender.send(()).unwrap();
-            release_sender.send(()).unwrap();
-            processor.force_flush().unwrap();
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter.clone())
+            .build();
 
-            meter_provider.force_flush().unwrap();
+        let (started_sender, started_receiver) = mpsc::sync_channel(8);
+        let (release_sender, release_receiver) = mpsc::sync_channel(8);
+        let exported_count = Arc::new(AtomicUsize::new(0));
+        let exporter = BlockingExporter {
+            exported_count: exported_count.clone(),
+            export_started: started_sender,
+            release: Arc::new(Mutex::new(release_receiver)),
+        };
+        let config = BatchConfigBuilder::default()
+            .with_max_queue_size(4)
+            .with_max_export_batch_size(4)
+            .with_scheduled_delay(Duration::from_secs(60))
+            .build();
+        let processor = BatchLogProcessor::builder(exporter)
+            .with_batch_config(config)
+            .with_meter_provider(&meter_provider)
+            .build();
+        let instrumentation = InstrumentationScope::default();
+        let emit = || {
+            let mut record = SdkLogRecord::new();
+            processor.emit(&mut record, &instrumentation);
+        };
 
-            let queue_full =
-                sum_processed_log_records_with_error_type(&metric_exporter, "queue_full");
-            assert_eq!(
-                queue_full, 2,
-                "expected 2 queue_full drops, got {queue_full}"
-            );
+        // Fill the queue to the export threshold; the worker drains all four
+        // records and blocks inside export().
+        for _ in 0..4 {
+            emit();
+        }
+        started_receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("worker should start exporting the first batch");
 
-            processor.shutdown().unwrap();
-            meter_provider.shutdown().unwrap();
+        // While the worker is blocked, refill the queue (4) and overflow it by
+        // two records, which must be dropped and counted as queue_full.
+        for _ in 0..6 {
+            emit();
         }
 
-        /// Verifies that `otel.sdk.processor.log.processed` records post-shutdown
-        /// emits with `error.type = already_shutdown`.
-        ///
-        /// `#[ignore]`d because it mutates process-wide state via
-        /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        #[test]
-        #[ignore]
-        fn self_diagnostics_counter_records_already_shutdown_drops() {
-            use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
-
-            let metric_exporter = InMemoryMetricExporter::default();
-            let meter_provider = SdkMeterProvider::builder()
-                .with_periodic_exporter(metric_exporter.clone())
-                .build();
-            opentelemetry::global::set_meter_provider(meter_provider.clone());
+        // Release the in-flight export and the one triggered by force_flush.
+        release_sender.send(()).unwrap();
+        release_sender.send(()).unwrap();
+        processor.force_flush().unwrap();
 
-            let log_exporter = InMemoryLogExporter::default();
-            let processor = BatchLogProcessor::new(log_exporter, BatchConfig::default());
+        meter_provider.force_flush().unwrap();
 
-            // Shut the processor down so the worker thread (the only receiver)
-            // disconnects; subsequent emits hit the already_shutdown branch.
-            processor.shutdown().unwrap();
+        let queue_full = sum_processed_log_records_with_error_type(&metric_exporter, "queue_full");
+        assert_eq!(
+            queue_full, 2,
+            "expected 2 queue_full drops, got {queue_full}"
+        );
 
-            let instrumentation = InstrumentationScope::default();
-            for _ in 0..7 {
-                let mut record = SdkLogRecord::new();
-                processor.emit(&mut record, &instrumentation);
-            }
+        processor.shutdown().unwrap();
+        meter_provider.shutdown().unwrap();
+    }
 
-            meter_provider.force_flush().unwrap();
+    /// Verifies that `otel.sdk.processor.log.processed` records post-shutdown
+    /// emits with `error.type = already_shutdown`.
+    #[test]
+    fn self_diagnostics_counter_records_already_shutdown_drops() {
+        use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
 
-            let already_shutdown =
-                sum_processed_log_records_with_error_type(&metric_exporter, "already_shutdown");
-            assert_eq!(
-                already_shutdown, 7,
-                "expected 7 already_shutdown drops, got {already_shutdown}"
-            );
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter.clone())
+            .build();
 
-            meter_provider.shutdown().unwrap();
+        let log_exporter = InMemoryLogExporter::default();
+        let processor = BatchLogProcessor::builder(log_exporter)
+            .with_meter_provider(&meter_provider)
+            .build();
+
+        // Shut the processor down so the worker thread (the only receiver)
+        // disconnects; subsequent emits hit the already_shutdown branch.
+        processor.shutdown().unwrap();
+
+        let instrumentation = InstrumentationScope::default();
+        for _ in 0..7 {
+            let mut record = SdkLogRecord::new();
+            processor.emit(&mut record, &instrumentation);
         }
+
+        meter_provider.force_flush().unwrap();
+
+        let already_shutdown =
+            sum_processed_log_records_with_error_type(&metric_exporter, "already_shutdown");
+        assert_eq!(
+            already_shutdown, 7,
+            "expected 7 already_shutdown drops, got {already_shutdown}"
+        );
+
+        meter_provider.shutdown().unwrap();
     }
 }
@@ -0,0 +1,54 @@
+/// # Log record limit
+/// Erroneous code can add unintended attributes to a log record, or attributes with huge values.
+/// If they are unbounded, they can quickly exhaust available memory, or produce records that are
+/// too big for the backend to accept.
+///
+/// To protect against those errors, users can use log record limits to configure
+///  - Maximum allowed attribute count per log record
+///  - Maximum allowed length of attribute values
+///
+/// If the attribute count limit has been breached, the attributes added later are dropped and
+/// counted in [`SdkLogRecord::dropped_attributes_count`]. Attribute values longer than the limit
+/// are truncated.
+///
+/// [`SdkLogRecord::dropped_attributes_count`]: crate::logs::SdkLogRecord::dropped_attributes_count
+pub(crate) const DEFAULT_MAX_ATTRIBUTES_PER_LOG_RECORD: u32 = 128;
+
+/// Log record limit configuration to keep the attributes of a log record in a reasonable size.
+///
+/// Start from [`LogRecordLimits::default`] and override the limits with the
+/// `with_*` methods, so that adding limits is not a breaking change.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct LogRecordLimits {
+    /// The max attributes that can be added to a log record.
+    pub max_attributes_per_log_record: u32,
+    /// The max length of the string values of log record attributes, in
+    /// characters. Strings, including the ones nested in lists and maps, that
+    /// are longer are truncated. `None` means values are not truncated.
+    pub max_attribute_value_length: Option<u32>,
+}
+
+impl Default for LogRecordLimits {
+    fn default() -> Self {
+        LogRecordLimits {
+            max_attributes_per_log_record: DEFAULT_MAX_ATTRIBUTES_PER_LOG_RECORD,
+            max_attribute_value_length: None,
+        }
+    }
+}
+
+impl LogRecordLimits {
+    /// Sets the max attributes that can be added to a log record.
+    pub fn with_max_attributes_per_log_record(mut self, max_attributes: u32) -> Self {
+        self.max_attributes_per_log_record = max_attributes;
+        self
+    }
+
+    /// Sets the max length of the string values of log record attributes, in
+    /// characters.
+    pub fn with_max_attribute_value_length(mut self, max_length: u32) -> Self {
+        self.max_attribute_value_length = Some(max_length);
+        self
+    }
+}
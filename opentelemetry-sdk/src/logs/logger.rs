@@ -1,6 +1,6 @@
 #[cfg(feature = "trace")]
 use super::TraceContext;
-use super::{SdkLogRecord, SdkLoggerProvider};
+use super::{LoggerConfig, SdkLogRecord, SdkLoggerProvider};
 #[cfg(feature = "trace")]
 use opentelemetry::trace::TraceContextExt;
 use opentelemetry::{Context, InstrumentationScope};
@@ -15,29 +15,23 @@ use opentelemetry::time::now;
 pub struct SdkLogger {
     scope: InstrumentationScope,
     provider: SdkLoggerProvider,
-
-    // Bound is not strictly needed (no attributes), but the semconv is still
-    // `development` so the metric must be feature-gated; reuse the same
-    // `experimental_metrics_bound_instruments` flag as the other SDK
-    // self-observability metrics for consistency.
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    log_created_counter: opentelemetry::metrics::BoundCounter<u64>,
+    config: LoggerConfig,
 }
 
 impl SdkLogger {
     pub(crate) fn new(scope: InstrumentationScope, provider: SdkLoggerProvider) -> Self {
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        let log_created_counter = opentelemetry::global::meter("otel.sdk")
-            .u64_counter("otel.sdk.log.created")
-            .with_description("The number of log records submitted to the SDK.")
-            .with_unit("{log_record}")
-            .build()
-            .bind(&[]);
+        Self::with_config(scope, provider, LoggerConfig::default())
+    }
+
+    pub(crate) fn with_config(
+        scope: InstrumentationScope,
+        provider: SdkLoggerProvider,
+        config: LoggerConfig,
+    ) -> Self {
         SdkLogger {
             scope,
             provider,
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            log_created_counter,
+            config,
         }
     }
 }
@@ -46,7 +40,7 @@ impl opentelemetry::logs::Logger for SdkLogger {
     type LogRecord = SdkLogRecord;
 
     fn create_log_record(&self) -> Self::LogRecord {
-        SdkLogRecord::new()
+        SdkLogRecord::with_limits(self.provider.log_record_limits())
     }
 
     /// Emit a `LogRecord`.
@@ -60,11 +54,16 @@ impl opentelemetry::logs::Logger for SdkLogger {
             return;
         }
 
+        // Records dropped by the `LoggerConfig` of the scope never enter the
+        // SDK, like the records of the no-op logger.
+        if !self.config.is_severity_enabled(record.severity_number) {
+            return;
+        }
+
         // Count every record submitted to the SDK, before any processing, so
         // this metric is the top of the delivery funnel: records dropped by
         // downstream processing show up as a gap against downstream metrics.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        self.log_created_counter.add(1);
+        self.provider.log_metrics().record_created();
 
         let provider = &self.provider;
         let processors = provider.log_processors();
@@ -89,7 +88,9 @@ impl opentelemetry::logs::Logger for SdkLogger {
 
     #[inline]
     fn event_enabled(&self, level: Severity, target: &str, name: Option<&str>) -> bool {
-        if Context::is_current_telemetry_suppressed() {
+        if Context::is_current_telemetry_suppressed()
+            || !self.config.is_severity_enabled(Some(level))
+        {
             return false;
         }
         // Returns false if there are no log processors.
@@ -101,8 +102,8 @@ impl opentelemetry::logs::Logger for SdkLogger {
     }
 }
 
-#[cfg(all(test, feature = "experimental_metrics_bound_instruments"))]
-mod self_obs {
+#[cfg(all(test, feature = "testing"))]
+mod tests {
     use crate::logs::SdkLoggerProvider;
     use crate::metrics::data::{AggregatedMetrics, MetricData};
     use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
@@ -131,20 +132,17 @@ mod self_obs {
     /// even when no processors are registered, proving the metric is a
     /// pre-processing intake count (the top of the delivery funnel) rather than
     /// a post-filter count.
-    ///
-    /// `#[ignore]`d because it calls `global::set_meter_provider()`, which
-    /// mutates process-wide state; CI runs it in isolation via `test.sh`.
     #[test]
-    #[ignore]
     fn log_created_counts_intake_without_processors() {
         let metric_exporter = InMemoryMetricExporter::default();
         let meter_provider = SdkMeterProvider::builder()
             .with_periodic_exporter(metric_exporter.clone())
             .build();
-        opentelemetry::global::set_meter_provider(meter_provider.clone());
 
         // Provider with NO log processors registered.
-        let logger_provider = SdkLoggerProvider::builder().build();
+        let logger_provider = SdkLoggerProvider::builder()
+            .with_meter_provider(&meter_provider)
+            .build();
         let logger = logger_provider.logger("test");
 
         for _ in 0..10 {
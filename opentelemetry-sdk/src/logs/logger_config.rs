@@ -0,0 +1,93 @@
+use crate::scope_config::ScopeConfigurator;
+use opentelemetry::logs::Severity;
+use opentelemetry::InstrumentationScope;
+use std::fmt;
+
+/// The configuration of the loggers of an instrumentation scope, returned by
+/// a [`LoggerConfigurator`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LoggerConfig {
+    /// Whether the loggers emit log records. A disabled logger drops every
+    /// record.
+    pub enabled: bool,
+    /// The minimum severity of the records emitted by the loggers. Records
+    /// with a lower severity are dropped, records without severity are kept.
+    pub min_severity: Option<Severity>,
+}
+
+impl Default for LoggerConfig {
+    /// Create an enabled logger config without minimum severity.
+    fn default() -> Self {
+        LoggerConfig {
+            enabled: true,
+            min_severity: None,
+        }
+    }
+}
+
+impl LoggerConfig {
+    /// Create a disabled logger config.
+    pub fn disabled() -> Self {
+        LoggerConfig {
+            enabled: false,
+            ..LoggerConfig::default()
+        }
+    }
+
+    /// Set whether the loggers emit log records.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the minimum severity of the records emitted by the loggers.
+    pub fn with_min_severity(mut self, severity: Severity) -> Self {
+        self.min_severity = Some(severity);
+        self
+    }
+
+    /// Returns `true` if the loggers emit the records of the given severity.
+    pub(crate) fn is_severity_enabled(&self, severity: Option<Severity>) -> bool {
+        self.enabled
+            && match (self.min_severity, severity) {
+                (Some(min_severity), Some(severity)) => severity >= min_severity,
+                _ => true,
+            }
+    }
+}
+
+/// Computes the [`LoggerConfig`] of the loggers of each instrumentation scope.
+///
+/// It is called every time a logger is obtained from a provider that is not
+/// shut down, without caching the result, so it should be cheap, see
+/// [`LoggerProviderBuilder::with_logger_configurator`]. It is implemented by
+/// [`ScopeConfigurator<LoggerConfig>`] and by closures taking an
+/// [`InstrumentationScope`] and returning a [`LoggerConfig`].
+///
+/// [`LoggerProviderBuilder::with_logger_configurator`]: crate::logs::LoggerProviderBuilder::with_logger_configurator
+pub trait LoggerConfigurator: Send + Sync {
+    /// Returns the config of the loggers of `scope`.
+    fn logger_config(&self, scope: &InstrumentationScope) -> LoggerConfig;
+}
+
+impl<T> LoggerConfigurator for T
+where
+    T: Fn(&InstrumentationScope) -> LoggerConfig + Send + Sync,
+{
+    fn logger_config(&self, scope: &InstrumentationScope) -> LoggerConfig {
+        self(scope)
+    }
+}
+
+impl LoggerConfigurator for ScopeConfigurator<LoggerConfig> {
+    fn logger_config(&self, scope: &InstrumentationScope) -> LoggerConfig {
+        self.config(scope)
+    }
+}
+
+impl fmt::Debug for dyn LoggerConfigurator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LoggerConfigurator")
+    }
+}
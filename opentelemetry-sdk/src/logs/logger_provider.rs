@@ -1,8 +1,14 @@
-use super::{BatchLogProcessor, LogProcessor, SdkLogger, SimpleLogProcessor};
+use super::{
+    BatchLogProcessor, LogProcessor, LogRecordLimits, LoggerConfig, LoggerConfigurator, SdkLogger,
+    SimpleLogProcessor,
+};
 use crate::error::{OTelSdkError, OTelSdkResult};
 use crate::logs::LogExporter;
+use crate::self_diagnostics::{LogMetrics, SdkMeter};
 use crate::Resource;
 use opentelemetry::{otel_debug, otel_info, InstrumentationScope};
+use std::env;
+use std::str::FromStr;
 use std::time::Duration;
 use std::{
     borrow::Cow,
@@ -21,7 +27,10 @@ fn noop_logger_provider() -> &'static SdkLoggerProvider {
     NOOP_LOGGER_PROVIDER.get_or_init(|| SdkLoggerProvider {
         inner: Arc::new(LoggerProviderInner {
             processors: Vec::new(),
+            log_record_limits: LogRecordLimits::default(),
             is_shutdown: AtomicBool::new(true),
+            log_metrics: Default::default(),
+            logger_configurator: None,
         }),
     })
 }
@@ -64,11 +73,18 @@ impl opentelemetry::logs::LoggerProvider for SdkLoggerProvider {
         if scope.name().is_empty() {
             otel_info!(name: "LoggerNameEmpty",  message = "Logger name is empty; consider providing a meaningful name. Logger will function normally and the provided name will be used as-is.");
         };
+        let config = self
+            .inner
+            .logger_configurator
+            .as_ref()
+            .map_or_else(LoggerConfig::default, |configurator| {
+                configurator.logger_config(&scope)
+            });
         otel_debug!(
             name: "LoggerProvider.NewLoggerReturned",
             logger_name = scope.name(),
         );
-        SdkLogger::new(scope, self.clone())
+        SdkLogger::with_config(scope, self.clone(), config)
     }
 }
 
@@ -78,10 +94,18 @@ impl SdkLoggerProvider {
         LoggerProviderBuilder::default()
     }
 
+    pub(crate) fn log_record_limits(&self) -> LogRecordLimits {
+        self.inner.log_record_limits
+    }
+
     pub(crate) fn log_processors(&self) -> &[Box<dyn LogProcessor>] {
         &self.inner.processors
     }
 
+    pub(crate) fn log_metrics(&self) -> &LogMetrics {
+        &self.inner.log_metrics
+    }
+
     /// Force flush all remaining logs in log processors and return results.
     pub fn force_flush(&self) -> OTelSdkResult {
         let result: Vec<_> = self
@@ -134,12 +158,16 @@ impl SdkLoggerProvider {
 #[derive(Debug)]
 struct LoggerProviderInner {
     processors: Vec<Box<dyn LogProcessor>>,
+    log_record_limits: LogRecordLimits,
     is_shutdown: AtomicBool,
+    log_metrics: Arc<LogMetrics>,
+    logger_configurator: Option<Box<dyn LoggerConfigurator>>,
 }
 
 impl LoggerProviderInner {
     /// Shuts down the `LoggerProviderInner` and returns any errors.
     pub(crate) fn shutdown_with_timeout(&self, timeout: Duration) -> Vec<OTelSdkResult> {
+        self.log_metrics.unregister_callbacks();
         let mut results = vec![];
         for processor in &self.processors {
             let result = processor.shutdown_with_timeout(timeout);
@@ -179,11 +207,49 @@ impl Drop for LoggerProviderInner {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 /// Builder for provider attributes.
 pub struct LoggerProviderBuilder {
     processors: Vec<Box<dyn LogProcessor>>,
     resource: Option<Resource>,
+    log_record_limits: LogRecordLimits,
+    meter: SdkMeter,
+    logger_configurator: Option<Box<dyn LoggerConfigurator>>,
+}
+
+impl Default for LoggerProviderBuilder {
+    /// Create a new [`LoggerProviderBuilder`] with the default log record
+    /// limits, overridden by environment variables if set.
+    /// The supported environment variables are:
+    /// * `OTEL_LOGRECORD_ATTRIBUTE_COUNT_LIMIT`
+    /// * `OTEL_LOGRECORD_ATTRIBUTE_VALUE_LENGTH_LIMIT`, falling back to
+    ///   `OTEL_ATTRIBUTE_VALUE_LENGTH_LIMIT`
+    fn default() -> Self {
+        let mut log_record_limits = LogRecordLimits::default();
+
+        if let Some(max_attributes) = env::var("OTEL_LOGRECORD_ATTRIBUTE_COUNT_LIMIT")
+            .ok()
+            .and_then(|count_limit| u32::from_str(&count_limit).ok())
+        {
+            log_record_limits.max_attributes_per_log_record = max_attributes;
+        }
+
+        if let Some(max_length) = env::var("OTEL_LOGRECORD_ATTRIBUTE_VALUE_LENGTH_LIMIT")
+            .or_else(|_| env::var("OTEL_ATTRIBUTE_VALUE_LENGTH_LIMIT"))
+            .ok()
+            .and_then(|length_limit| u32::from_str(&length_limit).ok())
+        {
+            log_record_limits.max_attribute_value_length = Some(max_length);
+        }
+
+        LoggerProviderBuilder {
+            processors: Vec::new(),
+            resource: None,
+            meter: SdkMeter::default(),
+            log_record_limits,
+            logger_configurator: None,
+        }
+    }
 }
 
 impl LoggerProviderBuilder {
@@ -287,6 +353,86 @@ impl LoggerProviderBuilder {
         LoggerProviderBuilder { resource, ..self }
     }
 
+    /// Specify the number of attributes to be recorded per log record.
+    pub fn with_max_attributes_per_log_record(mut self, max_attributes: u32) -> Self {
+        self.log_record_limits.max_attributes_per_log_record = max_attributes;
+        self
+    }
+
+    /// Specify the max length of the string values of log record attributes,
+    /// in characters. Longer values are truncated.
+    pub fn with_max_attribute_value_length(mut self, max_length: u32) -> Self {
+        self.log_record_limits.max_attribute_value_length = Some(max_length);
+        self
+    }
+
+    /// Specify all limits via the log_record_limits
+    pub fn with_log_record_limits(mut self, log_record_limits: LogRecordLimits) -> Self {
+        self.log_record_limits = log_record_limits;
+        self
+    }
+
+    /// Set the [LoggerConfigurator] computing the [LoggerConfig] of the
+    /// loggers of each instrumentation scope, e.g. to disable the loggers of
+    /// some libraries or to drop their records below a minimum severity.
+    ///
+    /// The records dropped by the config of a logger are neither processed
+    /// nor counted by the `otel.sdk.log.created` metric, and
+    /// [`Logger::event_enabled`] returns `false` for them. By default every
+    /// logger is enabled without minimum severity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use opentelemetry::logs::{Logger, LoggerProvider, Severity};
+    /// use opentelemetry_sdk::logs::{LoggerConfig, SdkLoggerProvider};
+    /// use opentelemetry_sdk::scope_config::{ScopeConfigurator, ScopeSelector};
+    ///
+    /// let provider = SdkLoggerProvider::builder()
+    ///     .with_logger_configurator(
+    ///         ScopeConfigurator::new(LoggerConfig::default())
+    ///             .with_rule(
+    ///                 ScopeSelector::new().with_name("hyper*"),
+    ///                 LoggerConfig::default().with_min_severity(Severity::Warn),
+    ///             )
+    ///             .with_rule(ScopeSelector::new().with_name("noisy-*"), LoggerConfig::disabled()),
+    ///     )
+    ///     .build();
+    ///
+    /// let logger = provider.logger("hyper-util");
+    /// assert!(!logger.event_enabled(Severity::Info, "hyper-util", None));
+    /// ```
+    ///
+    /// [`Logger::event_enabled`]: opentelemetry::logs::Logger::event_enabled
+    pub fn with_logger_configurator<T: LoggerConfigurator + 'static>(
+        mut self,
+        configurator: T,
+    ) -> Self {
+        self.logger_configurator = Some(Box::new(configurator));
+        self
+    }
+
+    /// Report the self-diagnostics metric of the provider,
+    /// `otel.sdk.log.created`, to `meter_provider`.
+    ///
+    /// By default, it is reported to the global meter provider set when the
+    /// first log record is emitted. The metrics of the log processors are configured on
+    /// the processors, e.g. with
+    /// [`BatchLogProcessorBuilder::with_meter_provider`].
+    ///
+    /// [`BatchLogProcessorBuilder::with_meter_provider`]: crate::logs::BatchLogProcessorBuilder::with_meter_provider
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn with_meter_provider(
+        self,
+        meter_provider: &impl opentelemetry::metrics::MeterProvider,
+    ) -> Self {
+        LoggerProviderBuilder {
+            meter: SdkMeter::new(meter_provider),
+            ..self
+        }
+    }
+
     /// Create a new provider from this configuration.
     pub fn build(self) -> SdkLoggerProvider {
         let resource = self.resource.unwrap_or(Resource::builder().build());
@@ -298,7 +444,10 @@ impl LoggerProviderBuilder {
         let logger_provider = SdkLoggerProvider {
             inner: Arc::new(LoggerProviderInner {
                 processors,
+                log_record_limits: self.log_record_limits,
                 is_shutdown: AtomicBool::new(false),
+                log_metrics: LogMetrics::new(&self.meter),
+                logger_configurator: self.logger_configurator,
             }),
         };
 
@@ -792,7 +941,10 @@ mod tests {
                     shutdown_called.clone(),
                     flush_called.clone(),
                 ))],
+                log_record_limits: LogRecordLimits::default(),
                 is_shutdown: AtomicBool::new(false),
+                log_metrics: Default::default(),
+                logger_configurator: None,
             });
 
             {
@@ -832,7 +984,10 @@ mod tests {
                 shutdown_called.clone(),
                 flush_called.clone(),
             ))],
+            log_record_limits: LogRecordLimits::default(),
             is_shutdown: AtomicBool::new(false),
+            log_metrics: Default::default(),
+            logger_configurator: None,
         });
 
         // Create a scope to test behavior when providers are dropped
@@ -942,6 +1097,47 @@ mod tests {
         assert_eq!(resource.schema_url(), Some("http://example.com"));
     }
 
+    #[test]
+    fn log_record_limits_from_env_and_builder() {
+        temp_env::with_vars(
+            [
+                ("OTEL_LOGRECORD_ATTRIBUTE_COUNT_LIMIT", Some("2")),
+                ("OTEL_LOGRECORD_ATTRIBUTE_VALUE_LENGTH_LIMIT", None),
+                ("OTEL_ATTRIBUTE_VALUE_LENGTH_LIMIT", Some("3")),
+            ],
+            || {
+                let builder = SdkLoggerProvider::builder();
+                assert_eq!(
+                    builder.log_record_limits,
+                    LogRecordLimits::default()
+                        .with_max_attributes_per_log_record(2)
+                        .with_max_attribute_value_length(3)
+                );
+
+                let exporter = InMemoryLogExporter::default();
+                let provider = builder
+                    .with_max_attribute_value_length(4)
+                    .with_simple_exporter(exporter.clone())
+                    .build();
+                let logger = provider.logger("test-logger");
+                let mut record = logger.create_log_record();
+                record.add_attributes([("k1", "abcdef"), ("k2", "ab"), ("k3", "abc")]);
+                logger.emit(record);
+
+                let emitted_logs = exporter.get_emitted_logs().unwrap();
+                let record = &emitted_logs[0].record;
+                assert_eq!(
+                    record.attributes_iter().cloned().collect::<Vec<_>>(),
+                    vec![
+                        (Key::new("k1"), AnyValue::from("abcd")),
+                        (Key::new("k2"), AnyValue::from("ab")),
+                    ]
+                );
+                assert_eq!(record.dropped_attributes_count(), 1);
+            },
+        );
+    }
+
     #[derive(Debug)]
     pub(crate) struct LazyLogProcessor {
         shutdown_called: Arc<Mutex<bool>>,
@@ -1007,4 +1203,63 @@ mod tests {
             Ok(())
         }
     }
+
+    #[test]
+    fn logger_configurator_filters_scopes() {
+        use crate::scope_config::{ScopeConfigurator, ScopeSelector};
+        use opentelemetry::logs::Severity;
+
+        let exporter = InMemoryLogExporter::default();
+        let provider = SdkLoggerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .with_logger_configurator(
+                ScopeConfigurator::new(LoggerConfig::default())
+                    .with_rule(
+                        ScopeSelector::new().with_name("verbose"),
+                        LoggerConfig::default().with_min_severity(Severity::Warn),
+                    )
+                    .with_rule(
+                        ScopeSelector::new().with_name("noisy"),
+                        LoggerConfig::disabled(),
+                    ),
+            )
+            .build();
+
+        let emit = |name: &'static str, severity: Option<Severity>| {
+            let logger = provider.logger(name);
+            let mut record = logger.create_log_record();
+            if let Some(severity) = severity {
+                record.set_severity_number(severity);
+            }
+            record.set_body(AnyValue::from(format!("{name} {severity:?}")));
+            logger.emit(record);
+        };
+        emit("app", Some(Severity::Debug));
+        emit("verbose", Some(Severity::Info));
+        emit("verbose", Some(Severity::Error));
+        emit("verbose", None);
+        emit("noisy", Some(Severity::Error));
+
+        let verbose = provider.logger("verbose");
+        assert!(!verbose.event_enabled(Severity::Info, "verbose", None));
+        assert!(verbose.event_enabled(Severity::Warn, "verbose", None));
+        assert!(!provider
+            .logger("noisy")
+            .event_enabled(Severity::Fatal, "noisy", None));
+
+        let bodies: Vec<_> = exporter
+            .get_emitted_logs()
+            .unwrap()
+            .into_iter()
+            .map(|log| log.record.body().cloned().unwrap())
+            .collect();
+        assert_eq!(
+            bodies,
+            [
+                AnyValue::from("app Some(Debug)".to_string()),
+                AnyValue::from("verbose Some(Error)".to_string()),
+                AnyValue::from("verbose None".to_string()),
+            ]
+        );
+    }
 }
@@ -2,9 +2,12 @@
 mod batch_log_processor;
 mod export;
 mod log_processor;
+mod log_record_limit;
 mod logger;
+mod logger_config;
 mod logger_provider;
 pub(crate) mod record;
+mod redacting_log_processor;
 mod simple_log_processor;
 
 /// In-Memory log exporter for testing purpose.
@@ -20,9 +23,12 @@ pub use batch_log_processor::{
 };
 pub use export::{LogBatch, LogExporter};
 pub use log_processor::LogProcessor;
+pub use log_record_limit::LogRecordLimits;
 pub use logger::SdkLogger;
+pub use logger_config::{LoggerConfig, LoggerConfigurator};
 pub use logger_provider::{LoggerProviderBuilder, SdkLoggerProvider};
 pub use record::{SdkLogRecord, TraceContext};
+pub use redacting_log_processor::RedactingLogProcessor;
 pub use simple_log_processor::SimpleLogProcessor;
 
 #[cfg(feature = "experimental_logs_batch_log_processor_with_async_runtime")]
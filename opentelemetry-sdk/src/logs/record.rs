@@ -1,4 +1,6 @@
+use crate::attribute_limits::truncate_any_value;
 use crate::growable_array::GrowableArray;
+use crate::logs::LogRecordLimits;
 #[cfg(feature = "trace")]
 use opentelemetry::trace::SpanContext;
 use opentelemetry::{
@@ -50,6 +52,13 @@ pub struct SdkLogRecord {
 
     /// Additional attributes associated with this record
     pub(crate) attributes: LogRecordAttributes,
+
+    /// The number of attributes that were above the configured limit, and thus
+    /// dropped.
+    pub(crate) dropped_attributes_count: u32,
+
+    /// Limits applied to the attributes added to this record
+    pub(crate) limits: LogRecordLimits,
 }
 
 impl opentelemetry::logs::LogRecord for SdkLogRecord {
@@ -101,7 +110,15 @@ impl opentelemetry::logs::LogRecord for SdkLogRecord {
         K: Into<Key>,
         V: Into<AnyValue>,
     {
-        self.attributes.push(Some((key.into(), value.into())));
+        if self.attributes.len() < self.limits.max_attributes_per_log_record as usize {
+            let mut value = value.into();
+            if let Some(max_value_length) = self.limits.max_attribute_value_length {
+                truncate_any_value(&mut value, max_value_length as usize);
+            }
+            self.attributes.push(Some((key.into(), value)));
+        } else {
+            self.dropped_attributes_count += 1;
+        }
     }
 
     fn set_trace_context(
@@ -119,8 +136,15 @@ impl opentelemetry::logs::LogRecord for SdkLogRecord {
 }
 
 impl SdkLogRecord {
+    #[allow(dead_code)]
     /// Crate only default constructor
     pub(crate) fn new() -> Self {
+        SdkLogRecord::with_limits(LogRecordLimits::default())
+    }
+
+    /// Crate only constructor for records created by a logger, which applies
+    /// the limits configured on the provider.
+    pub(crate) fn with_limits(limits: LogRecordLimits) -> Self {
         SdkLogRecord {
             event_name: None,
             target: None,
@@ -131,6 +155,8 @@ impl SdkLogRecord {
             severity_number: None,
             body: None,
             attributes: LogRecordAttributes::default(),
+            dropped_attributes_count: 0,
+            limits,
         }
     }
 
@@ -188,6 +214,13 @@ impl SdkLogRecord {
         self.attributes.iter().filter_map(|opt| opt.as_ref())
     }
 
+    /// Returns the number of attributes that were dropped because the
+    /// attribute count limit was reached.
+    #[inline]
+    pub fn dropped_attributes_count(&self) -> u32 {
+        self.dropped_attributes_count
+    }
+
     #[allow(dead_code)]
     /// Returns the number of attributes in the `LogRecord`.
     pub(crate) fn attributes_len(&self) -> usize {
@@ -307,6 +340,29 @@ mod tests {
         assert!(log_record.attributes_contains(&key, &value));
     }
 
+    #[test]
+    fn test_attribute_limits() {
+        let mut log_record = SdkLogRecord::with_limits(
+            LogRecordLimits::default()
+                .with_max_attributes_per_log_record(2)
+                .with_max_attribute_value_length(3),
+        );
+        log_record.add_attribute("key1", "abcdef");
+        log_record.add_attribute(
+            "key2",
+            AnyValue::ListAny(Box::new(vec!["abcdef".into(), 123456.into()])),
+        );
+        log_record.add_attributes([("key3", "value3"), ("key4", "value4")]);
+
+        assert_eq!(log_record.attributes_len(), 2);
+        assert!(log_record.attributes_contains(&Key::new("key1"), &AnyValue::from("abc")));
+        assert!(log_record.attributes_contains(
+            &Key::new("key2"),
+            &AnyValue::ListAny(Box::new(vec!["abc".into(), 123456.into()]))
+        ));
+        assert_eq!(log_record.dropped_attributes_count(), 2);
+    }
+
     #[test]
     fn compare_trace_context() {
         let trace_context = TraceContext {
@@ -339,6 +395,8 @@ mod tests {
             severity_number: Some(Severity::Error),
             body: Some(AnyValue::String("Test body".into())),
             attributes: LogRecordAttributes::new(),
+            dropped_attributes_count: 0,
+            limits: LogRecordLimits::default(),
             trace_context: Some(TraceContext {
                 trace_id: TraceId::from(1),
                 span_id: SpanId::from(1),
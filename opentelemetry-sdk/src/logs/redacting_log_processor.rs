@@ -0,0 +1,129 @@
+use crate::error::OTelSdkResult;
+use crate::logs::{LogProcessor, SdkLogRecord};
+use crate::redaction::Redactor;
+use crate::Resource;
+use opentelemetry::logs::Severity;
+use opentelemetry::InstrumentationScope;
+use std::time::Duration;
+
+/// A [`LogProcessor`] applying a [`Redactor`] to the attributes and the body
+/// of log records before forwarding them to another processor, such as a
+/// [`BatchLogProcessor`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use opentelemetry_sdk::logs::{
+///     InMemoryLogExporter, RedactingLogProcessor, SdkLoggerProvider, SimpleLogProcessor,
+/// };
+/// use opentelemetry_sdk::redaction::{RedactionAction, Redactor};
+///
+/// let exporter = InMemoryLogExporter::default();
+///
+/// let redactor = Redactor::new()
+///     .with_attribute("user.email", RedactionAction::Hash)
+///     // Mask the tokens in log messages.
+///     .with_value_redaction(|body| {
+///         body.split_once("token=")
+///             .map(|(before, _)| format!("{before}token=REDACTED"))
+///     });
+/// let processor = RedactingLogProcessor::new(SimpleLogProcessor::new(exporter), redactor);
+///
+/// let provider = SdkLoggerProvider::builder()
+///     .with_log_processor(processor)
+///     .build();
+/// # }
+/// ```
+///
+/// [`BatchLogProcessor`]: crate::logs::BatchLogProcessor
+#[derive(Debug)]
+pub struct RedactingLogProcessor<P> {
+    processor: P,
+    redactor: Redactor,
+}
+
+impl<P: LogProcessor> RedactingLogProcessor<P> {
+    /// Create a processor redacting log records with `redactor` before
+    /// forwarding them to `processor`.
+    pub fn new(processor: P, redactor: Redactor) -> Self {
+        RedactingLogProcessor {
+            processor,
+            redactor,
+        }
+    }
+}
+
+impl<P: LogProcessor> LogProcessor for RedactingLogProcessor<P> {
+    fn emit(&self, data: &mut SdkLogRecord, instrumentation: &InstrumentationScope) {
+        self.redactor.redact_log_record(data);
+        self.processor.emit(data, instrumentation);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.processor.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.processor.shutdown_with_timeout(timeout)
+    }
+
+    fn event_enabled(&self, level: Severity, target: &str, name: Option<&str>) -> bool {
+        self.processor.event_enabled(level, target, name)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.processor.set_resource(resource);
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::logs::{InMemoryLogExporter, SdkLoggerProvider, SimpleLogProcessor};
+    use crate::redaction::{RedactionAction, MASK};
+    use opentelemetry::logs::{AnyValue, LogRecord, Logger, LoggerProvider};
+    use opentelemetry::Key;
+
+    #[test]
+    fn attributes_and_body_are_redacted() {
+        let exporter = InMemoryLogExporter::default();
+        let redactor = Redactor::new()
+            .with_attribute("password", RedactionAction::Remove)
+            .with_attribute("enduser.id", RedactionAction::Mask)
+            .with_value_redaction(|value| {
+                value
+                    .contains("john@example.com")
+                    .then(|| value.replace("john@example.com", "<email>"))
+            });
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(RedactingLogProcessor::new(
+                SimpleLogProcessor::new(exporter.clone()),
+                redactor,
+            ))
+            .build();
+
+        let logger = provider.logger("test");
+        let mut record = logger.create_log_record();
+        record.set_body("password reset for john@example.com".into());
+        record.add_attribute("password", "hunter2");
+        record.add_attribute("enduser.id", "john");
+        record.add_attribute("to", "john@example.com");
+        logger.emit(record);
+
+        let logs = exporter.get_emitted_logs().unwrap();
+        let record = &logs[0].record;
+        assert_eq!(
+            record.body(),
+            Some(&AnyValue::from("password reset for <email>"))
+        );
+        assert_eq!(
+            record.attributes_iter().cloned().collect::<Vec<_>>(),
+            vec![
+                (Key::new("enduser.id"), AnyValue::from(MASK)),
+                (Key::new("to"), AnyValue::from("<email>")),
+            ]
+        );
+    }
+}
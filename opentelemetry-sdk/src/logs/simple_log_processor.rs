@@ -18,20 +18,17 @@
 
 use crate::error::{OTelSdkError, OTelSdkResult};
 use crate::logs::log_processor::LogProcessor;
+use crate::self_diagnostics::{ComponentType, ProcessorMetrics, SdkMeter};
 use crate::{
     logs::{LogBatch, LogExporter, SdkLogRecord},
     Resource,
 };
 
-#[cfg(feature = "experimental_metrics_bound_instruments")]
-use opentelemetry::KeyValue;
 use opentelemetry::{otel_debug, otel_error, otel_warn, Context, InstrumentationScope};
 
 use std::fmt::Debug;
 use std::sync::atomic::AtomicBool;
-#[cfg(feature = "experimental_metrics_bound_instruments")]
-use std::sync::atomic::AtomicUsize;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 /// A [`LogProcessor`] designed for testing and debugging purpose, that immediately
@@ -70,65 +67,40 @@ pub struct SimpleLogProcessor<T: LogExporter> {
     exporter: Mutex<T>,
     is_shutdown: AtomicBool,
 
-    // Self-diagnostics: otel.sdk.processor.log.processed counter, gated behind
-    // experimental_metrics_bound_instruments so the hot-path `add` is a single
-    // atomic increment with no per-call attribute resolution.
-    //
-    // The SimpleLogProcessor submits each record to the exporter synchronously
-    // and has no queue, so the only processor-side drop is `already_shutdown`.
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    processed_success: opentelemetry::metrics::BoundCounter<u64>,
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    processed_after_shutdown: opentelemetry::metrics::BoundCounter<u64>,
+    // Self-diagnostics. The SimpleLogProcessor submits each record to the
+    // exporter synchronously and has no queue, so the only processor-side drop
+    // is `already_shutdown`.
+    metrics: Arc<ProcessorMetrics>,
 }
 
 impl<T: LogExporter> SimpleLogProcessor<T> {
     /// Creates a new instance of `SimpleLogProcessor`.
     pub fn new(exporter: T) -> Self {
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        let (processed_success, processed_after_shutdown) = {
-            static INSTANCE_COUNTER: AtomicUsize = AtomicUsize::new(0);
-            let instance_id = INSTANCE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-            let component_name = format!("simple_log_processor/{instance_id}");
-
-            let meter = opentelemetry::global::meter("otel.sdk");
-            let counter = meter
-                .u64_counter("otel.sdk.processor.log.processed")
-                .with_description(
-                    "The number of log records for which the processing has finished, \
-                     either successful or failed.",
-                )
-                .with_unit("{log_record}")
-                .build();
-
-            // Attribute values follow the OTel semantic conventions for SDK metrics:
-            // https://github.com/open-telemetry/semantic-conventions/blob/main/docs/otel/sdk-metrics.md#metric-otelsdkprocessorlogprocessed
-            // https://github.com/open-telemetry/semantic-conventions/blob/main/docs/registry/attributes/otel.md#otel-component-attributes
-            let success_attrs = [
-                KeyValue::new("otel.component.type", "simple_log_processor"),
-                KeyValue::new("otel.component.name", component_name.clone()),
-            ];
-            let after_shutdown_attrs = [
-                KeyValue::new("error.type", "already_shutdown"),
-                KeyValue::new("otel.component.type", "simple_log_processor"),
-                KeyValue::new("otel.component.name", component_name),
-            ];
-
-            (
-                counter.bind(&success_attrs),
-                counter.bind(&after_shutdown_attrs),
-            )
-        };
-
         SimpleLogProcessor {
             exporter: Mutex::new(exporter),
             is_shutdown: AtomicBool::new(false),
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            processed_success,
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            processed_after_shutdown,
+            metrics: ProcessorMetrics::new(
+                &SdkMeter::default(),
+                ComponentType::SimpleLogProcessor,
+                None,
+            ),
         }
     }
+
+    /// Report the self-diagnostics metrics of the processor,
+    /// `otel.sdk.processor.log.processed`, to `meter_provider`.
+    ///
+    /// By default, they are reported to the global meter provider set when
+    /// the processor receives its first log record.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn with_meter_provider(
+        mut self,
+        meter_provider: &impl opentelemetry::metrics::MeterProvider,
+    ) -> Self {
+        self.metrics = self.metrics.with_meter(&SdkMeter::new(meter_provider));
+        self
+    }
 }
 
 impl<T: LogExporter> LogProcessor for SimpleLogProcessor<T> {
@@ -137,8 +109,7 @@ impl<T: LogExporter> LogProcessor for SimpleLogProcessor<T> {
         // noop after shutdown
         if self.is_shutdown.load(std::sync::atomic::Ordering::Relaxed) {
             // Record the post-shutdown drop in self-diagnostics before returning.
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            self.processed_after_shutdown.add(1);
+            self.metrics.record_already_shutdown();
 
             // this is a warning, as the user is trying to log after the processor has been shutdown
             otel_warn!(
@@ -153,8 +124,7 @@ impl<T: LogExporter> LogProcessor for SimpleLogProcessor<T> {
                 // Count the record as processed right before submitting it to
                 // the exporter, independent of the export outcome, per semconv.
                 // Matches BatchLogProcessor, which records success before export.
-                #[cfg(feature = "experimental_metrics_bound_instruments")]
-                self.processed_success.add(1);
+                self.metrics.record_success(1);
                 futures_executor::block_on(exporter.export(LogBatch::new(log_tuple)))
             }
             Err(_) => Err(OTelSdkError::InternalFailure(
@@ -558,14 +528,12 @@ mod tests {
         logger.emit(log_record);
     }
 
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
     mod self_obs {
         use super::*;
 
         /// Sums the values of `otel.sdk.processor.log.processed` data points whose
         /// `error.type` attribute equals `error_type` (or that have no `error.type`
         /// attribute when `error_type` is `None`).
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
         fn sum_processed_log_records(
             metric_exporter: &crate::metrics::InMemoryMetricExporter,
             error_type: Option<&str>,
@@ -608,7 +576,6 @@ mod tests {
         ///
         /// `#[ignore]`d because it mutates process-wide state via
         /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
         #[test]
         #[ignore]
         fn self_diagnostics_counter_records_success() {
@@ -643,7 +610,6 @@ mod tests {
         ///
         /// `#[ignore]`d because it mutates process-wide state via
         /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
         #[test]
         #[ignore]
         fn self_diagnostics_counter_records_already_shutdown_drops() {
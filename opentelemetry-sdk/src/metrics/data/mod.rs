@@ -48,6 +48,14 @@ pub struct ScopeMetrics {
 }
 
 impl ScopeMetrics {
+    /// Create the metrics of an instrumentation scope, e.g. in a
+    /// [`MetricProducer`].
+    ///
+    /// [`MetricProducer`]: crate::metrics::MetricProducer
+    pub fn new(scope: InstrumentationScope, metrics: Vec<Metric>) -> Self {
+        ScopeMetrics { scope, metrics }
+    }
+
     /// Returns a reference to the [InstrumentationScope] in [ScopeMetrics].
     pub fn scope(&self) -> &InstrumentationScope {
         &self.scope
@@ -75,6 +83,21 @@ pub struct Metric {
 }
 
 impl Metric {
+    /// Create a metric from its name, description, unit and data.
+    pub fn new(
+        name: impl Into<Cow<'static, str>>,
+        description: impl Into<Cow<'static, str>>,
+        unit: impl Into<Cow<'static, str>>,
+        data: impl Into<AggregatedMetrics>,
+    ) -> Self {
+        Metric {
+            name: name.into(),
+            description: description.into(),
+            unit: unit.into(),
+            data: data.into(),
+        }
+    }
+
     /// Returns the name of the instrument that created this data.
     pub fn name(&self) -> &str {
         &self.name
@@ -175,6 +198,15 @@ pub struct GaugeDataPoint<T> {
 }
 
 impl<T> GaugeDataPoint<T> {
+    /// Create a data point without exemplars.
+    pub fn new(attributes: Vec<KeyValue>, value: T) -> Self {
+        GaugeDataPoint {
+            attributes,
+            value,
+            exemplars: Vec::new(),
+        }
+    }
+
     /// Returns an iterator over the attributes in [GaugeDataPoint].
     pub fn attributes(&self) -> impl Iterator<Item = &KeyValue> {
         self.attributes.iter()
@@ -205,6 +237,19 @@ pub struct Gauge<T> {
 }
 
 impl<T> Gauge<T> {
+    /// Create a gauge from its data points, recorded at `time`.
+    pub fn new(
+        data_points: Vec<GaugeDataPoint<T>>,
+        start_time: Option<SystemTime>,
+        time: SystemTime,
+    ) -> Self {
+        Gauge {
+            data_points,
+            start_time,
+            time,
+        }
+    }
+
     /// Returns an iterator over the [GaugeDataPoint]s in [Gauge].
     pub fn data_points(&self) -> impl Iterator<Item = &GaugeDataPoint<T>> {
         self.data_points.iter()
@@ -234,6 +279,15 @@ pub struct SumDataPoint<T> {
 }
 
 impl<T> SumDataPoint<T> {
+    /// Create a data point without exemplars.
+    pub fn new(attributes: Vec<KeyValue>, value: T) -> Self {
+        SumDataPoint {
+            attributes,
+            value,
+            exemplars: Vec::new(),
+        }
+    }
+
     /// Returns an iterator over the attributes in [SumDataPoint].
     pub fn attributes(&self) -> impl Iterator<Item = &KeyValue> {
         self.attributes.iter()
@@ -269,6 +323,24 @@ pub struct Sum<T> {
 }
 
 impl<T> Sum<T> {
+    /// Create a sum from its data points, aggregated from `start_time` to
+    /// `time`.
+    pub fn new(
+        data_points: Vec<SumDataPoint<T>>,
+        start_time: SystemTime,
+        time: SystemTime,
+        temporality: Temporality,
+        is_monotonic: bool,
+    ) -> Self {
+        Sum {
+            data_points,
+            start_time,
+            time,
+            temporality,
+            is_monotonic,
+        }
+    }
+
     /// Returns an iterator over the [SumDataPoint]s in [Sum].
     pub fn data_points(&self) -> impl Iterator<Item = &SumDataPoint<T>> {
         self.data_points.iter()
@@ -311,6 +383,22 @@ pub struct Histogram<T> {
 }
 
 impl<T> Histogram<T> {
+    /// Create a histogram from its data points, aggregated from `start_time`
+    /// to `time`.
+    pub fn new(
+        data_points: Vec<HistogramDataPoint<T>>,
+        start_time: SystemTime,
+        time: SystemTime,
+        temporality: Temporality,
+    ) -> Self {
+        Histogram {
+            data_points,
+            start_time,
+            time,
+            temporality,
+        }
+    }
+
     /// Returns an iterator over the [HistogramDataPoint]s in [Histogram].
     pub fn data_points(&self) -> impl Iterator<Item = &HistogramDataPoint<T>> {
         self.data_points.iter()
@@ -359,6 +447,31 @@ pub struct HistogramDataPoint<T> {
 }
 
 impl<T> HistogramDataPoint<T> {
+    /// Create a data point without exemplars.
+    ///
+    /// `bucket_counts` has one more element than `bounds`, the count of the
+    /// values greater than the last bound.
+    pub fn new(
+        attributes: Vec<KeyValue>,
+        count: u64,
+        bounds: Vec<f64>,
+        bucket_counts: Vec<u64>,
+        min: Option<T>,
+        max: Option<T>,
+        sum: T,
+    ) -> Self {
+        HistogramDataPoint {
+            attributes,
+            count,
+            bounds,
+            bucket_counts,
+            min,
+            max,
+            sum,
+            exemplars: Vec::new(),
+        }
+    }
+
     /// Returns an iterator over the attributes in [HistogramDataPoint].
     pub fn attributes(&self) -> impl Iterator<Item = &KeyValue> {
         self.attributes.iter()
@@ -417,6 +530,22 @@ pub struct ExponentialHistogram<T> {
 }
 
 impl<T> ExponentialHistogram<T> {
+    /// Create an exponential histogram from its data points, aggregated from
+    /// `start_time` to `time`.
+    pub fn new(
+        data_points: Vec<ExponentialHistogramDataPoint<T>>,
+        start_time: SystemTime,
+        time: SystemTime,
+        temporality: Temporality,
+    ) -> Self {
+        ExponentialHistogram {
+            data_points,
+            start_time,
+            time,
+            temporality,
+        }
+    }
+
     /// Returns an iterator over the [ExponentialHistogramDataPoint]s in [ExponentialHistogram].
     pub fn data_points(&self) -> impl Iterator<Item = &ExponentialHistogramDataPoint<T>> {
         self.data_points.iter()
@@ -485,6 +614,39 @@ pub struct ExponentialHistogramDataPoint<T> {
 }
 
 impl<T> ExponentialHistogramDataPoint<T> {
+    /// Create a data point without exemplars.
+    ///
+    /// The bucket boundaries are the powers of `2 ^ (2 ^ -scale)`, and
+    /// `zero_count` is the number of values in the zero region, the closed
+    /// interval `[-zero_threshold, zero_threshold]`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        attributes: Vec<KeyValue>,
+        count: usize,
+        min: Option<T>,
+        max: Option<T>,
+        sum: T,
+        scale: i8,
+        zero_count: u64,
+        positive_bucket: ExponentialBucket,
+        negative_bucket: ExponentialBucket,
+        zero_threshold: f64,
+    ) -> Self {
+        ExponentialHistogramDataPoint {
+            attributes,
+            count,
+            min,
+            max,
+            sum,
+            scale,
+            zero_count,
+            positive_bucket,
+            negative_bucket,
+            zero_threshold,
+            exemplars: Vec::new(),
+        }
+    }
+
     /// Returns an iterator over the attributes in [ExponentialHistogramDataPoint].
     pub fn attributes(&self) -> impl Iterator<Item = &KeyValue> {
         self.attributes.iter()
@@ -557,6 +719,12 @@ pub struct ExponentialBucket {
 }
 
 impl ExponentialBucket {
+    /// Create a set of buckets, `counts[i]` being the count of the bucket at
+    /// index `offset + i`.
+    pub fn new(offset: i32, counts: Vec<u64>) -> Self {
+        ExponentialBucket { offset, counts }
+    }
+
     /// Returns the bucket index of the first entry in the counts vec.
     pub fn offset(&self) -> i32 {
         self.offset
@@ -0,0 +1,83 @@
+use std::str::FromStr;
+
+/// Name of the environment variable used to configure the [ExemplarFilter].
+pub(crate) const METRICS_EXEMPLAR_FILTER: &str = "OTEL_METRICS_EXEMPLAR_FILTER";
+
+/// Determines which measurements are eligible to become [Exemplar]s.
+///
+/// Exemplars are only sampled from synchronous instruments, as measurements
+/// from observable instruments are not recorded in the context of a request.
+///
+/// The filter can be configured with
+/// [`MeterProviderBuilder::with_exemplar_filter`] or with the
+/// `OTEL_METRICS_EXEMPLAR_FILTER` environment variable, which accepts
+/// `always_on`, `always_off` and `trace_based`. If neither is set,
+/// [ExemplarFilter::TraceBased] is used.
+///
+/// [Exemplar]: crate::metrics::data::Exemplar
+/// [`MeterProviderBuilder::with_exemplar_filter`]: crate::metrics::MeterProviderBuilder::with_exemplar_filter
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ExemplarFilter {
+    /// Every measurement is offered to the exemplar reservoir.
+    AlwaysOn,
+
+    /// No measurement is offered to the exemplar reservoir, which disables
+    /// exemplar collection entirely.
+    AlwaysOff,
+
+    /// Only measurements recorded in the context of a sampled span are offered
+    /// to the exemplar reservoir.
+    #[default]
+    TraceBased,
+}
+
+impl ExemplarFilter {
+    /// Reads the filter from the `OTEL_METRICS_EXEMPLAR_FILTER` environment
+    /// variable, falling back to the default when unset or invalid.
+    pub(crate) fn from_env() -> Self {
+        std::env::var(METRICS_EXEMPLAR_FILTER)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for ExemplarFilter {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "always_on" => Ok(ExemplarFilter::AlwaysOn),
+            "always_off" => Ok(ExemplarFilter::AlwaysOff),
+            "trace_based" => Ok(ExemplarFilter::TraceBased),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exemplar_filter_from_str() {
+        assert_eq!("always_on".parse(), Ok(ExemplarFilter::AlwaysOn));
+        assert_eq!("ALWAYS_OFF".parse(), Ok(ExemplarFilter::AlwaysOff));
+        assert_eq!(" trace_based ".parse(), Ok(ExemplarFilter::TraceBased));
+        assert_eq!("sometimes".parse::<ExemplarFilter>(), Err(()));
+    }
+
+    #[test]
+    fn exemplar_filter_from_env() {
+        temp_env::with_var(METRICS_EXEMPLAR_FILTER, Some("always_on"), || {
+            assert_eq!(ExemplarFilter::from_env(), ExemplarFilter::AlwaysOn);
+        });
+        temp_env::with_var(METRICS_EXEMPLAR_FILTER, Some("invalid"), || {
+            assert_eq!(ExemplarFilter::from_env(), ExemplarFilter::TraceBased);
+        });
+        temp_env::with_var_unset(METRICS_EXEMPLAR_FILTER, || {
+            assert_eq!(ExemplarFilter::from_env(), ExemplarFilter::TraceBased);
+        });
+    }
+}
@@ -1,4 +1,4 @@
-use std::{borrow::Cow, collections::HashSet, error::Error, sync::Arc};
+use std::{borrow::Cow, collections::HashSet, error::Error, str::FromStr, sync::Arc};
 
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 use opentelemetry::metrics::BoundSyncInstrument;
@@ -70,6 +70,26 @@ impl InstrumentKind {
         }
     }
 }
+
+impl FromStr for InstrumentKind {
+    type Err = ();
+
+    /// Parses the instrument type names used by declarative configuration,
+    /// e.g. `counter`, `up_down_counter` or `observable_gauge`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "counter" => Ok(InstrumentKind::Counter),
+            "up_down_counter" => Ok(InstrumentKind::UpDownCounter),
+            "histogram" => Ok(InstrumentKind::Histogram),
+            "gauge" => Ok(InstrumentKind::Gauge),
+            "observable_counter" => Ok(InstrumentKind::ObservableCounter),
+            "observable_up_down_counter" => Ok(InstrumentKind::ObservableUpDownCounter),
+            "observable_gauge" => Ok(InstrumentKind::ObservableGauge),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Describes the properties of an instrument at creation, used for filtering in
 /// views. This is utilized in the `with_view` methods on `MeterProviderBuilder`
 /// to customize metric output.
@@ -305,7 +325,7 @@ fn validate_bucket_boundaries(boundaries: &[f64]) -> Result<(), String> {
 
 /// Describes the stream of data an instrument produces. Used in `with_view`
 /// methods on `MeterProviderBuilder` to customize the metric output.
-#[derive(Default, Debug)]
+#[derive(Clone, Default, Debug, PartialEq)]
 pub struct Stream {
     /// The human-readable identifier of the stream.
     pub(crate) name: Option<Cow<'static, str>>,
@@ -334,7 +354,7 @@ impl Stream {
 }
 
 /// The identifying properties of an instrument.
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub(crate) struct InstrumentId {
     /// The human-readable identifier of the instrument.
     pub(crate) name: Cow<'static, str>,
@@ -375,6 +395,12 @@ impl<T: Copy + 'static> SyncInstrument<T> for ResolvedMeasures<T> {
         }
     }
 
+    fn enabled(&self) -> bool {
+        // No measure is resolved when there is no reader or when every stream
+        // of the instrument is dropped.
+        !self.measures.is_empty()
+    }
+
     #[cfg(feature = "experimental_metrics_bound_instruments")]
     fn bind(&self, attrs: &[KeyValue]) -> Box<dyn BoundSyncInstrument<T> + Send + Sync> {
         let bound_measures: Vec<Box<dyn BoundMeasure<T>>> =
@@ -420,7 +446,7 @@ impl<T: Copy + Send + Sync + 'static> AsyncInstrument<T> for Observable<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::StreamBuilder;
+    use super::{InstrumentKind, StreamBuilder};
     use crate::metrics::meter::{INSTRUMENT_UNIT_INVALID_CHAR, INSTRUMENT_UNIT_LENGTH};
 
     #[test]
@@ -801,4 +827,24 @@ mod tests {
             "Expected successful build with max_scale = EXPO_MAX_SCALE"
         );
     }
+
+    #[test]
+    fn instrument_kind_from_str() {
+        let kinds = [
+            ("counter", InstrumentKind::Counter),
+            ("up_down_counter", InstrumentKind::UpDownCounter),
+            ("histogram", InstrumentKind::Histogram),
+            ("gauge", InstrumentKind::Gauge),
+            ("observable_counter", InstrumentKind::ObservableCounter),
+            (
+                "observable_up_down_counter",
+                InstrumentKind::ObservableUpDownCounter,
+            ),
+            ("Observable_Gauge", InstrumentKind::ObservableGauge),
+        ];
+        for (name, kind) in kinds {
+            assert_eq!(name.parse(), Ok(kind));
+        }
+        assert_eq!("summary".parse::<InstrumentKind>(), Err(()));
+    }
 }
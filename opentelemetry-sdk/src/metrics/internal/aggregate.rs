@@ -6,13 +6,18 @@ use std::{
     time::SystemTime,
 };
 
-use crate::metrics::{data::AggregatedMetrics, Temporality};
+use crate::metrics::{data::AggregatedMetrics, ExemplarFilter, Temporality};
 use opentelemetry::time::now;
 use opentelemetry::KeyValue;
 
 use super::{
-    exponential_histogram::ExpoHistogram, histogram::Histogram, last_value::LastValue,
-    precomputed_sum::PrecomputedSum, sum::Sum, Number,
+    exemplar::{ExemplarSampler, ReservoirConfig},
+    exponential_histogram::ExpoHistogram,
+    histogram::Histogram,
+    last_value::LastValue,
+    precomputed_sum::PrecomputedSum,
+    sum::Sum,
+    Number,
 };
 
 /// Receives measurements to be aggregated.
@@ -145,6 +150,15 @@ impl AttributeSetFilter {
             run(attrs);
         };
     }
+
+    /// Returns the attributes removed by the filter, which are recorded on
+    /// exemplars as their filtered attributes.
+    pub(crate) fn dropped(&self, attrs: &[KeyValue]) -> Vec<KeyValue> {
+        match &self.filter {
+            Some(filter) => attrs.iter().filter(|kv| !filter(kv)).cloned().collect(),
+            None => Vec::new(),
+        }
+    }
 }
 
 /// Builds aggregate functions
@@ -159,6 +173,9 @@ pub(crate) struct AggregateBuilder<T> {
     /// Cardinality limit for the metric stream
     cardinality_limit: usize,
 
+    /// Selects the measurements that are offered to exemplar reservoirs.
+    exemplar_filter: ExemplarFilter,
+
     _marker: marker::PhantomData<T>,
 }
 
@@ -167,11 +184,13 @@ impl<T: Number> AggregateBuilder<T> {
         temporality: Temporality,
         filter: Option<Filter>,
         cardinality_limit: usize,
+        exemplar_filter: ExemplarFilter,
     ) -> Self {
         AggregateBuilder {
             temporality,
             filter: AttributeSetFilter::new(filter),
             cardinality_limit,
+            exemplar_filter,
             _marker: marker::PhantomData,
         }
     }
@@ -182,6 +201,7 @@ impl<T: Number> AggregateBuilder<T> {
             overwrite_temporality.unwrap_or(self.temporality),
             self.filter.clone(),
             self.cardinality_limit,
+            ExemplarSampler::new(self.exemplar_filter, ReservoirConfig::simple_fixed_size()),
         )
         .into()
     }
@@ -204,6 +224,7 @@ impl<T: Number> AggregateBuilder<T> {
             self.filter.clone(),
             monotonic,
             self.cardinality_limit,
+            ExemplarSampler::new(self.exemplar_filter, ReservoirConfig::simple_fixed_size()),
         )
         .into()
     }
@@ -215,6 +236,12 @@ impl<T: Number> AggregateBuilder<T> {
         record_min_max: bool,
         record_sum: bool,
     ) -> AggregateFns<T> {
+        let exemplar_sampler = ExemplarSampler::new(
+            self.exemplar_filter,
+            ReservoirConfig::AlignedHistogramBucket {
+                buckets: boundaries.len() + 1,
+            },
+        );
         Histogram::new(
             self.temporality,
             self.filter.clone(),
@@ -222,6 +249,7 @@ impl<T: Number> AggregateBuilder<T> {
             record_min_max,
             record_sum,
             self.cardinality_limit,
+            exemplar_sampler,
         )
         .into()
     }
@@ -242,6 +270,10 @@ impl<T: Number> AggregateBuilder<T> {
             record_min_max,
             record_sum,
             self.cardinality_limit,
+            ExemplarSampler::new(
+                self.exemplar_filter,
+                ReservoirConfig::exponential_histogram(max_size),
+            ),
         )
         .into()
     }
@@ -261,9 +293,13 @@ mod tests {
 
     #[test]
     fn last_value_aggregation() {
-        let AggregateFns { measure, collect } =
-            AggregateBuilder::<u64>::new(Temporality::Cumulative, None, CARDINALITY_LIMIT_DEFAULT)
-                .last_value(None);
+        let AggregateFns { measure, collect } = AggregateBuilder::<u64>::new(
+            Temporality::Cumulative,
+            None,
+            CARDINALITY_LIMIT_DEFAULT,
+            ExemplarFilter::AlwaysOff,
+        )
+        .last_value(None);
         let mut a = MetricData::Gauge(Gauge {
             data_points: vec![GaugeDataPoint {
                 attributes: vec![KeyValue::new("a", 1)],
@@ -292,9 +328,13 @@ mod tests {
     #[test]
     fn precomputed_sum_aggregation() {
         for temporality in [Temporality::Delta, Temporality::Cumulative] {
-            let AggregateFns { measure, collect } =
-                AggregateBuilder::<u64>::new(temporality, None, CARDINALITY_LIMIT_DEFAULT)
-                    .precomputed_sum(true);
+            let AggregateFns { measure, collect } = AggregateBuilder::<u64>::new(
+                temporality,
+                None,
+                CARDINALITY_LIMIT_DEFAULT,
+                ExemplarFilter::AlwaysOff,
+            )
+            .precomputed_sum(true);
             let mut a = MetricData::Sum(Sum {
                 data_points: vec![
                     SumDataPoint {
@@ -339,9 +379,13 @@ mod tests {
     #[test]
     fn sum_aggregation() {
         for temporality in [Temporality::Delta, Temporality::Cumulative] {
-            let AggregateFns { measure, collect } =
-                AggregateBuilder::<u64>::new(temporality, None, CARDINALITY_LIMIT_DEFAULT)
-                    .sum(true);
+            let AggregateFns { measure, collect } = AggregateBuilder::<u64>::new(
+                temporality,
+                None,
+                CARDINALITY_LIMIT_DEFAULT,
+                ExemplarFilter::AlwaysOff,
+            )
+            .sum(true);
             let mut a = MetricData::Sum(Sum {
                 data_points: vec![
                     SumDataPoint {
@@ -386,9 +430,13 @@ mod tests {
     #[test]
     fn explicit_bucket_histogram_aggregation() {
         for temporality in [Temporality::Delta, Temporality::Cumulative] {
-            let AggregateFns { measure, collect } =
-                AggregateBuilder::<u64>::new(temporality, None, CARDINALITY_LIMIT_DEFAULT)
-                    .explicit_bucket_histogram(vec![1.0], true, true);
+            let AggregateFns { measure, collect } = AggregateBuilder::<u64>::new(
+                temporality,
+                None,
+                CARDINALITY_LIMIT_DEFAULT,
+                ExemplarFilter::AlwaysOff,
+            )
+            .explicit_bucket_histogram(vec![1.0], true, true);
             let mut a = MetricData::Histogram(Histogram {
                 data_points: vec![HistogramDataPoint {
                     attributes: vec![KeyValue::new("a1", 1)],
@@ -434,9 +482,13 @@ mod tests {
     #[test]
     fn exponential_histogram_aggregation() {
         for temporality in [Temporality::Delta, Temporality::Cumulative] {
-            let AggregateFns { measure, collect } =
-                AggregateBuilder::<u64>::new(temporality, None, CARDINALITY_LIMIT_DEFAULT)
-                    .exponential_bucket_histogram(4, 20, true, true);
+            let AggregateFns { measure, collect } = AggregateBuilder::<u64>::new(
+                temporality,
+                None,
+                CARDINALITY_LIMIT_DEFAULT,
+                ExemplarFilter::AlwaysOff,
+            )
+            .exponential_bucket_histogram(4, 20, true, true);
             let mut a = MetricData::ExponentialHistogram(ExponentialHistogram {
                 data_points: vec![ExponentialHistogramDataPoint {
                     attributes: vec![KeyValue::new("a1", 1)],
@@ -0,0 +1,390 @@
+use std::cell::RefCell;
+use std::mem;
+use std::sync::Mutex;
+
+#[cfg(feature = "trace")]
+use opentelemetry::trace::TraceContextExt;
+#[cfg(feature = "trace")]
+use opentelemetry::Context;
+use opentelemetry::KeyValue;
+use rand::{rngs, Rng, SeedableRng};
+
+use crate::metrics::data::Exemplar;
+use crate::metrics::ExemplarFilter;
+
+use super::{Aggregator, Number};
+
+/// The maximum size of the reservoir used by exponential histograms.
+const EXPO_HISTOGRAM_MAX_RESERVOIR_SIZE: usize = 20;
+
+thread_local! {
+    /// Store random number generator for each thread
+    static CURRENT_RNG: RefCell<rngs::SmallRng> = RefCell::new(rngs::SmallRng::from_os_rng());
+}
+
+/// The kind and size of the [Reservoir] used for each attribute set of an
+/// aggregation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ReservoirConfig {
+    /// Uniformly samples up to `size` measurements per collection cycle.
+    SimpleFixedSize { size: usize },
+    /// Keeps the last measurement that fell into each of the `buckets`
+    /// histogram buckets.
+    AlignedHistogramBucket { buckets: usize },
+}
+
+impl ReservoirConfig {
+    /// The default reservoir for aggregations other than histograms, sized by
+    /// the number of available CPUs.
+    pub(crate) fn simple_fixed_size() -> Self {
+        let size = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        ReservoirConfig::SimpleFixedSize { size }
+    }
+
+    /// The default reservoir for exponential histograms, sized by the smaller
+    /// of the maximum number of buckets and twenty.
+    pub(crate) fn exponential_histogram(max_size: u32) -> Self {
+        ReservoirConfig::SimpleFixedSize {
+            size: (max_size as usize).clamp(1, EXPO_HISTOGRAM_MAX_RESERVOIR_SIZE),
+        }
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            ReservoirConfig::SimpleFixedSize { size } => *size,
+            ReservoirConfig::AlignedHistogramBucket { buckets } => *buckets,
+        }
+    }
+}
+
+/// A measurement that was selected by the [ExemplarFilter] and is offered to a
+/// [Reservoir].
+pub(crate) struct ExemplarMeasurement<T> {
+    exemplar: Exemplar<T>,
+    /// Index of the histogram bucket the measurement fell into, used by
+    /// [ReservoirConfig::AlignedHistogramBucket].
+    bucket: usize,
+}
+
+/// Decides whether a measurement should be offered as an exemplar and
+/// captures the information an exemplar needs at measurement time.
+#[derive(Debug, Clone)]
+pub(crate) struct ExemplarSampler {
+    filter: ExemplarFilter,
+    reservoir: ReservoirConfig,
+}
+
+impl ExemplarSampler {
+    pub(crate) fn new(filter: ExemplarFilter, reservoir: ReservoirConfig) -> Self {
+        ExemplarSampler { filter, reservoir }
+    }
+
+    /// The reservoir each attribute set should use, or `None` if exemplars are
+    /// disabled.
+    pub(crate) fn reservoir(&self) -> Option<ReservoirConfig> {
+        match self.filter {
+            ExemplarFilter::AlwaysOff => None,
+            _ if self.reservoir.size() == 0 => None,
+            _ => Some(self.reservoir),
+        }
+    }
+
+    /// Returns the exemplar candidate for `value` if the filter accepts it.
+    ///
+    /// `filtered_attributes` is only invoked when a candidate is produced, and
+    /// should return the measurement attributes that were removed by the
+    /// stream's attribute filter.
+    #[inline]
+    pub(crate) fn sample<T>(
+        &self,
+        value: T,
+        bucket: usize,
+        filtered_attributes: impl FnOnce() -> Vec<KeyValue>,
+    ) -> Option<ExemplarMeasurement<T>> {
+        self.reservoir()?;
+
+        let (trace_id, span_id, sampled) = current_span();
+        if self.filter == ExemplarFilter::TraceBased && !sampled {
+            return None;
+        }
+
+        Some(ExemplarMeasurement {
+            exemplar: Exemplar {
+                filtered_attributes: filtered_attributes(),
+                time: opentelemetry::time::now(),
+                value,
+                span_id,
+                trace_id,
+            },
+            bucket,
+        })
+    }
+}
+
+/// Returns the trace id, span id and sampled flag of the active span.
+///
+/// Ids are only returned for sampled spans, as exemplars should only link to
+/// traces that are exported.
+#[cfg(feature = "trace")]
+fn current_span() -> ([u8; 16], [u8; 8], bool) {
+    Context::map_current(|cx| {
+        let span_context = cx.span().span_context().clone();
+        if span_context.is_valid() && span_context.is_sampled() {
+            (
+                span_context.trace_id().to_bytes(),
+                span_context.span_id().to_bytes(),
+                true,
+            )
+        } else {
+            ([0; 16], [0; 8], false)
+        }
+    })
+}
+
+#[cfg(not(feature = "trace"))]
+fn current_span() -> ([u8; 16], [u8; 8], bool) {
+    ([0; 16], [0; 8], false)
+}
+
+/// Holds the exemplars sampled for a single attribute set.
+pub(crate) struct Reservoir<T> {
+    config: ReservoirConfig,
+    state: Mutex<ReservoirState<T>>,
+}
+
+struct ReservoirState<T> {
+    /// Slots are allocated on the first offered measurement so attribute sets
+    /// which never see a sampled measurement don't pay for them.
+    exemplars: Vec<Option<Exemplar<T>>>,
+    /// Number of measurements offered since the last reset.
+    measurements_seen: usize,
+}
+
+impl<T: Number> Reservoir<T> {
+    pub(crate) fn new(config: ReservoirConfig) -> Self {
+        Reservoir {
+            config,
+            state: Mutex::new(ReservoirState {
+                exemplars: Vec::new(),
+                measurements_seen: 0,
+            }),
+        }
+    }
+
+    /// Offers a measurement to the reservoir, which decides whether to keep it.
+    pub(crate) fn offer(&self, measurement: ExemplarMeasurement<T>) {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        let size = self.config.size();
+        if state.exemplars.is_empty() {
+            state.exemplars.resize_with(size, || None);
+        }
+
+        let index = match self.config {
+            ReservoirConfig::SimpleFixedSize { .. } => {
+                // Reservoir sampling (algorithm R): the first `size`
+                // measurements fill the slots, every later one replaces a
+                // random slot with probability `size / measurements_seen`.
+                let seen = state.measurements_seen;
+                state.measurements_seen += 1;
+                if seen < size {
+                    Some(seen)
+                } else {
+                    let candidate = CURRENT_RNG.with(|rng| rng.borrow_mut().random_range(0..=seen));
+                    (candidate < size).then_some(candidate)
+                }
+            }
+            ReservoirConfig::AlignedHistogramBucket { .. } => {
+                state.measurements_seen += 1;
+                (measurement.bucket < size).then_some(measurement.bucket)
+            }
+        };
+
+        if let Some(index) = index {
+            state.exemplars[index] = Some(measurement.exemplar);
+        }
+    }
+
+    /// Appends the sampled exemplars to `dest`, keeping them until they are
+    /// replaced by the measurements of the next collection cycle.
+    pub(crate) fn collect(&self, dest: &mut Vec<Exemplar<T>>) {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        dest.clear();
+        dest.extend(state.exemplars.iter().flatten().cloned());
+        // Restart the sampling, or the chance of a measurement replacing an
+        // exemplar would decrease for the lifetime of a cumulative stream.
+        state.measurements_seen = 0;
+    }
+
+    /// Moves the sampled exemplars into `dest` and resets the reservoir for the
+    /// next collection cycle.
+    pub(crate) fn collect_and_reset(&self, dest: &mut Vec<Exemplar<T>>) {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        dest.clear();
+        dest.extend(state.exemplars.iter_mut().filter_map(Option::take));
+        state.measurements_seen = 0;
+    }
+
+    fn take(&self) -> Self {
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+        Reservoir {
+            config: self.config,
+            state: Mutex::new(ReservoirState {
+                exemplars: mem::take(&mut state.exemplars),
+                measurements_seen: mem::take(&mut state.measurements_seen),
+            }),
+        }
+    }
+}
+
+/// Pairs an [Aggregator] with an optional exemplar [Reservoir], so that each
+/// attribute set tracked by a `ValueMap` samples its own exemplars.
+pub(crate) struct WithExemplars<A, T> {
+    pub(crate) aggregator: A,
+    pub(crate) reservoir: Option<Reservoir<T>>,
+}
+
+impl<A, T> WithExemplars<A, T>
+where
+    T: Number,
+{
+    /// Returns the exemplars of this attribute set, resetting the reservoir
+    /// if `reset` is set.
+    pub(crate) fn exemplars(&self, reset: bool) -> Vec<Exemplar<T>> {
+        let mut exemplars = Vec::new();
+        if let Some(reservoir) = &self.reservoir {
+            if reset {
+                reservoir.collect_and_reset(&mut exemplars);
+            } else {
+                reservoir.collect(&mut exemplars);
+            }
+        }
+        exemplars
+    }
+}
+
+impl<A, T> Aggregator for WithExemplars<A, T>
+where
+    A: Aggregator,
+    T: Number,
+{
+    type InitConfig = (A::InitConfig, Option<ReservoirConfig>);
+    type PreComputedValue = (A::PreComputedValue, Option<ExemplarMeasurement<T>>);
+
+    fn create((init, reservoir): &Self::InitConfig) -> Self {
+        WithExemplars {
+            aggregator: A::create(init),
+            reservoir: reservoir.map(Reservoir::new),
+        }
+    }
+
+    #[inline]
+    fn update(&self, (value, exemplar): Self::PreComputedValue) {
+        self.aggregator.update(value);
+        if let (Some(reservoir), Some(exemplar)) = (&self.reservoir, exemplar) {
+            reservoir.offer(exemplar);
+        }
+    }
+
+    fn clone_and_reset(&self, (init, _): &Self::InitConfig) -> Self {
+        WithExemplars {
+            aggregator: self.aggregator.clone_and_reset(init),
+            reservoir: self.reservoir.as_ref().map(Reservoir::take),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(value: u64, bucket: usize) -> ExemplarMeasurement<u64> {
+        ExemplarSampler::new(
+            ExemplarFilter::AlwaysOn,
+            ReservoirConfig::SimpleFixedSize { size: 1 },
+        )
+        .sample(value, bucket, || vec![KeyValue::new("dropped", true)])
+        .expect("always on filter samples every measurement")
+    }
+
+    #[test]
+    fn always_off_filter_disables_reservoir() {
+        let sampler = ExemplarSampler::new(
+            ExemplarFilter::AlwaysOff,
+            ReservoirConfig::SimpleFixedSize { size: 4 },
+        );
+        assert!(sampler.reservoir().is_none());
+        assert!(sampler
+            .sample(1u64, 0, || unreachable!("no attributes needed"))
+            .is_none());
+    }
+
+    #[test]
+    fn trace_based_filter_requires_sampled_span() {
+        let sampler = ExemplarSampler::new(
+            ExemplarFilter::TraceBased,
+            ReservoirConfig::SimpleFixedSize { size: 4 },
+        );
+        assert!(sampler.reservoir().is_some());
+        assert!(sampler.sample(1u64, 0, Vec::new).is_none());
+    }
+
+    #[test]
+    fn simple_fixed_size_reservoir_keeps_at_most_size_exemplars() {
+        let reservoir = Reservoir::new(ReservoirConfig::SimpleFixedSize { size: 3 });
+        for v in 0..100 {
+            reservoir.offer(measurement(v, 0));
+        }
+
+        let mut exemplars = Vec::new();
+        reservoir.collect(&mut exemplars);
+        assert_eq!(exemplars.len(), 3);
+        assert_eq!(
+            exemplars[0].filtered_attributes,
+            vec![KeyValue::new("dropped", true)]
+        );
+
+        reservoir.collect_and_reset(&mut exemplars);
+        assert_eq!(exemplars.len(), 3);
+        reservoir.collect(&mut exemplars);
+        assert!(exemplars.is_empty());
+    }
+
+    #[test]
+    fn cumulative_collections_sample_fresh_exemplars() {
+        let reservoir = Reservoir::new(ReservoirConfig::SimpleFixedSize { size: 3 });
+        let mut exemplars = Vec::new();
+        for cycle in 0..3u64 {
+            for v in cycle * 1000..(cycle + 1) * 1000 {
+                reservoir.offer(measurement(v, 0));
+            }
+            reservoir.collect(&mut exemplars);
+            assert_eq!(exemplars.len(), 3);
+            assert!(
+                exemplars
+                    .iter()
+                    .all(|e| (cycle * 1000..(cycle + 1) * 1000).contains(&e.value)),
+                "cycle {cycle} reported old exemplars: {:?}",
+                exemplars.iter().map(|e| e.value).collect::<Vec<_>>()
+            );
+        }
+
+        // Without new measurements, the exemplars are kept.
+        reservoir.collect(&mut exemplars);
+        assert_eq!(exemplars.len(), 3);
+    }
+
+    #[test]
+    fn aligned_histogram_reservoir_keeps_last_measurement_per_bucket() {
+        let reservoir = Reservoir::new(ReservoirConfig::AlignedHistogramBucket { buckets: 3 });
+        reservoir.offer(measurement(1, 0));
+        reservoir.offer(measurement(2, 0));
+        reservoir.offer(measurement(7, 2));
+
+        let mut exemplars = Vec::new();
+        reservoir.collect_and_reset(&mut exemplars);
+        let values: Vec<u64> = exemplars.iter().map(|e| e.value).collect();
+        assert_eq!(values, vec![2, 7]);
+    }
+}
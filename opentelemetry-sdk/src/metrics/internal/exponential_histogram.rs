@@ -14,6 +14,7 @@ use crate::metrics::{
 
 use super::{
     aggregate::{AggregateTimeInitiator, AttributeSetFilter},
+    exemplar::{ExemplarSampler, WithExemplars},
     Aggregator, ComputeAggregation, Measure, Number, ValueMap,
 };
 #[cfg(feature = "experimental_metrics_bound_instruments")]
@@ -29,7 +30,9 @@ pub(crate) const EXPO_MIN_SCALE: i8 = -10;
 /// from the unbound `call()` path is preserved here.
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 struct BoundExpoHistogramHandle<T: Number> {
-    tracker: Arc<TrackerEntry<Mutex<ExpoHistogramDataPoint<T>>>>,
+    tracker: Arc<TrackerEntry<WithExemplars<Mutex<ExpoHistogramDataPoint<T>>, T>>>,
+    exemplar_sampler: ExemplarSampler,
+    dropped_attributes: Vec<KeyValue>,
 }
 
 #[cfg(feature = "experimental_metrics_bound_instruments")]
@@ -40,7 +43,10 @@ impl<T: Number> BoundMeasure<T> for BoundExpoHistogramHandle<T> {
         if !measurement.into_float().is_finite() {
             return;
         }
-        self.tracker.aggregator.update(measurement);
+        let exemplar = self
+            .exemplar_sampler
+            .sample(measurement, 0, || self.dropped_attributes.clone());
+        self.tracker.aggregator.update((measurement, exemplar));
         self.tracker.has_been_updated.store(true, Ordering::Release);
     }
 }
@@ -388,16 +394,18 @@ struct BucketConfig {
 /// Each histogram is scoped by attributes and the aggregation cycle the
 /// measurements were made in.
 pub(crate) struct ExpoHistogram<T: Number> {
-    value_map: ValueMap<Mutex<ExpoHistogramDataPoint<T>>>,
+    value_map: ValueMap<WithExemplars<Mutex<ExpoHistogramDataPoint<T>>, T>>,
     init_time: AggregateTimeInitiator,
     temporality: Temporality,
     filter: AttributeSetFilter,
     record_sum: bool,
     record_min_max: bool,
+    exemplar_sampler: ExemplarSampler,
 }
 
 impl<T: Number> ExpoHistogram<T> {
     /// Create a new exponential histogram.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         temporality: Temporality,
         filter: AttributeSetFilter,
@@ -406,13 +414,17 @@ impl<T: Number> ExpoHistogram<T> {
         record_min_max: bool,
         record_sum: bool,
         cardinality_limit: usize,
+        exemplar_sampler: ExemplarSampler,
     ) -> Self {
         ExpoHistogram {
             value_map: ValueMap::new(
-                BucketConfig {
-                    max_size: max_size as i32,
-                    max_scale,
-                },
+                (
+                    BucketConfig {
+                        max_size: max_size as i32,
+                        max_scale,
+                    },
+                    exemplar_sampler.reservoir(),
+                ),
                 cardinality_limit,
             ),
             init_time: AggregateTimeInitiator::default(),
@@ -420,6 +432,7 @@ impl<T: Number> ExpoHistogram<T> {
             filter,
             record_sum,
             record_min_max,
+            exemplar_sampler,
         }
     }
 
@@ -448,10 +461,10 @@ impl<T: Number> ExpoHistogram<T> {
         h.start_time = time.start;
         h.time = time.current;
 
-        let config = *self.value_map.config();
+        let (config, _) = *self.value_map.config();
         self.value_map
             .collect_and_reset(&mut h.data_points, |attributes, attr| {
-                let reset = attr.clone_and_reset(&config);
+                let reset = attr.aggregator.clone_and_reset(&config);
                 let b = reset.into_inner().unwrap_or_else(|err| err.into_inner());
                 data::ExponentialHistogramDataPoint {
                     attributes,
@@ -478,7 +491,7 @@ impl<T: Number> ExpoHistogram<T> {
                         counts: b.neg_buckets.counts,
                     },
                     zero_threshold: 0.0,
-                    exemplars: vec![],
+                    exemplars: attr.exemplars(true),
                 }
             });
 
@@ -512,7 +525,10 @@ impl<T: Number> ExpoHistogram<T> {
 
         self.value_map
             .collect_readonly(&mut h.data_points, |attributes, attr| {
-                let b = attr.lock().unwrap_or_else(|err| err.into_inner());
+                let b = attr
+                    .aggregator
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner());
                 data::ExponentialHistogramDataPoint {
                     attributes,
                     count: b.count,
@@ -538,7 +554,7 @@ impl<T: Number> ExpoHistogram<T> {
                         counts: b.neg_buckets.counts.clone(),
                     },
                     zero_threshold: 0.0,
-                    exemplars: vec![],
+                    exemplars: attr.exemplars(false),
                 }
             });
 
@@ -558,8 +574,11 @@ where
             return;
         }
 
+        let exemplar = self
+            .exemplar_sampler
+            .sample(measurement, 0, || self.filter.dropped(attrs));
         self.filter.apply(attrs, |filtered| {
-            self.value_map.measure(measurement, filtered);
+            self.value_map.measure((measurement, exemplar), filtered);
         })
     }
 
@@ -570,7 +589,11 @@ where
             bound_attrs = filtered.to_vec();
         });
         match self.value_map.bind(&bound_attrs) {
-            Some(tracker) => Box::new(BoundExpoHistogramHandle { tracker }),
+            Some(tracker) => Box::new(BoundExpoHistogramHandle {
+                tracker,
+                exemplar_sampler: self.exemplar_sampler.clone(),
+                dropped_attributes: self.filter.dropped(attrs),
+            }),
             None => Box::new(NoopBoundMeasure::new()),
         }
     }
@@ -596,7 +619,9 @@ mod tests {
     use std::{any::Any, ops::Neg};
     use tests::internal::AggregateFns;
 
+    use crate::metrics::internal::exemplar::ReservoirConfig;
     use crate::metrics::internal::{self, AggregateBuilder};
+    use crate::metrics::ExemplarFilter;
 
     use super::*;
 
@@ -767,11 +792,21 @@ mod tests {
                 true,
                 true,
                 CARDINALITY_LIMIT_DEFAULT,
+                ExemplarSampler::new(
+                    ExemplarFilter::AlwaysOff,
+                    ReservoirConfig::exponential_histogram(4),
+                ),
             );
             for v in test.values {
                 Measure::call(&h, v, &[]);
             }
-            let dp = h.value_map.no_attribute_tracker.aggregator.lock().unwrap();
+            let dp = h
+                .value_map
+                .no_attribute_tracker
+                .aggregator
+                .aggregator
+                .lock()
+                .unwrap();
 
             assert_eq!(test.expected.max, dp.max);
             assert_eq!(test.expected.min, dp.min);
@@ -824,11 +859,21 @@ mod tests {
                 true,
                 true,
                 CARDINALITY_LIMIT_DEFAULT,
+                ExemplarSampler::new(
+                    ExemplarFilter::AlwaysOff,
+                    ReservoirConfig::exponential_histogram(4),
+                ),
             );
             for v in test.values {
                 Measure::call(&h, v, &[]);
             }
-            let dp = h.value_map.no_attribute_tracker.aggregator.lock().unwrap();
+            let dp = h
+                .value_map
+                .no_attribute_tracker
+                .aggregator
+                .aggregator
+                .lock()
+                .unwrap();
 
             assert_eq!(test.expected.max, dp.max);
             assert_eq!(test.expected.min, dp.min);
@@ -1336,13 +1381,18 @@ mod tests {
             TestCase {
                 name: "Delta Single",
                 build: Box::new(move || {
-                    AggregateBuilder::new(Temporality::Delta, None, CARDINALITY_LIMIT_DEFAULT)
-                        .exponential_bucket_histogram(
-                            max_size,
-                            max_scale,
-                            record_min_max,
-                            record_sum,
-                        )
+                    AggregateBuilder::new(
+                        Temporality::Delta,
+                        None,
+                        CARDINALITY_LIMIT_DEFAULT,
+                        ExemplarFilter::AlwaysOff,
+                    )
+                    .exponential_bucket_histogram(
+                        max_size,
+                        max_scale,
+                        record_min_max,
+                        record_sum,
+                    )
                 }),
                 input: vec![vec![4, 4, 4, 2, 16, 1]
                     .into_iter()
@@ -1381,6 +1431,7 @@ mod tests {
                         Temporality::Cumulative,
                         None,
                         CARDINALITY_LIMIT_DEFAULT,
+                        ExemplarFilter::AlwaysOff,
                     )
                     .exponential_bucket_histogram(
                         max_size,
@@ -1426,6 +1477,7 @@ mod tests {
                         Temporality::Delta,
                         None,
                         CARDINALITY_LIMIT_DEFAULT,
+                        ExemplarFilter::AlwaysOff,
                     )
                     .exponential_bucket_histogram(
                         max_size,
@@ -1474,6 +1526,7 @@ mod tests {
                         Temporality::Cumulative,
                         None,
                         CARDINALITY_LIMIT_DEFAULT,
+                        ExemplarFilter::AlwaysOff,
                     )
                     .exponential_bucket_histogram(
                         max_size,
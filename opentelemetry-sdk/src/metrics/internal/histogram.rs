@@ -12,6 +12,7 @@ use crate::metrics::Temporality;
 use opentelemetry::KeyValue;
 
 use super::aggregate::{AggregateTimeInitiator, AttributeSetFilter};
+use super::exemplar::{ExemplarSampler, WithExemplars};
 use super::{Aggregator, ComputeAggregation, Measure, Number, ValueMap};
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 use super::{BoundMeasure, NoopBoundMeasure, TrackerEntry};
@@ -78,8 +79,10 @@ impl<T: Number> Buckets<T> {
 /// shared overflow tracker.
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 struct BoundHistogramHandle<T: Number> {
-    tracker: Arc<TrackerEntry<Mutex<Buckets<T>>>>,
+    tracker: Arc<TrackerEntry<WithExemplars<Mutex<Buckets<T>>, T>>>,
     bounds: Vec<f64>,
+    exemplar_sampler: ExemplarSampler,
+    dropped_attributes: Vec<KeyValue>,
 }
 
 #[cfg(feature = "experimental_metrics_bound_instruments")]
@@ -87,7 +90,12 @@ impl<T: Number> BoundMeasure<T> for BoundHistogramHandle<T> {
     fn call(&self, measurement: T) {
         let f = measurement.into_float();
         let index = self.bounds.partition_point(|&x| x < f);
-        self.tracker.aggregator.update((measurement, index));
+        let exemplar = self
+            .exemplar_sampler
+            .sample(measurement, index, || self.dropped_attributes.clone());
+        self.tracker
+            .aggregator
+            .update(((measurement, index), exemplar));
         self.tracker.has_been_updated.store(true, Ordering::Release);
     }
 }
@@ -102,13 +110,14 @@ impl<T: Number> Drop for BoundHistogramHandle<T> {
 /// Summarizes a set of measurements as a histogram with explicitly defined
 /// buckets.
 pub(crate) struct Histogram<T: Number> {
-    value_map: ValueMap<Mutex<Buckets<T>>>,
+    value_map: ValueMap<WithExemplars<Mutex<Buckets<T>>, T>>,
     init_time: AggregateTimeInitiator,
     temporality: Temporality,
     filter: AttributeSetFilter,
     bounds: Vec<f64>,
     record_min_max: bool,
     record_sum: bool,
+    exemplar_sampler: ExemplarSampler,
 }
 
 impl<T: Number> Histogram<T> {
@@ -119,6 +128,7 @@ impl<T: Number> Histogram<T> {
         record_min_max: bool,
         record_sum: bool,
         cardinality_limit: usize,
+        exemplar_sampler: ExemplarSampler,
     ) -> Self {
         let buckets_count = if bounds.is_empty() {
             0
@@ -127,13 +137,17 @@ impl<T: Number> Histogram<T> {
         };
 
         Histogram {
-            value_map: ValueMap::new(buckets_count, cardinality_limit),
+            value_map: ValueMap::new(
+                (buckets_count, exemplar_sampler.reservoir()),
+                cardinality_limit,
+            ),
             init_time: AggregateTimeInitiator::default(),
             temporality,
             filter,
             bounds,
             record_min_max,
             record_sum,
+            exemplar_sampler,
         }
     }
 
@@ -162,10 +176,10 @@ impl<T: Number> Histogram<T> {
         h.start_time = time.start;
         h.time = time.current;
 
-        let buckets_count = *self.value_map.config();
+        let (buckets_count, _) = *self.value_map.config();
         self.value_map
             .collect_and_reset(&mut h.data_points, |attributes, aggr| {
-                let reset = aggr.clone_and_reset(&buckets_count);
+                let reset = aggr.aggregator.clone_and_reset(&buckets_count);
                 let b = reset.into_inner().unwrap_or_else(|err| err.into_inner());
                 HistogramDataPoint {
                     attributes,
@@ -187,7 +201,7 @@ impl<T: Number> Histogram<T> {
                     } else {
                         None
                     },
-                    exemplars: vec![],
+                    exemplars: aggr.exemplars(true),
                 }
             });
 
@@ -220,7 +234,10 @@ impl<T: Number> Histogram<T> {
 
         self.value_map
             .collect_readonly(&mut h.data_points, |attributes, aggr| {
-                let b = aggr.lock().unwrap_or_else(|err| err.into_inner());
+                let b = aggr
+                    .aggregator
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner());
                 HistogramDataPoint {
                     attributes,
                     count: b.count,
@@ -241,7 +258,7 @@ impl<T: Number> Histogram<T> {
                     } else {
                         None
                     },
-                    exemplars: vec![],
+                    exemplars: aggr.exemplars(false),
                 }
             });
 
@@ -261,9 +278,13 @@ where
         // is `bounds.len()+1`, with the last bucket representing:
         // `(bounds[bounds.len()-1], +∞)`.
         let index = self.bounds.partition_point(|&x| x < f);
+        let exemplar = self
+            .exemplar_sampler
+            .sample(measurement, index, || self.filter.dropped(attrs));
 
         self.filter.apply(attrs, |filtered| {
-            self.value_map.measure((measurement, index), filtered);
+            self.value_map
+                .measure(((measurement, index), exemplar), filtered);
         })
     }
 
@@ -277,6 +298,8 @@ where
             Some(tracker) => Box::new(BoundHistogramHandle {
                 tracker,
                 bounds: self.bounds.clone(),
+                exemplar_sampler: self.exemplar_sampler.clone(),
+                dropped_attributes: self.filter.dropped(attrs),
             }),
             // Trackers RwLock is poisoned — return a noop handle so writes
             // silently drop, mirroring `measure()`'s own poison handling.
@@ -302,6 +325,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::metrics::internal::exemplar::ReservoirConfig;
+    use crate::metrics::ExemplarFilter;
 
     #[test]
     fn check_buckets_are_selected_correctly() {
@@ -312,6 +337,10 @@ mod tests {
             false,
             false,
             2000,
+            ExemplarSampler::new(
+                ExemplarFilter::AlwaysOff,
+                ReservoirConfig::AlignedHistogramBucket { buckets: 4 },
+            ),
         );
         for v in 1..11 {
             Measure::call(&hist, v, &[]);
@@ -10,6 +10,7 @@ use std::sync::Arc;
 
 use super::{
     aggregate::{AggregateTimeInitiator, AttributeSetFilter},
+    exemplar::{ExemplarSampler, WithExemplars},
     Aggregator, AtomicTracker, AtomicallyUpdate, ComputeAggregation, Measure, Number, ValueMap,
 };
 #[cfg(feature = "experimental_metrics_bound_instruments")]
@@ -21,13 +22,18 @@ use super::{BoundMeasure, NoopBoundMeasure, TrackerEntry};
 /// cardinality limit — the shared overflow tracker.
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 struct BoundLastValueHandle<T: Number> {
-    tracker: Arc<TrackerEntry<Assign<T>>>,
+    tracker: Arc<TrackerEntry<WithExemplars<Assign<T>, T>>>,
+    exemplar_sampler: ExemplarSampler,
+    dropped_attributes: Vec<KeyValue>,
 }
 
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 impl<T: Number> BoundMeasure<T> for BoundLastValueHandle<T> {
     fn call(&self, measurement: T) {
-        self.tracker.aggregator.update(measurement);
+        let exemplar = self
+            .exemplar_sampler
+            .sample(measurement, 0, || self.dropped_attributes.clone());
+        self.tracker.aggregator.update((measurement, exemplar));
         self.tracker.has_been_updated.store(true, Ordering::Release);
     }
 }
@@ -73,10 +79,11 @@ where
 
 /// Summarizes a set of measurements as the last one made.
 pub(crate) struct LastValue<T: Number> {
-    value_map: ValueMap<Assign<T>>,
+    value_map: ValueMap<WithExemplars<Assign<T>, T>>,
     init_time: AggregateTimeInitiator,
     temporality: Temporality,
     filter: AttributeSetFilter,
+    exemplar_sampler: ExemplarSampler,
 }
 
 impl<T: Number> LastValue<T> {
@@ -84,12 +91,14 @@ impl<T: Number> LastValue<T> {
         temporality: Temporality,
         filter: AttributeSetFilter,
         cardinality_limit: usize,
+        exemplar_sampler: ExemplarSampler,
     ) -> Self {
         LastValue {
-            value_map: ValueMap::new((), cardinality_limit),
+            value_map: ValueMap::new(((), exemplar_sampler.reservoir()), cardinality_limit),
             init_time: AggregateTimeInitiator::default(),
             temporality,
             filter,
+            exemplar_sampler,
         }
     }
 
@@ -119,8 +128,8 @@ impl<T: Number> LastValue<T> {
         self.value_map
             .collect_and_reset(&mut s_data.data_points, |attributes, aggr| GaugeDataPoint {
                 attributes,
-                value: aggr.value.get_and_reset_value(),
-                exemplars: vec![],
+                value: aggr.aggregator.value.get_and_reset_value(),
+                exemplars: aggr.exemplars(true),
             });
 
         (s_data.data_points.len(), new_agg.map(Into::into))
@@ -155,8 +164,8 @@ impl<T: Number> LastValue<T> {
         self.value_map
             .collect_readonly(&mut s_data.data_points, |attributes, aggr| GaugeDataPoint {
                 attributes,
-                value: aggr.value.get_value(),
-                exemplars: vec![],
+                value: aggr.aggregator.value.get_value(),
+                exemplars: aggr.exemplars(false),
             });
 
         (s_data.data_points.len(), new_agg.map(Into::into))
@@ -168,8 +177,11 @@ where
     T: Number,
 {
     fn call(&self, measurement: T, attrs: &[KeyValue]) {
+        let exemplar = self
+            .exemplar_sampler
+            .sample(measurement, 0, || self.filter.dropped(attrs));
         self.filter.apply(attrs, |filtered| {
-            self.value_map.measure(measurement, filtered);
+            self.value_map.measure((measurement, exemplar), filtered);
         })
     }
 
@@ -180,7 +192,11 @@ where
             bound_attrs = filtered.to_vec();
         });
         match self.value_map.bind(&bound_attrs) {
-            Some(tracker) => Box::new(BoundLastValueHandle { tracker }),
+            Some(tracker) => Box::new(BoundLastValueHandle {
+                tracker,
+                exemplar_sampler: self.exemplar_sampler.clone(),
+                dropped_attributes: self.filter.dropped(attrs),
+            }),
             None => Box::new(NoopBoundMeasure::new()),
         }
     }
@@ -204,6 +220,8 @@ where
 mod tests {
     use super::*;
     use crate::metrics::data::{AggregatedMetrics, Gauge, MetricData};
+    use crate::metrics::internal::exemplar::ReservoirConfig;
+    use crate::metrics::ExemplarFilter;
 
     fn extract_gauge(agg: AggregatedMetrics) -> Gauge<u64> {
         match agg {
@@ -219,8 +237,15 @@ mod tests {
     /// the `Measure` / `BoundMeasure` traits to keep the impl honest.
     #[test]
     fn bind_writes_through_bound_handle() {
-        let last_value =
-            LastValue::<u64>::new(Temporality::Cumulative, AttributeSetFilter::new(None), 100);
+        let last_value = LastValue::<u64>::new(
+            Temporality::Cumulative,
+            AttributeSetFilter::new(None),
+            100,
+            ExemplarSampler::new(
+                ExemplarFilter::AlwaysOff,
+                ReservoirConfig::simple_fixed_size(),
+            ),
+        );
         let attrs = [KeyValue::new("k", "v")];
         let bound = Measure::bind(&last_value, &attrs);
 
@@ -237,8 +262,15 @@ mod tests {
 
     #[test]
     fn bound_handle_drop_decrements_bound_count() {
-        let last_value =
-            LastValue::<u64>::new(Temporality::Delta, AttributeSetFilter::new(None), 100);
+        let last_value = LastValue::<u64>::new(
+            Temporality::Delta,
+            AttributeSetFilter::new(None),
+            100,
+            ExemplarSampler::new(
+                ExemplarFilter::AlwaysOff,
+                ReservoirConfig::simple_fixed_size(),
+            ),
+        );
         let attrs = [KeyValue::new("k", "v")];
 
         let bound = Measure::bind(&last_value, &attrs);
@@ -1,4 +1,5 @@
 mod aggregate;
+mod exemplar;
 mod exponential_histogram;
 mod histogram;
 mod last_value;
@@ -477,6 +478,13 @@ pub(crate) trait Number:
     fn max() -> Self;
 
     fn into_float(self) -> f64;
+
+    /// Adds `other`, wrapping around at the bounds of integers like the
+    /// atomic aggregations do.
+    fn wrapping_add(self, other: Self) -> Self;
+
+    /// Subtracts `other`, wrapping around at the bounds of integers.
+    fn wrapping_sub(self, other: Self) -> Self;
 }
 
 impl Number for i64 {
@@ -492,6 +500,14 @@ impl Number for i64 {
         // May have precision loss at high values
         self as f64
     }
+
+    fn wrapping_add(self, other: Self) -> Self {
+        i64::wrapping_add(self, other)
+    }
+
+    fn wrapping_sub(self, other: Self) -> Self {
+        i64::wrapping_sub(self, other)
+    }
 }
 impl Number for u64 {
     fn min() -> Self {
@@ -506,6 +522,14 @@ impl Number for u64 {
         // May have precision loss at high values
         self as f64
     }
+
+    fn wrapping_add(self, other: Self) -> Self {
+        u64::wrapping_add(self, other)
+    }
+
+    fn wrapping_sub(self, other: Self) -> Self {
+        u64::wrapping_sub(self, other)
+    }
 }
 impl Number for f64 {
     fn min() -> Self {
@@ -519,6 +543,14 @@ impl Number for f64 {
     fn into_float(self) -> f64 {
         self
     }
+
+    fn wrapping_add(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn wrapping_sub(self, other: Self) -> Self {
+        self - other
+    }
 }
 
 impl AggregatedMetricsAccess for i64 {
@@ -7,6 +7,7 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use super::aggregate::{AggregateTimeInitiator, AttributeSetFilter};
+use super::exemplar::{ExemplarSampler, WithExemplars};
 use super::{Aggregator, AtomicTracker, ComputeAggregation, Measure, Number};
 use super::{AtomicallyUpdate, ValueMap};
 #[cfg(feature = "experimental_metrics_bound_instruments")]
@@ -50,13 +51,18 @@ where
 /// release store; no map lookup, no lock acquisition.
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 struct BoundSumHandle<T: Number> {
-    tracker: Arc<TrackerEntry<Increment<T>>>,
+    tracker: Arc<TrackerEntry<WithExemplars<Increment<T>, T>>>,
+    exemplar_sampler: ExemplarSampler,
+    dropped_attributes: Vec<KeyValue>,
 }
 
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 impl<T: Number> BoundMeasure<T> for BoundSumHandle<T> {
     fn call(&self, measurement: T) {
-        self.tracker.aggregator.update(measurement);
+        let exemplar = self
+            .exemplar_sampler
+            .sample(measurement, 0, || self.dropped_attributes.clone());
+        self.tracker.aggregator.update((measurement, exemplar));
         self.tracker.has_been_updated.store(true, Ordering::Release);
     }
 }
@@ -70,11 +76,12 @@ impl<T: Number> Drop for BoundSumHandle<T> {
 
 /// Summarizes a set of measurements made as their arithmetic sum.
 pub(crate) struct Sum<T: Number> {
-    value_map: ValueMap<Increment<T>>,
+    value_map: ValueMap<WithExemplars<Increment<T>, T>>,
     init_time: AggregateTimeInitiator,
     temporality: Temporality,
     filter: AttributeSetFilter,
     monotonic: bool,
+    exemplar_sampler: ExemplarSampler,
 }
 
 impl<T: Number> Sum<T> {
@@ -88,13 +95,15 @@ impl<T: Number> Sum<T> {
         filter: AttributeSetFilter,
         monotonic: bool,
         cardinality_limit: usize,
+        exemplar_sampler: ExemplarSampler,
     ) -> Self {
         Sum {
-            value_map: ValueMap::new((), cardinality_limit),
+            value_map: ValueMap::new(((), exemplar_sampler.reservoir()), cardinality_limit),
             init_time: AggregateTimeInitiator::default(),
             temporality,
             filter,
             monotonic,
+            exemplar_sampler,
         }
     }
 
@@ -127,8 +136,8 @@ impl<T: Number> Sum<T> {
         self.value_map
             .collect_and_reset(&mut s_data.data_points, |attributes, aggr| SumDataPoint {
                 attributes,
-                value: aggr.value.get_and_reset_value(),
-                exemplars: vec![],
+                value: aggr.aggregator.value.get_and_reset_value(),
+                exemplars: aggr.exemplars(true),
             });
 
         (s_data.data_points.len(), new_agg.map(Into::into))
@@ -167,8 +176,8 @@ impl<T: Number> Sum<T> {
         self.value_map
             .collect_readonly(&mut s_data.data_points, |attributes, aggr| SumDataPoint {
                 attributes,
-                value: aggr.value.get_value(),
-                exemplars: vec![],
+                value: aggr.aggregator.value.get_value(),
+                exemplars: aggr.exemplars(false),
             });
 
         (s_data.data_points.len(), new_agg.map(Into::into))
@@ -180,8 +189,11 @@ where
     T: Number,
 {
     fn call(&self, measurement: T, attrs: &[KeyValue]) {
+        let exemplar = self
+            .exemplar_sampler
+            .sample(measurement, 0, || self.filter.dropped(attrs));
         self.filter.apply(attrs, |filtered| {
-            self.value_map.measure(measurement, filtered);
+            self.value_map.measure((measurement, exemplar), filtered);
         })
     }
 
@@ -192,7 +204,11 @@ where
             bound_attrs = filtered.to_vec();
         });
         match self.value_map.bind(&bound_attrs) {
-            Some(tracker) => Box::new(BoundSumHandle { tracker }),
+            Some(tracker) => Box::new(BoundSumHandle {
+                tracker,
+                exemplar_sampler: self.exemplar_sampler.clone(),
+                dropped_attributes: self.filter.dropped(attrs),
+            }),
             // Trackers RwLock is poisoned — return a noop handle so writes
             // silently drop, mirroring `measure()`'s own poison handling.
             None => Box::new(NoopBoundMeasure::new()),
@@ -1,12 +1,15 @@
 #![allow(dead_code)]
 use core::fmt;
-use std::{borrow::Cow, sync::Arc};
+use std::{
+    borrow::Cow,
+    sync::{Arc, Weak},
+};
 
 use opentelemetry::{
     metrics::{
-        AsyncInstrumentBuilder, Counter, Gauge, Histogram, HistogramBuilder, InstrumentBuilder,
-        InstrumentProvider, ObservableCounter, ObservableGauge, ObservableUpDownCounter,
-        UpDownCounter,
+        AsyncInstrumentBuilder, CallbackRegistration, Counter, Gauge, Histogram, HistogramBuilder,
+        InstrumentBuilder, InstrumentProvider, ObservableCounter, ObservableGauge,
+        ObservableUpDownCounter, Registration, UpDownCounter,
     },
     otel_error, InstrumentationScope,
 };
@@ -15,7 +18,7 @@ use crate::metrics::{
     error::{MetricError, MetricResult},
     instrument::{Instrument, InstrumentKind, Observable, ResolvedMeasures},
     internal::{self, Number},
-    pipeline::{Pipelines, Resolver},
+    pipeline::{GenericCallback, Pipelines, Resolver},
 };
 
 use super::noop::NoopSyncInstrument;
@@ -158,7 +161,7 @@ impl SdkMeter {
                         .register_callback(move || callback(cb_inst.as_ref()));
                 }
 
-                ObservableCounter::new()
+                ObservableCounter::with_instrument(observable)
             }
             Err(err) => {
                 otel_error!(
@@ -217,7 +220,7 @@ impl SdkMeter {
                         .register_callback(move || callback(cb_inst.as_ref()));
                 }
 
-                ObservableUpDownCounter::new()
+                ObservableUpDownCounter::with_instrument(observable)
             }
             Err(err) => {
                 otel_error!(
@@ -276,7 +279,7 @@ impl SdkMeter {
                         .register_callback(move || callback(cb_inst.as_ref()));
                 }
 
-                ObservableGauge::new()
+                ObservableGauge::with_instrument(observable)
             }
             Err(err) => {
                 otel_error!(
@@ -438,8 +441,30 @@ impl SdkMeter {
     }
 }
 
+/// A callback registered with [opentelemetry::metrics::Meter::register_callback].
+struct PipelinesRegistration {
+    pipes: Weak<Pipelines>,
+    callback: GenericCallback,
+}
+
+impl CallbackRegistration for PipelinesRegistration {
+    fn unregister(&self) {
+        if let Some(pipes) = self.pipes.upgrade() {
+            pipes.unregister_callback(&self.callback);
+        }
+    }
+}
+
 #[doc(hidden)]
 impl InstrumentProvider for SdkMeter {
+    fn register_callback(&self, callback: Box<dyn Fn() + Send + Sync>) -> Registration {
+        let callback = self.pipes.register_callback(callback);
+        Registration::new(PipelinesRegistration {
+            pipes: Arc::downgrade(&self.pipes),
+            callback,
+        })
+    }
+
     fn u64_counter(&self, builder: InstrumentBuilder<'_, Counter<u64>>) -> Counter<u64> {
         let resolver = InstrumentResolver::new(self, &self.u64_resolver);
         self.create_counter(builder, &resolver)
@@ -0,0 +1,68 @@
+use crate::scope_config::ScopeConfigurator;
+use opentelemetry::InstrumentationScope;
+use std::fmt;
+
+/// The configuration of the meters of an instrumentation scope, returned by
+/// a [`MeterConfigurator`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MeterConfig {
+    /// Whether the meters record measurements. A disabled meter creates no-op
+    /// instruments, which are not exported.
+    pub enabled: bool,
+}
+
+impl Default for MeterConfig {
+    /// Create an enabled meter config.
+    fn default() -> Self {
+        MeterConfig { enabled: true }
+    }
+}
+
+impl MeterConfig {
+    /// Create a disabled meter config.
+    pub fn disabled() -> Self {
+        MeterConfig { enabled: false }
+    }
+
+    /// Set whether the meters record measurements.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+/// Computes the [`MeterConfig`] of the meters of each instrumentation scope.
+///
+/// It is called every time a meter is obtained from a provider that is not
+/// shut down, without caching the result, so it should be cheap, see
+/// [`MeterProviderBuilder::with_meter_configurator`]. It is implemented by
+/// [`ScopeConfigurator<MeterConfig>`] and by closures taking an
+/// [`InstrumentationScope`] and returning a [`MeterConfig`].
+///
+/// [`MeterProviderBuilder::with_meter_configurator`]: crate::metrics::MeterProviderBuilder::with_meter_configurator
+pub trait MeterConfigurator: Send + Sync {
+    /// Returns the config of the meters of `scope`.
+    fn meter_config(&self, scope: &InstrumentationScope) -> MeterConfig;
+}
+
+impl<T> MeterConfigurator for T
+where
+    T: Fn(&InstrumentationScope) -> MeterConfig + Send + Sync,
+{
+    fn meter_config(&self, scope: &InstrumentationScope) -> MeterConfig {
+        self(scope)
+    }
+}
+
+impl MeterConfigurator for ScopeConfigurator<MeterConfig> {
+    fn meter_config(&self, scope: &InstrumentationScope) -> MeterConfig {
+        self.config(scope)
+    }
+}
+
+impl fmt::Debug for dyn MeterConfigurator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MeterConfigurator")
+    }
+}
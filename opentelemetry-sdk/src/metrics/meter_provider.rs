@@ -16,9 +16,9 @@ use crate::error::OTelSdkResult;
 use crate::Resource;
 
 use super::{
-    exporter::PushMetricExporter, meter::SdkMeter, noop::NoopMeter,
-    periodic_reader::PeriodicReader, pipeline::Pipelines, reader::MetricReader, view::View,
-    Instrument, Stream,
+    exemplar::ExemplarFilter, exporter::PushMetricExporter, meter::SdkMeter, noop::NoopMeter,
+    periodic_reader::PeriodicReader, pipeline::Pipelines, reader::MetricReader, view::ViewMatcher,
+    Instrument, MeterConfig, MeterConfigurator, Stream, View,
 };
 
 /// Handles the creation and coordination of [Meter]s.
@@ -41,6 +41,7 @@ struct SdkMeterProviderInner {
     pipes: Arc<Pipelines>,
     meters: Mutex<HashMap<InstrumentationScope, Arc<SdkMeter>>>,
     shutdown_invoked: AtomicBool,
+    meter_configurator: Option<Box<dyn MeterConfigurator>>,
 }
 
 impl Default for SdkMeterProvider {
@@ -201,6 +202,21 @@ impl MeterProvider for SdkMeterProvider {
             otel_info!(name: "MeterNameEmpty", message = "Meter name is empty; consider providing a meaningful name. Meter will function normally and the provided name will be used as-is.");
         };
 
+        let config = self
+            .inner
+            .meter_configurator
+            .as_ref()
+            .map_or_else(MeterConfig::default, |configurator| {
+                configurator.meter_config(&scope)
+            });
+        if !config.enabled {
+            otel_debug!(
+                name: "MeterProvider.DisabledMeterReturned",
+                meter_name = scope.name(),
+            );
+            return Meter::new(Arc::new(NoopMeter::new()));
+        }
+
         if let Ok(mut meters) = self.inner.meters.lock() {
             if let Some(existing_meter) = meters.get(&scope) {
                 otel_debug!(
@@ -232,7 +248,9 @@ impl MeterProvider for SdkMeterProvider {
 pub struct MeterProviderBuilder {
     resource: Option<Resource>,
     readers: Vec<Box<dyn MetricReader>>,
-    views: Vec<Arc<dyn View>>,
+    views: Vec<Arc<dyn ViewMatcher>>,
+    exemplar_filter: Option<ExemplarFilter>,
+    meter_configurator: Option<Box<dyn MeterConfigurator>>,
 }
 
 impl MeterProviderBuilder {
@@ -394,6 +412,95 @@ impl MeterProviderBuilder {
         self
     }
 
+    /// Adds a declarative [View] to the [MeterProvider].
+    ///
+    /// This behaves like [`MeterProviderBuilder::with_view`], but takes a
+    /// [View] built from an [`InstrumentSelector`] and a [`Stream`] rather than a
+    /// closure, so views can be created from configuration data.
+    ///
+    /// Views are applied in the order they are added, regardless of which
+    /// method was used to add them. If several views produce identical or
+    /// conflicting streams for an instrument, a warning is logged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use opentelemetry_sdk::metrics::{InstrumentSelector, SdkMeterProvider, Stream, View};
+    ///
+    /// let view = View::new(
+    ///     InstrumentSelector::new().with_name("my_counter"),
+    ///     Stream::builder().with_name("my_counter_renamed").build().unwrap(),
+    /// )
+    /// .unwrap();
+    ///
+    /// let provider = SdkMeterProvider::builder()
+    ///     .with_declarative_view(view)
+    ///     .build();
+    /// # drop(provider)
+    /// ```
+    ///
+    /// [`InstrumentSelector`]: crate::metrics::InstrumentSelector
+    /// [`Stream`]: crate::metrics::Stream
+    pub fn with_declarative_view(mut self, view: View) -> Self {
+        self.views.push(Arc::new(view));
+        self
+    }
+
+    /// Sets the [ExemplarFilter] used to select which measurements are
+    /// sampled as exemplars.
+    ///
+    /// This option overrides any value set for the
+    /// `OTEL_METRICS_EXEMPLAR_FILTER` environment variable. If neither is set,
+    /// [ExemplarFilter::TraceBased] is used, so only measurements recorded
+    /// while a sampled span is active become exemplars.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use opentelemetry_sdk::metrics::{ExemplarFilter, SdkMeterProvider};
+    ///
+    /// let provider = SdkMeterProvider::builder()
+    ///     .with_exemplar_filter(ExemplarFilter::AlwaysOff)
+    ///     .build();
+    /// ```
+    pub fn with_exemplar_filter(mut self, filter: ExemplarFilter) -> Self {
+        self.exemplar_filter = Some(filter);
+        self
+    }
+
+    /// Set the [MeterConfigurator] computing the [MeterConfig] of the meters
+    /// of each instrumentation scope, e.g. to disable the meters of some
+    /// libraries.
+    ///
+    /// Disabled meters create no-op instruments, which report they are not
+    /// enabled and whose callbacks are never invoked. By default every meter
+    /// is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use opentelemetry::metrics::MeterProvider;
+    /// use opentelemetry_sdk::metrics::{MeterConfig, SdkMeterProvider};
+    /// use opentelemetry_sdk::scope_config::{ScopeConfigurator, ScopeSelector};
+    ///
+    /// let provider = SdkMeterProvider::builder()
+    ///     .with_meter_configurator(
+    ///         ScopeConfigurator::new(MeterConfig::default())
+    ///             .with_rule(ScopeSelector::new().with_name("noisy-*"), MeterConfig::disabled()),
+    ///     )
+    ///     .build();
+    ///
+    /// let counter = provider.meter("noisy-library").u64_counter("requests").build();
+    /// assert!(!counter.enabled());
+    /// ```
+    pub fn with_meter_configurator<T: MeterConfigurator + 'static>(
+        mut self,
+        configurator: T,
+    ) -> Self {
+        self.meter_configurator = Some(Box::new(configurator));
+        self
+    }
+
     /// Construct a new [MeterProvider] with this configuration.
     pub fn build(self) -> SdkMeterProvider {
         otel_debug!(
@@ -407,9 +514,12 @@ impl MeterProviderBuilder {
                     self.resource.unwrap_or(Resource::builder().build()),
                     self.readers,
                     self.views,
+                    self.exemplar_filter
+                        .unwrap_or_else(ExemplarFilter::from_env),
                 )),
                 meters: Default::default(),
                 shutdown_invoked: AtomicBool::new(false),
+                meter_configurator: self.meter_configurator,
             }),
         };
 
@@ -426,6 +536,8 @@ impl fmt::Debug for MeterProviderBuilder {
             .field("resource", &self.resource)
             .field("readers", &self.readers)
             .field("views", &self.views.len())
+            .field("exemplar_filter", &self.exemplar_filter)
+            .field("meter_configurator", &self.meter_configurator)
             .finish()
     }
 }
@@ -778,4 +890,38 @@ mod tests {
         );
         assert_eq!(resource.schema_url(), Some("http://example.com"));
     }
+
+    #[test]
+    fn meter_configurator_disables_scopes() {
+        use crate::metrics::{InMemoryMetricExporter, MeterConfig};
+
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(exporter.clone())
+            .with_meter_configurator(|scope: &InstrumentationScope| {
+                MeterConfig::default().with_enabled(scope.version() != Some("0.1.0"))
+            })
+            .build();
+        let meter = provider.meter("app");
+        let disabled_meter = provider.meter_with_scope(
+            InstrumentationScope::builder("noisy-library")
+                .with_version("0.1.0")
+                .build(),
+        );
+
+        let counter = meter.u64_counter("requests").build();
+        let disabled_counter = disabled_meter.u64_counter("requests").build();
+        assert!(counter.enabled());
+        assert!(!disabled_counter.enabled());
+        counter.add(1, &[]);
+        disabled_counter.add(1, &[]);
+        provider.force_flush().unwrap();
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let scopes: Vec<_> = metrics[0]
+            .scope_metrics()
+            .map(|scope_metrics| scope_metrics.scope().name())
+            .collect();
+        assert_eq!(scopes, ["app"]);
+    }
 }
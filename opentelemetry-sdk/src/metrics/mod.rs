@@ -44,12 +44,14 @@
 pub(crate) mod aggregation;
 pub mod data;
 mod error;
+mod exemplar;
 pub mod exporter;
 pub(crate) mod instrument;
 pub(crate) mod internal;
 #[cfg(feature = "experimental_metrics_custom_reader")]
 pub(crate) mod manual_reader;
 pub(crate) mod meter;
+mod meter_config;
 mod meter_provider;
 pub(crate) mod noop;
 pub(crate) mod periodic_reader;
@@ -57,11 +59,13 @@ pub(crate) mod periodic_reader;
 /// Module for periodic reader with async runtime.
 pub mod periodic_reader_with_async_runtime;
 pub(crate) mod pipeline;
+mod producer;
 #[cfg(feature = "experimental_metrics_custom_reader")]
 pub mod reader;
 #[cfg(not(feature = "experimental_metrics_custom_reader"))]
 pub(crate) mod reader;
-pub(crate) mod view;
+mod temporality_conversion;
+mod view;
 
 /// In-Memory metric exporter for testing purpose.
 #[cfg(any(feature = "testing", test))]
@@ -72,14 +76,21 @@ pub mod in_memory_exporter;
 pub use in_memory_exporter::{InMemoryMetricExporter, InMemoryMetricExporterBuilder};
 
 pub use aggregation::*;
+pub use exemplar::ExemplarFilter;
 #[cfg(feature = "experimental_metrics_custom_reader")]
 pub use manual_reader::*;
+pub use meter_config::{MeterConfig, MeterConfigurator};
 pub use meter_provider::*;
 pub use periodic_reader::*;
 #[cfg(feature = "experimental_metrics_custom_reader")]
 pub use pipeline::Pipeline;
+pub use producer::MetricProducer;
+pub use temporality_conversion::{
+    TemporalityConverter, TemporalityConverterBuilder, TemporalityConvertingExporter,
+};
 
 pub use instrument::{Instrument, InstrumentKind, Stream, StreamBuilder};
+pub use view::{InstrumentSelector, View};
 
 use std::hash::Hash;
 use std::str::FromStr;
@@ -657,6 +668,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn batch_callback_observes_registered_instruments() {
+        // Arrange
+        let test_context = TestContext::new(Temporality::Delta);
+        let meter = test_context.meter();
+        let gauge = meter.u64_observable_gauge("my_gauge").build();
+        let counter = meter.f64_observable_counter("my_counter").build();
+        let unregistered = meter.i64_observable_up_down_counter("my_updown").build();
+
+        // Act
+        let registration = meter.register_callback(&[&gauge, &counter], {
+            let (gauge, counter) = (gauge.clone(), counter.clone());
+            move |observer| {
+                observer.observe(&gauge, 5, &[KeyValue::new("key1", "value1")]);
+                observer.observe(&counter, 2.5, &[]);
+                observer.observe(&unregistered, 7, &[]);
+            }
+        });
+        test_context.flush_metrics();
+
+        // Assert
+        let resource_metrics = test_context.exporter.get_finished_metrics().unwrap();
+        let metrics = &resource_metrics[0].scope_metrics[0].metrics;
+        assert_eq!(
+            metrics.len(),
+            2,
+            "only the registered instruments are observed"
+        );
+        let gauge = metrics.iter().find(|m| m.name == "my_gauge").unwrap();
+        let Some(MetricData::Gauge(gauge)) = u64::extract_metrics_data_ref(&gauge.data) else {
+            unreachable!()
+        };
+        let data_point = find_gauge_datapoint_with_key_value(&gauge.data_points, "key1", "value1")
+            .expect("datapoint with key1=value1 expected");
+        assert_eq!(data_point.value, 5);
+        let counter = metrics.iter().find(|m| m.name == "my_counter").unwrap();
+        let Some(MetricData::Sum(sum)) = f64::extract_metrics_data_ref(&counter.data) else {
+            unreachable!()
+        };
+        assert_eq!(sum.data_points[0].value, 2.5);
+
+        // Unregistered callbacks are not invoked anymore.
+        registration.unregister();
+        test_context.reset_metrics();
+        test_context.flush_metrics();
+        test_context.check_no_metrics();
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn observable_counter_delta_attribute_set_reappears_after_gap() {
         // Run this test with stdout enabled to see output.
@@ -1102,6 +1161,92 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn declarative_view_selects_instruments() {
+        // Run this test with stdout enabled to see output.
+        // cargo test declarative_view_selects_instruments --features=testing -- --nocapture
+
+        // Arrange
+        let exporter = InMemoryMetricExporter::default();
+        let rename = View::new(
+            InstrumentSelector::new()
+                .with_name("my_counter")
+                .with_meter_name("test"),
+            Stream::builder()
+                .with_name("my_counter_renamed")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let drop_histograms = View::new(
+            InstrumentSelector::new()
+                .with_name("http.*")
+                .with_kind(InstrumentKind::Histogram),
+            Stream::builder()
+                .with_aggregation(Aggregation::Drop)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(exporter.clone())
+            .with_declarative_view(rename)
+            .with_declarative_view(drop_histograms)
+            .build();
+
+        // Act
+        let meter = meter_provider.meter("test");
+        let counter = meter.u64_counter("my_counter").build();
+        let histogram = meter.f64_histogram("http.server.duration").build();
+        counter.add(1, &[]);
+        histogram.record(1.5, &[]);
+        meter_provider.force_flush().unwrap();
+
+        // Assert
+        let resource_metrics = exporter
+            .get_finished_metrics()
+            .expect("metrics are expected to be exported.");
+        let metrics = &resource_metrics[0].scope_metrics[0].metrics;
+        assert_eq!(metrics.len(), 1, "histogram should be dropped by the view");
+        assert_eq!(metrics[0].name, "my_counter_renamed");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn duplicate_declarative_views_produce_single_stream() {
+        // Run this test with stdout enabled to see output.
+        // cargo test duplicate_declarative_views_produce_single_stream --features=testing -- --nocapture
+
+        // Arrange
+        let exporter = InMemoryMetricExporter::default();
+        let view = View::new(
+            InstrumentSelector::new().with_kind(InstrumentKind::Counter),
+            Stream::builder()
+                .with_cardinality_limit(10)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(exporter.clone())
+            .with_declarative_view(view.clone())
+            .with_declarative_view(view)
+            .build();
+
+        // Act
+        let meter = meter_provider.meter("test");
+        let counter = meter.u64_counter("my_counter").build();
+        counter.add(5, &[]);
+        meter_provider.force_flush().unwrap();
+
+        // Assert
+        let resource_metrics = exporter
+            .get_finished_metrics()
+            .expect("metrics are expected to be exported.");
+        let metrics = &resource_metrics[0].scope_metrics[0].metrics;
+        assert_eq!(metrics.len(), 1, "duplicate view should be ignored");
+        assert_eq!(metrics[0].name, "my_counter");
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn counter_with_lastvalue_aggregation_uses_default() {
         // LastValue aggregation is only valid for Gauge instruments.
@@ -1973,6 +2118,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sync_instruments_disabled_when_all_streams_dropped() {
+        // Arrange
+        let view = |i: &Instrument| {
+            if i.name.starts_with("dropped") {
+                Stream::builder()
+                    .with_aggregation(aggregation::Aggregation::Drop)
+                    .build()
+                    .ok()
+            } else {
+                None
+            }
+        };
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(InMemoryMetricExporter::default())
+            .with_view(view)
+            .build();
+        let meter = meter_provider.meter("test");
+
+        // Act & Assert
+        assert!(meter.u64_counter("my_counter").build().enabled());
+        assert!(!meter.u64_counter("dropped_counter").build().enabled());
+        assert!(!meter
+            .i64_up_down_counter("dropped_updown")
+            .build()
+            .enabled());
+        assert!(!meter.f64_gauge("dropped_gauge").build().enabled());
+        assert!(!meter.f64_histogram("dropped_histogram").build().enabled());
+    }
+
+    #[test]
+    fn sync_instruments_disabled_without_reader() {
+        let meter_provider = SdkMeterProvider::builder().build();
+        let meter = meter_provider.meter("test");
+
+        assert!(!meter.u64_counter("my_counter").build().enabled());
+        assert!(!meter.i64_up_down_counter("my_updown").build().enabled());
+        assert!(!meter.u64_gauge("my_gauge").build().enabled());
+        assert!(!meter.u64_histogram("my_histogram").build().enabled());
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn counter_with_drop_aggregation_and_rename_should_still_drop() {
         // Run this test with stdout enabled to see output.
@@ -3975,6 +4161,155 @@ mod tests {
         assert_eq!(data_point2.value, 5);
     }
 
+    #[cfg(feature = "spec_unstable_metrics_views")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn counter_exemplars_trace_based() {
+        use opentelemetry::trace::{
+            SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+        };
+        use opentelemetry::Context;
+
+        // Arrange
+        let exporter = InMemoryMetricExporterBuilder::new()
+            .with_temporality(Temporality::Delta)
+            .build();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(exporter.clone())
+            .with_exemplar_filter(ExemplarFilter::TraceBased)
+            .with_view(|_: &Instrument| {
+                Stream::builder()
+                    .with_allowed_attribute_keys([opentelemetry::Key::new("key1")])
+                    .build()
+                    .ok()
+            })
+            .build();
+        let counter = meter_provider
+            .meter("test")
+            .u64_counter("my_counter")
+            .build();
+        let span_context = SpanContext::new(
+            TraceId::from(0x0102_0304_0506_0708_0910_1112_1314_1516),
+            SpanId::from(0x0102_0304_0506_0708),
+            TraceFlags::SAMPLED,
+            false,
+            TraceState::default(),
+        );
+
+        // Act
+        counter.add(1, &[KeyValue::new("key1", "value1")]);
+        {
+            let _guard = Context::current()
+                .with_remote_span_context(span_context.clone())
+                .attach();
+            counter.add(
+                5,
+                &[
+                    KeyValue::new("key1", "value1"),
+                    KeyValue::new("key2", "value2"),
+                ],
+            );
+        }
+        meter_provider.force_flush().unwrap();
+
+        // Assert
+        let resource_metrics = exporter.get_finished_metrics().unwrap();
+        let metric = &resource_metrics[0].scope_metrics[0].metrics[0];
+        let MetricData::Sum(sum) = u64::extract_metrics_data_ref(&metric.data).unwrap() else {
+            unreachable!()
+        };
+        let data_point = &sum.data_points[0];
+        assert_eq!(data_point.value, 6);
+        assert_eq!(
+            data_point.exemplars.len(),
+            1,
+            "only the sampled measurement"
+        );
+        let exemplar = &data_point.exemplars[0];
+        assert_eq!(exemplar.value, 5);
+        assert_eq!(exemplar.trace_id, span_context.trace_id().to_bytes());
+        assert_eq!(exemplar.span_id, span_context.span_id().to_bytes());
+        assert_eq!(
+            exemplar.filtered_attributes,
+            vec![KeyValue::new("key2", "value2")]
+        );
+
+        // Delta reservoirs are reset after each collection.
+        exporter.reset();
+        counter.add(1, &[KeyValue::new("key1", "value1")]);
+        meter_provider.force_flush().unwrap();
+        let resource_metrics = exporter.get_finished_metrics().unwrap();
+        let metric = &resource_metrics[0].scope_metrics[0].metrics[0];
+        let MetricData::Sum(sum) = u64::extract_metrics_data_ref(&metric.data).unwrap() else {
+            unreachable!()
+        };
+        assert!(sum.data_points[0].exemplars.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn histogram_exemplars_aligned_to_buckets() {
+        // Arrange
+        let exporter = InMemoryMetricExporterBuilder::new()
+            .with_temporality(Temporality::Cumulative)
+            .build();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(exporter.clone())
+            .with_exemplar_filter(ExemplarFilter::AlwaysOn)
+            .build();
+        let histogram = meter_provider
+            .meter("test")
+            .f64_histogram("my_histogram")
+            .with_boundaries(vec![10.0, 100.0])
+            .build();
+
+        // Act
+        histogram.record(1.0, &[]);
+        histogram.record(2.0, &[]);
+        histogram.record(500.0, &[]);
+        meter_provider.force_flush().unwrap();
+
+        // Assert
+        let resource_metrics = exporter.get_finished_metrics().unwrap();
+        let metric = &resource_metrics[0].scope_metrics[0].metrics[0];
+        let MetricData::Histogram(histogram) = f64::extract_metrics_data_ref(&metric.data).unwrap()
+        else {
+            unreachable!()
+        };
+        let values: Vec<f64> = histogram.data_points[0]
+            .exemplars
+            .iter()
+            .map(|e| e.value)
+            .collect();
+        assert_eq!(values, vec![2.0, 500.0], "last measurement per bucket");
+        assert!(histogram.data_points[0]
+            .exemplars
+            .iter()
+            .all(|e| e.trace_id == [0; 16] && e.span_id == [0; 8]));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn exemplars_disabled_with_always_off_filter() {
+        // Arrange
+        let exporter = InMemoryMetricExporterBuilder::new().build();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(exporter.clone())
+            .with_exemplar_filter(ExemplarFilter::AlwaysOff)
+            .build();
+        let gauge = meter_provider.meter("test").u64_gauge("my_gauge").build();
+
+        // Act
+        gauge.record(1, &[KeyValue::new("key1", "value1")]);
+        meter_provider.force_flush().unwrap();
+
+        // Assert
+        let resource_metrics = exporter.get_finished_metrics().unwrap();
+        let metric = &resource_metrics[0].scope_metrics[0].metrics[0];
+        let MetricData::Gauge(gauge) = u64::extract_metrics_data_ref(&metric.data).unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(gauge.data_points[0].value, 1);
+        assert!(gauge.data_points[0].exemplars.is_empty());
+    }
+
     fn counter_aggregation_helper(temporality: Temporality) {
         // Arrange
         let mut test_context = TestContext::new(temporality);
@@ -38,6 +38,10 @@ impl<T> SyncInstrument<T> for NoopSyncInstrument {
         // Ignored
     }
 
+    fn enabled(&self) -> bool {
+        false
+    }
+
     #[cfg(feature = "experimental_metrics_bound_instruments")]
     fn bind(&self, _attributes: &[KeyValue]) -> Box<dyn BoundSyncInstrument<T> + Send + Sync> {
         Box::new(NoopBoundSyncInstrument { _private: () })
@@ -12,7 +12,12 @@ use opentelemetry::{otel_debug, otel_error, otel_info, otel_warn, Context};
 
 use crate::{
     error::{OTelSdkError, OTelSdkResult},
-    metrics::{exporter::PushMetricExporter, reader::SdkProducer},
+    metrics::{
+        exporter::PushMetricExporter,
+        producer::{produce_into, MetricProducer},
+        reader::SdkProducer,
+    },
+    self_diagnostics::{ComponentType, ReaderMetrics, SdkMeter},
     Resource,
 };
 
@@ -30,6 +35,8 @@ const METRIC_EXPORT_INTERVAL_NAME: &str = "OTEL_METRIC_EXPORT_INTERVAL";
 pub struct PeriodicReaderBuilder<E> {
     interval: Duration,
     exporter: E,
+    meter: SdkMeter,
+    producers: Vec<Box<dyn MetricProducer>>,
 }
 
 impl<E> PeriodicReaderBuilder<E>
@@ -42,7 +49,12 @@ where
             .and_then(|v| v.parse().map(Duration::from_millis).ok())
             .unwrap_or(DEFAULT_INTERVAL);
 
-        PeriodicReaderBuilder { interval, exporter }
+        PeriodicReaderBuilder {
+            interval,
+            exporter,
+            meter: SdkMeter::default(),
+            producers: Vec::new(),
+        }
     }
 
     /// Configures the intervening time between exports for a [PeriodicReader].
@@ -59,9 +71,31 @@ where
         self
     }
 
+    /// Report the self-diagnostics metric of the reader,
+    /// `otel.sdk.metric_reader.collection.duration`, to `meter_provider`.
+    ///
+    /// By default, it is reported to the global meter provider set when the
+    /// reader first collects metrics.
+    pub fn with_meter_provider(
+        mut self,
+        meter_provider: &impl opentelemetry::metrics::MeterProvider,
+    ) -> Self {
+        self.meter = SdkMeter::new(meter_provider);
+        self
+    }
+
+    /// Registers a [MetricProducer] whose metrics are added to each
+    /// collection of the reader.
+    ///
+    /// Producers are called in the order they are registered.
+    pub fn with_producer(mut self, producer: impl MetricProducer) -> Self {
+        self.producers.push(Box::new(producer));
+        self
+    }
+
     /// Create a [PeriodicReader] with the given config.
     pub fn build(self) -> PeriodicReader<E> {
-        PeriodicReader::new(self.exporter, self.interval)
+        PeriodicReader::new(self.exporter, self.interval, self.meter, self.producers)
     }
 }
 
@@ -150,7 +184,12 @@ impl<E: PushMetricExporter> PeriodicReader<E> {
         PeriodicReaderBuilder::new(exporter)
     }
 
-    fn new(exporter: E, interval: Duration) -> Self {
+    fn new(
+        exporter: E,
+        interval: Duration,
+        meter: SdkMeter,
+        producers: Vec<Box<dyn MetricProducer>>,
+    ) -> Self {
         let (message_sender, message_receiver): (Sender<Message>, Receiver<Message>) =
             mpsc::channel();
         let exporter_arc = Arc::new(exporter);
@@ -158,7 +197,9 @@ impl<E: PushMetricExporter> PeriodicReader<E> {
             inner: Arc::new(PeriodicReaderInner {
                 message_sender,
                 producer: Mutex::new(None),
+                producers,
                 exporter: exporter_arc.clone(),
+                metrics: ReaderMetrics::new(meter, ComponentType::PeriodicMetricReader),
             }),
         };
         let cloned_reader = reader.clone();
@@ -358,6 +399,8 @@ struct PeriodicReaderInner<E: PushMetricExporter> {
     exporter: Arc<E>,
     message_sender: mpsc::Sender<Message>,
     producer: Mutex<Option<Weak<dyn SdkProducer>>>,
+    producers: Vec<Box<dyn MetricProducer>>,
+    metrics: ReaderMetrics,
 }
 
 impl<E: PushMetricExporter> PeriodicReaderInner<E> {
@@ -370,13 +413,17 @@ impl<E: PushMetricExporter> PeriodicReaderInner<E> {
         self.exporter.temporality()
     }
 
-    fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
+    /// Collects metrics, returning the number of observable callbacks which
+    /// panicked.
+    fn collect(&self, rm: &mut ResourceMetrics) -> Result<usize, OTelSdkError> {
         let producer = self.producer.lock().expect("lock poisoned");
         if let Some(p) = producer.as_ref() {
-            p.upgrade()
+            let failed_callbacks = p
+                .upgrade()
                 .ok_or(OTelSdkError::AlreadyShutdown)?
                 .produce(rm)?;
-            Ok(())
+            produce_into(&self.producers, rm);
+            Ok(failed_callbacks)
         } else {
             otel_warn!(
             name: "PeriodReader.MeterProviderNotRegistered",
@@ -394,6 +441,15 @@ impl<E: PushMetricExporter> PeriodicReaderInner<E> {
         let current_time = Instant::now();
         let collect_result = self.collect(rm);
         let time_taken_for_collect = current_time.elapsed();
+        let error_type = match &collect_result {
+            Ok(0) => None,
+            Ok(_) => Some("callback_panicked"),
+            Err(OTelSdkError::AlreadyShutdown) => Some("already_shutdown"),
+            Err(OTelSdkError::Timeout(_)) => Some("timeout"),
+            Err(OTelSdkError::InternalFailure(_)) => Some("internal_failure"),
+        };
+        self.metrics
+            .record_collection(time_taken_for_collect, error_type);
 
         #[allow(clippy::question_mark)]
         if let Err(e) = collect_result {
@@ -488,7 +544,7 @@ impl<E: PushMetricExporter> MetricReader for PeriodicReader<E> {
     }
 
     fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
-        self.inner.collect(rm)
+        self.inner.collect(rm).map(|_| ())
     }
 
     fn force_flush(&self) -> OTelSdkResult {
@@ -781,6 +837,70 @@ mod tests {
         assert!(exporter.is_shutdown.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn collection_duration_is_recorded() {
+        use crate::metrics::data::{AggregatedMetrics, MetricData};
+
+        // Arrange
+        let self_metrics_exporter = InMemoryMetricExporter::default();
+        let self_meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(self_metrics_exporter.clone())
+            .build();
+        let reader = PeriodicReader::builder(InMemoryMetricExporter::default())
+            .with_meter_provider(&self_meter_provider)
+            .build();
+        let meter_provider = SdkMeterProvider::builder().with_reader(reader).build();
+        let meter = meter_provider.meter("test");
+        let fail = Arc::new(AtomicBool::new(false));
+        let fail_in_callback = fail.clone();
+        let _gauge = meter
+            .u64_observable_gauge("testgauge")
+            .with_callback(move |observer| {
+                if fail_in_callback.load(Ordering::Relaxed) {
+                    panic!("callback failure");
+                }
+                observer.observe(1, &[]);
+            })
+            .build();
+
+        // Act
+        meter_provider.force_flush().unwrap();
+        fail.store(true, Ordering::Relaxed);
+        meter_provider.force_flush().unwrap();
+        self_meter_provider.force_flush().unwrap();
+
+        // Assert
+        let metrics = self_metrics_exporter.get_finished_metrics().unwrap();
+        let metric = metrics[0]
+            .scope_metrics
+            .iter()
+            .flat_map(|sm| &sm.metrics)
+            .find(|metric| metric.name == "otel.sdk.metric_reader.collection.duration")
+            .expect("collection duration should be recorded");
+        let AggregatedMetrics::F64(MetricData::Histogram(histogram)) = &metric.data else {
+            panic!("collection duration should be a histogram");
+        };
+        let mut counts: Vec<(Option<String>, u64)> = histogram
+            .data_points()
+            .map(|dp| {
+                assert!(dp
+                    .attributes()
+                    .any(|kv| kv.key.as_str() == "otel.component.type"
+                        && kv.value.as_str() == "periodic_metric_reader"));
+                let error_type = dp
+                    .attributes()
+                    .find(|kv| kv.key.as_str() == "error.type")
+                    .map(|kv| kv.value.to_string());
+                (error_type, dp.count())
+            })
+            .collect();
+        counts.sort();
+        assert_eq!(
+            counts,
+            vec![(None, 1), (Some("callback_panicked".to_string()), 1)]
+        );
+    }
+
     #[test]
     fn collection() {
         collection_triggered_by_interval_helper();
@@ -1,7 +1,8 @@
 use core::fmt;
 use std::{
     borrow::Cow,
-    collections::{HashMap, HashSet},
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
     sync::{Arc, Mutex},
 };
 
@@ -13,10 +14,11 @@ use crate::{
         aggregation,
         data::{Metric, ResourceMetrics, ScopeMetrics},
         error::{MetricError, MetricResult},
+        exemplar::ExemplarFilter,
         instrument::{Instrument, InstrumentId, InstrumentKind, Stream},
         internal::{self, AggregateBuilder, Number},
         reader::{MetricReader, SdkProducer},
-        view::View,
+        view::ViewMatcher,
     },
     Resource,
 };
@@ -37,7 +39,8 @@ use super::{aggregation::Aggregation, Temporality};
 pub struct Pipeline {
     pub(crate) resource: Resource,
     reader: Box<dyn MetricReader>,
-    views: Vec<Arc<dyn View>>,
+    views: Vec<Arc<dyn ViewMatcher>>,
+    exemplar_filter: ExemplarFilter,
     inner: Mutex<PipelineInner>,
 }
 
@@ -48,7 +51,7 @@ impl fmt::Debug for Pipeline {
 }
 
 /// Single or multi-instrument callbacks
-type GenericCallback = Arc<dyn Fn() + Send + Sync>;
+pub(crate) type GenericCallback = Arc<dyn Fn() + Send + Sync>;
 
 const DEFAULT_CARDINALITY_LIMIT: usize = 2000;
 
@@ -87,6 +90,14 @@ impl Pipeline {
             .map(|mut inner| inner.callbacks.push(callback));
     }
 
+    /// Removes a callback registered with [Pipeline::add_callback].
+    fn remove_callback(&self, callback: &GenericCallback) {
+        let _ = self
+            .inner
+            .lock()
+            .map(|mut inner| inner.callbacks.retain(|cb| !Arc::ptr_eq(cb, callback)));
+    }
+
     /// Send accumulated telemetry
     fn force_flush(&self) -> OTelSdkResult {
         self.reader.force_flush()
@@ -100,20 +111,41 @@ impl Pipeline {
 
 impl SdkProducer for Pipeline {
     /// Returns aggregated metrics from a single collection.
-    fn produce(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
-        let inner = self
+    ///
+    /// A panicking observable callback does not prevent the collection of the
+    /// other instruments.
+    fn produce(&self, rm: &mut ResourceMetrics) -> Result<usize, OTelSdkError> {
+        // The callbacks are invoked without holding the lock, so that they can
+        // unregister callbacks.
+        let callbacks = self
             .inner
             .lock()
-            .map_err(|_| OTelSdkError::InternalFailure("Failed to lock pipeline".into()))?;
+            .map_err(|_| OTelSdkError::InternalFailure("Failed to lock pipeline".into()))?
+            .callbacks
+            .clone();
         otel_debug!(
             name: "MeterProviderInvokingObservableCallbacks",
-            count =  inner.callbacks.len(),
+            count =  callbacks.len(),
         );
-        for cb in &inner.callbacks {
+        let mut failed_callbacks = 0;
+        for cb in &callbacks {
             // TODO consider parallel callbacks.
-            cb();
+            if panic::catch_unwind(AssertUnwindSafe(|| cb())).is_err() {
+                failed_callbacks += 1;
+            }
+        }
+        if failed_callbacks > 0 {
+            otel_warn!(
+                name: "MeterProvider.ObservableCallbackPanicked",
+                count = failed_callbacks,
+                message = "Observable instrument callbacks panicked during collection. Their measurements are missing from this collection."
+            );
         }
 
+        let inner = self
+            .inner
+            .lock()
+            .map_err(|_| OTelSdkError::InternalFailure("Failed to lock pipeline".into()))?;
         rm.resource = self.resource.clone();
         if inner.aggregations.len() > rm.scope_metrics.len() {
             rm.scope_metrics
@@ -169,7 +201,7 @@ impl SdkProducer for Pipeline {
 
         rm.scope_metrics.truncate(i);
 
-        Ok(())
+        Ok(failed_callbacks)
     }
 }
 
@@ -260,8 +292,10 @@ where
         let mut errs = vec![];
         let kind = inst.kind;
 
-        // The cache will return the same Aggregator instance. Use stream ids to de duplicate.
-        let mut seen = HashSet::new();
+        // The cache will return the same Aggregator instance. Use stream ids to de
+        // duplicate, keeping the aggregation each id was first created with so
+        // conflicting views can be reported.
+        let mut seen: HashMap<InstrumentId, Option<Aggregation>> = HashMap::new();
         for v in &self.pipeline.views {
             let mut stream = match v.match_inst(&inst) {
                 Some(stream) => stream,
@@ -290,9 +324,45 @@ where
             }
 
             let id = self.inst_id(kind, &stream);
-            if seen.contains(&id) {
-                continue; // This aggregator has already been added
+            if let Some(aggregation) = seen.get(&id) {
+                // This aggregator has already been added
+                if *aggregation == stream.aggregation {
+                    otel_warn!(
+                        name: "Metrics.View.DuplicateStream",
+                        message = "Multiple views produce the same stream for the instrument. The duplicate stream is ignored.",
+                        instrument_name = inst.name.as_ref(),
+                        stream_name = id.name.as_ref(),
+                    );
+                } else {
+                    otel_warn!(
+                        name: "Metrics.View.ConflictingStream",
+                        message = "Multiple views produce streams with the same identity but different aggregations for the instrument. Only the first stream is used.",
+                        instrument_name = inst.name.as_ref(),
+                        stream_name = id.name.as_ref(),
+                        aggregations = format!("{:?} and {:?}", aggregation, stream.aggregation).as_str(),
+                    );
+                }
+                continue;
             }
+            if let Some(existing) = seen
+                .keys()
+                .find(|existing| existing.name.eq_ignore_ascii_case(&id.name))
+            {
+                // Both streams are exported, as required by the specification,
+                // but backends will likely see them as a single metric.
+                otel_warn!(
+                    name: "Metrics.View.ConflictingStream",
+                    message = "Multiple views produce streams with the same name but different descriptions or units for the instrument.",
+                    instrument_name = inst.name.as_ref(),
+                    stream_name = id.name.as_ref(),
+                    reason = format!(
+                        "descriptions: ({} and {}), units: ({} and {})",
+                        existing.description, id.description, existing.unit, id.unit
+                    )
+                    .as_str(),
+                );
+            }
+            let aggregation = stream.aggregation.clone();
 
             let agg = match self.cached_aggregator(&inst.scope, kind, stream) {
                 Ok(Some(agg)) => agg,
@@ -302,7 +372,7 @@ where
                     continue;
                 }
             };
-            seen.insert(id);
+            seen.insert(id, aggregation);
             measures.push(agg);
         }
 
@@ -420,6 +490,7 @@ where
                 self.pipeline.reader.temporality(kind),
                 filter,
                 cardinality_limit,
+                self.pipeline.exemplar_filter,
             );
             let AggregateFns { measure, collect } = match aggregate_fn(b, &agg, kind) {
                 Ok(Some(inst)) => inst,
@@ -455,8 +526,9 @@ where
     ///
     /// If that instrument conflicts with id, a warning is logged.
     fn log_conflict(&self, id: &InstrumentId) {
-        if let Ok(views) = self.views.lock() {
-            if let Some(existing) = views.get(id.name.to_lowercase().as_str()) {
+        if let Ok(mut views) = self.views.lock() {
+            let name = id.name.to_lowercase();
+            if let Some(existing) = views.get(name.as_str()) {
                 if existing == id {
                     return;
                 }
@@ -472,6 +544,8 @@ where
                     existing.unit, id.unit,
                     existing.number, id.number,)
                 );
+            } else {
+                views.insert(Cow::Owned(name), id.clone());
             }
         }
     }
@@ -660,7 +734,8 @@ impl Pipelines {
     pub(crate) fn new(
         res: Resource,
         readers: Vec<Box<dyn MetricReader>>,
-        views: Vec<Arc<dyn View>>,
+        views: Vec<Arc<dyn ViewMatcher>>,
+        exemplar_filter: ExemplarFilter,
     ) -> Self {
         let mut pipes = Vec::with_capacity(readers.len());
         for r in readers {
@@ -668,6 +743,7 @@ impl Pipelines {
                 resource: res.clone(),
                 reader: r,
                 views: views.clone(),
+                exemplar_filter,
                 inner: Default::default(),
             });
             p.reader.register_pipeline(Arc::downgrade(&p));
@@ -677,14 +753,23 @@ impl Pipelines {
         Pipelines(pipes)
     }
 
-    pub(crate) fn register_callback<F>(&self, callback: F)
+    /// Registers a callback invoked by the collections of all pipelines, and
+    /// returns it to unregister it with [Pipelines::unregister_callback].
+    pub(crate) fn register_callback<F>(&self, callback: F) -> GenericCallback
     where
         F: Fn() + Send + Sync + 'static,
     {
-        let cb = Arc::new(callback);
+        let cb: GenericCallback = Arc::new(callback);
         for pipe in &self.0 {
             pipe.add_callback(cb.clone())
         }
+        cb
+    }
+
+    pub(crate) fn unregister_callback(&self, callback: &GenericCallback) {
+        for pipe in &self.0 {
+            pipe.remove_callback(callback)
+        }
     }
 
     /// Force flush all pipelines
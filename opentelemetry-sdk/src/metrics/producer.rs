@@ -0,0 +1,251 @@
+//! Bridges of metrics produced outside of the SDK.
+use std::fmt;
+
+use opentelemetry::otel_warn;
+
+use crate::error::OTelSdkError;
+
+use super::data::{ResourceMetrics, ScopeMetrics};
+
+/// Produces metrics from a source outside of the SDK, such as the registry of
+/// another metrics library, to be exported along with the metrics of the SDK.
+///
+/// A producer is registered on a reader, with
+/// [`PeriodicReaderBuilder::with_producer`] or
+/// [`ManualReaderBuilder::with_producer`], and is called each time the reader
+/// collects. The metrics it returns are added to the metrics collected from the
+/// SDK, under the [`Resource`] of the meter provider. Metrics of an
+/// instrumentation scope also used by a meter of the SDK, or by another
+/// producer, are added to the metrics of that scope, except the ones whose
+/// name is already used in the scope, which are dropped with a warning.
+///
+/// The producer is responsible for the temporality of its metrics, which
+/// should match the temporality expected by the exporter of the reader.
+///
+/// # Example
+///
+/// ```
+/// use opentelemetry::InstrumentationScope;
+/// use opentelemetry_sdk::error::OTelSdkError;
+/// use opentelemetry_sdk::metrics::data::{Metric, MetricData, ScopeMetrics, Sum, SumDataPoint};
+/// use opentelemetry_sdk::metrics::{MetricProducer, Temporality};
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::time::SystemTime;
+///
+/// #[derive(Debug)]
+/// struct LegacyCounters {
+///     start_time: SystemTime,
+///     requests: AtomicU64,
+/// }
+///
+/// impl MetricProducer for LegacyCounters {
+///     fn produce(&self) -> Result<Vec<ScopeMetrics>, OTelSdkError> {
+///         let requests = Sum::new(
+///             vec![SumDataPoint::new(vec![], self.requests.load(Ordering::Relaxed))],
+///             self.start_time,
+///             SystemTime::now(),
+///             Temporality::Cumulative,
+///             true,
+///         );
+///         Ok(vec![ScopeMetrics::new(
+///             InstrumentationScope::builder("legacy").build(),
+///             vec![Metric::new(
+///                 "requests",
+///                 "Handled requests",
+///                 "{request}",
+///                 MetricData::from(requests),
+///             )],
+///         )])
+///     }
+/// }
+/// ```
+///
+/// [`PeriodicReaderBuilder::with_producer`]: crate::metrics::PeriodicReaderBuilder::with_producer
+#[cfg_attr(
+    feature = "experimental_metrics_custom_reader",
+    doc = "[`ManualReaderBuilder::with_producer`]: crate::metrics::ManualReaderBuilder::with_producer"
+)]
+#[cfg_attr(
+    not(feature = "experimental_metrics_custom_reader"),
+    doc = "[`ManualReaderBuilder::with_producer`]: https://docs.rs/opentelemetry_sdk/latest/opentelemetry_sdk/metrics/struct.ManualReaderBuilder.html#method.with_producer"
+)]
+/// [`Resource`]: crate::Resource
+pub trait MetricProducer: fmt::Debug + Send + Sync + 'static {
+    /// Returns the current metrics of the source, grouped by instrumentation
+    /// scope.
+    ///
+    /// An error only drops the metrics of this producer from the collection.
+    fn produce(&self) -> Result<Vec<ScopeMetrics>, OTelSdkError>;
+}
+
+/// Adds the metrics of `producers` to `rm`.
+pub(crate) fn produce_into(producers: &[Box<dyn MetricProducer>], rm: &mut ResourceMetrics) {
+    for producer in producers {
+        let scope_metrics = match producer.produce() {
+            Ok(scope_metrics) => scope_metrics,
+            Err(err) => {
+                otel_warn!(
+                    name: "MetricReader.ProducerFailed",
+                    message = "A metric producer failed. Its metrics are missing from this collection.",
+                    producer = format!("{:?}", producer),
+                    error = format!("{}", err)
+                );
+                continue;
+            }
+        };
+        for sm in scope_metrics {
+            if sm.metrics.is_empty() {
+                continue;
+            }
+            let Some(existing) = rm.scope_metrics.iter_mut().find(|s| s.scope == sm.scope) else {
+                rm.scope_metrics.push(sm);
+                continue;
+            };
+            for metric in sm.metrics {
+                // Instrument names are case-insensitive.
+                if existing
+                    .metrics
+                    .iter()
+                    .any(|m| m.name.eq_ignore_ascii_case(&metric.name))
+                {
+                    otel_warn!(
+                        name: "MetricReader.ProducerMetricDropped",
+                        message = "A metric of a producer has the name of a metric already collected in the same instrumentation scope. It is missing from this collection.",
+                        producer = format!("{:?}", producer),
+                        scope = existing.scope.name().to_string(),
+                        metric_name = metric.name.to_string()
+                    );
+                    continue;
+                }
+                existing.metrics.push(metric);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::metrics::data::{Metric, MetricData, Sum, SumDataPoint};
+    use crate::metrics::{InMemoryMetricExporter, PeriodicReader, SdkMeterProvider, Temporality};
+    use opentelemetry::metrics::MeterProvider as _;
+    use opentelemetry::{InstrumentationScope, KeyValue};
+    use std::time::SystemTime;
+
+    #[derive(Debug)]
+    struct TestProducer {
+        scope: &'static str,
+        fail: bool,
+    }
+
+    impl MetricProducer for TestProducer {
+        fn produce(&self) -> Result<Vec<ScopeMetrics>, OTelSdkError> {
+            if self.fail {
+                return Err(OTelSdkError::InternalFailure("unavailable".into()));
+            }
+            let now = SystemTime::now();
+            let sum = Sum::new(
+                vec![SumDataPoint::new(vec![KeyValue::new("k", "v")], 7u64)],
+                now,
+                now,
+                Temporality::Cumulative,
+                true,
+            );
+            Ok(vec![ScopeMetrics::new(
+                InstrumentationScope::builder(self.scope).build(),
+                vec![Metric::new(
+                    "external_counter",
+                    "",
+                    "",
+                    MetricData::from(sum),
+                )],
+            )])
+        }
+    }
+
+    #[test]
+    fn producer_metrics_are_merged_into_collection() {
+        let exporter = InMemoryMetricExporter::default();
+        let reader = PeriodicReader::builder(exporter.clone())
+            .with_producer(TestProducer {
+                scope: "external",
+                fail: false,
+            })
+            .with_producer(TestProducer {
+                scope: "app",
+                fail: false,
+            })
+            .with_producer(TestProducer {
+                scope: "broken",
+                fail: true,
+            })
+            .build();
+        let provider = SdkMeterProvider::builder().with_reader(reader).build();
+        provider
+            .meter("app")
+            .u64_counter("sdk_counter")
+            .build()
+            .add(1, &[]);
+
+        provider.force_flush().unwrap();
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let mut exported: Vec<(String, Vec<String>)> = metrics[0]
+            .scope_metrics()
+            .map(|sm| {
+                (
+                    sm.scope().name().to_string(),
+                    sm.metrics().map(|m| m.name().to_string()).collect(),
+                )
+            })
+            .collect();
+        exported.sort();
+        assert_eq!(
+            exported,
+            [
+                (
+                    "app".to_string(),
+                    vec!["sdk_counter".to_string(), "external_counter".to_string()]
+                ),
+                ("external".to_string(), vec!["external_counter".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn producer_metrics_colliding_in_a_scope_are_dropped() {
+        let exporter = InMemoryMetricExporter::default();
+        let reader = PeriodicReader::builder(exporter.clone())
+            .with_producer(TestProducer {
+                scope: "app",
+                fail: false,
+            })
+            .with_producer(TestProducer {
+                scope: "external",
+                fail: false,
+            })
+            .with_producer(TestProducer {
+                scope: "external",
+                fail: false,
+            })
+            .build();
+        let provider = SdkMeterProvider::builder().with_reader(reader).build();
+        provider
+            .meter("app")
+            .u64_counter("External_Counter")
+            .build()
+            .add(1, &[]);
+
+        provider.force_flush().unwrap();
+
+        let metrics = exporter.get_finished_metrics().unwrap();
+        for sm in metrics[0].scope_metrics() {
+            let names: Vec<&str> = sm.metrics().map(|m| m.name()).collect();
+            match sm.scope().name() {
+                "app" => assert_eq!(names, ["External_Counter"]),
+                "external" => assert_eq!(names, ["external_counter"]),
+                scope => panic!("unexpected scope {scope}"),
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 //! Interfaces for reading and producing metrics
-use crate::error::OTelSdkResult;
+use crate::error::{OTelSdkError, OTelSdkResult};
 use std::time::Duration;
 use std::{fmt, sync::Weak};
 
@@ -62,6 +62,7 @@ pub trait MetricReader: fmt::Debug + Send + Sync + 'static {
 
 /// Produces metrics for a [MetricReader].
 pub(crate) trait SdkProducer: fmt::Debug + Send + Sync {
-    /// Returns aggregated metrics from a single collection.
-    fn produce(&self, rm: &mut ResourceMetrics) -> OTelSdkResult;
+    /// Returns aggregated metrics from a single collection, and the number of
+    /// observable instrument callbacks which panicked during it.
+    fn produce(&self, rm: &mut ResourceMetrics) -> Result<usize, OTelSdkError>;
 }
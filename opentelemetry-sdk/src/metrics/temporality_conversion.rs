@@ -0,0 +1,1180 @@
+//! Conversion of collected metrics between cumulative and delta temporality.
+use std::{
+    borrow::Cow,
+    collections::{hash_map::Entry, HashMap},
+    fmt,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use opentelemetry::{otel_warn, InstrumentationScope, KeyValue};
+
+use crate::error::OTelSdkResult;
+
+use super::{
+    data::{
+        AggregatedMetrics, Exemplar, Histogram, HistogramDataPoint, Metric, MetricData,
+        ResourceMetrics, ScopeMetrics, Sum, SumDataPoint,
+    },
+    exporter::PushMetricExporter,
+    internal::Number,
+    Temporality,
+};
+
+/// The default duration after which a stream that is not reported is
+/// forgotten.
+const DEFAULT_STALE_AFTER: Duration = Duration::from_secs(5 * 60);
+/// The default maximum number of streams tracked by a converter.
+const DEFAULT_MAX_STREAMS: usize = 10_000;
+
+/// Converts collected metrics to a target [Temporality].
+///
+/// Sums and histograms with another temporality are converted, keeping for
+/// each stream, identified by its instrumentation scope, metric name and
+/// attributes, the state needed by the next conversion:
+///
+/// * Cumulative metrics are converted to delta by subtracting the previous
+///   value of each stream. The first value of a stream is kept whole. A value
+///   lower than the previous one of a monotonic sum or histogram, or a new
+///   start time, is handled as a reset of the stream.
+/// * Delta metrics are converted to cumulative by adding them to the values of
+///   each stream, which are reported until the stream becomes stale. A change
+///   of the bucket boundaries of a histogram restarts the metric from the
+///   start time of the new data points.
+///
+/// A stream not reported for the duration set with
+/// [`TemporalityConverterBuilder::with_stale_after`] is forgotten, see there
+/// how it is handled when it is reported again. The number of tracked streams
+/// is bounded by [`TemporalityConverterBuilder::with_max_streams`]. The data
+/// points of new streams over that limit are dropped.
+///
+/// The integer values wrap around on overflow, like the aggregations of the
+/// SDK.
+///
+/// Gauges and exponential histograms are left unchanged.
+///
+/// With [`Temporality::LowMemory`] as target, monotonic sums and histograms
+/// are converted to delta and non-monotonic sums to cumulative.
+///
+/// See [TemporalityConvertingExporter] to convert the metrics exported by a
+/// [PushMetricExporter].
+pub struct TemporalityConverter {
+    target: Temporality,
+    stale_after: Duration,
+    max_streams: usize,
+    state: Mutex<ConverterState>,
+}
+
+impl fmt::Debug for TemporalityConverter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TemporalityConverter")
+            .field("target", &self.target)
+            .field("stale_after", &self.stale_after)
+            .field("max_streams", &self.max_streams)
+            .finish()
+    }
+}
+
+impl TemporalityConverter {
+    /// Configuration for a converter to the `target` temporality.
+    pub fn builder(target: Temporality) -> TemporalityConverterBuilder {
+        TemporalityConverterBuilder {
+            target,
+            stale_after: DEFAULT_STALE_AFTER,
+            max_streams: DEFAULT_MAX_STREAMS,
+        }
+    }
+
+    /// Returns the temporality of the converted metrics.
+    pub fn target(&self) -> Temporality {
+        self.target
+    }
+
+    /// Returns a copy of `metrics` with sums and histograms converted to the
+    /// target temporality.
+    pub fn convert(&self, metrics: &ResourceMetrics) -> ResourceMetrics {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.generation = state.generation.wrapping_add(1);
+        let mut conversion = Conversion {
+            target: self.target,
+            max_streams: self.max_streams,
+            now: SystemTime::now(),
+            generation: state.generation,
+            dropped: 0,
+        };
+
+        let ConverterState {
+            f64,
+            u64,
+            i64,
+            streams,
+            ..
+        } = &mut *state;
+        let mut scope_metrics = Vec::with_capacity(metrics.scope_metrics.len());
+        for sm in &metrics.scope_metrics {
+            let mut converted = Vec::with_capacity(sm.metrics.len());
+            for metric in &sm.metrics {
+                let key = (sm.scope.clone(), metric.name.clone());
+                let data = match &metric.data {
+                    AggregatedMetrics::F64(data) => {
+                        AggregatedMetrics::F64(conversion.convert_data(data, f64, key, streams))
+                    }
+                    AggregatedMetrics::U64(data) => {
+                        AggregatedMetrics::U64(conversion.convert_data(data, u64, key, streams))
+                    }
+                    AggregatedMetrics::I64(data) => {
+                        AggregatedMetrics::I64(conversion.convert_data(data, i64, key, streams))
+                    }
+                };
+                converted.push(Metric {
+                    name: metric.name.clone(),
+                    description: metric.description.clone(),
+                    unit: metric.unit.clone(),
+                    data,
+                });
+            }
+            scope_metrics.push(ScopeMetrics {
+                scope: sm.scope.clone(),
+                metrics: converted,
+            });
+        }
+
+        if conversion.dropped > 0 {
+            otel_warn!(
+                name: "TemporalityConverter.StreamLimitReached",
+                message = "The maximum number of streams tracked by the temporality converter is reached. Data points of new streams are dropped.",
+                max_streams = self.max_streams,
+                dropped_data_points = conversion.dropped
+            );
+        }
+        state.evict_stale(conversion.now, self.stale_after);
+
+        ResourceMetrics {
+            resource: metrics.resource.clone(),
+            scope_metrics,
+        }
+    }
+}
+
+/// Configuration for a [TemporalityConverter].
+#[derive(Debug)]
+pub struct TemporalityConverterBuilder {
+    target: Temporality,
+    stale_after: Duration,
+    max_streams: usize,
+}
+
+impl TemporalityConverterBuilder {
+    /// Set the duration after which a stream that is not reported is
+    /// forgotten.
+    ///
+    /// A new stream can't be told apart from a forgotten one reported again,
+    /// so once a metric forgot some of its streams:
+    ///
+    /// * Converted to delta, the first value of a new stream is not reported,
+    ///   only used as the base of the next delta, unless the cumulative metric
+    ///   started after the previous conversion.
+    /// * Converted to cumulative, the metric restarts with the start time of
+    ///   the delta metric when a new stream is reported: every stream starts
+    ///   again from zero.
+    ///
+    /// If this option is not set, streams are forgotten after 5 minutes.
+    pub fn with_stale_after(mut self, stale_after: Duration) -> Self {
+        self.stale_after = stale_after;
+        self
+    }
+
+    /// Set the maximum number of streams tracked by the converter.
+    ///
+    /// If this option is not set, at most 10 000 streams are tracked.
+    pub fn with_max_streams(mut self, max_streams: usize) -> Self {
+        self.max_streams = max_streams;
+        self
+    }
+
+    /// Create a [TemporalityConverter] with the given config.
+    pub fn build(self) -> TemporalityConverter {
+        TemporalityConverter {
+            target: self.target,
+            stale_after: self.stale_after,
+            max_streams: self.max_streams,
+            state: Mutex::new(ConverterState::default()),
+        }
+    }
+}
+
+/// A [PushMetricExporter] converting the metrics it exports to the
+/// temporality of the exporter it wraps.
+///
+/// Readers aggregate the metrics with the temporality given to
+/// [`TemporalityConvertingExporter::new`], so a single reader can aggregate
+/// metrics in the temporality of one backend and export them to another one.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use opentelemetry_sdk::metrics::{
+///     InMemoryMetricExporterBuilder, PeriodicReader, SdkMeterProvider, Temporality,
+///     TemporalityConvertingExporter,
+/// };
+///
+/// // A backend expecting delta metrics, fed from cumulative aggregations.
+/// let delta_exporter = InMemoryMetricExporterBuilder::new()
+///     .with_temporality(Temporality::Delta)
+///     .build();
+/// let exporter = TemporalityConvertingExporter::new(delta_exporter, Temporality::Cumulative);
+///
+/// let provider = SdkMeterProvider::builder()
+///     .with_reader(PeriodicReader::builder(exporter).build())
+///     .build();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TemporalityConvertingExporter<E> {
+    exporter: E,
+    temporality: Temporality,
+    converter: TemporalityConverter,
+}
+
+impl<E: PushMetricExporter> TemporalityConvertingExporter<E> {
+    /// Wraps `exporter`, which receives the metrics aggregated with
+    /// `temporality` converted to its own temporality.
+    pub fn new(exporter: E, temporality: Temporality) -> Self {
+        let converter = TemporalityConverter::builder(exporter.temporality()).build();
+        TemporalityConvertingExporter {
+            exporter,
+            temporality,
+            converter,
+        }
+    }
+
+    /// Set the duration after which a stream that is not reported is
+    /// forgotten, see [`TemporalityConverterBuilder::with_stale_after`].
+    pub fn with_stale_after(mut self, stale_after: Duration) -> Self {
+        self.converter.stale_after = stale_after;
+        self
+    }
+
+    /// Set the maximum number of streams tracked by the converter, see
+    /// [`TemporalityConverterBuilder::with_max_streams`].
+    pub fn with_max_streams(mut self, max_streams: usize) -> Self {
+        self.converter.max_streams = max_streams;
+        self
+    }
+}
+
+impl<E: PushMetricExporter> PushMetricExporter for TemporalityConvertingExporter<E> {
+    async fn export(&self, metrics: &ResourceMetrics) -> OTelSdkResult {
+        let converted = self.converter.convert(metrics);
+        self.exporter.export(&converted).await
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.exporter.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.exporter.shutdown_with_timeout(timeout)
+    }
+
+    fn temporality(&self) -> Temporality {
+        self.temporality
+    }
+}
+
+type MetricKey = (InstrumentationScope, Cow<'static, str>);
+
+#[derive(Default)]
+struct ConverterState {
+    f64: Metrics<f64>,
+    u64: Metrics<u64>,
+    i64: Metrics<i64>,
+    /// The number of tracked streams.
+    streams: usize,
+    /// Incremented on each conversion, to find the streams without data
+    /// points in a collection.
+    generation: u64,
+}
+
+impl ConverterState {
+    fn evict_stale(&mut self, now: SystemTime, stale_after: Duration) {
+        self.streams = self.f64.evict_stale(now, stale_after)
+            + self.u64.evict_stale(now, stale_after)
+            + self.i64.evict_stale(now, stale_after);
+    }
+}
+
+/// The state of the sums and histograms with values of type `T`.
+#[derive(Default)]
+struct Metrics<T> {
+    sums: HashMap<MetricKey, MetricState<T>>,
+    histograms: HashMap<MetricKey, MetricState<HistogramValue<T>>>,
+}
+
+impl<T> Metrics<T> {
+    /// Forgets the streams not seen since `stale_after`, returning the number
+    /// of remaining streams.
+    fn evict_stale(&mut self, now: SystemTime, stale_after: Duration) -> usize {
+        fn evict<S>(
+            metrics: &mut HashMap<MetricKey, MetricState<S>>,
+            now: SystemTime,
+            stale_after: Duration,
+        ) -> usize {
+            let mut remaining = 0;
+            metrics.retain(|_, metric| {
+                let tracked = metric.streams.len();
+                metric.streams.retain(|_, stream| {
+                    now.duration_since(stream.last_seen).unwrap_or_default() < stale_after
+                });
+                metric.forgot_streams |= metric.streams.len() < tracked;
+                remaining += metric.streams.len();
+                !metric.streams.is_empty()
+            });
+            remaining
+        }
+        evict(&mut self.sums, now, stale_after) + evict(&mut self.histograms, now, stale_after)
+    }
+}
+
+/// The state of the streams of a metric, with values of type `S`.
+struct MetricState<S> {
+    /// The temporality of the collected metric.
+    temporality: Temporality,
+    /// The start time of the collected metric if it is cumulative, of the
+    /// converted metric otherwise.
+    start_time: SystemTime,
+    /// The time of the last collection.
+    time: SystemTime,
+    streams: HashMap<Vec<KeyValue>, Stream<S>>,
+    /// Whether stale streams were forgotten since the state started, so that
+    /// a new stream may be one of them.
+    forgot_streams: bool,
+}
+
+impl<S> MetricState<S> {
+    /// Restarts the state of every stream from `start_time`.
+    fn restart(&mut self, temporality: Temporality, start_time: SystemTime, streams: &mut usize) {
+        *streams -= self.streams.len();
+        self.temporality = temporality;
+        self.start_time = start_time;
+        self.time = start_time;
+        self.streams.clear();
+        self.forgot_streams = false;
+    }
+
+    /// Whether one of the `attributes` identifies a stream that is not tracked.
+    fn has_new_stream<'a>(&self, mut attributes: impl Iterator<Item = &'a Vec<KeyValue>>) -> bool {
+        attributes.any(|attributes| !self.streams.contains_key(&sorted(attributes)))
+    }
+}
+
+impl<T: Number> MetricState<HistogramValue<T>> {
+    /// Whether one of the `data_points` changes the bucket boundaries of its
+    /// stream.
+    fn has_changed_bounds(&self, data_points: &[HistogramDataPoint<T>]) -> bool {
+        data_points.iter().any(|data_point| {
+            self.streams
+                .get(&sorted(&data_point.attributes))
+                .is_some_and(|stream| !stream.value.has_bounds_of(data_point))
+        })
+    }
+}
+
+/// The last cumulative value of a stream.
+struct Stream<S> {
+    value: S,
+    last_seen: SystemTime,
+    generation: u64,
+}
+
+struct HistogramValue<T> {
+    count: u64,
+    bounds: Vec<f64>,
+    bucket_counts: Vec<u64>,
+    min: Option<T>,
+    max: Option<T>,
+    sum: T,
+}
+
+impl<T: Number> HistogramValue<T> {
+    fn new(data_point: &HistogramDataPoint<T>) -> Self {
+        HistogramValue {
+            count: data_point.count,
+            bounds: data_point.bounds.clone(),
+            bucket_counts: data_point.bucket_counts.clone(),
+            min: data_point.min,
+            max: data_point.max,
+            sum: data_point.sum,
+        }
+    }
+
+    fn has_bounds_of(&self, data_point: &HistogramDataPoint<T>) -> bool {
+        self.bounds == data_point.bounds
+            && self.bucket_counts.len() == data_point.bucket_counts.len()
+    }
+
+    /// Returns the difference from this value to the cumulative
+    /// `data_point`, or `None` if the stream was reset in between.
+    fn delta_to(&self, data_point: &HistogramDataPoint<T>) -> Option<HistogramDataPoint<T>> {
+        if !self.has_bounds_of(data_point) {
+            return None;
+        }
+        let count = data_point.count.checked_sub(self.count)?;
+        let bucket_counts = self
+            .bucket_counts
+            .iter()
+            .zip(&data_point.bucket_counts)
+            .map(|(previous, current)| current.checked_sub(*previous))
+            .collect::<Option<Vec<_>>>()?;
+        let sum = cumulative_delta(self.sum, data_point.sum, false)?;
+        Some(HistogramDataPoint {
+            attributes: data_point.attributes.clone(),
+            count,
+            bounds: data_point.bounds.clone(),
+            bucket_counts,
+            // The extremes of the interval are unknown.
+            min: None,
+            max: None,
+            sum,
+            exemplars: data_point.exemplars.clone(),
+        })
+    }
+
+    /// Adds the delta `data_point`, with the same bucket boundaries, to this
+    /// value.
+    fn accumulate(&mut self, data_point: &HistogramDataPoint<T>) {
+        self.count = self.count.wrapping_add(data_point.count);
+        for (total, count) in self.bucket_counts.iter_mut().zip(&data_point.bucket_counts) {
+            *total = total.wrapping_add(*count);
+        }
+        self.sum = self.sum.wrapping_add(data_point.sum);
+        self.min = match (self.min, data_point.min) {
+            (Some(min), Some(other)) if other < min => Some(other),
+            (min, other) => min.or(other),
+        };
+        self.max = match (self.max, data_point.max) {
+            (Some(max), Some(other)) if other > max => Some(other),
+            (max, other) => max.or(other),
+        };
+    }
+
+    fn to_data_point(
+        &self,
+        attributes: Vec<KeyValue>,
+        exemplars: Vec<Exemplar<T>>,
+    ) -> HistogramDataPoint<T> {
+        HistogramDataPoint {
+            attributes,
+            count: self.count,
+            bounds: self.bounds.clone(),
+            bucket_counts: self.bucket_counts.clone(),
+            min: self.min,
+            max: self.max,
+            sum: self.sum,
+            exemplars,
+        }
+    }
+}
+
+/// A single call to [`TemporalityConverter::convert`].
+struct Conversion {
+    target: Temporality,
+    max_streams: usize,
+    now: SystemTime,
+    generation: u64,
+    /// The number of data points dropped because of the stream limit.
+    dropped: usize,
+}
+
+impl Conversion {
+    fn convert_data<T: Number>(
+        &mut self,
+        data: &MetricData<T>,
+        metrics: &mut Metrics<T>,
+        key: MetricKey,
+        streams: &mut usize,
+    ) -> MetricData<T> {
+        match data {
+            MetricData::Sum(sum) if sum.temporality != self.target_for(sum.is_monotonic) => {
+                let (metric, is_new) = metric_state(
+                    &mut metrics.sums,
+                    key,
+                    sum.temporality,
+                    sum.start_time,
+                    streams,
+                );
+                if sum.temporality == Temporality::Cumulative {
+                    self.sum_to_delta(sum, metric, is_new, streams).into()
+                } else {
+                    self.sum_to_cumulative(sum, metric, streams).into()
+                }
+            }
+            MetricData::Histogram(histogram) if histogram.temporality != self.target_for(true) => {
+                let (metric, is_new) = metric_state(
+                    &mut metrics.histograms,
+                    key,
+                    histogram.temporality,
+                    histogram.start_time,
+                    streams,
+                );
+                if histogram.temporality == Temporality::Cumulative {
+                    self.histogram_to_delta(histogram, metric, is_new, streams)
+                        .into()
+                } else {
+                    self.histogram_to_cumulative(histogram, metric, streams)
+                        .into()
+                }
+            }
+            MetricData::Gauge(gauge) => gauge.clone().into(),
+            MetricData::Sum(sum) => sum.clone().into(),
+            MetricData::Histogram(histogram) => histogram.clone().into(),
+            MetricData::ExponentialHistogram(histogram) => histogram.clone().into(),
+        }
+    }
+
+    /// Returns the temporality of converted sums or histograms.
+    fn target_for(&self, is_monotonic: bool) -> Temporality {
+        match self.target {
+            Temporality::LowMemory if is_monotonic => Temporality::Delta,
+            Temporality::LowMemory => Temporality::Cumulative,
+            target => target,
+        }
+    }
+
+    fn sum_to_delta<T: Number>(
+        &mut self,
+        sum: &Sum<T>,
+        metric: &mut MetricState<T>,
+        is_new: bool,
+        streams: &mut usize,
+    ) -> Sum<T> {
+        let start_time = if is_new { sum.start_time } else { metric.time };
+        metric.time = sum.time;
+        // The whole value of a new stream is in the interval if it was zero at
+        // the previous conversion, which is unknown for a forgotten stream.
+        let report_new_streams = !metric.forgot_streams || sum.start_time >= start_time;
+
+        let mut data_points = Vec::with_capacity(sum.data_points.len());
+        for data_point in &sum.data_points {
+            let value = match metric.streams.entry(sorted(&data_point.attributes)) {
+                Entry::Occupied(entry) => {
+                    let stream = entry.into_mut();
+                    let delta = cumulative_delta(stream.value, data_point.value, sum.is_monotonic);
+                    stream.value = data_point.value;
+                    stream.last_seen = self.now;
+                    delta.unwrap_or(data_point.value)
+                }
+                Entry::Vacant(entry) => {
+                    if !self.reserve_stream(streams) {
+                        continue;
+                    }
+                    entry.insert(self.new_stream(data_point.value));
+                    if !report_new_streams {
+                        continue;
+                    }
+                    data_point.value
+                }
+            };
+            data_points.push(SumDataPoint {
+                attributes: data_point.attributes.clone(),
+                value,
+                exemplars: data_point.exemplars.clone(),
+            });
+        }
+
+        Sum {
+            data_points,
+            start_time,
+            time: sum.time,
+            temporality: Temporality::Delta,
+            is_monotonic: sum.is_monotonic,
+        }
+    }
+
+    fn sum_to_cumulative<T: Number>(
+        &mut self,
+        sum: &Sum<T>,
+        metric: &mut MetricState<T>,
+        streams: &mut usize,
+    ) -> Sum<T> {
+        if metric.forgot_streams
+            && metric.has_new_stream(sum.data_points.iter().map(|dp| &dp.attributes))
+        {
+            metric.restart(sum.temporality, sum.start_time, streams);
+        }
+        metric.time = sum.time;
+
+        let mut data_points = Vec::with_capacity(metric.streams.len());
+        for data_point in &sum.data_points {
+            let value = match metric.streams.entry(sorted(&data_point.attributes)) {
+                Entry::Occupied(entry) => {
+                    let stream = entry.into_mut();
+                    stream.value = stream.value.wrapping_add(data_point.value);
+                    stream.last_seen = self.now;
+                    stream.generation = self.generation;
+                    stream.value
+                }
+                Entry::Vacant(entry) => {
+                    if !self.reserve_stream(streams) {
+                        continue;
+                    }
+                    entry.insert(self.new_stream(data_point.value));
+                    data_point.value
+                }
+            };
+            data_points.push(SumDataPoint {
+                attributes: data_point.attributes.clone(),
+                value,
+                exemplars: data_point.exemplars.clone(),
+            });
+        }
+        // Streams without data point in this collection keep their value.
+        for (attributes, stream) in &metric.streams {
+            if stream.generation != self.generation {
+                data_points.push(SumDataPoint::new(attributes.clone(), stream.value));
+            }
+        }
+
+        Sum {
+            data_points,
+            start_time: metric.start_time,
+            time: sum.time,
+            temporality: Temporality::Cumulative,
+            is_monotonic: sum.is_monotonic,
+        }
+    }
+
+    fn histogram_to_delta<T: Number>(
+        &mut self,
+        histogram: &Histogram<T>,
+        metric: &mut MetricState<HistogramValue<T>>,
+        is_new: bool,
+        streams: &mut usize,
+    ) -> Histogram<T> {
+        let start_time = if is_new {
+            histogram.start_time
+        } else {
+            metric.time
+        };
+        metric.time = histogram.time;
+        // See `sum_to_delta`.
+        let report_new_streams = !metric.forgot_streams || histogram.start_time >= start_time;
+
+        let mut data_points = Vec::with_capacity(histogram.data_points.len());
+        for data_point in &histogram.data_points {
+            let delta = match metric.streams.entry(sorted(&data_point.attributes)) {
+                Entry::Occupied(entry) => {
+                    let stream = entry.into_mut();
+                    let delta = stream.value.delta_to(data_point);
+                    stream.value = HistogramValue::new(data_point);
+                    stream.last_seen = self.now;
+                    delta.unwrap_or_else(|| data_point.clone())
+                }
+                Entry::Vacant(entry) => {
+                    if !self.reserve_stream(streams) {
+                        continue;
+                    }
+                    entry.insert(self.new_stream(HistogramValue::new(data_point)));
+                    if !report_new_streams {
+                        continue;
+                    }
+                    data_point.clone()
+                }
+            };
+            data_points.push(delta);
+        }
+
+        Histogram {
+            data_points,
+            start_time,
+            time: histogram.time,
+            temporality: Temporality::Delta,
+        }
+    }
+
+    fn histogram_to_cumulative<T: Number>(
+        &mut self,
+        histogram: &Histogram<T>,
+        metric: &mut MetricState<HistogramValue<T>>,
+        streams: &mut usize,
+    ) -> Histogram<T> {
+        // A change of the bucket boundaries restarts the metric, like a
+        // forgotten stream reported again.
+        if (metric.forgot_streams
+            && metric.has_new_stream(histogram.data_points.iter().map(|dp| &dp.attributes)))
+            || metric.has_changed_bounds(&histogram.data_points)
+        {
+            metric.restart(histogram.temporality, histogram.start_time, streams);
+        }
+        metric.time = histogram.time;
+
+        let mut data_points = Vec::with_capacity(metric.streams.len());
+        for data_point in &histogram.data_points {
+            let value = match metric.streams.entry(sorted(&data_point.attributes)) {
+                Entry::Occupied(entry) => {
+                    let stream = entry.into_mut();
+                    stream.value.accumulate(data_point);
+                    stream.last_seen = self.now;
+                    stream.generation = self.generation;
+                    &stream.value
+                }
+                Entry::Vacant(entry) => {
+                    if !self.reserve_stream(streams) {
+                        continue;
+                    }
+                    &entry
+                        .insert(self.new_stream(HistogramValue::new(data_point)))
+                        .value
+                }
+            };
+            data_points.push(
+                value.to_data_point(data_point.attributes.clone(), data_point.exemplars.clone()),
+            );
+        }
+        // Streams without data point in this collection keep their value.
+        for (attributes, stream) in &metric.streams {
+            if stream.generation != self.generation {
+                data_points.push(stream.value.to_data_point(attributes.clone(), Vec::new()));
+            }
+        }
+
+        Histogram {
+            data_points,
+            start_time: metric.start_time,
+            time: histogram.time,
+            temporality: Temporality::Cumulative,
+        }
+    }
+
+    /// Accounts for a new stream, returning `false` and dropping its data
+    /// point if the limit of streams is reached.
+    fn reserve_stream(&mut self, streams: &mut usize) -> bool {
+        if *streams >= self.max_streams {
+            self.dropped += 1;
+            return false;
+        }
+        *streams += 1;
+        true
+    }
+
+    fn new_stream<S>(&self, value: S) -> Stream<S> {
+        Stream {
+            value,
+            last_seen: self.now,
+            generation: self.generation,
+        }
+    }
+}
+
+/// Returns the state of the metric at `key`, and whether it was just created.
+///
+/// The state is restarted when the temporality of the collected metric
+/// changes, or when the start time of a cumulative metric changes.
+fn metric_state<'a, S>(
+    metrics: &'a mut HashMap<MetricKey, MetricState<S>>,
+    key: MetricKey,
+    temporality: Temporality,
+    start_time: SystemTime,
+    streams: &mut usize,
+) -> (&'a mut MetricState<S>, bool) {
+    match metrics.entry(key) {
+        Entry::Occupied(entry) => {
+            let metric = entry.into_mut();
+            let restarted = metric.temporality != temporality
+                || (temporality == Temporality::Cumulative && metric.start_time != start_time);
+            if restarted {
+                metric.restart(temporality, start_time, streams);
+            }
+            (metric, restarted)
+        }
+        Entry::Vacant(entry) => (
+            entry.insert(MetricState {
+                temporality,
+                start_time,
+                time: start_time,
+                streams: HashMap::new(),
+                forgot_streams: false,
+            }),
+            true,
+        ),
+    }
+}
+
+/// Returns the increase from the `previous` to the `current` cumulative
+/// value, or `None` if the stream was reset in between.
+fn cumulative_delta<T: Number>(previous: T, current: T, is_monotonic: bool) -> Option<T> {
+    // Unsigned values can only decrease with a reset.
+    let is_unsigned = <T as Number>::min() == T::default();
+    if current < previous && (is_monotonic || is_unsigned) {
+        None
+    } else {
+        Some(current.wrapping_sub(previous))
+    }
+}
+
+fn sorted(attributes: &[KeyValue]) -> Vec<KeyValue> {
+    let mut sorted = attributes.to_vec();
+    sorted.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{InMemoryMetricExporterBuilder, PeriodicReader, SdkMeterProvider};
+    use opentelemetry::metrics::MeterProvider as _;
+
+    fn sums(
+        temporality: Temporality,
+        start_time: SystemTime,
+        points: &[(&'static str, u64)],
+    ) -> ResourceMetrics {
+        let data_points = points
+            .iter()
+            .map(|(k, v)| SumDataPoint::new(vec![KeyValue::new("k", *k)], *v))
+            .collect();
+        let sum = Sum::new(
+            data_points,
+            start_time,
+            SystemTime::now(),
+            temporality,
+            true,
+        );
+        ResourceMetrics {
+            resource: crate::Resource::empty(),
+            scope_metrics: vec![ScopeMetrics::new(
+                InstrumentationScope::builder("test").build(),
+                vec![Metric::new("counter", "", "", MetricData::from(sum))],
+            )],
+        }
+    }
+
+    fn sum_values(metrics: &ResourceMetrics) -> (Temporality, Vec<(String, u64)>) {
+        let metric = &metrics.scope_metrics[0].metrics[0];
+        let AggregatedMetrics::U64(MetricData::Sum(sum)) = &metric.data else {
+            panic!("expected a u64 sum, got {:?}", metric.data);
+        };
+        let mut values: Vec<_> = sum
+            .data_points
+            .iter()
+            .map(|dp| {
+                let key = dp.attributes.first().map(|kv| kv.value.to_string());
+                (key.unwrap_or_default(), dp.value)
+            })
+            .collect();
+        values.sort();
+        (sum.temporality, values)
+    }
+
+    fn values(points: &[(&str, u64)]) -> Vec<(String, u64)> {
+        points.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn cumulative_sums_are_converted_to_delta() {
+        let converter = TemporalityConverter::builder(Temporality::Delta).build();
+        let start = SystemTime::now();
+        let restart = start + Duration::from_secs(1);
+
+        let first = converter.convert(&sums(Temporality::Cumulative, start, &[("a", 5)]));
+        assert_eq!(
+            sum_values(&first),
+            (Temporality::Delta, values(&[("a", 5)]))
+        );
+
+        let second =
+            converter.convert(&sums(Temporality::Cumulative, start, &[("a", 8), ("b", 2)]));
+        assert_eq!(
+            sum_values(&second),
+            (Temporality::Delta, values(&[("a", 3), ("b", 2)]))
+        );
+
+        // A monotonic sum decreasing was reset.
+        let third = converter.convert(&sums(Temporality::Cumulative, start, &[("a", 4)]));
+        assert_eq!(
+            sum_values(&third),
+            (Temporality::Delta, values(&[("a", 4)]))
+        );
+
+        // A new start time restarts every stream.
+        let fourth = converter.convert(&sums(
+            Temporality::Cumulative,
+            restart,
+            &[("a", 6), ("b", 3)],
+        ));
+        assert_eq!(
+            sum_values(&fourth),
+            (Temporality::Delta, values(&[("a", 6), ("b", 3)]))
+        );
+    }
+
+    #[test]
+    fn delta_sums_are_converted_to_cumulative() {
+        let converter = TemporalityConverter::builder(Temporality::Cumulative).build();
+        let start = SystemTime::now();
+
+        converter.convert(&sums(Temporality::Delta, start, &[("a", 2), ("b", 1)]));
+        let second = converter.convert(&sums(Temporality::Delta, start, &[("a", 3)]));
+
+        // Streams without data points keep their value.
+        assert_eq!(
+            sum_values(&second),
+            (Temporality::Cumulative, values(&[("a", 5), ("b", 1)]))
+        );
+        let metric = &second.scope_metrics[0].metrics[0];
+        let AggregatedMetrics::U64(MetricData::Sum(sum)) = &metric.data else {
+            panic!("expected a u64 sum, got {:?}", metric.data);
+        };
+        assert_eq!(sum.start_time, start);
+    }
+
+    #[test]
+    fn stale_streams_are_forgotten() {
+        let converter = TemporalityConverter::builder(Temporality::Cumulative)
+            .with_stale_after(Duration::ZERO)
+            .build();
+        let start = SystemTime::now();
+
+        converter.convert(&sums(Temporality::Delta, start, &[("a", 2), ("b", 1)]));
+        let second = converter.convert(&sums(Temporality::Delta, start, &[("a", 3)]));
+
+        assert_eq!(
+            sum_values(&second),
+            (Temporality::Cumulative, values(&[("a", 3)]))
+        );
+    }
+
+    #[test]
+    fn delta_sums_restart_when_a_forgotten_stream_is_reported_again() {
+        let converter = TemporalityConverter::builder(Temporality::Cumulative)
+            .with_stale_after(Duration::from_millis(20))
+            .build();
+        let start = SystemTime::now();
+        let second_start = start + Duration::from_secs(1);
+        let third_start = start + Duration::from_secs(2);
+
+        converter.convert(&sums(Temporality::Delta, start, &[("a", 2), ("b", 1)]));
+        std::thread::sleep(Duration::from_millis(50));
+        // "a" stays live while "b" goes stale and is forgotten.
+        let second = converter.convert(&sums(Temporality::Delta, second_start, &[("a", 3)]));
+        assert_eq!(
+            sum_values(&second),
+            (Temporality::Cumulative, values(&[("a", 5), ("b", 1)]))
+        );
+
+        // "b" reported again restarts every stream with a new start time.
+        let third = converter.convert(&sums(
+            Temporality::Delta,
+            third_start,
+            &[("a", 1), ("b", 4)],
+        ));
+        assert_eq!(
+            sum_values(&third),
+            (Temporality::Cumulative, values(&[("a", 1), ("b", 4)]))
+        );
+        let AggregatedMetrics::U64(MetricData::Sum(sum)) = &third.scope_metrics[0].metrics[0].data
+        else {
+            panic!("expected a u64 sum");
+        };
+        assert_eq!(sum.start_time, third_start);
+    }
+
+    #[test]
+    fn cumulative_sums_skip_the_first_point_of_a_forgotten_stream() {
+        let converter = TemporalityConverter::builder(Temporality::Delta)
+            .with_stale_after(Duration::from_millis(20))
+            .build();
+        let start = SystemTime::now();
+
+        converter.convert(&sums(Temporality::Cumulative, start, &[("a", 2), ("b", 1)]));
+        std::thread::sleep(Duration::from_millis(50));
+        // "a" stays live while "b" goes stale and is forgotten.
+        let second = converter.convert(&sums(Temporality::Cumulative, start, &[("a", 3)]));
+        assert_eq!(
+            sum_values(&second),
+            (Temporality::Delta, values(&[("a", 1)]))
+        );
+
+        // The increase of "b" since it was forgotten is unknown.
+        let third = converter.convert(&sums(Temporality::Cumulative, start, &[("a", 4), ("b", 9)]));
+        assert_eq!(
+            sum_values(&third),
+            (Temporality::Delta, values(&[("a", 1)]))
+        );
+        let fourth = converter.convert(&sums(
+            Temporality::Cumulative,
+            start,
+            &[("a", 4), ("b", 12)],
+        ));
+        assert_eq!(
+            sum_values(&fourth),
+            (Temporality::Delta, values(&[("a", 0), ("b", 3)]))
+        );
+    }
+
+    #[test]
+    fn accumulated_values_wrap_around_on_overflow() {
+        let converter = TemporalityConverter::builder(Temporality::Cumulative).build();
+        let start = SystemTime::now();
+
+        converter.convert(&sums(Temporality::Delta, start, &[("a", u64::MAX)]));
+        let second = converter.convert(&sums(Temporality::Delta, start, &[("a", 2)]));
+        assert_eq!(
+            sum_values(&second),
+            (Temporality::Cumulative, values(&[("a", 1)]))
+        );
+    }
+
+    #[test]
+    fn new_streams_over_limit_are_dropped() {
+        let converter = TemporalityConverter::builder(Temporality::Delta)
+            .with_max_streams(1)
+            .build();
+        let start = SystemTime::now();
+
+        let first = converter.convert(&sums(Temporality::Cumulative, start, &[("a", 1), ("b", 1)]));
+        assert_eq!(sum_values(&first).1.len(), 1);
+    }
+
+    #[test]
+    fn cumulative_histograms_are_converted_to_delta() {
+        let converter = TemporalityConverter::builder(Temporality::Delta).build();
+        let start = SystemTime::now();
+        let histogram = |count, bucket_counts, sum| {
+            let data_point = HistogramDataPoint::new(
+                vec![],
+                count,
+                vec![10.0],
+                bucket_counts,
+                Some(1.0),
+                Some(20.0),
+                sum,
+            );
+            let histogram = Histogram::new(
+                vec![data_point],
+                start,
+                SystemTime::now(),
+                Temporality::Cumulative,
+            );
+            ResourceMetrics {
+                resource: crate::Resource::empty(),
+                scope_metrics: vec![ScopeMetrics::new(
+                    InstrumentationScope::builder("test").build(),
+                    vec![Metric::new(
+                        "histogram",
+                        "",
+                        "",
+                        MetricData::from(histogram),
+                    )],
+                )],
+            }
+        };
+
+        converter.convert(&histogram(2, vec![1, 1], 21.0));
+        let second = converter.convert(&histogram(5, vec![2, 3], 50.0));
+
+        let metric = &second.scope_metrics[0].metrics[0];
+        let AggregatedMetrics::F64(MetricData::Histogram(histogram)) = &metric.data else {
+            panic!("expected a f64 histogram, got {:?}", metric.data);
+        };
+        assert_eq!(histogram.temporality, Temporality::Delta);
+        let data_point = &histogram.data_points[0];
+        assert_eq!(data_point.count, 3);
+        assert_eq!(data_point.bucket_counts, vec![1, 2]);
+        assert_eq!(data_point.sum, 29.0);
+        assert_eq!((data_point.min, data_point.max), (None, None));
+    }
+
+    #[test]
+    fn delta_histograms_restart_when_the_bounds_change() {
+        let converter = TemporalityConverter::builder(Temporality::Cumulative).build();
+        let histogram = |start_time, bounds: Vec<f64>, bucket_counts: Vec<u64>| {
+            let count = bucket_counts.iter().sum();
+            let data_point =
+                HistogramDataPoint::new(vec![], count, bounds, bucket_counts, None, None, 1.0);
+            let histogram = Histogram::new(
+                vec![data_point],
+                start_time,
+                SystemTime::now(),
+                Temporality::Delta,
+            );
+            ResourceMetrics {
+                resource: crate::Resource::empty(),
+                scope_metrics: vec![ScopeMetrics::new(
+                    InstrumentationScope::builder("test").build(),
+                    vec![Metric::new(
+                        "histogram",
+                        "",
+                        "",
+                        MetricData::from(histogram),
+                    )],
+                )],
+            }
+        };
+        let cumulative = |metrics: &ResourceMetrics| {
+            let metric = &metrics.scope_metrics[0].metrics[0];
+            let AggregatedMetrics::F64(MetricData::Histogram(histogram)) = &metric.data else {
+                panic!("expected a f64 histogram, got {:?}", metric.data);
+            };
+            let data_point = &histogram.data_points[0];
+            (
+                histogram.start_time,
+                data_point.bounds.clone(),
+                data_point.bucket_counts.clone(),
+            )
+        };
+        let start = SystemTime::now();
+        let restart = start + Duration::from_secs(10);
+
+        converter.convert(&histogram(start, vec![10.0], vec![1, 1]));
+        let second = converter.convert(&histogram(start, vec![10.0], vec![1, 0]));
+        assert_eq!(cumulative(&second), (start, vec![10.0], vec![2, 1]));
+
+        let third = converter.convert(&histogram(restart, vec![5.0, 10.0], vec![1, 0, 1]));
+        assert_eq!(
+            cumulative(&third),
+            (restart, vec![5.0, 10.0], vec![1, 0, 1])
+        );
+
+        let fourth = converter.convert(&histogram(restart, vec![5.0, 10.0], vec![0, 1, 0]));
+        assert_eq!(
+            cumulative(&fourth),
+            (restart, vec![5.0, 10.0], vec![1, 1, 1])
+        );
+    }
+
+    #[test]
+    fn converting_exporter_exports_in_its_temporality() {
+        let delta_exporter = InMemoryMetricExporterBuilder::new()
+            .with_temporality(Temporality::Delta)
+            .build();
+        let exporter =
+            TemporalityConvertingExporter::new(delta_exporter.clone(), Temporality::Cumulative);
+        assert_eq!(exporter.temporality(), Temporality::Cumulative);
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter).build())
+            .build();
+        let counter = provider.meter("test").u64_counter("counter").build();
+
+        counter.add(5, &[]);
+        provider.force_flush().unwrap();
+        counter.add(2, &[]);
+        provider.force_flush().unwrap();
+
+        let exported: Vec<_> = delta_exporter
+            .get_finished_metrics()
+            .unwrap()
+            .iter()
+            .map(sum_values)
+            .collect();
+        assert_eq!(
+            exported,
+            [
+                (Temporality::Delta, vec![(String::new(), 5)]),
+                (Temporality::Delta, vec![(String::new(), 2)]),
+            ]
+        );
+    }
+}
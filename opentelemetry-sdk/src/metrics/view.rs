@@ -1,4 +1,7 @@
-use super::instrument::{Instrument, Stream};
+use std::{borrow::Cow, error::Error};
+
+use super::instrument::{Instrument, InstrumentKind, Stream};
+use crate::util::wildcard_match;
 
 /// Used to customize the metrics that are output by the SDK.
 ///
@@ -36,7 +39,10 @@ use super::instrument::{Instrument, Stream};
 /// let provider = SdkMeterProvider::builder().with_view(my_view).build();
 /// # drop(provider)
 /// ```
-pub(crate) trait View: Send + Sync + 'static {
+///
+/// Views that can be described as data rather than code are represented by
+/// [View], which pairs an [InstrumentSelector] with a [Stream].
+pub(crate) trait ViewMatcher: Send + Sync + 'static {
     /// Defines how data should be collected for certain instruments.
     ///
     /// Return [Stream] to use for matching [Instrument]s,
@@ -44,7 +50,7 @@ pub(crate) trait View: Send + Sync + 'static {
     fn match_inst(&self, inst: &Instrument) -> Option<Stream>;
 }
 
-impl<T> View for T
+impl<T> ViewMatcher for T
 where
     T: Fn(&Instrument) -> Option<Stream> + Send + Sync + 'static,
 {
@@ -52,3 +58,285 @@ where
         self(inst)
     }
 }
+
+/// Selects the instruments a [View] applies to.
+///
+/// An instrument is selected if it matches every criterion that is set. The
+/// name criterion supports the wildcards `*`, matching any number of
+/// characters, and `?`, matching exactly one character, and is compared
+/// case-insensitively as instrument names are. All other criteria must match
+/// exactly.
+///
+/// # Example
+///
+/// ```
+/// use opentelemetry_sdk::metrics::{InstrumentKind, InstrumentSelector};
+///
+/// let selector = InstrumentSelector::new()
+///     .with_name("http.server.*")
+///     .with_kind(InstrumentKind::Histogram)
+///     .with_meter_name("my-library");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct InstrumentSelector {
+    name: Option<Cow<'static, str>>,
+    kind: Option<InstrumentKind>,
+    unit: Option<Cow<'static, str>>,
+    meter_name: Option<Cow<'static, str>>,
+    meter_version: Option<Cow<'static, str>>,
+    meter_schema_url: Option<Cow<'static, str>>,
+}
+
+impl InstrumentSelector {
+    /// Create a new selector without any criteria.
+    ///
+    /// At least one criterion must be set before the selector can be used to
+    /// create a [View].
+    pub fn new() -> Self {
+        InstrumentSelector::default()
+    }
+
+    /// Select instruments whose name matches `name`, which may contain the
+    /// wildcards `*` and `?`.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Select instruments of the given [InstrumentKind].
+    pub fn with_kind(mut self, kind: InstrumentKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Select instruments with the given unit.
+    pub fn with_unit(mut self, unit: impl Into<Cow<'static, str>>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// Select instruments created by a meter with the given name.
+    pub fn with_meter_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.meter_name = Some(name.into());
+        self
+    }
+
+    /// Select instruments created by a meter with the given version.
+    pub fn with_meter_version(mut self, version: impl Into<Cow<'static, str>>) -> Self {
+        self.meter_version = Some(version.into());
+        self
+    }
+
+    /// Select instruments created by a meter with the given schema URL.
+    pub fn with_meter_schema_url(mut self, schema_url: impl Into<Cow<'static, str>>) -> Self {
+        self.meter_schema_url = Some(schema_url.into());
+        self
+    }
+
+    /// Returns `true` if the instrument matches every criterion of this
+    /// selector.
+    pub fn matches(&self, inst: &Instrument) -> bool {
+        let scope = inst.scope();
+        self.name
+            .as_deref()
+            .map_or(true, |pattern| wildcard_match(pattern, inst.name(), true))
+            && self.kind.map_or(true, |kind| kind == inst.kind())
+            && self
+                .unit
+                .as_deref()
+                .map_or(true, |unit| unit == inst.unit())
+            && self
+                .meter_name
+                .as_deref()
+                .map_or(true, |name| name == scope.name())
+            && self
+                .meter_version
+                .as_deref()
+                .map_or(true, |version| Some(version) == scope.version())
+            && self
+                .meter_schema_url
+                .as_deref()
+                .map_or(true, |url| Some(url) == scope.schema_url())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.kind.is_none()
+            && self.unit.is_none()
+            && self.meter_name.is_none()
+            && self.meter_version.is_none()
+            && self.meter_schema_url.is_none()
+    }
+
+    /// Returns `true` if the selector can match more than one instrument
+    /// name.
+    fn has_wildcard(&self) -> bool {
+        self.name
+            .as_deref()
+            .map_or(true, |name| name.contains(['*', '?']))
+    }
+}
+
+/// A view described as data: the [Stream] applied to every instrument
+/// selected by an [InstrumentSelector].
+///
+/// Unlike closures passed to [`MeterProviderBuilder::with_view`], a `View` can
+/// be inspected, compared and built from configuration.
+///
+/// # Example
+///
+/// ```
+/// use opentelemetry_sdk::metrics::{
+///     Aggregation, InstrumentSelector, SdkMeterProvider, Stream, View,
+/// };
+///
+/// let view = View::new(
+///     InstrumentSelector::new().with_name("http.server.duration"),
+///     Stream::builder()
+///         .with_aggregation(Aggregation::ExplicitBucketHistogram {
+///             boundaries: vec![0.0, 0.1, 0.5, 1.0, 5.0],
+///             record_min_max: true,
+///         })
+///         .build()
+///         .unwrap(),
+/// )
+/// .unwrap();
+///
+/// let provider = SdkMeterProvider::builder()
+///     .with_declarative_view(view)
+///     .build();
+/// # drop(provider)
+/// ```
+///
+/// [`MeterProviderBuilder::with_view`]: crate::metrics::MeterProviderBuilder::with_view
+#[derive(Clone, Debug, PartialEq)]
+pub struct View {
+    selector: InstrumentSelector,
+    stream: Stream,
+}
+
+impl View {
+    /// Create a new view applying `stream` to the instruments selected by
+    /// `selector`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the selector has no criteria, or if the stream sets
+    /// a name while the selector can match more than one instrument, as that
+    /// would produce conflicting streams with the same name.
+    pub fn new(selector: InstrumentSelector, stream: Stream) -> Result<Self, Box<dyn Error>> {
+        if selector.is_empty() {
+            return Err("Instrument selector must set at least one criterion".into());
+        }
+        if stream.name.is_some() && selector.has_wildcard() {
+            return Err(
+                "Stream name can only be set if the instrument selector matches a single instrument name"
+                    .into(),
+            );
+        }
+        Ok(View { selector, stream })
+    }
+
+    /// The selector of the instruments this view applies to.
+    pub fn selector(&self) -> &InstrumentSelector {
+        &self.selector
+    }
+
+    /// The stream applied to the selected instruments.
+    pub fn stream(&self) -> &Stream {
+        &self.stream
+    }
+}
+
+impl ViewMatcher for View {
+    fn match_inst(&self, inst: &Instrument) -> Option<Stream> {
+        self.selector.matches(inst).then(|| self.stream.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::InstrumentationScope;
+
+    fn instrument(name: &'static str, kind: InstrumentKind, unit: &'static str) -> Instrument {
+        Instrument {
+            name: name.into(),
+            description: "".into(),
+            kind,
+            unit: unit.into(),
+            scope: InstrumentationScope::builder("my-meter")
+                .with_version("1.0")
+                .with_schema_url("https://opentelemetry.io/schemas/1.0.0")
+                .build(),
+        }
+    }
+
+    #[test]
+    fn selector_matches_all_criteria() {
+        let inst = instrument("http.server.duration", InstrumentKind::Histogram, "s");
+
+        assert!(InstrumentSelector::new().with_name("http.*").matches(&inst));
+        assert!(InstrumentSelector::new()
+            .with_kind(InstrumentKind::Histogram)
+            .with_unit("s")
+            .with_meter_name("my-meter")
+            .with_meter_version("1.0")
+            .with_meter_schema_url("https://opentelemetry.io/schemas/1.0.0")
+            .matches(&inst));
+
+        assert!(!InstrumentSelector::new()
+            .with_name("http.*")
+            .with_kind(InstrumentKind::Counter)
+            .matches(&inst));
+        assert!(!InstrumentSelector::new().with_unit("ms").matches(&inst));
+        assert!(!InstrumentSelector::new()
+            .with_meter_name("other-meter")
+            .matches(&inst));
+        assert!(!InstrumentSelector::new()
+            .with_meter_version("2.0")
+            .matches(&inst));
+    }
+
+    #[test]
+    fn view_validation() {
+        let renamed = || Stream::builder().with_name("renamed").build().unwrap();
+
+        assert!(View::new(InstrumentSelector::new(), Stream::default()).is_err());
+        assert!(View::new(InstrumentSelector::new().with_name("http.*"), renamed()).is_err());
+        assert!(View::new(
+            InstrumentSelector::new().with_kind(InstrumentKind::Counter),
+            renamed()
+        )
+        .is_err());
+        assert!(View::new(InstrumentSelector::new().with_name("requests"), renamed()).is_ok());
+        assert!(View::new(
+            InstrumentSelector::new().with_name("http.*"),
+            Stream::builder()
+                .with_cardinality_limit(10)
+                .build()
+                .unwrap()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn view_matches_selected_instruments() {
+        let view = View::new(
+            InstrumentSelector::new().with_kind(InstrumentKind::Counter),
+            Stream::builder()
+                .with_cardinality_limit(10)
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        let stream = view
+            .match_inst(&instrument("requests", InstrumentKind::Counter, ""))
+            .expect("counter is selected");
+        assert_eq!(stream.cardinality_limit, Some(10));
+        assert!(view
+            .match_inst(&instrument("latency", InstrumentKind::Histogram, "ms"))
+            .is_none());
+    }
+}
@@ -1,6 +1,11 @@
 //! OpenTelemetry Propagators
 mod baggage;
+mod registry;
 mod trace_context;
 
 pub use baggage::BaggagePropagator;
+pub use registry::{
+    register_text_map_propagator, text_map_propagator_from_env, text_map_propagator_from_names,
+    OTEL_PROPAGATORS,
+};
 pub use trace_context::TraceContextPropagator;
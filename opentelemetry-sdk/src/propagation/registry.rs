@@ -0,0 +1,240 @@
+use opentelemetry::{
+    otel_debug, otel_warn,
+    propagation::{TextMapCompositePropagator, TextMapPropagator},
+};
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use super::{BaggagePropagator, TraceContextPropagator};
+
+/// Name of the environment variable listing the propagators to use, separated
+/// by commas.
+pub const OTEL_PROPAGATORS: &str = "OTEL_PROPAGATORS";
+
+/// Propagators used if `OTEL_PROPAGATORS` is not set.
+const DEFAULT_PROPAGATORS: &str = "tracecontext,baggage";
+
+/// Name that disables propagation when used in `OTEL_PROPAGATORS`.
+const NONE: &str = "none";
+
+type PropagatorFactory = Arc<dyn Fn() -> Box<dyn TextMapPropagator + Send + Sync> + Send + Sync>;
+
+static PROPAGATOR_REGISTRY: OnceLock<RwLock<HashMap<String, PropagatorFactory>>> = OnceLock::new();
+
+#[inline]
+fn propagator_registry() -> &'static RwLock<HashMap<String, PropagatorFactory>> {
+    PROPAGATOR_REGISTRY.get_or_init(|| {
+        let mut registry: HashMap<String, PropagatorFactory> = HashMap::new();
+        registry.insert(
+            "tracecontext".into(),
+            Arc::new(|| Box::new(TraceContextPropagator::new())),
+        );
+        registry.insert(
+            "baggage".into(),
+            Arc::new(|| Box::new(BaggagePropagator::new())),
+        );
+        RwLock::new(registry)
+    })
+}
+
+/// Registers a [`TextMapPropagator`] under `name` so it can be selected with
+/// the `OTEL_PROPAGATORS` environment variable.
+///
+/// `tracecontext` and `baggage` are registered by the SDK. Crates providing
+/// other propagators, such as `opentelemetry-zipkin` for `b3` and `b3multi`,
+/// offer a function registering them. Names are case-insensitive, and
+/// registering a name again replaces the previous propagator.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry_sdk::propagation::{
+///     register_text_map_propagator, text_map_propagator_from_names, TraceContextPropagator,
+/// };
+///
+/// register_text_map_propagator("w3c", TraceContextPropagator::new);
+///
+/// let propagator = text_map_propagator_from_names(["w3c", "baggage"]);
+/// ```
+pub fn register_text_map_propagator<F, P>(name: &str, factory: F)
+where
+    F: Fn() -> P + Send + Sync + 'static,
+    P: TextMapPropagator + Send + Sync + 'static,
+{
+    let name = name.trim().to_ascii_lowercase();
+    if name.is_empty() || name == NONE || name.contains(',') {
+        otel_warn!(
+            name: "Propagators.InvalidName",
+            message = "Propagator names must be non-empty, must not contain ',' and can't be 'none'. The propagator is not registered.",
+            propagator_name = name.as_str(),
+        );
+        return;
+    }
+
+    if let Ok(mut registry) = propagator_registry().write() {
+        let previous = registry.insert(name.clone(), Arc::new(move || Box::new(factory())));
+        if previous.is_some() {
+            otel_debug!(
+                name: "Propagators.Replaced",
+                propagator_name = name.as_str(),
+            );
+        }
+    }
+}
+
+/// Builds a [`TextMapCompositePropagator`] from the propagators registered
+/// under `names`, in the given order.
+///
+/// Unknown names are logged and skipped, and duplicates are only used once.
+/// If any name is `none`, the returned propagator propagates nothing.
+pub fn text_map_propagator_from_names<I, S>(names: I) -> TextMapCompositePropagator
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let names: Vec<String> = names
+        .into_iter()
+        .map(|name| name.as_ref().trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect();
+
+    if names.iter().any(|name| name == NONE) {
+        return TextMapCompositePropagator::new(Vec::new());
+    }
+
+    let mut propagators = Vec::with_capacity(names.len());
+    if let Ok(registry) = propagator_registry().read() {
+        let mut seen = Vec::with_capacity(names.len());
+        for name in names {
+            if seen.contains(&name) {
+                continue;
+            }
+            match registry.get(&name) {
+                Some(factory) => propagators.push(factory()),
+                None => {
+                    otel_warn!(
+                        name: "Propagators.UnknownPropagator",
+                        message = "No propagator is registered with this name, it is ignored. Propagators of other crates must be registered before they can be used.",
+                        propagator_name = name.as_str(),
+                    );
+                }
+            }
+            seen.push(name);
+        }
+    }
+
+    TextMapCompositePropagator::new(propagators)
+}
+
+/// Builds a [`TextMapCompositePropagator`] from the comma-separated
+/// propagator names in the `OTEL_PROPAGATORS` environment variable.
+///
+/// If the variable is unset or empty, `tracecontext,baggage` is used. See
+/// [`text_map_propagator_from_names`] for how the names are resolved.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::global;
+/// use opentelemetry_sdk::propagation::text_map_propagator_from_env;
+///
+/// global::set_text_map_propagator(text_map_propagator_from_env());
+/// ```
+pub fn text_map_propagator_from_env() -> TextMapCompositePropagator {
+    let value = env::var(OTEL_PROPAGATORS)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PROPAGATORS.to_owned());
+    text_map_propagator_from_names(value.split(','))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::propagation::text_map_propagator::FieldIter;
+    use opentelemetry::propagation::{Extractor, Injector};
+    use opentelemetry::Context;
+
+    #[derive(Debug)]
+    struct TestPropagator {
+        fields: [String; 1],
+    }
+
+    impl TestPropagator {
+        fn new(field: &str) -> Self {
+            TestPropagator {
+                fields: [field.to_owned()],
+            }
+        }
+    }
+
+    impl TextMapPropagator for TestPropagator {
+        fn inject_context(&self, _cx: &Context, injector: &mut dyn Injector) {
+            injector.set(&self.fields[0], "1".to_owned());
+        }
+
+        fn extract_with_context(&self, cx: &Context, _extractor: &dyn Extractor) -> Context {
+            cx.clone()
+        }
+
+        fn fields(&self) -> FieldIter<'_> {
+            FieldIter::new(&self.fields)
+        }
+    }
+
+    fn injected_fields(propagator: &TextMapCompositePropagator) -> Vec<String> {
+        let mut fields: Vec<String> = propagator.fields().map(str::to_owned).collect();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn default_propagators_from_env() {
+        for value in [None, Some(""), Some(" ")] {
+            temp_env::with_var(OTEL_PROPAGATORS, value, || {
+                let propagator = text_map_propagator_from_env();
+                assert_eq!(
+                    injected_fields(&propagator),
+                    vec!["baggage", "traceparent", "tracestate"]
+                );
+            });
+        }
+    }
+
+    #[test]
+    fn propagators_from_env() {
+        temp_env::with_var(OTEL_PROPAGATORS, Some(" Baggage "), || {
+            let propagator = text_map_propagator_from_env();
+            assert_eq!(injected_fields(&propagator), vec!["baggage"]);
+        });
+        temp_env::with_var(OTEL_PROPAGATORS, Some("tracecontext,none"), || {
+            let propagator = text_map_propagator_from_env();
+            assert!(injected_fields(&propagator).is_empty());
+        });
+        temp_env::with_var(
+            OTEL_PROPAGATORS,
+            Some("unknown,tracecontext,tracecontext"),
+            || {
+                let propagator = text_map_propagator_from_env();
+                assert_eq!(
+                    injected_fields(&propagator),
+                    vec!["traceparent", "tracestate"]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn registered_propagators_are_selected_by_name() {
+        register_text_map_propagator("Registry-Test", || TestPropagator::new("registry-test"));
+        register_text_map_propagator("none", || TestPropagator::new("never"));
+
+        let propagator = text_map_propagator_from_names(["registry-test"]);
+        let mut injected = HashMap::new();
+        propagator.inject_context(&Context::new(), &mut injected);
+        assert_eq!(injected.get("registry-test"), Some(&"1".to_owned()));
+
+        assert!(injected_fields(&text_map_propagator_from_names(["none"])).is_empty());
+    }
+}
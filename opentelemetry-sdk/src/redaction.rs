@@ -0,0 +1,480 @@
+//! # Redaction
+//!
+//! Rules to remove or obfuscate sensitive data, such as personally
+//! identifiable information, before telemetry leaves the process.
+//!
+//! A [`Redactor`] is applied by the [`RedactingSpanProcessor`] to span, event
+//! and link attributes, and by the [`RedactingLogProcessor`] to log record
+//! attributes and bodies.
+//!
+//! # Example
+//!
+//! ```
+//! use opentelemetry_sdk::redaction::{RedactionAction, Redactor};
+//!
+//! let redactor = Redactor::new()
+//!     .with_attribute("user.email", RedactionAction::Hash)
+//!     .with_attribute("*.password", RedactionAction::Remove)
+//!     .with_attribute("http.url", RedactionAction::MaskUrlQuery)
+//!     .with_attribute("url.full", RedactionAction::MaskUrlQuery)
+//!     // Values can be rewritten with any function, e.g. a regular expression
+//!     // replacing email addresses.
+//!     .with_value_redaction(|value| {
+//!         value
+//!             .contains("Bearer ")
+//!             .then(|| value.replace("Bearer ", "Bearer REDACTED "))
+//!     });
+//! ```
+//!
+//! [`RedactingSpanProcessor`]: crate::trace::RedactingSpanProcessor
+//! [`RedactingLogProcessor`]: crate::logs::RedactingLogProcessor
+#[cfg(feature = "logs")]
+use crate::logs::SdkLogRecord;
+use crate::util::wildcard_match;
+#[cfg(feature = "logs")]
+use opentelemetry::logs::AnyValue;
+#[cfg(feature = "trace")]
+use opentelemetry::{Array, KeyValue, Value};
+use opentelemetry::{Key, StringValue};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+/// The value replacing masked values.
+pub const MASK: &str = "REDACTED";
+
+/// What to do with an attribute whose key matches a [`Redactor`] rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RedactionAction {
+    /// Remove the attribute.
+    Remove,
+    /// Replace the value with [`MASK`].
+    Mask,
+    /// Replace the value with the hexadecimal FNV-1a hash of its UTF-8 bytes
+    /// for strings, or of a binary encoding of its type and contents for
+    /// other values, so equal values can still be correlated.
+    ///
+    /// The hash is not cryptographic, low entropy values like phone numbers
+    /// can be recovered by brute force. Use [`RedactionAction::Remove`] or
+    /// [`RedactionAction::Mask`] for such values.
+    Hash,
+    /// Replace the value of every query parameter of a URL with [`MASK`],
+    /// e.g. `https://host/path?token=REDACTED&page=REDACTED`. Values that are
+    /// not strings are masked entirely.
+    MaskUrlQuery,
+}
+
+type ValueRedaction = Arc<dyn Fn(&str) -> Option<String> + Send + Sync>;
+
+/// A set of redaction rules.
+///
+/// Attributes are matched by key against the patterns of the rules, in the
+/// order the rules are added, and the action of the first matching rule is
+/// applied. Patterns are case sensitive and support the wildcards `*`,
+/// matching any number of characters, and `?`, matching exactly one
+/// character.
+///
+/// The value redactions are applied to the string values of the other
+/// attributes, including strings nested in arrays, lists and maps, and to the
+/// string log bodies.
+#[derive(Clone, Default)]
+pub struct Redactor {
+    rules: Vec<(Cow<'static, str>, RedactionAction)>,
+    value_redactions: Vec<ValueRedaction>,
+}
+
+impl fmt::Debug for Redactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Redactor")
+            .field("rules", &self.rules)
+            .field("value_redactions", &self.value_redactions.len())
+            .finish()
+    }
+}
+
+impl Redactor {
+    /// Create a redactor without any rule.
+    pub fn new() -> Self {
+        Redactor::default()
+    }
+
+    /// Apply `action` to the attributes whose key matches `pattern`.
+    pub fn with_attribute(
+        mut self,
+        pattern: impl Into<Cow<'static, str>>,
+        action: RedactionAction,
+    ) -> Self {
+        self.rules.push((pattern.into(), action));
+        self
+    }
+
+    /// Rewrite string values with `redaction`, which returns the new value, or
+    /// `None` to keep the value unchanged.
+    ///
+    /// Value redactions are applied in the order they are added, each one to
+    /// the output of the previous one.
+    pub fn with_value_redaction<F>(mut self, redaction: F) -> Self
+    where
+        F: Fn(&str) -> Option<String> + Send + Sync + 'static,
+    {
+        self.value_redactions.push(Arc::new(redaction));
+        self
+    }
+
+    fn action(&self, key: &Key) -> Option<RedactionAction> {
+        self.rules
+            .iter()
+            .find(|(pattern, _)| wildcard_match(pattern, key.as_str(), false))
+            .map(|(_, action)| *action)
+    }
+
+    fn redact_string(&self, value: &mut StringValue) {
+        let mut redacted: Option<String> = None;
+        for redaction in &self.value_redactions {
+            let current = redacted.as_deref().unwrap_or(value.as_str());
+            if let Some(new_value) = redaction(current) {
+                redacted = Some(new_value);
+            }
+        }
+        if let Some(redacted) = redacted {
+            *value = redacted.into();
+        }
+    }
+
+    /// Redacts span, event or link attributes.
+    #[cfg(feature = "trace")]
+    pub(crate) fn redact_attributes(&self, attributes: &mut Vec<KeyValue>) {
+        attributes.retain_mut(|attribute| match self.action(&attribute.key) {
+            Some(RedactionAction::Remove) => false,
+            Some(action) => {
+                attribute.value = match (action, &attribute.value) {
+                    (RedactionAction::Hash, value) => hash_value(value).into(),
+                    (RedactionAction::MaskUrlQuery, Value::String(url)) => {
+                        mask_url_query(url.as_str()).into()
+                    }
+                    _ => MASK.into(),
+                };
+                true
+            }
+            None => {
+                match &mut attribute.value {
+                    Value::String(value) => self.redact_string(value),
+                    Value::Array(Array::String(values)) => values
+                        .iter_mut()
+                        .for_each(|value| self.redact_string(value)),
+                    _ => {}
+                }
+                true
+            }
+        });
+    }
+
+    /// Redacts the attributes and the body of a log record.
+    #[cfg(feature = "logs")]
+    pub(crate) fn redact_log_record(&self, record: &mut SdkLogRecord) {
+        for attribute in record.attributes.iter_mut() {
+            let keep = match attribute {
+                Some((key, value)) => self.redact_log_attribute(key, value),
+                None => true,
+            };
+            if !keep {
+                *attribute = None;
+            }
+        }
+        if let Some(body) = record.body.as_mut() {
+            self.redact_any_value(body);
+        }
+    }
+
+    /// Redacts a log record attribute, returning `false` if it must be
+    /// removed.
+    #[cfg(feature = "logs")]
+    fn redact_log_attribute(&self, key: &Key, value: &mut AnyValue) -> bool {
+        match self.action(key) {
+            Some(RedactionAction::Remove) => return false,
+            Some(action) => {
+                *value = match (action, &*value) {
+                    (RedactionAction::Hash, value) => hash_any_value(value).into(),
+                    (RedactionAction::MaskUrlQuery, AnyValue::String(url)) => {
+                        mask_url_query(url.as_str()).into()
+                    }
+                    _ => MASK.into(),
+                };
+            }
+            None => self.redact_any_value(value),
+        }
+        true
+    }
+
+    /// Applies the value redactions to the strings of a log body or attribute.
+    #[cfg(feature = "logs")]
+    fn redact_any_value(&self, value: &mut AnyValue) {
+        match value {
+            AnyValue::String(value) => self.redact_string(value),
+            AnyValue::ListAny(values) => {
+                for value in values.iter_mut() {
+                    self.redact_any_value(value);
+                }
+            }
+            AnyValue::Map(map) => {
+                for value in map.values_mut() {
+                    self.redact_any_value(value);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 64-bit FNV-1a, which unlike the std hashers is stable across releases.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Fnv1a(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// Writes a type tag followed by the length of a string, list or map, so
+    /// that values of different types or shapes hash differently.
+    fn write_header(&mut self, tag: u8, len: usize) {
+        self.write(&[tag]);
+        self.write(&(len as u64).to_le_bytes());
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write(&[b'b', u8::from(value)]);
+    }
+
+    fn write_i64(&mut self, value: i64) {
+        self.write(b"i");
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        self.write(b"d");
+        self.write(&value.to_bits().to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_header(b's', value.len());
+        self.write(value.as_bytes());
+    }
+
+    #[cfg(feature = "logs")]
+    fn write_any_value(&mut self, value: &AnyValue) {
+        match value {
+            AnyValue::Int(value) => self.write_i64(*value),
+            AnyValue::Double(value) => self.write_f64(*value),
+            AnyValue::String(value) => self.write_str(value.as_str()),
+            AnyValue::Boolean(value) => self.write_bool(*value),
+            AnyValue::Bytes(bytes) => {
+                self.write_header(b'y', bytes.len());
+                self.write(bytes);
+            }
+            AnyValue::ListAny(values) => {
+                self.write_header(b'l', values.len());
+                values.iter().for_each(|value| self.write_any_value(value));
+            }
+            AnyValue::Map(map) => {
+                // Map entries are hashed in key order, the iteration order of
+                // the map being random.
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_unstable_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+                self.write_header(b'm', entries.len());
+                for (key, value) in entries {
+                    self.write_str(key.as_str());
+                    self.write_any_value(value);
+                }
+            }
+            value => self.write_str(&format!("{value:?}")),
+        }
+    }
+
+    fn finish(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+fn hash(value: &str) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write(value.as_bytes());
+    hasher.finish()
+}
+
+/// Hashes strings as [`hash`] does, and other values with their type.
+#[cfg(feature = "trace")]
+fn hash_value(value: &Value) -> String {
+    let mut hasher = Fnv1a::new();
+    match value {
+        Value::String(value) => return hash(value.as_str()),
+        Value::Bool(value) => hasher.write_bool(*value),
+        Value::I64(value) => hasher.write_i64(*value),
+        Value::F64(value) => hasher.write_f64(*value),
+        Value::Array(Array::Bool(values)) => {
+            hasher.write_header(b'l', values.len());
+            values.iter().for_each(|value| hasher.write_bool(*value));
+        }
+        Value::Array(Array::I64(values)) => {
+            hasher.write_header(b'l', values.len());
+            values.iter().for_each(|value| hasher.write_i64(*value));
+        }
+        Value::Array(Array::F64(values)) => {
+            hasher.write_header(b'l', values.len());
+            values.iter().for_each(|value| hasher.write_f64(*value));
+        }
+        Value::Array(Array::String(values)) => {
+            hasher.write_header(b'l', values.len());
+            values
+                .iter()
+                .for_each(|value| hasher.write_str(value.as_str()));
+        }
+        value => hasher.write_str(&value.as_str()),
+    }
+    hasher.finish()
+}
+
+/// Hashes strings as [`hash`] does, and other values with their type, so the
+/// hashes of span and log attributes with the same value are equal.
+#[cfg(feature = "logs")]
+fn hash_any_value(value: &AnyValue) -> String {
+    match value {
+        AnyValue::String(value) => hash(value.as_str()),
+        value => {
+            let mut hasher = Fnv1a::new();
+            hasher.write_any_value(value);
+            hasher.finish()
+        }
+    }
+}
+
+fn mask_url_query(url: &str) -> String {
+    let Some((base, rest)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let (query, fragment) = match rest.split_once('#') {
+        Some((query, fragment)) => (query, Some(fragment)),
+        None => (rest, None),
+    };
+    let query = query
+        .split('&')
+        .map(|parameter| match parameter.split_once('=') {
+            Some((name, _)) => format!("{name}={MASK}"),
+            None => parameter.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    match fragment {
+        Some(fragment) => format!("{base}?{query}#{fragment}"),
+        None => format!("{base}?{query}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_queries_are_masked() {
+        assert_eq!(
+            mask_url_query("https://host/path?token=abc&page=2&flag#top"),
+            "https://host/path?token=REDACTED&page=REDACTED&flag#top"
+        );
+        assert_eq!(mask_url_query("https://host/path"), "https://host/path");
+    }
+
+    #[test]
+    fn hashes_are_stable() {
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(hash("john@example.com"), hash("jane@example.com"));
+    }
+
+    #[cfg(all(feature = "trace", feature = "logs"))]
+    #[test]
+    fn typed_values_are_hashed_alike_for_spans_and_logs() {
+        use std::collections::HashMap;
+
+        assert_eq!(
+            hash_value(&Value::I64(42)),
+            hash_any_value(&AnyValue::Int(42))
+        );
+        assert_eq!(
+            hash_value(&Value::String("42".into())),
+            hash_any_value(&AnyValue::String("42".into()))
+        );
+        assert_ne!(hash_value(&Value::I64(42)), hash("42"));
+        assert_ne!(hash_value(&Value::I64(1)), hash_value(&Value::Bool(true)));
+        assert_eq!(
+            hash_value(&Value::Array(Array::F64(vec![1.5, 2.0]))),
+            hash_any_value(&AnyValue::ListAny(Box::new(vec![
+                AnyValue::Double(1.5),
+                AnyValue::Double(2.0)
+            ])))
+        );
+
+        let map = |entries: &[(&'static str, i64)]| {
+            AnyValue::Map(Box::new(
+                entries
+                    .iter()
+                    .map(|(key, value)| (Key::from_static_str(key), AnyValue::Int(*value)))
+                    .collect::<HashMap<_, _>>(),
+            ))
+        };
+        assert_eq!(
+            hash_any_value(&map(&[("a", 1), ("b", 2), ("c", 3)])),
+            hash_any_value(&map(&[("c", 3), ("b", 2), ("a", 1)]))
+        );
+        assert_ne!(
+            hash_any_value(&map(&[("a", 1), ("b", 2)])),
+            hash_any_value(&map(&[("a", 2), ("b", 1)]))
+        );
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn first_matching_rule_applies() {
+        let redactor = Redactor::new()
+            .with_attribute("user.email", RedactionAction::Hash)
+            .with_attribute("*.password", RedactionAction::Remove)
+            .with_attribute("user.*", RedactionAction::Mask)
+            .with_attribute("http.url", RedactionAction::MaskUrlQuery)
+            .with_value_redaction(|value| value.contains('@').then(|| "<email>".to_string()))
+            .with_value_redaction(|value| Some(value.to_uppercase()));
+
+        let mut attributes = vec![
+            KeyValue::new("user.email", "john@example.com"),
+            KeyValue::new("user.password", "hunter2"),
+            KeyValue::new("user.id", 42),
+            KeyValue::new("http.url", "https://host/?token=abc"),
+            KeyValue::new("message", "sent to john@example.com"),
+            KeyValue::new(
+                "recipients",
+                Value::Array(Array::String(vec!["a@b.c".into(), "d".into()])),
+            ),
+            KeyValue::new("count", 3),
+        ];
+        redactor.redact_attributes(&mut attributes);
+
+        assert_eq!(
+            attributes,
+            vec![
+                KeyValue::new("user.email", hash("john@example.com")),
+                KeyValue::new("user.id", MASK),
+                KeyValue::new("http.url", "https://host/?token=REDACTED"),
+                KeyValue::new("message", "<EMAIL>"),
+                KeyValue::new(
+                    "recipients",
+                    Value::Array(Array::String(vec!["<EMAIL>".into(), "D".into()])),
+                ),
+                KeyValue::new("count", 3),
+            ]
+        );
+    }
+}
@@ -30,3 +30,54 @@ pub(crate) const TELEMETRY_SDK_NAME: &str = "telemetry.sdk.name";
 ///
 /// - `1.2.3`
 pub(crate) const TELEMETRY_SDK_VERSION: &str = "telemetry.sdk.version";
+
+/// Name of the host. On Unix systems, it may contain what the hostname command returns, or the fully qualified hostname, or another name specified by the user.
+pub(crate) const HOST_NAME: &str = "host.name";
+
+/// The CPU architecture the host system is running on.
+pub(crate) const HOST_ARCH: &str = "host.arch";
+
+/// The operating system type.
+pub(crate) const OS_TYPE: &str = "os.type";
+
+/// Human readable (not intended to be parsed) OS version information, like e.g. reported by `ver` or `lsb_release -a` commands.
+pub(crate) const OS_DESCRIPTION: &str = "os.description";
+
+/// Process identifier (PID).
+pub(crate) const PROCESS_PID: &str = "process.pid";
+
+/// The name of the process executable. On Linux based systems, this SHOULD be set to the base name of the target of `/proc/[pid]/exe`.
+pub(crate) const PROCESS_EXECUTABLE_NAME: &str = "process.executable.name";
+
+/// The full path to the process executable. On Linux based systems, can be set to the target of `proc/[pid]/exe`.
+pub(crate) const PROCESS_EXECUTABLE_PATH: &str = "process.executable.path";
+
+/// All the command arguments (including the command/executable itself) as received by the process.
+pub(crate) const PROCESS_COMMAND_ARGS: &str = "process.command_args";
+
+/// The username of the user that owns the process.
+pub(crate) const PROCESS_OWNER: &str = "process.owner";
+
+/// The name of the runtime of this process.
+pub(crate) const PROCESS_RUNTIME_NAME: &str = "process.runtime.name";
+
+/// The version of the runtime of this process, as returned by the runtime without modification.
+pub(crate) const PROCESS_RUNTIME_VERSION: &str = "process.runtime.version";
+
+/// An additional description about the runtime of the process, for example a specific vendor customization of the runtime environment.
+pub(crate) const PROCESS_RUNTIME_DESCRIPTION: &str = "process.runtime.description";
+
+/// Container ID. Usually a UUID, as for example used to [identify Docker containers](https://docs.docker.com/engine/containers/run/#container-identification). The UUID might be abbreviated.
+pub(crate) const CONTAINER_ID: &str = "container.id";
+
+/// The name of the Pod.
+pub(crate) const K8S_POD_NAME: &str = "k8s.pod.name";
+
+/// The UID of the Pod.
+pub(crate) const K8S_POD_UID: &str = "k8s.pod.uid";
+
+/// The name of the namespace that the pod is running in.
+pub(crate) const K8S_NAMESPACE_NAME: &str = "k8s.namespace.name";
+
+/// The name of the Node.
+pub(crate) const K8S_NODE_NAME: &str = "k8s.node.name";
@@ -0,0 +1,207 @@
+//! Container resource detector
+//!
+//! Implementation of `ResourceDetector` to extract a `Resource` describing the
+//! container the process runs in.
+use crate::resource::{Resource, ResourceDetector};
+use opentelemetry::KeyValue;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Length of the hex encoded ids used by Docker, containerd and CRI-O.
+const CONTAINER_ID_LENGTH: usize = 64;
+
+/// Detects the [container] the process runs in.
+///
+/// It provides the id of the container (`container.id`), which is read from
+/// `/proc/self/cgroup` with cgroup v1, and from the mounts listed in
+/// `/proc/self/mountinfo` with cgroup v2. If the process doesn't run in a
+/// container, an empty resource is returned.
+///
+/// [container]: https://github.com/open-telemetry/semantic-conventions/blob/main/docs/resource/container.md
+#[derive(Debug)]
+pub struct ContainerResourceDetector {
+    root: PathBuf,
+}
+
+impl ContainerResourceDetector {
+    /// Create `ContainerResourceDetector` instance.
+    pub fn new() -> Self {
+        ContainerResourceDetector {
+            root: PathBuf::from("/"),
+        }
+    }
+
+    /// Read the `/proc` files relative to `root` instead of `/`.
+    ///
+    /// This is mostly useful to test the detection with a fake filesystem.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+}
+
+impl Default for ContainerResourceDetector {
+    fn default() -> Self {
+        ContainerResourceDetector::new()
+    }
+}
+
+impl ResourceDetector for ContainerResourceDetector {
+    fn detect(&self) -> Resource {
+        match container_id(&self.root) {
+            Some(id) => Resource::builder_empty()
+                .with_attributes([KeyValue::new(super::CONTAINER_ID, id)])
+                .build(),
+            None => Resource::empty(),
+        }
+    }
+}
+
+/// Reads a file of the process below `root`.
+pub(super) fn read_proc_file(root: &Path, name: &str) -> Option<String> {
+    if cfg!(miri) {
+        return None;
+    }
+    fs::read_to_string(root.join("proc/self").join(name)).ok()
+}
+
+fn container_id(root: &Path) -> Option<String> {
+    read_proc_file(root, "cgroup")
+        .and_then(|cgroup| container_id_from_cgroup(&cgroup))
+        .or_else(|| {
+            read_proc_file(root, "mountinfo")
+                .and_then(|mountinfo| container_id_from_mountinfo(&mountinfo))
+        })
+}
+
+/// Extracts the container id from the cgroup v1 paths, like
+/// `12:cpu,cpuacct:/docker/<id>` or
+/// `1:name=systemd:/kubepods.slice/.../cri-containerd-<id>.scope`.
+fn container_id_from_cgroup(cgroup: &str) -> Option<String> {
+    cgroup.lines().find_map(|line| {
+        let path = line.splitn(3, ':').nth(2)?;
+        let segment = path.rsplit('/').next()?;
+        let segment = segment.strip_suffix(".scope").unwrap_or(segment);
+        let id = segment.rsplit(['-', ':']).next()?;
+        is_container_id(id).then(|| id.to_owned())
+    })
+}
+
+/// Extracts the container id from the mount points the runtime creates for
+/// the container, like `/var/lib/docker/containers/<id>/hostname`.
+///
+/// The files of the pod sandbox, like
+/// `/var/lib/containerd/.../sandboxes/<id>/resolv.conf`, are skipped: their
+/// id is the one of the pause container, not of the container of the process.
+fn container_id_from_mountinfo(mountinfo: &str) -> Option<String> {
+    mountinfo.lines().find_map(|line| {
+        line.split_whitespace()
+            .filter(|field| field.contains("/containers/") && !field.contains("/sandboxes/"))
+            .flat_map(|field| field.split('/'))
+            .find(|segment| is_container_id(segment))
+            .map(str::to_owned)
+    })
+}
+
+fn is_container_id(id: &str) -> bool {
+    id.len() == CONTAINER_ID_LENGTH && id.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::{Key, Value};
+
+    const ID: &str = "6a8b6a0b38f5bd2ae09bb47ebd0ddaa0e0c1b0ac2bb6ec1e2a7e2caf1e1bc77f";
+
+    fn fake_root(files: &[(&str, String)]) -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        let dir = root.path().join("proc/self");
+        fs::create_dir_all(&dir).unwrap();
+        for (name, content) in files {
+            fs::write(dir.join(name), content).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn parses_cgroup_v1() {
+        for path in [
+            format!("/docker/{ID}"),
+            format!("/kubepods/burstable/pod2c48913c-b29f-11e7-9350-020968147796/{ID}"),
+            format!("/system.slice/docker-{ID}.scope"),
+            format!("/kubepods.slice/kubepods-besteffort.slice/cri-containerd-{ID}.scope"),
+            format!("/kubepods.slice/crio-{ID}.scope"),
+            format!("/machine.slice/libpod-conmon-{ID}.scope"),
+            format!("/system.slice/containerd.service/kubepods-burstable:cri-containerd:{ID}"),
+        ] {
+            let cgroup = format!("13:pids:/\n12:cpu,cpuacct:{path}\n");
+            assert_eq!(
+                container_id_from_cgroup(&cgroup).as_deref(),
+                Some(ID),
+                "{path}"
+            );
+        }
+
+        assert_eq!(container_id_from_cgroup("0::/\n"), None);
+        assert_eq!(container_id_from_cgroup("12:cpu:/user.slice\n"), None);
+    }
+
+    #[test]
+    fn parses_cgroup_v2_mountinfo() {
+        let mountinfo = format!(
+            "1370 1348 0:55 / / rw,relatime master:374 - overlay overlay rw\n\
+             1377 1370 254:1 /docker/containers/{ID}/resolv.conf /etc/resolv.conf rw,relatime - ext4 /dev/vda1 rw\n\
+             1378 1370 254:1 /docker/containers/{ID}/hostname /etc/hostname rw,relatime - ext4 /dev/vda1 rw\n"
+        );
+        assert_eq!(container_id_from_mountinfo(&mountinfo).as_deref(), Some(ID));
+
+        // The sandbox id is the one of the pause container.
+        const SANDBOX_ID: &str = "0f2b8c0e2a7d4f4c9a1b3e5d7c9f1a3b5d7e9f1a3c5e7a9b1d3f5a7c9e1b3d5f";
+        let sandbox = format!(
+            "3346 3330 0:40 /var/lib/containerd/io.containerd.grpc.v1.cri/sandboxes/{SANDBOX_ID}/hostname /etc/hostname rw - ext4 /dev/sda1 rw\n"
+        );
+        assert_eq!(container_id_from_mountinfo(&sandbox), None);
+        let sandbox_and_container = format!(
+            "{sandbox}3347 3330 0:40 /var/lib/containerd/io.containerd.grpc.v1.cri/containers/{ID}/termination-log /dev/termination-log rw - ext4 /dev/sda1 rw\n"
+        );
+        assert_eq!(
+            container_id_from_mountinfo(&sandbox_and_container).as_deref(),
+            Some(ID)
+        );
+
+        assert_eq!(
+            container_id_from_mountinfo("22 1 8:1 / / rw,relatime - ext4 /dev/sda1 rw\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn detects_container_id_below_root() {
+        let key = Key::from_static_str(crate::resource::CONTAINER_ID);
+
+        let root = fake_root(&[("cgroup", format!("1:cpu:/docker/{ID}\n"))]);
+        let resource = ContainerResourceDetector::new()
+            .with_root(root.path())
+            .detect();
+        assert_eq!(resource.get(&key), Some(Value::from(ID)));
+
+        let root = fake_root(&[
+            ("cgroup", "0::/\n".to_owned()),
+            (
+                "mountinfo",
+                format!("1 0 8:1 /docker/containers/{ID}/hostname /etc/hostname rw - ext4 /dev/sda1 rw\n"),
+            ),
+        ]);
+        let resource = ContainerResourceDetector::new()
+            .with_root(root.path())
+            .detect();
+        assert_eq!(resource.get(&key), Some(Value::from(ID)));
+
+        let root = fake_root(&[]);
+        let resource = ContainerResourceDetector::new()
+            .with_root(root.path())
+            .detect();
+        assert!(resource.is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+//! Host and operating system resource detectors
+//!
+//! Implementation of `ResourceDetector` to extract a `Resource` describing the
+//! host and operating system the process runs on.
+use crate::resource::{Resource, ResourceDetector};
+use opentelemetry::KeyValue;
+use std::env::consts::{ARCH, OS};
+use std::fs;
+
+/// Detects the [host] the process runs on.
+///
+/// It provides:
+/// - The name of the host (`host.name`). On Linux, it is read from
+///   `/proc/sys/kernel/hostname` like `uname -n` does, other systems use the
+///   `HOSTNAME` or `COMPUTERNAME` environment variables.
+/// - The CPU architecture (`host.arch`), like `amd64` or `arm64`.
+///
+/// [host]: https://github.com/open-telemetry/semantic-conventions/blob/main/docs/resource/host.md
+#[derive(Debug)]
+pub struct HostResourceDetector;
+
+impl ResourceDetector for HostResourceDetector {
+    fn detect(&self) -> Resource {
+        let mut attributes = vec![KeyValue::new(super::HOST_ARCH, host_arch(ARCH))];
+        if let Some(name) = host_name() {
+            attributes.push(KeyValue::new(super::HOST_NAME, name));
+        }
+        Resource::builder_empty()
+            .with_attributes(attributes)
+            .build()
+    }
+}
+
+/// Detects the [operating system] the process runs on.
+///
+/// It provides:
+/// - The type of the operating system (`os.type`), like `linux` or `windows`.
+/// - A description of the operating system (`os.description`). On Linux, it
+///   is built from `/proc/sys/kernel` like `uname -srv` does.
+///
+/// [operating system]: https://github.com/open-telemetry/semantic-conventions/blob/main/docs/resource/os.md
+#[derive(Debug)]
+pub struct OsResourceDetector;
+
+impl ResourceDetector for OsResourceDetector {
+    fn detect(&self) -> Resource {
+        let mut attributes = vec![KeyValue::new(super::OS_TYPE, os_type(OS))];
+        if let Some(description) = os_description() {
+            attributes.push(KeyValue::new(super::OS_DESCRIPTION, description));
+        }
+        Resource::builder_empty()
+            .with_attributes(attributes)
+            .build()
+    }
+}
+
+/// Maps a Rust target architecture to the `host.arch` values of the semantic
+/// conventions.
+fn host_arch(arch: &'static str) -> &'static str {
+    match arch {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        "arm" => "arm32",
+        "x86" => "x86",
+        "powerpc" => "ppc32",
+        "powerpc64" => "ppc64",
+        "s390x" => "s390x",
+        other => other,
+    }
+}
+
+/// Maps a Rust target operating system to the `os.type` values of the
+/// semantic conventions.
+fn os_type(os: &'static str) -> &'static str {
+    match os {
+        "macos" => "darwin",
+        "dragonfly" => "dragonflybsd",
+        "illumos" => "solaris",
+        other => other,
+    }
+}
+
+/// Reads a value of the Linux kernel, like `uname` does.
+fn read_kernel_value(name: &str) -> Option<String> {
+    if !cfg!(target_os = "linux") || cfg!(miri) {
+        return None;
+    }
+    fs::read_to_string(format!("/proc/sys/kernel/{name}"))
+        .ok()
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+fn host_name() -> Option<String> {
+    read_kernel_value("hostname").or_else(|| {
+        ["HOSTNAME", "COMPUTERNAME"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .map(|value| value.trim().to_owned())
+            .find(|value| !value.is_empty())
+    })
+}
+
+fn os_description() -> Option<String> {
+    let parts: Vec<String> = ["ostype", "osrelease", "version"]
+        .iter()
+        .filter_map(|name| read_kernel_value(name))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::{Key, Value};
+
+    #[test]
+    fn maps_targets_to_semantic_convention_values() {
+        assert_eq!(host_arch("x86_64"), "amd64");
+        assert_eq!(host_arch("aarch64"), "arm64");
+        assert_eq!(host_arch("riscv64"), "riscv64");
+        assert_eq!(os_type("macos"), "darwin");
+        assert_eq!(os_type("linux"), "linux");
+        assert_eq!(os_type("windows"), "windows");
+    }
+
+    #[test]
+    fn detects_host_and_os() {
+        let host = HostResourceDetector.detect();
+        assert_eq!(
+            host.get(&Key::from_static_str(crate::resource::HOST_ARCH)),
+            Some(Value::from(host_arch(ARCH)))
+        );
+
+        let os = OsResourceDetector.detect();
+        assert_eq!(
+            os.get(&Key::from_static_str(crate::resource::OS_TYPE)),
+            Some(Value::from(os_type(OS)))
+        );
+
+        if cfg!(target_os = "linux") && !cfg!(miri) {
+            assert!(host
+                .get(&Key::from_static_str(crate::resource::HOST_NAME))
+                .is_some());
+            let description = os
+                .get(&Key::from_static_str(crate::resource::OS_DESCRIPTION))
+                .unwrap()
+                .to_string();
+            assert!(description.starts_with("Linux "), "{description}");
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! Kubernetes resource detector
+//!
+//! Implementation of `ResourceDetector` to extract a `Resource` describing the
+//! Kubernetes pod the process runs in.
+use crate::resource::{Resource, ResourceDetector};
+use opentelemetry::KeyValue;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::container::read_proc_file;
+
+/// Environment variable set by Kubernetes in every container.
+const KUBERNETES_SERVICE_HOST: &str = "KUBERNETES_SERVICE_HOST";
+const K8S_POD_NAME: &str = "K8S_POD_NAME";
+const K8S_POD_UID: &str = "K8S_POD_UID";
+const K8S_NAMESPACE_NAME: &str = "K8S_NAMESPACE_NAME";
+const K8S_NODE_NAME: &str = "K8S_NODE_NAME";
+/// File containing the namespace of the pod when a service account token is
+/// mounted.
+const NAMESPACE_FILE: &str = "var/run/secrets/kubernetes.io/serviceaccount/namespace";
+/// Length of the pod UIDs, which are UUIDs.
+const POD_UID_LENGTH: usize = 36;
+
+/// Detects the [Kubernetes] pod the process runs in.
+///
+/// The values are read from environment variables that should be set with
+/// the [downward API] in the pod specification:
+///
+/// | Attribute            | Environment variable  | Downward API field   |
+/// |----------------------|-----------------------|----------------------|
+/// | `k8s.pod.name`       | `K8S_POD_NAME`        | `metadata.name`      |
+/// | `k8s.pod.uid`        | `K8S_POD_UID`         | `metadata.uid`       |
+/// | `k8s.namespace.name` | `K8S_NAMESPACE_NAME`  | `metadata.namespace` |
+/// | `k8s.node.name`      | `K8S_NODE_NAME`       | `spec.nodeName`      |
+///
+/// Without them, the pod name falls back to `HOSTNAME`, the namespace to the
+/// one of the mounted service account, and the pod uid to the kubelet volume
+/// mounts listed in `/proc/self/mountinfo`. If the process doesn't run in
+/// Kubernetes, an empty resource is returned.
+///
+/// [Kubernetes]: https://github.com/open-telemetry/semantic-conventions/blob/main/docs/resource/k8s.md
+/// [downward API]: https://kubernetes.io/docs/concepts/workloads/pods/downward-api/
+#[derive(Debug)]
+pub struct KubernetesResourceDetector {
+    root: PathBuf,
+}
+
+impl KubernetesResourceDetector {
+    /// Create `KubernetesResourceDetector` instance.
+    pub fn new() -> Self {
+        KubernetesResourceDetector {
+            root: PathBuf::from("/"),
+        }
+    }
+
+    /// Read the service account and `/proc` files relative to `root` instead
+    /// of `/`.
+    ///
+    /// This is mostly useful to test the detection with a fake filesystem.
+    pub fn with_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.root = root.into();
+        self
+    }
+}
+
+impl Default for KubernetesResourceDetector {
+    fn default() -> Self {
+        KubernetesResourceDetector::new()
+    }
+}
+
+impl ResourceDetector for KubernetesResourceDetector {
+    fn detect(&self) -> Resource {
+        let namespace =
+            env_value(K8S_NAMESPACE_NAME).or_else(|| service_account_namespace(&self.root));
+        if namespace.is_none() && env_value(KUBERNETES_SERVICE_HOST).is_none() {
+            return Resource::empty();
+        }
+
+        let attributes = [
+            (
+                super::K8S_POD_NAME,
+                env_value(K8S_POD_NAME).or_else(|| env_value("HOSTNAME")),
+            ),
+            (
+                super::K8S_POD_UID,
+                env_value(K8S_POD_UID).or_else(|| {
+                    read_proc_file(&self.root, "mountinfo")
+                        .and_then(|mountinfo| pod_uid_from_mountinfo(&mountinfo))
+                }),
+            ),
+            (super::K8S_NAMESPACE_NAME, namespace),
+            (super::K8S_NODE_NAME, env_value(K8S_NODE_NAME)),
+        ];
+        Resource::builder_empty()
+            .with_attributes(
+                attributes
+                    .into_iter()
+                    .filter_map(|(key, value)| value.map(|value| KeyValue::new(key, value))),
+            )
+            .build()
+    }
+}
+
+fn env_value(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+fn service_account_namespace(root: &Path) -> Option<String> {
+    if cfg!(miri) {
+        return None;
+    }
+    fs::read_to_string(root.join(NAMESPACE_FILE))
+        .ok()
+        .map(|namespace| namespace.trim().to_owned())
+        .filter(|namespace| !namespace.is_empty())
+}
+
+/// Extracts the pod uid from the volumes the kubelet mounts into the
+/// container, like `/var/lib/kubelet/pods/<uid>/volumes/...`.
+fn pod_uid_from_mountinfo(mountinfo: &str) -> Option<String> {
+    mountinfo.lines().find_map(|line| {
+        line.split_whitespace().find_map(|field| {
+            let (_, rest) = field.split_once("/pods/")?;
+            let uid = rest.split('/').next()?;
+            (uid.len() == POD_UID_LENGTH && uid.bytes().all(|b| b.is_ascii_hexdigit() || b == b'-'))
+                .then(|| uid.to_owned())
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::{Key, Value};
+
+    const POD_UID: &str = "2c48913c-b29f-11e7-9350-020968147796";
+
+    fn get(resource: &Resource, key: &'static str) -> Option<Value> {
+        resource.get(&Key::from_static_str(key))
+    }
+
+    fn without_k8s_env<F: FnOnce()>(f: F) {
+        temp_env::with_vars_unset(
+            [
+                KUBERNETES_SERVICE_HOST,
+                K8S_POD_NAME,
+                K8S_POD_UID,
+                K8S_NAMESPACE_NAME,
+                K8S_NODE_NAME,
+            ],
+            f,
+        );
+    }
+
+    #[test]
+    fn detects_pod_from_downward_api() {
+        let root = tempfile::tempdir().unwrap();
+        without_k8s_env(|| {
+            temp_env::with_vars(
+                [
+                    (K8S_POD_NAME, Some("checkout-5d8f7")),
+                    (K8S_POD_UID, Some(POD_UID)),
+                    (K8S_NAMESPACE_NAME, Some("shop")),
+                    (K8S_NODE_NAME, Some("node-1")),
+                ],
+                || {
+                    let resource = KubernetesResourceDetector::new()
+                        .with_root(root.path())
+                        .detect();
+                    assert_eq!(
+                        get(&resource, crate::resource::K8S_POD_NAME),
+                        Some(Value::from("checkout-5d8f7"))
+                    );
+                    assert_eq!(
+                        get(&resource, crate::resource::K8S_POD_UID),
+                        Some(Value::from(POD_UID))
+                    );
+                    assert_eq!(
+                        get(&resource, crate::resource::K8S_NAMESPACE_NAME),
+                        Some(Value::from("shop"))
+                    );
+                    assert_eq!(
+                        get(&resource, crate::resource::K8S_NODE_NAME),
+                        Some(Value::from("node-1"))
+                    );
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn detects_pod_from_filesystem() {
+        let root = tempfile::tempdir().unwrap();
+        let namespace = root.path().join(NAMESPACE_FILE);
+        fs::create_dir_all(namespace.parent().unwrap()).unwrap();
+        fs::write(&namespace, "shop\n").unwrap();
+        fs::create_dir_all(root.path().join("proc/self")).unwrap();
+        fs::write(
+            root.path().join("proc/self/mountinfo"),
+            format!("2 1 8:1 /var/lib/kubelet/pods/{POD_UID}/volumes/kubernetes.io~secret/token /var/run/secrets ro - tmpfs tmpfs ro\n"),
+        )
+        .unwrap();
+
+        without_k8s_env(|| {
+            temp_env::with_var("HOSTNAME", Some("checkout-5d8f7"), || {
+                let resource = KubernetesResourceDetector::new()
+                    .with_root(root.path())
+                    .detect();
+                assert_eq!(
+                    get(&resource, crate::resource::K8S_POD_NAME),
+                    Some(Value::from("checkout-5d8f7"))
+                );
+                assert_eq!(
+                    get(&resource, crate::resource::K8S_POD_UID),
+                    Some(Value::from(POD_UID))
+                );
+                assert_eq!(
+                    get(&resource, crate::resource::K8S_NAMESPACE_NAME),
+                    Some(Value::from("shop"))
+                );
+                assert_eq!(get(&resource, crate::resource::K8S_NODE_NAME), None);
+            });
+        });
+    }
+
+    #[test]
+    fn outside_kubernetes_detects_nothing() {
+        let root = tempfile::tempdir().unwrap();
+        without_k8s_env(|| {
+            let resource = KubernetesResourceDetector::new()
+                .with_root(root.path())
+                .detect();
+            assert!(resource.is_empty());
+        });
+    }
+}
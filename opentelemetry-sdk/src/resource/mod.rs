@@ -18,16 +18,30 @@
 //! - [`EnvResourceDetector`] - detect resource from environmental variables.
 //! - [`TelemetryResourceDetector`] - detect telemetry SDK's information.
 //!
-//! The OS and Process resource detectors are packaged separately in the
-//! [`opentelemetry-resource-detector` crate](https://github.com/open-telemetry/opentelemetry-rust-contrib/tree/main/opentelemetry-resource-detectors).
+//! The following detectors are not used by default and can be added with
+//! [`ResourceBuilder::with_detectors`].
+//!
+//! - [`ContainerResourceDetector`] - detect the container id.
+//! - [`HostResourceDetector`] - detect the host name and architecture.
+//! - [`KubernetesResourceDetector`] - detect the Kubernetes pod.
+//! - [`OsResourceDetector`] - detect the operating system.
+//! - [`ProcessResourceDetector`] - detect the running process and its runtime.
+mod container;
 mod env;
+mod host;
+mod kubernetes;
+mod process;
 mod telemetry;
 
 mod attributes;
 pub(crate) use attributes::*;
 
+pub use container::ContainerResourceDetector;
 pub use env::EnvResourceDetector;
 pub use env::SdkProvidedResourceDetector;
+pub use host::{HostResourceDetector, OsResourceDetector};
+pub use kubernetes::KubernetesResourceDetector;
+pub use process::ProcessResourceDetector;
 pub use telemetry::TelemetryResourceDetector;
 
 use opentelemetry::{Key, KeyValue, Value};
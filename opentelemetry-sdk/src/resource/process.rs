@@ -0,0 +1,166 @@
+//! Process resource detector
+//!
+//! Implementation of `ResourceDetector` to extract a `Resource` describing the
+//! running process.
+use crate::resource::{Resource, ResourceDetector};
+use opentelemetry::{Array, KeyValue, StringValue, Value};
+use std::env;
+use std::fs;
+
+/// Detects the running [process].
+///
+/// It provides:
+/// - The process identifier (`process.pid`).
+/// - The name and full path of the executable (`process.executable.name` and
+///   `process.executable.path`).
+/// - The command line arguments, including the executable itself
+///   (`process.command_args`).
+/// - The name of the user owning the process (`process.owner`). On Linux, the
+///   user id is read from `/proc/self/status` and resolved with
+///   `/etc/passwd`, other systems use the `USER` or `USERNAME` environment
+///   variables.
+/// - The runtime (`process.runtime.name`, `process.runtime.version` and
+///   `process.runtime.description`), which is the Rust compiler, its version
+///   and the target the process was compiled for.
+///
+/// Command line arguments may contain sensitive information, only use this
+/// detector if they are safe to be exported.
+///
+/// [process]: https://github.com/open-telemetry/semantic-conventions/blob/main/docs/resource/process.md
+#[derive(Debug)]
+pub struct ProcessResourceDetector;
+
+impl ResourceDetector for ProcessResourceDetector {
+    fn detect(&self) -> Resource {
+        let mut attributes = vec![
+            KeyValue::new(super::PROCESS_PID, i64::from(std::process::id())),
+            KeyValue::new(
+                super::PROCESS_COMMAND_ARGS,
+                Value::Array(Array::String(
+                    env::args_os()
+                        .map(|arg| StringValue::from(arg.to_string_lossy().into_owned()))
+                        .collect(),
+                )),
+            ),
+            KeyValue::new(super::PROCESS_RUNTIME_NAME, "rustc"),
+            KeyValue::new(
+                super::PROCESS_RUNTIME_DESCRIPTION,
+                format!(
+                    "Rust compiled for {}-{}",
+                    env::consts::ARCH,
+                    env::consts::OS
+                ),
+            ),
+        ];
+
+        if let Some(version) = option_env!("OTEL_SDK_RUSTC_VERSION") {
+            attributes.push(KeyValue::new(super::PROCESS_RUNTIME_VERSION, version));
+        }
+        if let Ok(path) = env::current_exe() {
+            if let Some(name) = path.file_name() {
+                attributes.push(KeyValue::new(
+                    super::PROCESS_EXECUTABLE_NAME,
+                    name.to_string_lossy().into_owned(),
+                ));
+            }
+            attributes.push(KeyValue::new(
+                super::PROCESS_EXECUTABLE_PATH,
+                path.to_string_lossy().into_owned(),
+            ));
+        }
+        if let Some(owner) = process_owner() {
+            attributes.push(KeyValue::new(super::PROCESS_OWNER, owner));
+        }
+
+        Resource::builder_empty()
+            .with_attributes(attributes)
+            .build()
+    }
+}
+
+fn process_owner() -> Option<String> {
+    linux_process_owner().or_else(|| {
+        ["USER", "USERNAME"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.trim().is_empty())
+    })
+}
+
+fn linux_process_owner() -> Option<String> {
+    if !cfg!(target_os = "linux") || cfg!(miri) {
+        return None;
+    }
+    let uid = real_uid(&fs::read_to_string("/proc/self/status").ok()?)?;
+    user_name(&fs::read_to_string("/etc/passwd").ok()?, uid)
+}
+
+/// Extracts the real user id from the content of `/proc/<pid>/status`.
+fn real_uid(status: &str) -> Option<u32> {
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("Uid:"))
+        .and_then(|ids| ids.split_whitespace().next())
+        .and_then(|uid| uid.parse().ok())
+}
+
+/// Looks up the name of the user with `uid` in the content of `/etc/passwd`.
+fn user_name(passwd: &str, uid: u32) -> Option<String> {
+    passwd.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        let entry_uid: u32 = fields.nth(1)?.parse().ok()?;
+        (entry_uid == uid && !name.is_empty()).then(|| name.to_owned())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::Key;
+
+    #[test]
+    fn parses_process_owner() {
+        let status = "Name:\tcat\nUmask:\t0022\nUid:\t1000\t1000\t1000\t1000\nGid:\t1000\t1000\t1000\t1000\n";
+        assert_eq!(real_uid(status), Some(1000));
+        assert_eq!(real_uid("Name:\tcat\n"), None);
+
+        let passwd =
+            "root:x:0:0:root:/root:/bin/bash\n# comment\nalice:x:1000:1000::/home/alice:/bin/sh\n";
+        assert_eq!(user_name(passwd, 0), Some("root".to_owned()));
+        assert_eq!(user_name(passwd, 1000), Some("alice".to_owned()));
+        assert_eq!(user_name(passwd, 1001), None);
+    }
+
+    #[test]
+    fn detects_process() {
+        let resource = ProcessResourceDetector.detect();
+        let get = |key: &'static str| resource.get(&Key::from_static_str(key));
+
+        assert_eq!(
+            get(crate::resource::PROCESS_PID),
+            Some(Value::I64(i64::from(std::process::id())))
+        );
+        assert_eq!(
+            get(crate::resource::PROCESS_RUNTIME_NAME),
+            Some(Value::from("rustc"))
+        );
+        let version = get(crate::resource::PROCESS_RUNTIME_VERSION)
+            .unwrap()
+            .to_string();
+        assert!(version.starts_with("1."), "{version}");
+        let Some(Value::Array(Array::String(args))) = get(crate::resource::PROCESS_COMMAND_ARGS)
+        else {
+            panic!("process.command_args should be a string array");
+        };
+        assert_eq!(args.len(), env::args_os().count());
+
+        if !cfg!(miri) {
+            let name = get(crate::resource::PROCESS_EXECUTABLE_NAME)
+                .unwrap()
+                .to_string();
+            assert!(name.starts_with("opentelemetry_sdk-"), "{name}");
+            assert!(get(crate::resource::PROCESS_EXECUTABLE_PATH).is_some());
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! # Instrumentation scope configuration
+//!
+//! Rules to configure the tracers, meters and loggers of some instrumentation
+//! scopes differently from the others, e.g. to disable the telemetry of a
+//! single noisy library.
+//!
+//! A [`ScopeConfigurator`] maps each [`InstrumentationScope`] to the config of
+//! the first rule whose [`ScopeSelector`] matches it, or to a default config.
+//! It is set on the provider builders with
+//! [`TracerProviderBuilder::with_tracer_configurator`],
+//! [`MeterProviderBuilder::with_meter_configurator`] and
+//! [`LoggerProviderBuilder::with_logger_configurator`], which also accept
+//! closures.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "trace")]
+//! # {
+//! use opentelemetry::trace::{Tracer, TracerProvider};
+//! use opentelemetry_sdk::scope_config::{ScopeConfigurator, ScopeSelector};
+//! use opentelemetry_sdk::trace::{SdkTracerProvider, TracerConfig};
+//!
+//! let provider = SdkTracerProvider::builder()
+//!     .with_tracer_configurator(
+//!         ScopeConfigurator::new(TracerConfig::default())
+//!             .with_rule(ScopeSelector::new().with_name("sqlx*"), TracerConfig::disabled()),
+//!     )
+//!     .build();
+//!
+//! assert!(!provider.tracer("sqlx-postgres").enabled());
+//! # }
+//! ```
+//!
+//! [`TracerProviderBuilder::with_tracer_configurator`]: crate::trace::TracerProviderBuilder::with_tracer_configurator
+//! [`MeterProviderBuilder::with_meter_configurator`]: crate::metrics::MeterProviderBuilder::with_meter_configurator
+//! [`LoggerProviderBuilder::with_logger_configurator`]: crate::logs::LoggerProviderBuilder::with_logger_configurator
+use crate::util::wildcard_match;
+use opentelemetry::{InstrumentationScope, KeyValue};
+use std::borrow::Cow;
+
+/// Selects the instrumentation scopes a [`ScopeConfigurator`] rule applies
+/// to.
+///
+/// A scope is selected if it matches every criterion that is set, so a
+/// selector without criteria selects every scope. The name criterion is case
+/// sensitive and supports the wildcards `*`, matching any number of
+/// characters, and `?`, matching exactly one character. The version and
+/// schema URL must match exactly, and the scope must have every selected
+/// attribute with the same value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScopeSelector {
+    name: Option<Cow<'static, str>>,
+    version: Option<Cow<'static, str>>,
+    schema_url: Option<Cow<'static, str>>,
+    attributes: Vec<KeyValue>,
+}
+
+impl ScopeSelector {
+    /// Create a new selector without any criteria.
+    pub fn new() -> Self {
+        ScopeSelector::default()
+    }
+
+    /// Select scopes whose name matches `name`, which may contain the
+    /// wildcards `*` and `?`.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Select scopes with the given version.
+    pub fn with_version(mut self, version: impl Into<Cow<'static, str>>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Select scopes with the given schema URL.
+    pub fn with_schema_url(mut self, schema_url: impl Into<Cow<'static, str>>) -> Self {
+        self.schema_url = Some(schema_url.into());
+        self
+    }
+
+    /// Select scopes having the given attribute.
+    ///
+    /// Can be called several times to select scopes having all the attributes.
+    pub fn with_attribute(mut self, attribute: KeyValue) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Returns `true` if the scope matches every criterion of this selector.
+    pub fn matches(&self, scope: &InstrumentationScope) -> bool {
+        self.name
+            .as_deref()
+            .map_or(true, |pattern| wildcard_match(pattern, scope.name(), false))
+            && self
+                .version
+                .as_deref()
+                .map_or(true, |version| Some(version) == scope.version())
+            && self
+                .schema_url
+                .as_deref()
+                .map_or(true, |url| Some(url) == scope.schema_url())
+            && self
+                .attributes
+                .iter()
+                .all(|attribute| scope.attributes().any(|kv| kv == attribute))
+    }
+}
+
+/// Maps instrumentation scopes to configs of type `C`, such as
+/// [`TracerConfig`], [`MeterConfig`] or [`LoggerConfig`].
+///
+/// The config of a scope is the config of the first rule, in the order they
+/// are added, whose selector matches the scope, or the default config if no
+/// rule matches.
+///
+/// [`TracerConfig`]: crate::trace::TracerConfig
+/// [`MeterConfig`]: crate::metrics::MeterConfig
+/// [`LoggerConfig`]: crate::logs::LoggerConfig
+#[derive(Clone, Debug, Default)]
+pub struct ScopeConfigurator<C> {
+    default: C,
+    rules: Vec<(ScopeSelector, C)>,
+}
+
+impl<C: Clone> ScopeConfigurator<C> {
+    /// Create a configurator without any rule, returning `default` for every
+    /// scope.
+    pub fn new(default: C) -> Self {
+        ScopeConfigurator {
+            default,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Use `config` for the scopes selected by `selector`.
+    pub fn with_rule(mut self, selector: ScopeSelector, config: C) -> Self {
+        self.rules.push((selector, config));
+        self
+    }
+
+    /// Returns the config of `scope`.
+    pub fn config(&self, scope: &InstrumentationScope) -> C {
+        self.rules
+            .iter()
+            .find(|(selector, _)| selector.matches(scope))
+            .map_or(&self.default, |(_, config)| config)
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selector_matches_every_criterion() {
+        let scope = InstrumentationScope::builder("sqlx-postgres")
+            .with_version("0.8.0")
+            .with_attributes([KeyValue::new("db.system", "postgresql")])
+            .build();
+
+        assert!(ScopeSelector::new().matches(&scope));
+        assert!(ScopeSelector::new().with_name("sqlx*").matches(&scope));
+        assert!(!ScopeSelector::new().with_name("SQLX*").matches(&scope));
+        assert!(ScopeSelector::new()
+            .with_name("sqlx-?ostgres")
+            .with_version("0.8.0")
+            .with_attribute(KeyValue::new("db.system", "postgresql"))
+            .matches(&scope));
+        assert!(!ScopeSelector::new()
+            .with_name("sqlx*")
+            .with_version("0.7.0")
+            .matches(&scope));
+        assert!(!ScopeSelector::new()
+            .with_attribute(KeyValue::new("db.system", "mysql"))
+            .matches(&scope));
+        assert!(!ScopeSelector::new()
+            .with_schema_url("https://opentelemetry.io/schemas/1.26.0")
+            .matches(&scope));
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let configurator = ScopeConfigurator::new("default")
+            .with_rule(ScopeSelector::new().with_name("db.*"), "db")
+            .with_rule(ScopeSelector::new().with_name("db.sqlx"), "sqlx");
+
+        let config = |name| configurator.config(&InstrumentationScope::builder(name).build());
+        assert_eq!(config("db.sqlx"), "db");
+        assert_eq!(config("http"), "default");
+    }
+}
@@ -0,0 +1,659 @@
+//! Metrics about the SDK itself, following the [semantic conventions for SDK
+//! metrics]. See `docs/design/observability.md` for the list of metrics.
+//!
+//! Spans and logs are counted with plain atomics, so recording on the hot
+//! paths is a single atomic increment without any attribute lookup. The
+//! counts are reported by observable instruments when metrics are collected,
+//! whose callbacks are unregistered when the component shuts down or is
+//! dropped. The outcomes of the processors, recorded once per exported batch
+//! by the batching processors, are recorded with counters.
+//!
+//! [semantic conventions for SDK metrics]: https://github.com/open-telemetry/semantic-conventions/blob/main/docs/otel/sdk-metrics.md
+#[cfg(feature = "trace")]
+use crate::trace::SamplingDecision;
+#[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+use opentelemetry::metrics::{Counter, Registration};
+#[cfg(feature = "metrics")]
+use opentelemetry::metrics::{Histogram, Meter, MeterProvider};
+#[cfg(feature = "trace")]
+use opentelemetry::trace::SpanContext;
+#[cfg(feature = "metrics")]
+use opentelemetry::{global, KeyValue};
+#[cfg(all(feature = "trace", not(target_has_atomic = "64")))]
+use portable_atomic::AtomicI64;
+#[cfg(all(
+    any(feature = "trace", feature = "logs"),
+    not(target_has_atomic = "64")
+))]
+use portable_atomic::AtomicU64;
+#[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+use std::sync::atomic::AtomicBool;
+#[cfg(all(feature = "trace", target_has_atomic = "64"))]
+use std::sync::atomic::AtomicI64;
+#[cfg(all(any(feature = "trace", feature = "logs"), target_has_atomic = "64"))]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(any(feature = "trace", feature = "logs"))]
+use std::sync::Arc;
+#[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+use std::sync::Mutex;
+#[cfg(feature = "metrics")]
+use std::sync::OnceLock;
+#[cfg(feature = "metrics")]
+use std::time::Duration;
+
+/// The name of the meter reporting the self-diagnostics metrics.
+#[cfg(feature = "metrics")]
+pub(crate) const METER_NAME: &str = "otel.sdk";
+
+/// Explicit bucket boundaries of `otel.sdk.metric_reader.collection.duration`,
+/// in seconds.
+#[cfg(feature = "metrics")]
+const COLLECTION_DURATION_BOUNDARIES: [f64; 14] = [
+    0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0,
+];
+
+/// The meter a component reports its self-diagnostics metrics with.
+///
+/// Defaults to the `otel.sdk` meter of the global meter provider, which is
+/// only resolved when the component first records or registers a metric, so
+/// that the meter provider set by then is used.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SdkMeter {
+    #[cfg(feature = "metrics")]
+    meter: Option<Meter>,
+}
+
+impl SdkMeter {
+    /// Reports to the `otel.sdk` meter of `meter_provider`.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn new(meter_provider: &impl MeterProvider) -> Self {
+        SdkMeter {
+            meter: Some(meter_provider.meter(METER_NAME)),
+        }
+    }
+
+    /// The configured meter, or the one of the current global meter provider.
+    #[cfg(feature = "metrics")]
+    fn meter(&self) -> Meter {
+        self.meter
+            .clone()
+            .unwrap_or_else(|| global::meter(METER_NAME))
+    }
+
+    /// Whether the meter is the one of the global meter provider.
+    #[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+    fn is_global(&self) -> bool {
+        self.meter.is_none()
+    }
+}
+
+/// The observable callbacks of a component, unregistered when the component
+/// shuts down or is dropped.
+///
+/// The callbacks of a component reporting to the global meter provider are
+/// registered at its first use rather than when it is built.
+#[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+#[derive(Debug)]
+struct Callbacks {
+    meter: SdkMeter,
+    /// Set once the callbacks are registered or unregistered, so they are
+    /// never registered again.
+    registered: AtomicBool,
+    registrations: Mutex<Vec<Registration>>,
+}
+
+#[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+impl Callbacks {
+    /// Registers the callbacks with `register`, right away if `meter` is not
+    /// the global one.
+    fn new(meter: SdkMeter, register: impl FnOnce(&Meter) -> Vec<Registration>) -> Self {
+        let callbacks = Callbacks {
+            meter,
+            registered: AtomicBool::new(false),
+            registrations: Mutex::new(Vec::new()),
+        };
+        if !callbacks.meter.is_global() {
+            callbacks.register(register);
+        }
+        callbacks
+    }
+
+    /// Registers the callbacks with `register`, unless they are already
+    /// registered, being registered, e.g. by a component recording telemetry
+    /// while its callbacks are registered, or unregistered.
+    fn register(&self, register: impl FnOnce(&Meter) -> Vec<Registration>) {
+        if self.registered.load(Ordering::Acquire) {
+            return;
+        }
+        let Ok(mut registrations) = self.registrations.try_lock() else {
+            return;
+        };
+        if !self.registered.swap(true, Ordering::AcqRel) {
+            *registrations = register(&self.meter.meter());
+        }
+    }
+
+    fn unregister(&self) {
+        self.registered.store(true, Ordering::Release);
+        if let Ok(mut registrations) = self.registrations.lock() {
+            registrations.drain(..).for_each(Registration::unregister);
+        }
+    }
+}
+
+#[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+impl Drop for Callbacks {
+    fn drop(&mut self) {
+        self.unregister();
+    }
+}
+
+/// The `otel.component.type` of the SDK components.
+#[derive(Clone, Copy, Debug)]
+#[allow(clippy::enum_variant_names)] // Processors only without `metrics`.
+pub(crate) enum ComponentType {
+    #[cfg(feature = "trace")]
+    BatchingSpanProcessor,
+    #[cfg(feature = "trace")]
+    SimpleSpanProcessor,
+    #[cfg(feature = "logs")]
+    BatchingLogProcessor,
+    #[cfg(feature = "logs")]
+    SimpleLogProcessor,
+    #[cfg(feature = "metrics")]
+    PeriodicMetricReader,
+}
+
+impl ComponentType {
+    #[cfg(feature = "metrics")]
+    fn as_str(self) -> &'static str {
+        match self {
+            #[cfg(feature = "trace")]
+            ComponentType::BatchingSpanProcessor => "batching_span_processor",
+            #[cfg(feature = "trace")]
+            ComponentType::SimpleSpanProcessor => "simple_span_processor",
+            #[cfg(feature = "logs")]
+            ComponentType::BatchingLogProcessor => "batching_log_processor",
+            #[cfg(feature = "logs")]
+            ComponentType::SimpleLogProcessor => "simple_log_processor",
+            ComponentType::PeriodicMetricReader => "periodic_metric_reader",
+        }
+    }
+
+    /// The `otel.component.type` and `otel.component.name` attributes of a new
+    /// instance, named after its type and a per-type counter, e.g.
+    /// `batching_span_processor/0`.
+    #[cfg(feature = "metrics")]
+    fn new_instance_attributes(self) -> [KeyValue; 2] {
+        static INSTANCES: [AtomicUsize; 5] = [
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+            AtomicUsize::new(0),
+        ];
+        let id = INSTANCES[self as usize].fetch_add(1, Ordering::Relaxed);
+        [
+            KeyValue::new("otel.component.type", self.as_str()),
+            KeyValue::new("otel.component.name", format!("{}/{id}", self.as_str())),
+        ]
+    }
+}
+
+/// Metrics of a span or log processor, reported as
+/// `otel.sdk.processor.{span,log}.processed` and, for processors with a
+/// queue, `otel.sdk.processor.{span,log}.queue.capacity` and
+/// `otel.sdk.processor.{span,log}.queue.size`.
+#[cfg(any(feature = "trace", feature = "logs"))]
+#[derive(Debug)]
+pub(crate) struct ProcessorMetrics {
+    #[cfg(feature = "metrics")]
+    meter: SdkMeter,
+    #[cfg(feature = "metrics")]
+    signal: ProcessorSignal,
+    /// The attributes of the successful, `queue_full` and `already_shutdown`
+    /// outcomes.
+    #[cfg(feature = "metrics")]
+    outcomes: [Vec<KeyValue>; 3],
+    /// Created at the first recording, like the callbacks.
+    #[cfg(feature = "metrics")]
+    processed: OnceLock<Counter<u64>>,
+    #[cfg(feature = "metrics")]
+    queue: Option<(Callbacks, QueueCallbacks)>,
+}
+
+/// The names of a processor metrics.
+#[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+#[derive(Clone, Copy, Debug)]
+struct ProcessorSignal {
+    signal: &'static str,
+    unit: &'static str,
+    items: &'static str,
+}
+
+/// What the queue callbacks of a processor report.
+#[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+#[derive(Debug)]
+struct QueueCallbacks {
+    capacity: usize,
+    size: Arc<AtomicUsize>,
+    attributes: [KeyValue; 2],
+}
+
+#[cfg(all(any(feature = "trace", feature = "logs"), feature = "metrics"))]
+impl QueueCallbacks {
+    fn register(&self, meter: &Meter, names: ProcessorSignal) -> Vec<Registration> {
+        let ProcessorSignal {
+            signal,
+            unit,
+            items,
+        } = names;
+        let capacity_counter = meter
+            .i64_observable_up_down_counter(format!("otel.sdk.processor.{signal}.queue.capacity"))
+            .with_description(format!(
+                "The maximum number of {items} the queue of a given instance of an SDK {signal} processor can hold."
+            ))
+            .with_unit(unit)
+            .build();
+        let size_counter = meter
+            .i64_observable_up_down_counter(format!("otel.sdk.processor.{signal}.queue.size"))
+            .with_description(format!(
+                "The number of {items} in the queue of a given instance of an SDK {signal} processor."
+            ))
+            .with_unit(unit)
+            .build();
+
+        let capacity = i64::try_from(self.capacity).unwrap_or(i64::MAX);
+        let size = self.size.clone();
+        let attributes = self.attributes.clone();
+        let registration = meter.register_callback(&[&capacity_counter, &size_counter], {
+            let (capacity_counter, size_counter) = (capacity_counter.clone(), size_counter.clone());
+            move |observer| {
+                observer.observe(&capacity_counter, capacity, &attributes);
+                let size = i64::try_from(size.load(Ordering::Relaxed)).unwrap_or(i64::MAX);
+                observer.observe(&size_counter, size, &attributes);
+            }
+        });
+        vec![registration]
+    }
+}
+
+#[cfg(any(feature = "trace", feature = "logs"))]
+impl ProcessorMetrics {
+    /// Creates the metrics of a new processor instance. `queue` is the
+    /// capacity and the current size of the queue of the processor, if any.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn new(
+        meter: &SdkMeter,
+        component_type: ComponentType,
+        queue: Option<(usize, Arc<AtomicUsize>)>,
+    ) -> Arc<Self> {
+        #[cfg(feature = "metrics")]
+        {
+            let signal = match component_type {
+                #[cfg(feature = "trace")]
+                ComponentType::BatchingSpanProcessor | ComponentType::SimpleSpanProcessor => {
+                    ProcessorSignal {
+                        signal: "span",
+                        unit: "{span}",
+                        items: "spans",
+                    }
+                }
+                #[cfg(feature = "logs")]
+                ComponentType::BatchingLogProcessor | ComponentType::SimpleLogProcessor => {
+                    ProcessorSignal {
+                        signal: "log",
+                        unit: "{log_record}",
+                        items: "log records",
+                    }
+                }
+                ComponentType::PeriodicMetricReader => unreachable!("not a processor"),
+            };
+            let attributes = component_type.new_instance_attributes();
+            let outcomes = [None, Some("queue_full"), Some("already_shutdown")].map(|error_type| {
+                error_type
+                    .map(|error_type| KeyValue::new("error.type", error_type))
+                    .into_iter()
+                    .chain(attributes.iter().cloned())
+                    .collect::<Vec<_>>()
+            });
+            let queue = queue.map(|(capacity, size)| {
+                let queue = QueueCallbacks {
+                    capacity,
+                    size,
+                    attributes,
+                };
+                let callbacks =
+                    Callbacks::new(meter.clone(), |meter| queue.register(meter, signal));
+                (callbacks, queue)
+            });
+            Arc::new(ProcessorMetrics {
+                meter: meter.clone(),
+                signal,
+                outcomes,
+                processed: OnceLock::new(),
+                queue,
+            })
+        }
+        #[cfg(not(feature = "metrics"))]
+        Arc::new(ProcessorMetrics {})
+    }
+
+    /// The metrics of the same processor instance, reported to `meter`, for
+    /// the processors without a queue, which are configured once built.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn with_meter(&self, meter: &SdkMeter) -> Arc<Self> {
+        debug_assert!(self.queue.is_none(), "the queue callbacks are not moved");
+        Arc::new(ProcessorMetrics {
+            meter: meter.clone(),
+            signal: self.signal,
+            outcomes: self.outcomes.clone(),
+            processed: OnceLock::new(),
+            queue: None,
+        })
+    }
+
+    /// Registers the queue callbacks of a processor reporting to the global
+    /// meter provider, at its first use.
+    pub(crate) fn register_callbacks(&self) {
+        #[cfg(feature = "metrics")]
+        if let Some((callbacks, queue)) = &self.queue {
+            callbacks.register(|meter| queue.register(meter, self.signal));
+        }
+    }
+
+    /// Unregisters the queue callbacks, when the processor shuts down.
+    pub(crate) fn unregister_callbacks(&self) {
+        #[cfg(feature = "metrics")]
+        if let Some((callbacks, _)) = &self.queue {
+            callbacks.unregister();
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    fn record_processed(&self, count: u64, outcome: usize) {
+        self.register_callbacks();
+        let ProcessorSignal {
+            signal,
+            unit,
+            items,
+        } = self.signal;
+        self.processed
+            .get_or_init(|| {
+                self.meter
+                    .meter()
+                    .u64_counter(format!("otel.sdk.processor.{signal}.processed"))
+                    .with_description(format!(
+                        "The number of {items} for which the processing has finished, either successful or failed."
+                    ))
+                    .with_unit(unit)
+                    .build()
+            })
+            .add(count, &self.outcomes[outcome]);
+    }
+
+    /// Records items submitted to the exporter, whatever the export outcome.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn record_success(&self, count: usize) {
+        #[cfg(feature = "metrics")]
+        self.record_processed(count as u64, 0);
+    }
+
+    /// Records an item dropped because the queue is full.
+    pub(crate) fn record_queue_full(&self) {
+        #[cfg(feature = "metrics")]
+        self.record_processed(1, 1);
+    }
+
+    /// Records an item dropped because the processor is shut down.
+    pub(crate) fn record_already_shutdown(&self) {
+        #[cfg(feature = "metrics")]
+        self.record_processed(1, 2);
+    }
+}
+
+/// The `otel.span.sampling_result` values, indexed by sampling decision.
+#[cfg(all(feature = "trace", feature = "metrics"))]
+const SAMPLING_RESULTS: [&str; 3] = ["DROP", "RECORD_ONLY", "RECORD_AND_SAMPLE"];
+
+/// The `otel.span.parent.origin` values, indexed by parent origin.
+#[cfg(all(feature = "trace", feature = "metrics"))]
+const PARENT_ORIGINS: [&str; 3] = ["none", "local", "remote"];
+
+/// Counts of a tracer provider, reported as `otel.sdk.span.started` and
+/// `otel.sdk.span.live`.
+#[cfg(feature = "trace")]
+#[derive(Debug, Default)]
+pub(crate) struct SpanMetrics {
+    counts: Arc<SpanCounts>,
+    /// `None` for the providers not reporting metrics.
+    #[cfg(feature = "metrics")]
+    callbacks: Option<Callbacks>,
+}
+
+#[cfg(feature = "trace")]
+#[derive(Debug, Default)]
+struct SpanCounts {
+    /// Started spans, by parent origin and sampling decision.
+    started: [[AtomicU64; 3]; 3],
+    /// Recording spans not ended yet, by sampling decision.
+    live: [AtomicI64; 3],
+}
+
+#[cfg(feature = "trace")]
+impl SpanMetrics {
+    /// Creates the metrics of a new tracer provider.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn new(meter: &SdkMeter) -> Arc<Self> {
+        let counts = Arc::new(SpanCounts::default());
+        Arc::new(SpanMetrics {
+            #[cfg(feature = "metrics")]
+            callbacks: Some(Callbacks::new(meter.clone(), |meter| {
+                Self::register(meter, &counts)
+            })),
+            counts,
+        })
+    }
+
+    #[cfg(feature = "metrics")]
+    fn register(meter: &Meter, counts: &Arc<SpanCounts>) -> Vec<Registration> {
+        let started = meter
+            .u64_observable_counter("otel.sdk.span.started")
+            .with_description("The number of created spans.")
+            .with_unit("{span}")
+            .build();
+        let live = meter
+            .i64_observable_up_down_counter("otel.sdk.span.live")
+            .with_description(
+                "The number of created spans with `recording=true` for which the end operation has not been called yet.",
+            )
+            .with_unit("{span}")
+            .build();
+
+        let started_attributes = PARENT_ORIGINS.map(|origin| {
+            SAMPLING_RESULTS.map(|result| {
+                [
+                    KeyValue::new("otel.span.parent.origin", origin),
+                    KeyValue::new("otel.span.sampling_result", result),
+                ]
+            })
+        });
+        let live_attributes =
+            SAMPLING_RESULTS.map(|result| [KeyValue::new("otel.span.sampling_result", result)]);
+        let counts = counts.clone();
+        let registration = meter.register_callback(&[&started, &live], {
+            let (started, live) = (started.clone(), live.clone());
+            move |observer| {
+                for (counts, attributes) in counts.started.iter().zip(&started_attributes) {
+                    for (count, attributes) in counts.iter().zip(attributes) {
+                        let count = count.load(Ordering::Relaxed);
+                        if count > 0 {
+                            observer.observe(&started, count, attributes);
+                        }
+                    }
+                }
+                // Dropped spans are never recording.
+                for (count, attributes) in counts.live.iter().zip(&live_attributes).skip(1) {
+                    observer.observe(&live, count.load(Ordering::Relaxed), attributes);
+                }
+            }
+        });
+        vec![registration]
+    }
+
+    /// Unregisters the callbacks, when the provider shuts down.
+    pub(crate) fn unregister_callbacks(&self) {
+        #[cfg(feature = "metrics")]
+        if let Some(callbacks) = &self.callbacks {
+            callbacks.unregister();
+        }
+    }
+
+    /// Records a span started with `parent` as parent span context.
+    pub(crate) fn record_started(&self, parent: &SpanContext, decision: &SamplingDecision) {
+        #[cfg(feature = "metrics")]
+        if let Some(callbacks) = &self.callbacks {
+            callbacks.register(|meter| Self::register(meter, &self.counts));
+        }
+        let origin = if !parent.is_valid() {
+            0
+        } else if parent.is_remote() {
+            2
+        } else {
+            1
+        };
+        let decision = sampling_decision_index(decision);
+        self.counts.started[origin][decision].fetch_add(1, Ordering::Relaxed);
+        if decision > 0 {
+            self.counts.live[decision].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records the end of a recording span.
+    pub(crate) fn record_ended(&self, sampled: bool) {
+        let decision = if sampled {
+            SamplingDecision::RecordAndSample
+        } else {
+            SamplingDecision::RecordOnly
+        };
+        self.counts.live[sampling_decision_index(&decision)].fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "trace")]
+fn sampling_decision_index(decision: &SamplingDecision) -> usize {
+    match decision {
+        SamplingDecision::Drop => 0,
+        SamplingDecision::RecordOnly => 1,
+        SamplingDecision::RecordAndSample => 2,
+    }
+}
+
+/// Counts of a logger provider, reported as `otel.sdk.log.created`.
+#[cfg(feature = "logs")]
+#[derive(Debug, Default)]
+pub(crate) struct LogMetrics {
+    created: Arc<AtomicU64>,
+    /// `None` for the providers not reporting metrics.
+    #[cfg(feature = "metrics")]
+    callbacks: Option<Callbacks>,
+}
+
+#[cfg(feature = "logs")]
+impl LogMetrics {
+    /// Creates the metrics of a new logger provider.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    pub(crate) fn new(meter: &SdkMeter) -> Arc<Self> {
+        let created = Arc::new(AtomicU64::new(0));
+        Arc::new(LogMetrics {
+            #[cfg(feature = "metrics")]
+            callbacks: Some(Callbacks::new(meter.clone(), |meter| {
+                Self::register(meter, &created)
+            })),
+            created,
+        })
+    }
+
+    #[cfg(feature = "metrics")]
+    fn register(meter: &Meter, created: &Arc<AtomicU64>) -> Vec<Registration> {
+        let counter = meter
+            .u64_observable_counter("otel.sdk.log.created")
+            .with_description("The number of logs submitted to enabled SDK Loggers.")
+            .with_unit("{log_record}")
+            .build();
+        let created = created.clone();
+        let registration = meter.register_callback(&[&counter], {
+            let counter = counter.clone();
+            move |observer| observer.observe(&counter, created.load(Ordering::Relaxed), &[])
+        });
+        vec![registration]
+    }
+
+    /// Unregisters the callback, when the provider shuts down.
+    pub(crate) fn unregister_callbacks(&self) {
+        #[cfg(feature = "metrics")]
+        if let Some(callbacks) = &self.callbacks {
+            callbacks.unregister();
+        }
+    }
+
+    /// Records a log record submitted to a logger.
+    pub(crate) fn record_created(&self) {
+        #[cfg(feature = "metrics")]
+        if let Some(callbacks) = &self.callbacks {
+            callbacks.register(|meter| Self::register(meter, &self.created));
+        }
+        self.created.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Records `otel.sdk.metric_reader.collection.duration` for a metric reader.
+///
+/// The histogram is created at the first collection, so a reader using the
+/// global meter provider reports to the provider set by then.
+#[cfg(feature = "metrics")]
+#[derive(Debug)]
+pub(crate) struct ReaderMetrics {
+    meter: SdkMeter,
+    attributes: [KeyValue; 2],
+    collection_duration: OnceLock<Histogram<f64>>,
+}
+
+#[cfg(feature = "metrics")]
+impl ReaderMetrics {
+    pub(crate) fn new(meter: SdkMeter, component_type: ComponentType) -> Self {
+        ReaderMetrics {
+            meter,
+            attributes: component_type.new_instance_attributes(),
+            collection_duration: OnceLock::new(),
+        }
+    }
+
+    /// Records a collection, with the `error.type` of its failure, if any.
+    pub(crate) fn record_collection(&self, duration: Duration, error_type: Option<&'static str>) {
+        let histogram = self.collection_duration.get_or_init(|| {
+            self.meter
+                .meter()
+                .f64_histogram("otel.sdk.metric_reader.collection.duration")
+                .with_description("The duration of the collect operation of the metric reader.")
+                .with_unit("s")
+                .with_boundaries(COLLECTION_DURATION_BOUNDARIES.to_vec())
+                .build()
+        });
+        match error_type {
+            Some(error_type) => {
+                let [component_type, component_name] = self.attributes.clone();
+                histogram.record(
+                    duration.as_secs_f64(),
+                    &[
+                        KeyValue::new("error.type", error_type),
+                        component_type,
+                        component_name,
+                    ],
+                )
+            }
+            None => histogram.record(duration.as_secs_f64(), &self.attributes),
+        }
+    }
+}
@@ -2,12 +2,22 @@
 //!
 //! Configuration represents the global tracing configuration, overrides
 //! can be set for the default OpenTelemetry limits and Sampler.
+#[cfg(feature = "jaeger_remote_sampler")]
+use crate::resource::SERVICE_NAME;
+#[cfg(feature = "jaeger_remote_sampler")]
+use crate::trace::sampler::jaeger_remote_sampler_from_env;
 use crate::trace::{span_limit::SpanLimits, IdGenerator, RandomIdGenerator, Sampler, ShouldSample};
 use crate::Resource;
 use opentelemetry::otel_warn;
+#[cfg(feature = "jaeger_remote_sampler")]
+use opentelemetry::Key;
+#[cfg(feature = "jaeger_remote_sampler")]
+use opentelemetry_http::HttpClient;
 use std::borrow::Cow;
 use std::env;
 use std::str::FromStr;
+#[cfg(feature = "jaeger_remote_sampler")]
+use std::sync::Arc;
 
 /// Tracer configuration
 #[derive(Debug)]
@@ -24,6 +34,14 @@ pub struct Config {
 
     /// Contains attributes representing an entity that produces telemetry.
     pub resource: Cow<'static, Resource>,
+
+    /// The Jaeger remote sampler selected by `OTEL_TRACES_SAMPLER`. It is only
+    /// built by [`TracerProviderBuilder::build`] if no other sampler was set,
+    /// as it starts polling its sampling strategy.
+    ///
+    /// [`TracerProviderBuilder::build`]: crate::trace::TracerProviderBuilder::build
+    #[cfg(feature = "jaeger_remote_sampler")]
+    pub(crate) jaeger_remote_from_env: Option<JaegerRemoteFromEnv>,
 }
 
 impl Default for Config {
@@ -34,6 +52,8 @@ impl Default for Config {
             id_generator: Box::<RandomIdGenerator>::default(),
             span_limits: SpanLimits::default(),
             resource: Cow::Owned(Resource::builder().build()),
+            #[cfg(feature = "jaeger_remote_sampler")]
+            jaeger_remote_from_env: None,
         };
 
         if let Some(max_attributes_per_span) = env::var("OTEL_SPAN_ATTRIBUTE_COUNT_LIMIT")
@@ -57,6 +77,14 @@ impl Default for Config {
             config.span_limits.max_links_per_span = max_links_per_span;
         }
 
+        if let Some(max_attribute_value_length) = env::var("OTEL_SPAN_ATTRIBUTE_VALUE_LENGTH_LIMIT")
+            .or_else(|_| env::var("OTEL_ATTRIBUTE_VALUE_LENGTH_LIMIT"))
+            .ok()
+            .and_then(|length_limit| u32::from_str(&length_limit).ok())
+        {
+            config.span_limits.max_attribute_value_length = Some(max_attribute_value_length);
+        }
+
         let sampler_arg = env::var("OTEL_TRACES_SAMPLER_ARG").ok();
         if let Ok(sampler) = env::var("OTEL_TRACES_SAMPLER") {
             config.sampler = match sampler.as_str() {
@@ -98,17 +126,45 @@ impl Default for Config {
                         ))))
                     }
                 }
-                "parentbased_jaeger_remote" => {
-                    otel_warn!(
-                        name: "TracerProvider.Config.UnsupportedSampler",
-                        message = "OTEL_TRACES_SAMPLER is set to 'parentbased_jaeger_remote' which is not implemented in this SDK version. Using fallback sampler: ParentBased(AlwaysOn). Configure an alternative sampler using OTEL_TRACES_SAMPLER"
-                    );
+                "rate_limited" => {
+                    let rate = sampler_arg.as_ref().and_then(|r| r.parse::<f64>().ok());
+                    if let Some(r) = rate.filter(|r| *r >= 0.0) {
+                        Box::new(Sampler::rate_limited(r))
+                    } else {
+                        otel_warn!(
+                            name: "TracerProvider.Config.InvalidSamplerArgument",
+                            message = "OTEL_TRACES_SAMPLER is set to 'rate_limited' but OTEL_TRACES_SAMPLER_ARG environment variable is missing or invalid. OTEL_TRACES_SAMPLER_ARG must be a non-negative number of traces sampled per second. Using fallback sampler: ParentBased(AlwaysOn)",
+                            otel_traces_sampler_arg = format!("{:?}", sampler_arg)
+                        );
+                        Box::new(Sampler::ParentBased(Box::new(Sampler::AlwaysOn)))
+                    }
+                }
+                "parentbased_rate_limited" => {
+                    let rate = sampler_arg.as_ref().and_then(|r| r.parse::<f64>().ok());
+                    if let Some(r) = rate.filter(|r| *r >= 0.0) {
+                        Box::new(Sampler::ParentBased(Box::new(Sampler::rate_limited(r))))
+                    } else {
+                        otel_warn!(
+                            name: "TracerProvider.Config.InvalidSamplerArgument",
+                            message = "OTEL_TRACES_SAMPLER is set to 'parentbased_rate_limited' but OTEL_TRACES_SAMPLER_ARG environment variable is missing or invalid. OTEL_TRACES_SAMPLER_ARG must be a non-negative number of traces sampled per second. Using fallback sampler: ParentBased(AlwaysOn)",
+                            otel_traces_sampler_arg = format!("{:?}", sampler_arg)
+                        );
+                        Box::new(Sampler::ParentBased(Box::new(Sampler::AlwaysOn)))
+                    }
+                }
+                #[cfg(feature = "jaeger_remote_sampler")]
+                "jaeger_remote" | "parentbased_jaeger_remote" => {
+                    config.jaeger_remote_from_env = Some(JaegerRemoteFromEnv {
+                        parent_based: sampler == "parentbased_jaeger_remote",
+                        arg: sampler_arg,
+                    });
                     Box::new(Sampler::ParentBased(Box::new(Sampler::AlwaysOn)))
                 }
-                "jaeger_remote" => {
+                #[cfg(not(feature = "jaeger_remote_sampler"))]
+                "jaeger_remote" | "parentbased_jaeger_remote" => {
                     otel_warn!(
                         name: "TracerProvider.Config.UnsupportedSampler",
-                        message = "OTEL_TRACES_SAMPLER is set to 'jaeger_remote' which is implemented in this SDK version. Using fallback sampler: ParentBased(AlwaysOn). Configure an alternative sampler using OTEL_TRACES_SAMPLER"
+                        message = format!("OTEL_TRACES_SAMPLER is set to '{}' which requires the jaeger_remote_sampler feature of opentelemetry_sdk. Using fallback sampler: ParentBased(AlwaysOn). Enable the feature or configure an alternative sampler using OTEL_TRACES_SAMPLER", sampler)
                     );
                     Box::new(Sampler::ParentBased(Box::new(Sampler::AlwaysOn)))
                 }
@@ -123,7 +179,7 @@ impl Default for Config {
                     otel_warn!(
                         name: "TracerProvider.Config.InvalidSamplerType",
                         message = format!(
-                            "Unrecognized sampler type '{}' in OTEL_TRACES_SAMPLER environment variable. Valid values are: always_on, always_off, traceidratio, parentbased_always_on, parentbased_always_off, parentbased_traceidratio. Using fallback sampler: ParentBased(AlwaysOn)",
+                            "Unrecognized sampler type '{}' in OTEL_TRACES_SAMPLER environment variable. Valid values are: always_on, always_off, traceidratio, parentbased_always_on, parentbased_always_off, parentbased_traceidratio, rate_limited, parentbased_rate_limited, jaeger_remote, parentbased_jaeger_remote. Using fallback sampler: ParentBased(AlwaysOn)",
                             s
                         ),
                     );
@@ -135,3 +191,43 @@ impl Default for Config {
         config
     }
 }
+
+/// The Jaeger remote sampler configured with `OTEL_TRACES_SAMPLER` and
+/// `OTEL_TRACES_SAMPLER_ARG`.
+#[cfg(feature = "jaeger_remote_sampler")]
+#[derive(Debug)]
+pub(crate) struct JaegerRemoteFromEnv {
+    parent_based: bool,
+    arg: Option<String>,
+}
+
+#[cfg(feature = "jaeger_remote_sampler")]
+impl JaegerRemoteFromEnv {
+    /// Builds the sampler, reading the service name from `resource` and
+    /// polling remote endpoints with `client`.
+    pub(crate) fn build(
+        self,
+        resource: &Resource,
+        client: Option<Arc<dyn HttpClient>>,
+    ) -> Box<dyn ShouldSample> {
+        let service_name = resource
+            .get(&Key::from_static_str(SERVICE_NAME))
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+        match jaeger_remote_sampler_from_env(self.arg.as_deref(), &service_name, client) {
+            Ok(remote_sampler) if self.parent_based => {
+                Box::new(Sampler::ParentBased(Box::new(remote_sampler)))
+            }
+            Ok(remote_sampler) => Box::new(remote_sampler),
+            Err(reason) => {
+                otel_warn!(
+                    name: "TracerProvider.Config.InvalidSamplerArgument",
+                    message = "OTEL_TRACES_SAMPLER is set to a Jaeger remote sampler but it could not be built. OTEL_TRACES_SAMPLER_ARG must be a comma-separated list of endpoint, pollingIntervalMs and initialSamplingRate settings, e.g. 'endpoint=http://localhost:5778/sampling,pollingIntervalMs=5000,initialSamplingRate=0.25', and the HTTP client querying a remote endpoint must be set with TracerProviderBuilder::with_jaeger_remote_http_client. Using fallback sampler: ParentBased(AlwaysOn)",
+                    otel_traces_sampler_arg = format!("{:?}", self.arg),
+                    reason = format!("{}", reason)
+                );
+                Box::new(Sampler::ParentBased(Box::new(Sampler::AlwaysOn)))
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::error::OTelSdkResult;
+use crate::trace::{Span, SpanData, SpanProcessor};
+use crate::Resource;
+use opentelemetry::Context;
+use std::fmt;
+use std::time::Duration;
+
+/// A [`SpanProcessor`] dropping the ended spans matching a predicate, and
+/// forwarding the others to another processor, such as a
+/// [`BatchSpanProcessor`].
+///
+/// The predicate sees the whole [`SpanData`], so spans can be filtered by
+/// name, instrumentation scope, attributes, duration, etc.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use opentelemetry_sdk::trace::{
+///     FilteringSpanProcessor, InMemorySpanExporter, SdkTracerProvider,
+///     SimpleSpanProcessor,
+/// };
+/// use std::time::Duration;
+///
+/// let exporter = InMemorySpanExporter::default();
+///
+/// // Drop the fast health check spans.
+/// let processor = FilteringSpanProcessor::new(SimpleSpanProcessor::new(exporter), |span| {
+///     span.name == "GET /healthz"
+///         && span
+///             .end_time
+///             .duration_since(span.start_time)
+///             .is_ok_and(|duration| duration < Duration::from_millis(100))
+/// });
+///
+/// let provider = SdkTracerProvider::builder()
+///     .with_span_processor(processor)
+///     .build();
+/// # }
+/// ```
+///
+/// [`BatchSpanProcessor`]: crate::trace::BatchSpanProcessor
+pub struct FilteringSpanProcessor<P> {
+    processor: P,
+    predicate: Box<dyn Fn(&SpanData) -> bool + Send + Sync>,
+}
+
+impl<P: SpanProcessor> FilteringSpanProcessor<P> {
+    /// Create a processor forwarding to `processor` the spans for which
+    /// `drop` returns `false`.
+    pub fn new<F>(processor: P, drop: F) -> Self
+    where
+        F: Fn(&SpanData) -> bool + Send + Sync + 'static,
+    {
+        FilteringSpanProcessor {
+            processor,
+            predicate: Box::new(drop),
+        }
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for FilteringSpanProcessor<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilteringSpanProcessor")
+            .field("processor", &self.processor)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P: SpanProcessor> SpanProcessor for FilteringSpanProcessor<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.processor.on_start(span, cx);
+    }
+
+    fn on_ending(&self, span: &mut Span) {
+        self.processor.on_ending(span);
+    }
+
+    fn on_end(&self, span: SpanData) {
+        if !(self.predicate)(&span) {
+            self.processor.on_end(span);
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.processor.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.processor.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.processor.set_resource(resource);
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::trace::{InMemorySpanExporter, SdkTracerProvider, SimpleSpanProcessor};
+    use opentelemetry::trace::{Tracer, TracerProvider};
+
+    #[test]
+    fn matching_spans_are_dropped() {
+        let exporter = InMemorySpanExporter::default();
+        let processor =
+            FilteringSpanProcessor::new(SimpleSpanProcessor::new(exporter.clone()), |span| {
+                span.instrumentation_scope.name() == "noisy" || span.name.starts_with("drop")
+            });
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(processor)
+            .build();
+
+        provider.tracer("noisy").in_span("keep", |_| {});
+        let tracer = provider.tracer("test");
+        tracer.in_span("drop me", |_| {});
+        tracer.in_span("keep", |_| {});
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].instrumentation_scope.name(), "test");
+        assert_eq!(spans[0].name, "keep");
+    }
+}
@@ -9,9 +9,11 @@
 mod config;
 mod events;
 mod export;
+mod filtering_span_processor;
 mod id_generator;
 mod links;
 mod provider;
+mod redacting_span_processor;
 mod sampler;
 mod span;
 mod span_limit;
@@ -19,11 +21,14 @@ mod span_processor;
 #[cfg(feature = "experimental_trace_batch_span_processor_with_async_runtime")]
 /// Experimental feature to use async runtime with batch span processor.
 pub mod span_processor_with_async_runtime;
+mod tail_sampling;
 mod tracer;
+mod tracer_config;
 
 pub use config::Config;
 pub use events::SpanEvents;
 pub use export::{SpanData, SpanExporter};
+pub use filtering_span_processor::FilteringSpanProcessor;
 
 /// In-Memory span exporter for testing purpose.
 #[cfg(any(feature = "testing", test))]
@@ -36,16 +41,24 @@ pub use in_memory_exporter::{InMemorySpanExporter, InMemorySpanExporterBuilder};
 pub use id_generator::{IdGenerator, RandomIdGenerator};
 pub use links::SpanLinks;
 pub use provider::{SdkTracerProvider, TracerProviderBuilder};
-pub use sampler::{Sampler, SamplingDecision, SamplingResult, ShouldSample};
+pub use redacting_span_processor::RedactingSpanProcessor;
+pub use sampler::{
+    ComposableAlwaysOff, ComposableAlwaysOn, ComposableAnnotating, ComposableParentThreshold,
+    ComposableProbability, ComposableRuleBased, ComposableSampler, CompositeSampler,
+    RateLimitingSampler, RuleBasedSampler, Sampler, SamplingDecision, SamplingIntent,
+    SamplingParameters, SamplingResult, SamplingRule, ShouldSample, MAX_THRESHOLD,
+};
 pub use span::Span;
 pub use span_limit::SpanLimits;
 pub use span_processor::{
     BatchConfig, BatchConfigBuilder, BatchSpanProcessor, BatchSpanProcessorBuilder,
     SimpleSpanProcessor, SpanProcessor,
 };
+pub use tail_sampling::{TailSamplingPolicy, TailSamplingProcessor, TailSamplingProcessorBuilder};
 
 pub use tracer::SdkTracer;
 pub use tracer::SdkTracer as Tracer; // for back-compat else tracing-opentelemetry won't build
+pub use tracer_config::{TracerConfig, TracerConfigurator};
 
 #[cfg(feature = "jaeger_remote_sampler")]
 pub use sampler::{JaegerRemoteSampler, JaegerRemoteSamplerBuilder};
@@ -1,5 +1,6 @@
 use super::IdGenerator;
 use crate::error::{OTelSdkError, OTelSdkResult};
+use crate::self_diagnostics::{SdkMeter, SpanMetrics};
 /// # Trace Provider SDK
 ///
 /// The `TracerProvider` handles the creation and management of [`Tracer`] instances and coordinates
@@ -66,7 +67,7 @@ use crate::error::{OTelSdkError, OTelSdkResult};
 /// ```
 use crate::trace::{
     BatchSpanProcessor, Config, RandomIdGenerator, Sampler, SdkTracer, SimpleSpanProcessor,
-    SpanLimits,
+    SpanLimits, TracerConfig, TracerConfigurator,
 };
 use crate::Resource;
 use crate::{trace::SpanExporter, trace::SpanProcessor};
@@ -94,8 +95,12 @@ fn noop_tracer_provider() -> &'static SdkTracerProvider {
                     id_generator: Box::<RandomIdGenerator>::default(),
                     span_limits: SpanLimits::default(),
                     resource: Cow::Owned(Resource::empty()),
+                    #[cfg(feature = "jaeger_remote_sampler")]
+                    jaeger_remote_from_env: None,
                 },
                 is_shutdown: AtomicBool::new(true),
+                span_metrics: Default::default(),
+                tracer_configurator: None,
             }),
         }
     })
@@ -107,12 +112,15 @@ pub(crate) struct TracerProviderInner {
     processors: Vec<Box<dyn SpanProcessor>>,
     config: crate::trace::Config,
     is_shutdown: AtomicBool,
+    span_metrics: Arc<SpanMetrics>,
+    tracer_configurator: Option<Box<dyn TracerConfigurator>>,
 }
 
 impl TracerProviderInner {
     /// Crate-private shutdown method to be called both from explicit shutdown
     /// and from Drop when the last reference is released.
     pub(crate) fn shutdown_with_timeout(&self, timeout: Duration) -> Vec<OTelSdkResult> {
+        self.span_metrics.unregister_callbacks();
         let mut results = vec![];
         for processor in &self.processors {
             let result = processor.shutdown_with_timeout(timeout);
@@ -194,6 +202,11 @@ impl SdkTracerProvider {
         self.inner.is_shutdown.load(Ordering::Relaxed)
     }
 
+    /// Self-diagnostics counts of the spans of this provider
+    pub(crate) fn span_metrics(&self) -> &SpanMetrics {
+        &self.inner.span_metrics
+    }
+
     /// Force flush all remaining spans in span processors and return results.
     ///
     /// # Examples
@@ -291,6 +304,20 @@ impl opentelemetry::trace::TracerProvider for SdkTracerProvider {
         if scope.name().is_empty() {
             otel_info!(name: "TracerNameEmpty",  message = "Tracer name is empty; consider providing a meaningful name. Tracer will function normally and the provided name will be used as-is.");
         };
+        let config = self
+            .inner
+            .tracer_configurator
+            .as_ref()
+            .map_or_else(TracerConfig::default, |configurator| {
+                configurator.tracer_config(&scope)
+            });
+        if !config.enabled {
+            otel_debug!(
+                name: "TracerProvider.DisabledTracerReturned",
+                tracer_name = scope.name(),
+            );
+            return SdkTracer::disabled(scope, self.clone());
+        }
         SdkTracer::new(scope, self.clone())
     }
 }
@@ -301,6 +328,10 @@ pub struct TracerProviderBuilder {
     processors: Vec<Box<dyn SpanProcessor>>,
     config: crate::trace::Config,
     resource: Option<Resource>,
+    meter: SdkMeter,
+    tracer_configurator: Option<Box<dyn TracerConfigurator>>,
+    #[cfg(feature = "jaeger_remote_sampler")]
+    jaeger_remote_http_client: Option<Arc<dyn opentelemetry_http::HttpClient>>,
 }
 
 impl TracerProviderBuilder {
@@ -374,6 +405,27 @@ impl TracerProviderBuilder {
     /// ```
     pub fn with_sampler(mut self, sampler: impl Into<Box<dyn crate::trace::ShouldSample>>) -> Self {
         self.config.sampler = sampler.into();
+        #[cfg(feature = "jaeger_remote_sampler")]
+        {
+            self.config.jaeger_remote_from_env = None;
+        }
+        self
+    }
+
+    /// Set the HTTP client polling the sampling strategy of the Jaeger remote
+    /// sampler selected with `OTEL_TRACES_SAMPLER`. Without it, only a
+    /// `file://` endpoint can be set in `OTEL_TRACES_SAMPLER_ARG`.
+    ///
+    /// The strategy is polled from a dedicated thread without async runtime,
+    /// so the client must not need one, like the `reqwest::blocking::Client`
+    /// supported by the `reqwest-blocking` feature of `opentelemetry-http`.
+    #[cfg(feature = "jaeger_remote_sampler")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jaeger_remote_sampler")))]
+    pub fn with_jaeger_remote_http_client<C>(mut self, client: C) -> Self
+    where
+        C: opentelemetry_http::HttpClient + 'static,
+    {
+        self.jaeger_remote_http_client = Some(Arc::new(client));
         self
     }
 
@@ -413,12 +465,55 @@ impl TracerProviderBuilder {
         self
     }
 
+    /// Specify the max length of the string values of span, event and link
+    /// attributes, in characters. Longer values are truncated.
+    pub fn with_max_attribute_value_length(mut self, max_length: u32) -> Self {
+        self.config.span_limits.max_attribute_value_length = Some(max_length);
+        self
+    }
+
     /// Specify all limit via the span_limits
     pub fn with_span_limits(mut self, span_limits: SpanLimits) -> Self {
         self.config.span_limits = span_limits;
         self
     }
 
+    /// Set the [TracerConfigurator] computing the [TracerConfig] of the
+    /// tracers of each instrumentation scope, e.g. to disable the tracers of
+    /// some libraries.
+    ///
+    /// Disabled tracers only start non-recording spans, which propagate the
+    /// span context of their parent, and report they are not enabled. By
+    /// default every tracer is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use opentelemetry::trace::{Tracer, TracerProvider};
+    /// use opentelemetry_sdk::trace::{SdkTracerProvider, TracerConfig};
+    /// use opentelemetry::InstrumentationScope;
+    ///
+    /// let provider = SdkTracerProvider::builder()
+    ///     .with_tracer_configurator(|scope: &InstrumentationScope| {
+    ///         TracerConfig::default().with_enabled(scope.name() != "noisy-library")
+    ///     })
+    ///     .build();
+    ///
+    /// assert!(!provider.tracer("noisy-library").enabled());
+    /// ```
+    ///
+    /// See [`ScopeConfigurator`] to select the scopes by name pattern, version
+    /// or attributes.
+    ///
+    /// [`ScopeConfigurator`]: crate::scope_config::ScopeConfigurator
+    pub fn with_tracer_configurator<T: TracerConfigurator + 'static>(
+        mut self,
+        configurator: T,
+    ) -> Self {
+        self.tracer_configurator = Some(Box::new(configurator));
+        self
+    }
+
     /// Associates a [Resource] with a [SdkTracerProvider].
     ///
     /// This [Resource] represents the entity producing telemetry and is associated
@@ -461,6 +556,27 @@ impl TracerProviderBuilder {
         TracerProviderBuilder { resource, ..self }
     }
 
+    /// Report the self-diagnostics metrics of the provider,
+    /// `otel.sdk.span.started` and `otel.sdk.span.live`, to `meter_provider`.
+    ///
+    /// By default, they are reported to the global meter provider set when
+    /// the first span is started. The metrics of the span processors are
+    /// configured on the processors, e.g. with
+    /// [`BatchSpanProcessorBuilder::with_meter_provider`].
+    ///
+    /// [`BatchSpanProcessorBuilder::with_meter_provider`]: crate::trace::BatchSpanProcessorBuilder::with_meter_provider
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn with_meter_provider(
+        self,
+        meter_provider: &impl opentelemetry::metrics::MeterProvider,
+    ) -> Self {
+        TracerProviderBuilder {
+            meter: SdkMeter::new(meter_provider),
+            ..self
+        }
+    }
+
     /// Create a new provider from this configuration.
     pub fn build(self) -> SdkTracerProvider {
         let mut config = self.config;
@@ -470,6 +586,11 @@ impl TracerProviderBuilder {
             config.resource = Cow::Owned(resource);
         };
 
+        #[cfg(feature = "jaeger_remote_sampler")]
+        if let Some(jaeger_remote) = config.jaeger_remote_from_env.take() {
+            config.sampler = jaeger_remote.build(&config.resource, self.jaeger_remote_http_client);
+        }
+
         // Standard config will contain an owned [`Resource`] (either sdk default or use supplied)
         // we can optimize the common case with a static ref to avoid cloning the underlying
         // resource data for each span.
@@ -499,6 +620,8 @@ impl TracerProviderBuilder {
             processors,
             config,
             is_shutdown,
+            span_metrics: SpanMetrics::new(&self.meter),
+            tracer_configurator: self.tracer_configurator,
         })
     }
 }
@@ -601,6 +724,8 @@ mod tests {
             ],
             config: Default::default(),
             is_shutdown: AtomicBool::new(false),
+            span_metrics: Default::default(),
+            tracer_configurator: None,
         });
 
         let results = tracer_provider.force_flush();
@@ -754,6 +879,113 @@ mod tests {
         assert_eq!(no_service_name.config().resource.len(), 0)
     }
 
+    #[test]
+    fn test_rate_limited_sampler_from_env() {
+        temp_env::with_vars(
+            [
+                ("OTEL_TRACES_SAMPLER", Some("parentbased_rate_limited")),
+                ("OTEL_TRACES_SAMPLER_ARG", Some("5")),
+            ],
+            || {
+                let provider = super::SdkTracerProvider::builder().build();
+                let sampler = format!("{:?}", provider.config().sampler);
+                assert!(
+                    sampler.starts_with(
+                        "ParentBased(RateLimited(RateLimitingSampler { traces_per_second: 5.0"
+                    ),
+                    "{sampler}"
+                );
+            },
+        );
+
+        temp_env::with_vars(
+            [
+                ("OTEL_TRACES_SAMPLER", Some("rate_limited")),
+                ("OTEL_TRACES_SAMPLER_ARG", Some("-1")),
+            ],
+            || {
+                let provider = super::SdkTracerProvider::builder().build();
+                let sampler = format!("{:?}", provider.config().sampler);
+                assert_eq!(sampler, "ParentBased(AlwaysOn)");
+            },
+        );
+    }
+
+    #[cfg(feature = "jaeger_remote_sampler")]
+    #[test]
+    fn test_jaeger_remote_sampler_from_env_is_built_lazily() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("strategy.json");
+        std::fs::write(
+            &path,
+            r#"{"strategyType":"PROBABILISTIC","probabilisticSampling":{"samplingRate":0.5}}"#,
+        )
+        .unwrap();
+        let arg = format!("endpoint=file://{}", path.display());
+        temp_env::with_vars(
+            [
+                ("OTEL_TRACES_SAMPLER", Some("parentbased_jaeger_remote")),
+                ("OTEL_TRACES_SAMPLER_ARG", Some(arg.as_str())),
+            ],
+            || {
+                let builder = super::SdkTracerProvider::builder();
+                assert!(builder.config.jaeger_remote_from_env.is_some());
+                let provider = builder.build();
+                let sampler = format!("{:?}", provider.config().sampler);
+                assert!(
+                    sampler.starts_with("ParentBased(JaegerRemote("),
+                    "{sampler}"
+                );
+
+                // An explicit sampler replaces the one from the environment,
+                // which is then never built.
+                let provider = super::SdkTracerProvider::builder()
+                    .with_sampler(crate::trace::Sampler::AlwaysOff)
+                    .build();
+                assert!(provider.config().jaeger_remote_from_env.is_none());
+                assert_eq!(format!("{:?}", provider.config().sampler), "AlwaysOff");
+            },
+        );
+    }
+
+    #[cfg(feature = "jaeger_remote_sampler")]
+    #[test]
+    fn test_jaeger_remote_sampler_from_env_uses_the_http_client() {
+        use opentelemetry_http::{Bytes, HttpClient, HttpError, Request, Response};
+
+        #[derive(Debug)]
+        struct UnavailableClient;
+
+        #[async_trait::async_trait]
+        impl HttpClient for UnavailableClient {
+            async fn send_bytes(&self, _: Request<Bytes>) -> Result<Response<Bytes>, HttpError> {
+                Err("unavailable".into())
+            }
+        }
+
+        temp_env::with_vars(
+            [
+                ("OTEL_TRACES_SAMPLER", Some("jaeger_remote")),
+                (
+                    "OTEL_TRACES_SAMPLER_ARG",
+                    Some("endpoint=http://jaeger:5778/sampling"),
+                ),
+                ("OTEL_SERVICE_NAME", Some("my-service")),
+            ],
+            || {
+                // A remote endpoint can't be polled without client.
+                let provider = super::SdkTracerProvider::builder().build();
+                let sampler = format!("{:?}", provider.config().sampler);
+                assert_eq!(sampler, "ParentBased(AlwaysOn)");
+
+                let provider = super::SdkTracerProvider::builder()
+                    .with_jaeger_remote_http_client(UnavailableClient)
+                    .build();
+                let sampler = format!("{:?}", provider.config().sampler);
+                assert!(sampler.starts_with("JaegerRemote("), "{sampler}");
+            },
+        );
+    }
     #[test]
     fn test_shutdown_noops() {
         let processor = TestSpanProcessor::new(false);
@@ -762,6 +994,8 @@ mod tests {
             processors: vec![Box::from(processor)],
             config: Default::default(),
             is_shutdown: AtomicBool::new(false),
+            span_metrics: Default::default(),
+            tracer_configurator: None,
         });
 
         let test_tracer_1 = tracer_provider.tracer("test1");
@@ -884,6 +1118,8 @@ mod tests {
                 ))],
                 config: Config::default(),
                 is_shutdown: AtomicBool::new(false),
+                span_metrics: Default::default(),
+                tracer_configurator: None,
             });
 
             {
@@ -922,6 +1158,8 @@ mod tests {
             ))],
             config: Config::default(),
             is_shutdown: AtomicBool::new(false),
+            span_metrics: Default::default(),
+            tracer_configurator: None,
         });
 
         // Create a scope to test behavior when providers are dropped
@@ -951,4 +1189,141 @@ mod tests {
         // Verify that shutdown was only called once, even after drop
         assert_eq!(shutdown_count.load(Ordering::SeqCst), 1);
     }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn span_metrics_count_started_and_live_spans() {
+        use crate::metrics::data::{AggregatedMetrics, MetricData};
+        use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
+        use crate::trace::Sampler;
+        use opentelemetry::trace::{
+            Span as _, SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+        };
+
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter.clone())
+            .build();
+        let provider = SdkTracerProvider::builder()
+            .with_sampler(Sampler::ParentBased(Box::new(Sampler::AlwaysOn)))
+            .with_meter_provider(&meter_provider)
+            .build();
+        let tracer = provider.tracer("test");
+
+        let root = tracer.start("root");
+        let root_cx = Context::current_with_span(root);
+        let child = tracer.start_with_context("child", &root_cx);
+        let remote_cx = Context::new().with_remote_span_context(SpanContext::new(
+            TraceId::from(1),
+            SpanId::from(1),
+            TraceFlags::default(),
+            true,
+            TraceState::default(),
+        ));
+        let mut dropped = tracer.start_with_context("dropped", &remote_cx);
+        dropped.end();
+
+        // Returns the data points of `name` as (attributes, value) pairs.
+        let read = |name: &str| {
+            meter_provider.force_flush().unwrap();
+            let metrics = metric_exporter.get_finished_metrics().unwrap();
+            let metric = metrics
+                .last()
+                .unwrap()
+                .scope_metrics
+                .iter()
+                .flat_map(|sm| &sm.metrics)
+                .find(|metric| metric.name == name)
+                .unwrap();
+            let points: Vec<(Vec<KeyValue>, i64)> = match &metric.data {
+                AggregatedMetrics::U64(MetricData::Sum(sum)) => sum
+                    .data_points()
+                    .map(|dp| (dp.attributes().cloned().collect(), dp.value() as i64))
+                    .collect(),
+                AggregatedMetrics::I64(MetricData::Sum(sum)) => sum
+                    .data_points()
+                    .map(|dp| (dp.attributes().cloned().collect(), dp.value()))
+                    .collect(),
+                _ => panic!("unexpected data for {name}"),
+            };
+            points
+        };
+        let value = |points: &[(Vec<KeyValue>, i64)], attributes: &[KeyValue]| {
+            points
+                .iter()
+                .find(|(point_attributes, _)| {
+                    attributes
+                        .iter()
+                        .all(|attribute| point_attributes.contains(attribute))
+                })
+                .map(|(_, value)| *value)
+        };
+
+        let started = read("otel.sdk.span.started");
+        assert_eq!(started.len(), 3);
+        for (origin, result) in [
+            ("none", "RECORD_AND_SAMPLE"),
+            ("local", "RECORD_AND_SAMPLE"),
+            ("remote", "DROP"),
+        ] {
+            assert_eq!(
+                value(
+                    &started,
+                    &[
+                        KeyValue::new("otel.span.parent.origin", origin),
+                        KeyValue::new("otel.span.sampling_result", result),
+                    ]
+                ),
+                Some(1),
+                "started spans with a {origin} parent"
+            );
+        }
+        let live = read("otel.sdk.span.live");
+        let sampled = [KeyValue::new(
+            "otel.span.sampling_result",
+            "RECORD_AND_SAMPLE",
+        )];
+        assert_eq!(value(&live, &sampled), Some(2));
+
+        drop(child);
+        root_cx.span().end();
+        let live = read("otel.sdk.span.live");
+        assert_eq!(value(&live, &sampled), Some(0));
+    }
+
+    #[test]
+    fn tracer_configurator_disables_scopes() {
+        use crate::scope_config::{ScopeConfigurator, ScopeSelector};
+        use crate::trace::{InMemorySpanExporter, TracerConfig};
+        use opentelemetry::trace::{Span as _, TraceContextExt};
+
+        let exporter = InMemorySpanExporter::default();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .with_tracer_configurator(ScopeConfigurator::new(TracerConfig::default()).with_rule(
+                ScopeSelector::new().with_name("noisy*"),
+                TracerConfig::disabled(),
+            ))
+            .build();
+        let tracer = provider.tracer("app");
+        let noisy_tracer = provider.tracer("noisy-library");
+        assert!(tracer.enabled());
+        assert!(!noisy_tracer.enabled());
+
+        tracer.in_span("parent", |cx| {
+            let noisy_span = noisy_tracer.start_with_context("noisy", &cx);
+            assert!(!noisy_span.is_recording());
+            assert_eq!(noisy_span.span_context(), cx.span().span_context());
+            let noisy_cx = cx.with_span(noisy_span);
+            tracer.start_with_context("child", &noisy_cx).end();
+        });
+
+        // The span of the disabled tracer is not exported, and the span it
+        // encloses is a child of the enclosing span.
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].name, "child");
+        assert_eq!(spans[1].name, "parent");
+        assert_eq!(spans[0].parent_span_id, spans[1].span_context.span_id());
+    }
 }
@@ -0,0 +1,127 @@
+use crate::error::OTelSdkResult;
+use crate::redaction::Redactor;
+use crate::trace::{Span, SpanData, SpanProcessor};
+use crate::Resource;
+use opentelemetry::Context;
+use std::time::Duration;
+
+/// A [`SpanProcessor`] applying a [`Redactor`] to the attributes of ended
+/// spans, and of their events and links, before forwarding them to another
+/// processor, such as a [`BatchSpanProcessor`].
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use opentelemetry_sdk::redaction::{RedactionAction, Redactor};
+/// use opentelemetry_sdk::trace::{
+///     InMemorySpanExporter, RedactingSpanProcessor, SdkTracerProvider, SimpleSpanProcessor,
+/// };
+///
+/// let exporter = InMemorySpanExporter::default();
+///
+/// let redactor = Redactor::new()
+///     .with_attribute("user.email", RedactionAction::Hash)
+///     .with_attribute("url.full", RedactionAction::MaskUrlQuery);
+/// let processor = RedactingSpanProcessor::new(SimpleSpanProcessor::new(exporter), redactor);
+///
+/// let provider = SdkTracerProvider::builder()
+///     .with_span_processor(processor)
+///     .build();
+/// # }
+/// ```
+///
+/// [`BatchSpanProcessor`]: crate::trace::BatchSpanProcessor
+#[derive(Debug)]
+pub struct RedactingSpanProcessor<P> {
+    processor: P,
+    redactor: Redactor,
+}
+
+impl<P: SpanProcessor> RedactingSpanProcessor<P> {
+    /// Create a processor redacting spans with `redactor` before forwarding
+    /// them to `processor`.
+    pub fn new(processor: P, redactor: Redactor) -> Self {
+        RedactingSpanProcessor {
+            processor,
+            redactor,
+        }
+    }
+}
+
+impl<P: SpanProcessor> SpanProcessor for RedactingSpanProcessor<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.processor.on_start(span, cx);
+    }
+
+    fn on_ending(&self, span: &mut Span) {
+        self.processor.on_ending(span);
+    }
+
+    fn on_end(&self, mut span: SpanData) {
+        self.redactor.redact_attributes(&mut span.attributes);
+        for event in span.events.events.iter_mut() {
+            self.redactor.redact_attributes(&mut event.attributes);
+        }
+        for link in span.links.links.iter_mut() {
+            self.redactor.redact_attributes(&mut link.attributes);
+        }
+        self.processor.on_end(span);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.processor.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.processor.shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        self.processor.set_resource(resource);
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::redaction::{RedactionAction, MASK};
+    use crate::trace::{InMemorySpanExporter, SdkTracerProvider, SimpleSpanProcessor};
+    use opentelemetry::trace::{Span as _, Tracer, TracerProvider};
+    use opentelemetry::KeyValue;
+
+    #[test]
+    fn span_and_event_attributes_are_redacted() {
+        let exporter = InMemorySpanExporter::default();
+        let redactor = Redactor::new()
+            .with_attribute("enduser.id", RedactionAction::Mask)
+            .with_attribute("session.*", RedactionAction::Remove);
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(RedactingSpanProcessor::new(
+                SimpleSpanProcessor::new(exporter.clone()),
+                redactor,
+            ))
+            .build();
+
+        let mut span = provider.tracer("test").start("span");
+        span.set_attribute(KeyValue::new("enduser.id", "john"));
+        span.set_attribute(KeyValue::new("session.id", "abc"));
+        span.set_attribute(KeyValue::new("http.method", "GET"));
+        span.add_event("login", vec![KeyValue::new("enduser.id", "john")]);
+        span.end();
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(
+            spans[0].attributes,
+            vec![
+                KeyValue::new("enduser.id", MASK),
+                KeyValue::new("http.method", "GET"),
+            ]
+        );
+        assert_eq!(
+            spans[0].events[0].attributes,
+            vec![KeyValue::new("enduser.id", MASK)]
+        );
+    }
+}
@@ -3,8 +3,19 @@ use opentelemetry::{
     Context, KeyValue,
 };
 
+mod composable;
 #[cfg(feature = "jaeger_remote_sampler")]
 mod jaeger_remote;
+mod rate_limit;
+mod rule_based;
+
+pub use composable::{
+    ComposableAlwaysOff, ComposableAlwaysOn, ComposableAnnotating, ComposableParentThreshold,
+    ComposableProbability, ComposableRuleBased, ComposableSampler, CompositeSampler,
+    SamplingIntent, SamplingParameters, MAX_THRESHOLD,
+};
+pub use rate_limit::RateLimitingSampler;
+pub use rule_based::{RuleBasedSampler, SamplingRule};
 
 /// The result of sampling logic for a given span.
 #[derive(Clone, Debug, PartialEq)]
@@ -32,6 +43,8 @@ pub enum SamplingDecision {
     RecordAndSample,
 }
 
+#[cfg(feature = "jaeger_remote_sampler")]
+pub(crate) use jaeger_remote::sampler_from_env as jaeger_remote_sampler_from_env;
 #[cfg(feature = "jaeger_remote_sampler")]
 pub use jaeger_remote::{JaegerRemoteSampler, JaegerRemoteSamplerBuilder};
 #[cfg(feature = "jaeger_remote_sampler")]
@@ -104,6 +117,20 @@ pub trait ShouldSample: CloneShouldSample + Send + Sync + std::fmt::Debug {
         attributes: &[KeyValue],
         links: &[Link],
     ) -> SamplingResult;
+
+    /// Returns `true` if the sampler drops every [`Span`], whatever its parent, name or
+    /// attributes.
+    ///
+    /// A tracer whose sampler always drops reports that it is not enabled, see
+    /// [`Tracer::enabled`]. A [`Sampler::ParentBased`] always drops when its root sampler
+    /// does, although it would still sample the children of sampled remote parents. The
+    /// default implementation returns `false`.
+    ///
+    /// [`Span`]: opentelemetry::trace::Span
+    /// [`Tracer::enabled`]: opentelemetry::trace::Tracer::enabled
+    fn always_drops(&self) -> bool {
+        false
+    }
 }
 
 impl<T: ShouldSample + 'static> From<T> for Box<dyn ShouldSample> {
@@ -153,7 +180,16 @@ pub enum Sampler {
     /// zero, but spans may still be sampled if their parent is.
     /// *Note:* If this is used then all Spans in a trace will become sampled assuming that the
     /// first span is sampled as it is based on the `trace_id` not the `span_id`
+    ///
+    /// The decision is not consistent with the ratio samplers of other SDKs and the sampling
+    /// probability is not recorded, use a [`CompositeSampler`] with a [`ComposableProbability`]
+    /// for spec-compliant consistent probability sampling.
     TraceIdRatioBased(f64),
+    /// Sample at most a given number of traces per second, see [`RateLimitingSampler`].
+    ///
+    /// Like the other samplers, it ignores the parent span, use it as the root sampler of
+    /// [`Sampler::ParentBased`] to limit the rate of traces rather than spans.
+    RateLimited(RateLimitingSampler),
     /// Jaeger remote sampler supports any remote service that implemented the jaeger remote sampler protocol.
     /// The proto definition can be found [here](https://github.com/jaegertracing/jaeger-idl/blob/main/proto/api_v2/sampling.proto)
     ///
@@ -167,6 +203,12 @@ pub enum Sampler {
 }
 
 impl Sampler {
+    /// Create a [`Sampler::RateLimited`] sampler sampling at most `traces_per_second` traces
+    /// per second. A rate of zero or below samples no trace.
+    pub fn rate_limited(traces_per_second: f64) -> Self {
+        Sampler::RateLimited(RateLimitingSampler::new(traces_per_second))
+    }
+
     /// Create a jaeger remote sampler builder.
     ///
     /// ### Arguments
@@ -186,7 +228,7 @@ impl Sampler {
     where
         C: HttpClient + 'static,
         Sampler: ShouldSample,
-        R: crate::runtime::RuntimeChannel,
+        R: crate::runtime::Runtime,
         Svc: Into<String>,
     {
         JaegerRemoteSamplerBuilder::new(runtime, http_client, default_sampler, service_name)
@@ -236,6 +278,8 @@ impl ShouldSample for Sampler {
                 ),
             // Probabilistically sample the trace.
             Sampler::TraceIdRatioBased(prob) => sample_based_on_probability(prob, trace_id),
+            // Sample while the rate limit allows it.
+            Sampler::RateLimited(rate_limiting_sampler) => rate_limiting_sampler.decision(),
             #[cfg(feature = "jaeger_remote_sampler")]
             Sampler::JaegerRemote(remote_sampler) => {
                 remote_sampler
@@ -254,6 +298,16 @@ impl ShouldSample for Sampler {
             },
         }
     }
+
+    fn always_drops(&self) -> bool {
+        match self {
+            Sampler::AlwaysOff => true,
+            Sampler::TraceIdRatioBased(ratio) => *ratio <= 0.0,
+            // Only the spans of remote sampled parents would be sampled.
+            Sampler::ParentBased(root) => root.always_drops(),
+            _ => false,
+        }
+    }
 }
 
 pub(crate) fn sample_based_on_probability(prob: &f64, trace_id: TraceId) -> SamplingDecision {
@@ -0,0 +1,637 @@
+//! Composable samplers implementing [consistent probability sampling].
+//!
+//! With consistent probability sampling, every SDK makes the same decision
+//! for a given trace and sampling probability: the decision compares the
+//! randomness of the trace, which is the explicit `rv` value of the `ot`
+//! trace state entry or the 56 least significant bits of the trace id, with a
+//! rejection threshold. The threshold of sampled spans is recorded as `th` in
+//! the `ot` trace state entry, so that collectors and backends can compute
+//! the adjusted count of each span.
+//!
+//! [`ComposableSampler`]s only express their intent, which is combined by
+//! the other composable samplers and finally turned into a decision by the
+//! [`CompositeSampler`], the [`ShouldSample`] implementation to configure on
+//! the tracer provider.
+//!
+//! [consistent probability sampling]: https://opentelemetry.io/docs/specs/otel/trace/tracestate-probability-sampling/
+use crate::trace::{SamplingDecision, SamplingResult, ShouldSample};
+use opentelemetry::trace::{Link, SpanContext, SpanKind, TraceContextExt, TraceId, TraceState};
+use opentelemetry::{Context, KeyValue};
+use std::fmt;
+use std::sync::Arc;
+
+/// The exclusive upper bound of rejection thresholds, spans are sampled when
+/// their 56 bits of randomness are greater or equal to the threshold.
+pub const MAX_THRESHOLD: u64 = 1 << 56;
+
+const OT_TRACE_STATE_KEY: &str = "ot";
+const THRESHOLD_KEY: &str = "th";
+const RANDOMNESS_KEY: &str = "rv";
+/// Number of hex digits needed to encode 56 bits.
+const HEX_DIGITS: usize = 14;
+
+/// The information available to [`ComposableSampler`]s, which is the same as
+/// the arguments of [`ShouldSample::should_sample`].
+#[derive(Clone, Copy, Debug)]
+pub struct SamplingParameters<'a> {
+    /// The context of the parent span, if any.
+    pub parent_context: Option<&'a Context>,
+    /// The trace id of the span to be created.
+    pub trace_id: TraceId,
+    /// The name of the span to be created.
+    pub name: &'a str,
+    /// The kind of the span to be created.
+    pub span_kind: &'a SpanKind,
+    /// The initial attributes of the span to be created.
+    pub attributes: &'a [KeyValue],
+    /// The links of the span to be created.
+    pub links: &'a [Link],
+}
+
+impl SamplingParameters<'_> {
+    fn parent_span_context(&self) -> Option<SpanContext> {
+        self.parent_context
+            .filter(|cx| cx.has_active_span())
+            .map(|cx| cx.span().span_context().clone())
+    }
+}
+
+/// The sampling intent of a [`ComposableSampler`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SamplingIntent {
+    /// The rejection threshold, between `0` (sample all spans) and
+    /// [`MAX_THRESHOLD`] (exclusive). `None` drops the span.
+    pub threshold: Option<u64>,
+    /// Whether the threshold can be used to compute adjusted counts. It is
+    /// only recorded in the trace state when it is reliable.
+    pub threshold_reliable: bool,
+    /// Extra attributes to be added to the span if it is sampled.
+    pub attributes: Vec<KeyValue>,
+}
+
+impl SamplingIntent {
+    fn with_threshold(threshold: Option<u64>, threshold_reliable: bool) -> Self {
+        SamplingIntent {
+            threshold,
+            threshold_reliable,
+            attributes: Vec::new(),
+        }
+    }
+}
+
+/// A sampler which expresses its [`SamplingIntent`] instead of a decision,
+/// so that it can be composed with other samplers.
+///
+/// Use a [`CompositeSampler`] to turn it into a [`ShouldSample`].
+pub trait ComposableSampler: Send + Sync + fmt::Debug {
+    /// Returns the [`SamplingIntent`] for a span to be created.
+    fn sampling_intent(&self, params: &SamplingParameters<'_>) -> SamplingIntent;
+}
+
+impl<T: ComposableSampler + 'static> From<T> for Box<dyn ComposableSampler> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Box::new(value)
+    }
+}
+
+/// Samples all spans, with a reliable threshold of `0`.
+#[derive(Clone, Debug)]
+pub struct ComposableAlwaysOn;
+
+impl ComposableSampler for ComposableAlwaysOn {
+    fn sampling_intent(&self, _params: &SamplingParameters<'_>) -> SamplingIntent {
+        SamplingIntent::with_threshold(Some(0), true)
+    }
+}
+
+/// Drops all spans.
+#[derive(Clone, Debug)]
+pub struct ComposableAlwaysOff;
+
+impl ComposableSampler for ComposableAlwaysOff {
+    fn sampling_intent(&self, _params: &SamplingParameters<'_>) -> SamplingIntent {
+        SamplingIntent::with_threshold(None, false)
+    }
+}
+
+/// Samples a given fraction of traces, consistently with the other SDKs.
+#[derive(Clone, Debug)]
+pub struct ComposableProbability {
+    threshold: Option<u64>,
+}
+
+impl ComposableProbability {
+    /// Create a sampler keeping `ratio` of the traces. Ratios `>= 1` sample
+    /// all traces, ratios `<= 0` drop all of them.
+    pub fn new(ratio: f64) -> Self {
+        ComposableProbability {
+            threshold: probability_to_threshold(ratio),
+        }
+    }
+}
+
+impl ComposableSampler for ComposableProbability {
+    fn sampling_intent(&self, _params: &SamplingParameters<'_>) -> SamplingIntent {
+        SamplingIntent::with_threshold(self.threshold, true)
+    }
+}
+
+/// Follows the decision of the parent span, and delegates to a root sampler
+/// for spans without a parent.
+///
+/// The threshold of the parent is propagated when it is known, so children of
+/// a span sampled with a probability have the same adjusted count.
+#[derive(Debug)]
+pub struct ComposableParentThreshold {
+    root: Box<dyn ComposableSampler>,
+}
+
+impl ComposableParentThreshold {
+    /// Create a sampler delegating root spans to `root`.
+    pub fn new(root: impl Into<Box<dyn ComposableSampler>>) -> Self {
+        ComposableParentThreshold { root: root.into() }
+    }
+}
+
+impl ComposableSampler for ComposableParentThreshold {
+    fn sampling_intent(&self, params: &SamplingParameters<'_>) -> SamplingIntent {
+        match params.parent_span_context() {
+            None => self.root.sampling_intent(params),
+            Some(parent) if parent.is_sampled() => {
+                let threshold = parent
+                    .trace_state()
+                    .get(OT_TRACE_STATE_KEY)
+                    .and_then(|value| OtelTraceState::parse(value).threshold);
+                SamplingIntent::with_threshold(Some(threshold.unwrap_or(0)), threshold.is_some())
+            }
+            Some(_) => SamplingIntent::with_threshold(None, false),
+        }
+    }
+}
+
+type SamplingPredicate = Box<dyn Fn(&SamplingParameters<'_>) -> bool + Send + Sync>;
+
+/// Delegates to the sampler of the first rule whose predicate matches the
+/// span, and drops spans matching no rule.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::trace::SpanKind;
+/// use opentelemetry_sdk::trace::{
+///     ComposableAlwaysOff, ComposableParentThreshold, ComposableProbability,
+///     ComposableRuleBased, CompositeSampler, SdkTracerProvider,
+/// };
+///
+/// let sampler = CompositeSampler::new(ComposableParentThreshold::new(
+///     ComposableRuleBased::new()
+///         .with_rule(|params| params.name == "GET /healthz", ComposableAlwaysOff)
+///         .with_rule(
+///             |params| *params.span_kind == SpanKind::Server,
+///             ComposableProbability::new(0.1),
+///         ),
+/// ));
+/// let provider = SdkTracerProvider::builder().with_sampler(sampler).build();
+/// ```
+#[derive(Default)]
+pub struct ComposableRuleBased {
+    rules: Vec<(SamplingPredicate, Box<dyn ComposableSampler>)>,
+}
+
+impl ComposableRuleBased {
+    /// Create a sampler without rules, which drops all spans.
+    pub fn new() -> Self {
+        ComposableRuleBased::default()
+    }
+
+    /// Add a rule delegating the spans matching `predicate` to `sampler`.
+    ///
+    /// Rules are evaluated in the order they are added.
+    pub fn with_rule<P>(
+        mut self,
+        predicate: P,
+        sampler: impl Into<Box<dyn ComposableSampler>>,
+    ) -> Self
+    where
+        P: Fn(&SamplingParameters<'_>) -> bool + Send + Sync + 'static,
+    {
+        self.rules.push((Box::new(predicate), sampler.into()));
+        self
+    }
+}
+
+impl fmt::Debug for ComposableRuleBased {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComposableRuleBased")
+            .field(
+                "samplers",
+                &self
+                    .rules
+                    .iter()
+                    .map(|(_, sampler)| sampler)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl ComposableSampler for ComposableRuleBased {
+    fn sampling_intent(&self, params: &SamplingParameters<'_>) -> SamplingIntent {
+        self.rules
+            .iter()
+            .find(|(predicate, _)| predicate(params))
+            .map(|(_, sampler)| sampler.sampling_intent(params))
+            .unwrap_or_else(|| SamplingIntent::with_threshold(None, false))
+    }
+}
+
+/// Adds attributes to the spans sampled by another sampler.
+#[derive(Debug)]
+pub struct ComposableAnnotating {
+    delegate: Box<dyn ComposableSampler>,
+    attributes: Vec<KeyValue>,
+}
+
+impl ComposableAnnotating {
+    /// Create a sampler following `delegate` and adding `attributes` to the
+    /// sampled spans.
+    pub fn new(
+        delegate: impl Into<Box<dyn ComposableSampler>>,
+        attributes: impl IntoIterator<Item = KeyValue>,
+    ) -> Self {
+        ComposableAnnotating {
+            delegate: delegate.into(),
+            attributes: attributes.into_iter().collect(),
+        }
+    }
+}
+
+impl ComposableSampler for ComposableAnnotating {
+    fn sampling_intent(&self, params: &SamplingParameters<'_>) -> SamplingIntent {
+        let mut intent = self.delegate.sampling_intent(params);
+        intent.attributes.extend(self.attributes.iter().cloned());
+        intent
+    }
+}
+
+/// A [`ShouldSample`] making decisions from the intent of a
+/// [`ComposableSampler`].
+///
+/// A span is sampled when the randomness of its trace is greater or equal to
+/// the threshold of the intent. The `th` value of the `ot` trace state entry
+/// is set to the threshold when it is reliable, and removed otherwise.
+#[derive(Clone, Debug)]
+pub struct CompositeSampler {
+    delegate: Arc<dyn ComposableSampler>,
+}
+
+impl CompositeSampler {
+    /// Create a sampler following the intent of `delegate`.
+    pub fn new(delegate: impl Into<Box<dyn ComposableSampler>>) -> Self {
+        CompositeSampler {
+            delegate: Arc::from(delegate.into()),
+        }
+    }
+}
+
+impl ShouldSample for CompositeSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        let intent = self.delegate.sampling_intent(&SamplingParameters {
+            parent_context,
+            trace_id,
+            name,
+            span_kind,
+            attributes,
+            links,
+        });
+
+        let trace_state = match parent_context {
+            Some(ctx) => ctx.span().span_context().trace_state().clone(),
+            None => TraceState::default(),
+        };
+        let mut ot = trace_state
+            .get(OT_TRACE_STATE_KEY)
+            .map(OtelTraceState::parse)
+            .unwrap_or_default();
+
+        let randomness = ot
+            .randomness
+            .unwrap_or_else(|| randomness_from_trace_id(trace_id));
+        let sampled = intent
+            .threshold
+            .is_some_and(|threshold| threshold < MAX_THRESHOLD && randomness >= threshold);
+        ot.threshold = intent
+            .threshold
+            .filter(|_| sampled && intent.threshold_reliable);
+
+        if sampled {
+            SamplingResult {
+                decision: SamplingDecision::RecordAndSample,
+                attributes: intent.attributes,
+                trace_state: ot.update(trace_state),
+            }
+        } else {
+            SamplingResult {
+                decision: SamplingDecision::Drop,
+                attributes: Vec::new(),
+                trace_state: ot.update(trace_state),
+            }
+        }
+    }
+}
+
+/// Converts a sampling probability to a rejection threshold.
+fn probability_to_threshold(ratio: f64) -> Option<u64> {
+    if ratio >= 1.0 {
+        return Some(0);
+    }
+    if ratio.is_nan() || ratio <= 0.0 {
+        return None;
+    }
+    let threshold = MAX_THRESHOLD - (ratio * MAX_THRESHOLD as f64).round() as u64;
+    (threshold < MAX_THRESHOLD).then_some(threshold)
+}
+
+fn randomness_from_trace_id(trace_id: TraceId) -> u64 {
+    let bytes = trace_id.to_bytes();
+    let mut low = [0; 8];
+    low.copy_from_slice(&bytes[8..]);
+    u64::from_be_bytes(low) & (MAX_THRESHOLD - 1)
+}
+
+/// The `ot` entry of the trace state, like `th:c;rv:1a2b3c4d5e6f70`.
+#[derive(Debug, Default, PartialEq)]
+struct OtelTraceState {
+    threshold: Option<u64>,
+    randomness: Option<u64>,
+    /// Unknown sub-keys, which are preserved as is.
+    other: Vec<String>,
+}
+
+impl OtelTraceState {
+    fn parse(value: &str) -> Self {
+        let mut ot = OtelTraceState::default();
+        for field in value.split(';').filter(|field| !field.is_empty()) {
+            match field.split_once(':') {
+                Some((THRESHOLD_KEY, th)) => ot.threshold = parse_threshold(th),
+                Some((RANDOMNESS_KEY, rv)) => ot.randomness = parse_randomness(rv),
+                _ => ot.other.push(field.to_owned()),
+            }
+        }
+        ot
+    }
+
+    /// Writes this entry into `trace_state`, removing it when it is empty.
+    fn update(&self, trace_state: TraceState) -> TraceState {
+        let mut fields = Vec::with_capacity(self.other.len() + 2);
+        if let Some(threshold) = self.threshold {
+            fields.push(format!("{THRESHOLD_KEY}:{}", encode_threshold(threshold)));
+        }
+        if let Some(randomness) = self.randomness {
+            fields.push(format!("{RANDOMNESS_KEY}:{randomness:014x}"));
+        }
+        fields.extend(self.other.iter().cloned());
+
+        let updated = if fields.is_empty() {
+            trace_state.delete(OT_TRACE_STATE_KEY)
+        } else {
+            trace_state.insert(OT_TRACE_STATE_KEY, fields.join(";"))
+        };
+        updated.unwrap_or(trace_state)
+    }
+}
+
+/// Parses a threshold of 1 to 14 hex digits, with the trailing zeros omitted.
+fn parse_threshold(th: &str) -> Option<u64> {
+    if th.is_empty() || th.len() > HEX_DIGITS || !th.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let value = u64::from_str_radix(th, 16).ok()?;
+    Some(value << (4 * (HEX_DIGITS - th.len())))
+}
+
+/// Parses an explicit randomness value of exactly 14 hex digits.
+fn parse_randomness(rv: &str) -> Option<u64> {
+    if rv.len() != HEX_DIGITS || !rv.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    u64::from_str_radix(rv, 16).ok()
+}
+
+fn encode_threshold(threshold: u64) -> String {
+    let encoded = format!("{threshold:014x}");
+    match encoded.trim_end_matches('0') {
+        "" => "0".to_owned(),
+        trimmed => trimmed.to_owned(),
+    }
+}
+
+#[cfg(all(test, feature = "testing", feature = "trace"))]
+mod tests {
+    use super::*;
+    use crate::testing::trace::TestSpan;
+    use opentelemetry::trace::{SpanId, TraceFlags};
+
+    const HALF: u64 = MAX_THRESHOLD / 2;
+
+    fn trace_id_with_randomness(randomness: u64) -> TraceId {
+        TraceId::from(0xabcd_u128 << 64 | u128::from(randomness))
+    }
+
+    fn parent(trace_flags: TraceFlags, trace_state: &str) -> Context {
+        Context::current_with_span(TestSpan(SpanContext::new(
+            TraceId::from(1),
+            SpanId::from(1),
+            trace_flags,
+            true,
+            trace_state.parse().unwrap(),
+        )))
+    }
+
+    fn sample(
+        sampler: impl Into<Box<dyn ComposableSampler>>,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+    ) -> SamplingResult {
+        CompositeSampler::new(sampler).should_sample(
+            parent_context,
+            trace_id,
+            "span",
+            &SpanKind::Internal,
+            &[],
+            &[],
+        )
+    }
+
+    #[test]
+    fn encodes_thresholds() {
+        for (th, threshold) in [
+            ("0", 0),
+            ("8", HALF),
+            ("c", 3 * MAX_THRESHOLD / 4),
+            ("fffffffffffff", 0x00ff_ffff_ffff_fff0),
+            ("00000000000001", 1),
+        ] {
+            assert_eq!(parse_threshold(th), Some(threshold), "{th}");
+            assert_eq!(encode_threshold(threshold), th, "{th}");
+        }
+        assert_eq!(parse_threshold("08"), Some(HALF / 16));
+        assert_eq!(parse_threshold(""), None);
+        assert_eq!(parse_threshold("123456789abcdef"), None);
+        assert_eq!(parse_threshold("g"), None);
+        assert_eq!(parse_randomness("1a2b3c4d5e6f70"), Some(0x1a2b3c4d5e6f70));
+        assert_eq!(parse_randomness("1a2b"), None);
+    }
+
+    #[test]
+    fn converts_probabilities() {
+        assert_eq!(probability_to_threshold(1.0), Some(0));
+        assert_eq!(probability_to_threshold(2.0), Some(0));
+        assert_eq!(probability_to_threshold(0.5), Some(HALF));
+        assert_eq!(probability_to_threshold(0.25), Some(3 * MAX_THRESHOLD / 4));
+        assert_eq!(probability_to_threshold(0.0), None);
+        assert_eq!(probability_to_threshold(-1.0), None);
+        assert_eq!(probability_to_threshold(f64::NAN), None);
+        assert_eq!(probability_to_threshold(1e-20), None);
+    }
+
+    #[test]
+    fn parses_and_updates_ot_trace_state() {
+        let ot = OtelTraceState::parse("rv:1a2b3c4d5e6f70;th:8;xx:yy;th2");
+        assert_eq!(
+            ot,
+            OtelTraceState {
+                threshold: Some(HALF),
+                randomness: Some(0x1a2b3c4d5e6f70),
+                other: vec!["xx:yy".to_owned(), "th2".to_owned()],
+            }
+        );
+
+        let trace_state = TraceState::from_key_value([("vendor", "value")]).unwrap();
+        assert_eq!(
+            ot.update(trace_state.clone()).header(),
+            "ot=th:8;rv:1a2b3c4d5e6f70;xx:yy;th2,vendor=value"
+        );
+        let trace_state = trace_state.insert("ot", "th:8").unwrap();
+        assert_eq!(
+            OtelTraceState::default().update(trace_state).header(),
+            "vendor=value"
+        );
+    }
+
+    #[test]
+    fn probability_sampling_is_consistent() {
+        let result = sample(
+            ComposableProbability::new(0.5),
+            None,
+            trace_id_with_randomness(HALF),
+        );
+        assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+        assert_eq!(result.trace_state.header(), "ot=th:8");
+
+        let result = sample(
+            ComposableProbability::new(0.5),
+            None,
+            trace_id_with_randomness(HALF - 1),
+        );
+        assert_eq!(result.decision, SamplingDecision::Drop);
+        assert_eq!(result.trace_state.header(), "");
+
+        // The explicit randomness takes precedence over the trace id.
+        let cx = parent(TraceFlags::default(), "ot=rv:80000000000000;xx:yy");
+        let result = sample(
+            ComposableProbability::new(0.5),
+            Some(&cx),
+            trace_id_with_randomness(0),
+        );
+        assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+        assert_eq!(
+            result.trace_state.header(),
+            "ot=th:8;rv:80000000000000;xx:yy"
+        );
+
+        let result = sample(ComposableAlwaysOn, None, trace_id_with_randomness(0));
+        assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+        assert_eq!(result.trace_state.header(), "ot=th:0");
+
+        let result = sample(ComposableAlwaysOff, None, trace_id_with_randomness(HALF));
+        assert_eq!(result.decision, SamplingDecision::Drop);
+    }
+
+    #[test]
+    fn parent_threshold_propagates_parent_decision() {
+        let sampler = || ComposableParentThreshold::new(ComposableProbability::new(0.5));
+
+        let result = sample(sampler(), None, trace_id_with_randomness(HALF));
+        assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+        assert_eq!(result.trace_state.header(), "ot=th:8");
+
+        let cx = parent(TraceFlags::SAMPLED, "ot=th:c");
+        let result = sample(
+            sampler(),
+            Some(&cx),
+            trace_id_with_randomness(MAX_THRESHOLD - 1),
+        );
+        assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+        assert_eq!(result.trace_state.header(), "ot=th:c");
+
+        // The threshold of a parent sampled without one is unknown.
+        let cx = parent(TraceFlags::SAMPLED, "vendor=value");
+        let result = sample(sampler(), Some(&cx), trace_id_with_randomness(0));
+        assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+        assert_eq!(result.trace_state.header(), "vendor=value");
+
+        let cx = parent(TraceFlags::default(), "ot=th:0");
+        let result = sample(sampler(), Some(&cx), trace_id_with_randomness(HALF));
+        assert_eq!(result.decision, SamplingDecision::Drop);
+        assert_eq!(result.trace_state.header(), "");
+    }
+
+    #[test]
+    fn rule_based_and_annotating_samplers() {
+        let sampler = || {
+            ComposableRuleBased::new()
+                .with_rule(|params| params.name == "health", ComposableAlwaysOff)
+                .with_rule(
+                    |params| *params.span_kind == SpanKind::Server,
+                    ComposableAnnotating::new(
+                        ComposableAlwaysOn,
+                        [KeyValue::new("sampler.rule", "server")],
+                    ),
+                )
+        };
+        let should_sample = |name: &str, span_kind: SpanKind| {
+            CompositeSampler::new(sampler()).should_sample(
+                None,
+                trace_id_with_randomness(HALF),
+                name,
+                &span_kind,
+                &[],
+                &[],
+            )
+        };
+
+        let result = should_sample("health", SpanKind::Server);
+        assert_eq!(result.decision, SamplingDecision::Drop);
+        assert!(result.attributes.is_empty());
+
+        let result = should_sample("GET /orders", SpanKind::Server);
+        assert_eq!(result.decision, SamplingDecision::RecordAndSample);
+        assert_eq!(
+            result.attributes,
+            vec![KeyValue::new("sampler.rule", "server")]
+        );
+
+        let result = should_sample("GET /orders", SpanKind::Client);
+        assert_eq!(result.decision, SamplingDecision::Drop);
+    }
+}
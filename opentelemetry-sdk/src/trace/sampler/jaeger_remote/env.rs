@@ -0,0 +1,193 @@
+//! Jaeger remote sampler configured with `OTEL_TRACES_SAMPLER_ARG`.
+use super::sampler::{
+    JaegerRemoteSampler, StrategySource, DEFAULT_LEAKY_BUCKET_SIZE, DEFAULT_REMOTE_SAMPLER_ENDPOINT,
+};
+use crate::runtime::NoAsync;
+use crate::trace::Sampler;
+use opentelemetry_http::HttpClient;
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_millis(60_000);
+const DEFAULT_INITIAL_SAMPLING_RATE: f64 = 0.001;
+
+/// The arguments of the Jaeger remote sampler as defined by the [specification].
+///
+/// [specification]: https://opentelemetry.io/docs/specs/otel/configuration/sdk-environment-variables/#general-sdk-configuration
+#[derive(Debug, PartialEq)]
+struct JaegerRemoteArgs {
+    endpoint: Option<String>,
+    polling_interval: Duration,
+    initial_sampling_rate: f64,
+}
+
+impl Default for JaegerRemoteArgs {
+    fn default() -> Self {
+        JaegerRemoteArgs {
+            endpoint: None,
+            polling_interval: DEFAULT_POLLING_INTERVAL,
+            initial_sampling_rate: DEFAULT_INITIAL_SAMPLING_RATE,
+        }
+    }
+}
+
+/// Parses `endpoint=...,pollingIntervalMs=...,initialSamplingRate=...`, all
+/// the keys being optional.
+fn parse_args(arg: Option<&str>) -> Result<JaegerRemoteArgs, String> {
+    let mut args = JaegerRemoteArgs::default();
+    for pair in arg.unwrap_or_default().split(',') {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("missing value for `{pair}`"))?;
+        let value = value.trim();
+        match key.trim() {
+            "endpoint" => args.endpoint = Some(value.to_string()),
+            "pollingIntervalMs" => {
+                args.polling_interval = value
+                    .parse()
+                    .map(Duration::from_millis)
+                    .map_err(|_| format!("invalid pollingIntervalMs `{value}`"))?
+            }
+            "initialSamplingRate" => {
+                args.initial_sampling_rate = value
+                    .parse()
+                    .ok()
+                    .filter(|rate| (0.0..=1.0).contains(rate))
+                    .ok_or_else(|| format!("invalid initialSamplingRate `{value}`"))?
+            }
+            key => return Err(format!("unknown key `{key}`")),
+        }
+    }
+    Ok(args)
+}
+
+/// Builds a [`Sampler::JaegerRemote`] from `OTEL_TRACES_SAMPLER_ARG`.
+///
+/// The strategy is polled with `client` from a dedicated thread, or read from
+/// `file://` endpoints, which need no client. Until a strategy is fetched,
+/// traces are sampled with the initial sampling rate.
+pub(crate) fn sampler_from_env(
+    arg: Option<&str>,
+    service_name: &str,
+    client: Option<Arc<dyn HttpClient>>,
+) -> Result<Sampler, String> {
+    let args = parse_args(arg)?;
+    let endpoint = args
+        .endpoint
+        .as_deref()
+        .unwrap_or(DEFAULT_REMOTE_SAMPLER_ENDPOINT);
+    let source =
+        StrategySource::new(endpoint, service_name, client).map_err(|err| format!("{err:?}"))?;
+    Ok(Sampler::JaegerRemote(JaegerRemoteSampler::new(
+        NoAsync,
+        args.polling_interval,
+        source,
+        Sampler::TraceIdRatioBased(args.initial_sampling_rate),
+        DEFAULT_LEAKY_BUCKET_SIZE,
+    )))
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::trace::{SamplingDecision, ShouldSample};
+    use opentelemetry::trace::{SpanKind, TraceId};
+    use opentelemetry_http::{Bytes, HttpError, Request, Response};
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    const STRATEGY: &str =
+        r#"{"strategyType":"PROBABILISTIC","probabilisticSampling":{"samplingRate":0.0}}"#;
+
+    /// Answers every request with `STRATEGY`, recording the requested URIs.
+    #[derive(Debug, Default)]
+    struct StrategyClient {
+        uris: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl HttpClient for StrategyClient {
+        async fn send_bytes(&self, request: Request<Bytes>) -> Result<Response<Bytes>, HttpError> {
+            self.uris.lock().unwrap().push(request.uri().to_string());
+            Ok(Response::new(Bytes::from_static(STRATEGY.as_bytes())))
+        }
+    }
+
+    fn decision(sampler: &Sampler) -> SamplingDecision {
+        sampler
+            .should_sample(None, TraceId::from(1), "span", &SpanKind::Server, &[], &[])
+            .decision
+    }
+
+    #[test]
+    fn parse_sampler_args() {
+        assert_eq!(parse_args(None).unwrap(), JaegerRemoteArgs::default());
+        assert_eq!(
+            parse_args(Some(
+                "endpoint=http://jaeger:5778/sampling, pollingIntervalMs=5000,initialSamplingRate=0.25"
+            ))
+            .unwrap(),
+            JaegerRemoteArgs {
+                endpoint: Some("http://jaeger:5778/sampling".to_string()),
+                polling_interval: Duration::from_secs(5),
+                initial_sampling_rate: 0.25,
+            }
+        );
+        assert!(parse_args(Some("initialSamplingRate=2")).is_err());
+        assert!(parse_args(Some("pollingIntervalMs=-1")).is_err());
+        assert!(parse_args(Some("endpoint")).is_err());
+        assert!(parse_args(Some("interval=5")).is_err());
+    }
+
+    #[test]
+    fn strategy_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("strategy.json");
+        std::fs::write(&path, STRATEGY).unwrap();
+        let endpoint = format!("file://{}", path.display());
+
+        let sampler = sampler_from_env(
+            Some(&format!("endpoint={endpoint},initialSamplingRate=1.0")),
+            "",
+            None,
+        )
+        .unwrap();
+        // The file is read when the sampler is built.
+        assert_eq!(decision(&sampler), SamplingDecision::Drop);
+    }
+
+    #[test]
+    fn strategy_from_http_endpoint() {
+        let client = Arc::new(StrategyClient::default());
+        let sampler = sampler_from_env(
+            Some(
+                "endpoint=http://jaeger:5778/sampling,pollingIntervalMs=10,initialSamplingRate=1.0",
+            ),
+            "my-service",
+            Some(client.clone()),
+        )
+        .unwrap();
+
+        let start = Instant::now();
+        while decision(&sampler) != SamplingDecision::Drop {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "strategy not applied"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(
+            client.uris.lock().unwrap()[0],
+            "http://jaeger:5778/sampling?service=my-service"
+        );
+    }
+
+    #[test]
+    fn http_endpoint_requires_a_client() {
+        assert!(sampler_from_env(None, "my-service", None).is_err());
+    }
+}
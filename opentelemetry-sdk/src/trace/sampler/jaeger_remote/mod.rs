@@ -1,9 +1,10 @@
-mod rate_limit;
+mod env;
 #[allow(dead_code)]
 mod remote;
 mod sampler;
 mod sampling_strategy;
 
+pub(crate) use env::sampler_from_env;
 pub use sampler::{JaegerRemoteSampler, JaegerRemoteSamplerBuilder};
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use crate::runtime::{to_interval_stream, RuntimeChannel};
+use crate::runtime::{to_interval_stream, Runtime};
 use crate::trace::sampler::jaeger_remote::remote::SamplingStrategyResponse;
 use crate::trace::sampler::jaeger_remote::sampling_strategy::Inner;
 use crate::trace::{Sampler, SamplingResult, ShouldSample};
@@ -7,12 +7,14 @@ use http::Uri;
 use opentelemetry::trace::{Link, SpanKind, TraceId};
 use opentelemetry::{otel_warn, Context, KeyValue};
 use opentelemetry_http::HttpClient;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
 
-const DEFAULT_REMOTE_SAMPLER_ENDPOINT: &str = "http://localhost:5778/sampling";
+pub(super) const DEFAULT_REMOTE_SAMPLER_ENDPOINT: &str = "http://localhost:5778/sampling";
+pub(super) const DEFAULT_LEAKY_BUCKET_SIZE: f64 = 100.0;
 
 #[derive(Error, Debug)]
 #[non_exhaustive]
@@ -26,7 +28,7 @@ pub enum JaegerRemoteSamplerBuildError {
 #[derive(Debug)]
 pub struct JaegerRemoteSamplerBuilder<C, S, R>
 where
-    R: RuntimeChannel,
+    R: Runtime,
     C: HttpClient + 'static,
     S: ShouldSample + 'static,
 {
@@ -43,7 +45,7 @@ impl<C, S, R> JaegerRemoteSamplerBuilder<C, S, R>
 where
     C: HttpClient + 'static,
     S: ShouldSample + 'static,
-    R: RuntimeChannel,
+    R: Runtime,
 {
     pub(crate) fn new<Svc>(
         runtime: R,
@@ -60,7 +62,7 @@ where
             client: http_client,
             endpoint: DEFAULT_REMOTE_SAMPLER_ENDPOINT.to_string(),
             default_sampler,
-            leaky_bucket_size: 100.0,
+            leaky_bucket_size: DEFAULT_LEAKY_BUCKET_SIZE,
             service_name: service_name.into(),
         }
     }
@@ -82,6 +84,11 @@ where
     /// By default it's `http://localhost:5778/sampling`.
     ///
     /// If service name is provided as part of the endpoint, it will be ignored.
+    ///
+    /// A `file://` endpoint, like `file:///etc/otel/sampling.json`, loads the
+    /// sampling strategy from a local JSON file instead, in the format returned
+    /// by remote servers. The file is read when the sampler is built and again
+    /// at every update interval, and the HTTP client is not used.
     pub fn with_endpoint<Str: Into<String>>(self, endpoint: Str) -> Self {
         Self {
             endpoint: endpoint.into(),
@@ -106,22 +113,66 @@ where
     /// Return errors if:
     ///
     /// - the endpoint provided is empty.
-    /// - the service name provided is empty.
+    /// - the service name provided is empty, unless the endpoint is a file.
+    /// - the endpoint is a `file://` URL that isn't a valid local path, e.g.
+    ///   with a host outside of Windows.
     pub fn build(self) -> Result<Sampler, JaegerRemoteSamplerBuildError> {
-        let endpoint = Self::get_endpoint(&self.endpoint, &self.service_name)
-            .map_err(JaegerRemoteSamplerBuildError::InvalidEndpoint)?;
+        let source = StrategySource::new(
+            &self.endpoint,
+            &self.service_name,
+            Some(Arc::new(self.client)),
+        )?;
 
         Ok(Sampler::JaegerRemote(JaegerRemoteSampler::new(
             self.runtime,
             self.update_interval,
-            self.client,
-            endpoint,
+            source,
             self.default_sampler,
             self.leaky_bucket_size,
         )))
     }
+}
+
+// Where the sampling strategy is fetched from.
+#[derive(Debug)]
+pub(super) enum StrategySource {
+    Remote {
+        endpoint: Uri,
+        client: Arc<dyn HttpClient>,
+    },
+    File(PathBuf),
+}
+
+impl StrategySource {
+    /// Parses `endpoint`, a `file://` URL or the URL of a remote server
+    /// queried with `client` for the strategy of `service_name`.
+    pub(super) fn new(
+        endpoint: &str,
+        service_name: &str,
+        client: Option<Arc<dyn HttpClient>>,
+    ) -> Result<Self, JaegerRemoteSamplerBuildError> {
+        match url::Url::parse(endpoint) {
+            Ok(url) if url.scheme() == "file" => {
+                url.to_file_path().map(StrategySource::File).map_err(|()| {
+                    JaegerRemoteSamplerBuildError::InvalidEndpoint(format!(
+                        "{endpoint} is not a valid file path"
+                    ))
+                })
+            }
+            _ => {
+                let endpoint = Self::remote_endpoint(endpoint, service_name)
+                    .map_err(JaegerRemoteSamplerBuildError::InvalidEndpoint)?;
+                let client = client.ok_or_else(|| {
+                    JaegerRemoteSamplerBuildError::InvalidEndpoint(format!(
+                        "no HTTP client to query {endpoint}"
+                    ))
+                })?;
+                Ok(StrategySource::Remote { endpoint, client })
+            }
+        }
+    }
 
-    fn get_endpoint(endpoint: &str, service_name: &str) -> Result<Uri, String> {
+    fn remote_endpoint(endpoint: &str, service_name: &str) -> Result<Uri, String> {
         if endpoint.is_empty() || service_name.is_empty() {
             return Err("endpoint and service name cannot be empty".to_string());
         }
@@ -154,21 +205,32 @@ pub struct JaegerRemoteSampler {
 }
 
 impl JaegerRemoteSampler {
-    fn new<C, R, S>(
+    pub(super) fn new<R, S>(
         runtime: R,
         update_timeout: Duration,
-        client: C,
-        endpoint: Uri,
+        source: StrategySource,
         default_sampler: S,
         leaky_bucket_size: f64,
     ) -> Self
     where
-        R: RuntimeChannel,
-        C: HttpClient + 'static,
+        R: Runtime,
         S: ShouldSample + 'static,
     {
         let (shutdown_tx, shutdown_rx) = futures_channel::mpsc::channel(1);
         let inner = Arc::new(Inner::new(leaky_bucket_size, shutdown_tx));
+        // Local files are available right away, don't wait for the first update.
+        if let StrategySource::File(path) = &source {
+            match Self::read_strategy_file(path) {
+                Ok(strategy) => inner.update(strategy),
+                Err(err_msg) => {
+                    otel_warn!(
+                        name: "JaegerRemoteSampler.FailedToFetchStrategy",
+                        message = "Failed to read the sampling strategy from the local file. The default sampler will be applied until the file is successfully read.",
+                        reason = format!("{}", err_msg),
+                    );
+                }
+            }
+        }
         let sampler = JaegerRemoteSampler {
             inner,
             default_sampler: Arc::new(default_sampler),
@@ -177,24 +239,21 @@ impl JaegerRemoteSampler {
             runtime,
             sampler.inner.clone(),
             update_timeout,
-            client,
             shutdown_rx,
-            endpoint,
+            source,
         );
         sampler
     }
 
     // start a updating thread/task
-    fn run_update_task<C, R>(
+    fn run_update_task<R>(
         runtime: R,
         strategy: Arc<Inner>,
         update_timeout: Duration,
-        client: C,
         shutdown: futures_channel::mpsc::Receiver<()>,
-        endpoint: Uri,
+        source: StrategySource,
     ) where
-        R: RuntimeChannel,
-        C: HttpClient + 'static,
+        R: Runtime,
     {
         // todo: review if we need 'static here
         let interval = to_interval_stream(runtime.clone(), update_timeout);
@@ -210,7 +269,13 @@ impl JaegerRemoteSampler {
                 if should_update {
                     // poll next available configuration or shutdown
                     // send request
-                    match Self::request_new_strategy(&client, endpoint.clone()).await {
+                    let strategy_resp = match &source {
+                        StrategySource::Remote { endpoint, client } => {
+                            Self::request_new_strategy(client.as_ref(), endpoint.clone()).await
+                        }
+                        StrategySource::File(path) => Self::read_strategy_file(path),
+                    };
+                    match strategy_resp {
                         Ok(remote_strategy_resp) => strategy.update(remote_strategy_resp),
                         Err(err_msg) => {
                             otel_warn!(
@@ -228,13 +293,17 @@ impl JaegerRemoteSampler {
         });
     }
 
-    async fn request_new_strategy<C>(
-        client: &C,
+    fn read_strategy_file(path: &Path) -> Result<SamplingStrategyResponse, String> {
+        let content =
+            std::fs::read(path).map_err(|err| format!("cannot read {}, {err}", path.display()))?;
+        serde_json::from_slice(&content)
+            .map_err(|err| format!("cannot deserialize {}, {err}", path.display()))
+    }
+
+    async fn request_new_strategy(
+        client: &dyn HttpClient,
         endpoint: Uri,
-    ) -> Result<SamplingStrategyResponse, String>
-    where
-        C: HttpClient,
-    {
+    ) -> Result<SamplingStrategyResponse, String> {
         let request = http::Request::get(endpoint)
             .header("Content-Type", "application/json")
             .body(Default::default())
@@ -298,6 +367,22 @@ mod tests {
         }
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn file_endpoints_are_local_paths() {
+        use super::StrategySource;
+
+        let source = StrategySource::new("file:///etc/otel/sampling%20strategy.json", "", None);
+        let Ok(StrategySource::File(path)) = source else {
+            panic!("expected a file source, got {source:?}");
+        };
+        assert_eq!(
+            path,
+            std::path::Path::new("/etc/otel/sampling strategy.json")
+        );
+        assert!(StrategySource::new("file://host/sampling.json", "", None).is_err());
+    }
+
     #[test]
     fn deserialize_sampling_strategy_response() {
         let json = r#"{
@@ -0,0 +1,248 @@
+use crate::trace::{SamplingDecision, SamplingResult, ShouldSample};
+use opentelemetry::time::now;
+use opentelemetry::trace::{Link, SpanKind, TraceContextExt, TraceId, TraceState};
+use opentelemetry::{otel_debug, Context, KeyValue};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// A sampler sampling at most a given number of traces per second.
+///
+/// The rate is enforced with a leaky bucket holding up to one second worth of
+/// traces, or one trace if the rate is lower, so short bursts up to that size
+/// are sampled. A rate of zero or below samples no trace at all. Clones share
+/// the same bucket, capping the volume of the whole tracer provider regardless
+/// of the request rate.
+///
+/// The sampler decides for every span it is asked about, so it is usually the
+/// root sampler of a [`Sampler::ParentBased`], making child spans follow the
+/// decision of their root span.
+///
+/// # Example
+///
+/// ```
+/// use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+///
+/// let provider = SdkTracerProvider::builder()
+///     .with_sampler(Sampler::ParentBased(Box::new(Sampler::rate_limited(10.0))))
+///     .build();
+/// ```
+///
+/// [`Sampler::ParentBased`]: crate::trace::Sampler::ParentBased
+#[derive(Clone, Debug)]
+pub struct RateLimitingSampler {
+    traces_per_second: f64,
+    bucket: Arc<Mutex<LeakyBucket>>,
+}
+
+impl RateLimitingSampler {
+    /// Create a sampler sampling at most `traces_per_second` traces per
+    /// second. A rate of zero or below drops every trace, like
+    /// [`Sampler::AlwaysOff`].
+    ///
+    /// [`Sampler::AlwaysOff`]: crate::trace::Sampler::AlwaysOff
+    pub fn new(traces_per_second: f64) -> Self {
+        let traces_per_second = traces_per_second.max(0.0);
+        RateLimitingSampler {
+            traces_per_second,
+            bucket: Arc::new(Mutex::new(LeakyBucket::new(
+                traces_per_second.max(1.0),
+                traces_per_second,
+            ))),
+        }
+    }
+
+    /// The maximum number of traces sampled per second.
+    pub fn traces_per_second(&self) -> f64 {
+        self.traces_per_second
+    }
+
+    pub(crate) fn decision(&self) -> SamplingDecision {
+        if self.traces_per_second > 0.0
+            && self
+                .bucket
+                .lock()
+                .is_ok_and(|mut bucket| bucket.should_sample())
+        {
+            SamplingDecision::RecordAndSample
+        } else {
+            SamplingDecision::Drop
+        }
+    }
+}
+
+impl ShouldSample for RateLimitingSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        _trace_id: TraceId,
+        _name: &str,
+        _span_kind: &SpanKind,
+        _attributes: &[KeyValue],
+        _links: &[Link],
+    ) -> SamplingResult {
+        SamplingResult {
+            decision: self.decision(),
+            attributes: Vec::new(),
+            trace_state: match parent_context {
+                Some(ctx) => ctx.span().span_context().trace_state().clone(),
+                None => TraceState::default(),
+            },
+        }
+    }
+}
+
+// leaky bucket based rate limit
+// should be Send+Sync
+#[derive(Debug)]
+pub(crate) struct LeakyBucket {
+    span_per_sec: f64,
+    available: f64,
+    bucket_size: f64,
+    last_time: SystemTime,
+}
+
+impl LeakyBucket {
+    pub(crate) fn new(bucket_size: f64, span_per_sec: f64) -> LeakyBucket {
+        LeakyBucket {
+            span_per_sec,
+            available: bucket_size,
+            bucket_size,
+            last_time: now(),
+        }
+    }
+
+    #[cfg(feature = "jaeger_remote_sampler")]
+    pub(crate) fn update(&mut self, span_per_sec: f64) {
+        self.span_per_sec = span_per_sec;
+    }
+
+    pub(crate) fn should_sample(&mut self) -> bool {
+        self.check_availability(now)
+    }
+
+    fn check_availability<F>(&mut self, now: F) -> bool
+    where
+        F: Fn() -> SystemTime,
+    {
+        if self.available >= 1.0 {
+            self.available -= 1.0;
+            true
+        } else {
+            let cur_time = now();
+            let elapsed = cur_time.duration_since(self.last_time);
+            match elapsed {
+                Ok(dur) => {
+                    self.last_time = cur_time;
+                    self.available = f64::min(
+                        dur.as_secs_f64() * self.span_per_sec + self.available,
+                        self.bucket_size,
+                    );
+
+                    if self.available >= 1.0 {
+                        self.available -= 1.0;
+                        true
+                    } else {
+                        false
+                    }
+                }
+                Err(err) => {
+                    otel_debug!(
+                        name: "Sampler.LeakyBucket.ClockAdjustment",
+                        message = "Rate limiting sampler detected a rewind in system clock",
+                        reason = format!("{:?}", err),
+                    );
+                    true
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::Sampler;
+    use opentelemetry::time::now;
+    use std::ops::{Add, Sub};
+    use std::time::Duration;
+
+    #[test]
+    fn test_leaky_bucket() {
+        // maximum bucket size 2, add 1 allowance every 10 seconds
+        let mut leaky_bucket = LeakyBucket::new(2.0, 0.1);
+        let current_time = now();
+        leaky_bucket.last_time = current_time;
+
+        let test_cases = vec![
+            (0, vec![true, true, false]),
+            (1, vec![false]),
+            (5, vec![false]),
+            (10, vec![true, false]),
+            (60, vec![true, true, false]), // maximum allowance is 2
+        ];
+
+        for (elapsed_sec, cases) in test_cases.into_iter() {
+            for should_pass in cases {
+                assert_eq!(
+                    should_pass,
+                    leaky_bucket.check_availability(|| {
+                        current_time.add(Duration::from_secs(elapsed_sec))
+                    })
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn test_leaky_bucket_sub_second_refill() {
+        let mut leaky_bucket = LeakyBucket::new(1.0, 10.0);
+        let current_time = now();
+        leaky_bucket.last_time = current_time;
+
+        assert!(leaky_bucket.check_availability(|| current_time));
+        assert!(!leaky_bucket.check_availability(|| current_time));
+        assert!(
+            leaky_bucket.check_availability(|| { current_time.add(Duration::from_millis(100)) })
+        );
+    }
+
+    #[test]
+    fn rate_limiting_sampler_shares_bucket_between_clones() {
+        let sampler = Sampler::rate_limited(2.0);
+        let cloned = sampler.clone();
+        let decision = |sampler: &Sampler| {
+            sampler
+                .should_sample(None, TraceId::from(1), "span", &SpanKind::Server, &[], &[])
+                .decision
+        };
+
+        assert_eq!(decision(&sampler), SamplingDecision::RecordAndSample);
+        assert_eq!(decision(&cloned), SamplingDecision::RecordAndSample);
+        assert_eq!(decision(&sampler), SamplingDecision::Drop);
+        assert_eq!(decision(&cloned), SamplingDecision::Drop);
+    }
+
+    #[test]
+    fn rate_limiting_sampler_negative_rate() {
+        let sampler = RateLimitingSampler::new(-1.0);
+        assert_eq!(sampler.traces_per_second(), 0.0);
+        assert_eq!(sampler.decision(), SamplingDecision::Drop);
+    }
+
+    #[test]
+    fn rate_limiting_sampler_zero_rate_is_always_off() {
+        let sampler = RateLimitingSampler::new(0.0);
+        for _ in 0..3 {
+            assert_eq!(sampler.decision(), SamplingDecision::Drop);
+        }
+    }
+
+    #[test]
+    fn test_rewind_clock_should_pass() {
+        let mut leaky_bucket = LeakyBucket::new(2.0, 0.1);
+        let current_time = now();
+        leaky_bucket.last_time = current_time;
+
+        assert!(leaky_bucket.check_availability(|| { current_time.sub(Duration::from_secs(10)) }))
+    }
+}
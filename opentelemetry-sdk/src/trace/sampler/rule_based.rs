@@ -0,0 +1,325 @@
+use crate::trace::{SamplingResult, ShouldSample};
+use crate::util::wildcard_match;
+use opentelemetry::trace::{Link, SpanKind, TraceId};
+use opentelemetry::{Context, Key, KeyValue, Value};
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+/// A sampler delegating to the sampler of the first [`SamplingRule`] matching
+/// the span to be created, or to a fallback sampler if no rule matches.
+///
+/// It can be used on its own or as the root sampler of a
+/// [`Sampler::ParentBased`].
+///
+/// # Example
+///
+/// ```
+/// use opentelemetry::trace::SpanKind;
+/// use opentelemetry_sdk::trace::{RuleBasedSampler, Sampler, SamplingRule, SdkTracerProvider};
+///
+/// let sampler = RuleBasedSampler::new(Sampler::TraceIdRatioBased(0.1))
+///     // Drop health checks.
+///     .with_rule(
+///         SamplingRule::new(Sampler::AlwaysOff)
+///             .with_span_kind(SpanKind::Server)
+///             .with_attribute("http.route", "/healthz"),
+///     )
+///     // Keep all payment traces.
+///     .with_rule(
+///         SamplingRule::new(Sampler::AlwaysOn).with_attribute_prefix("http.route", "/payments/"),
+///     );
+///
+/// let provider = SdkTracerProvider::builder()
+///     .with_sampler(Sampler::ParentBased(Box::new(sampler)))
+///     .build();
+/// ```
+///
+/// [`Sampler::ParentBased`]: crate::trace::Sampler::ParentBased
+#[derive(Clone, Debug)]
+pub struct RuleBasedSampler {
+    rules: Vec<SamplingRule>,
+    fallback: Box<dyn ShouldSample>,
+}
+
+impl RuleBasedSampler {
+    /// Create a sampler without rules, delegating all spans to `fallback`
+    /// until rules are added.
+    pub fn new(fallback: impl Into<Box<dyn ShouldSample>>) -> Self {
+        RuleBasedSampler {
+            rules: Vec::new(),
+            fallback: fallback.into(),
+        }
+    }
+
+    /// Add a rule. Rules are evaluated in the order they are added.
+    pub fn with_rule(mut self, rule: SamplingRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+}
+
+impl ShouldSample for RuleBasedSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(name, span_kind, attributes))
+            .map_or(&self.fallback, |rule| &rule.sampler)
+            .should_sample(parent_context, trace_id, name, span_kind, attributes, links)
+    }
+
+    fn always_drops(&self) -> bool {
+        self.fallback.always_drops() && self.rules.iter().all(|rule| rule.sampler.always_drops())
+    }
+}
+
+/// A rule of a [`RuleBasedSampler`], delegating the spans matching every
+/// criterion that is set to a sampler.
+///
+/// The span name criterion supports the wildcards `*`, matching any number of
+/// characters, and `?`, matching exactly one character. Attribute criteria
+/// only look at the attributes known when the span is created, like the ones
+/// set with [`SpanBuilder::with_attributes`].
+///
+/// [`SpanBuilder::with_attributes`]: opentelemetry::trace::SpanBuilder::with_attributes
+#[derive(Clone, Debug)]
+pub struct SamplingRule {
+    span_name: Option<NameMatcher>,
+    span_kind: Option<SpanKind>,
+    attributes: Vec<AttributeMatcher>,
+    sampler: Box<dyn ShouldSample>,
+}
+
+#[derive(Clone)]
+enum NameMatcher {
+    Pattern(Cow<'static, str>),
+    Custom(Arc<dyn Fn(&str) -> bool + Send + Sync>),
+}
+
+impl fmt::Debug for NameMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameMatcher::Pattern(pattern) => f.debug_tuple("Pattern").field(pattern).finish(),
+            NameMatcher::Custom(_) => f.write_str("Custom"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum AttributeMatcher {
+    Equals(Key, Value),
+    Prefix(Key, Cow<'static, str>),
+}
+
+impl SamplingRule {
+    /// Create a rule matching all spans and delegating them to `sampler`.
+    pub fn new(sampler: impl Into<Box<dyn ShouldSample>>) -> Self {
+        SamplingRule {
+            span_name: None,
+            span_kind: None,
+            attributes: Vec::new(),
+            sampler: sampler.into(),
+        }
+    }
+
+    /// Match spans whose name matches `pattern`, which may contain the
+    /// wildcards `*` and `?`.
+    pub fn with_span_name(mut self, pattern: impl Into<Cow<'static, str>>) -> Self {
+        self.span_name = Some(NameMatcher::Pattern(pattern.into()));
+        self
+    }
+
+    /// Match spans whose name is accepted by `matcher`, for instance to match
+    /// names with a regular expression.
+    pub fn with_span_name_matching<F>(mut self, matcher: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.span_name = Some(NameMatcher::Custom(Arc::new(matcher)));
+        self
+    }
+
+    /// Match spans of the given [`SpanKind`].
+    pub fn with_span_kind(mut self, span_kind: SpanKind) -> Self {
+        self.span_kind = Some(span_kind);
+        self
+    }
+
+    /// Match spans with an attribute `key` equal to `value`.
+    ///
+    /// Calls are additive, spans must match every attribute criterion.
+    pub fn with_attribute(mut self, key: impl Into<Key>, value: impl Into<Value>) -> Self {
+        self.attributes
+            .push(AttributeMatcher::Equals(key.into(), value.into()));
+        self
+    }
+
+    /// Match spans with a string attribute `key` starting with `prefix`.
+    ///
+    /// Calls are additive, spans must match every attribute criterion.
+    pub fn with_attribute_prefix(
+        mut self,
+        key: impl Into<Key>,
+        prefix: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.attributes
+            .push(AttributeMatcher::Prefix(key.into(), prefix.into()));
+        self
+    }
+
+    fn matches(&self, name: &str, span_kind: &SpanKind, attributes: &[KeyValue]) -> bool {
+        self.span_name
+            .as_ref()
+            .map_or(true, |matcher| match matcher {
+                NameMatcher::Pattern(pattern) => wildcard_match(pattern, name, false),
+                NameMatcher::Custom(matcher) => matcher(name),
+            })
+            && self
+                .span_kind
+                .as_ref()
+                .map_or(true, |kind| kind == span_kind)
+            && self
+                .attributes
+                .iter()
+                .all(|matcher| matcher.matches(attributes))
+    }
+}
+
+impl AttributeMatcher {
+    fn matches(&self, attributes: &[KeyValue]) -> bool {
+        let key = match self {
+            AttributeMatcher::Equals(key, _) | AttributeMatcher::Prefix(key, _) => key,
+        };
+        attributes
+            .iter()
+            .filter(|attribute| attribute.key == *key)
+            .any(|attribute| match (self, &attribute.value) {
+                (AttributeMatcher::Equals(_, expected), value) => value == expected,
+                (AttributeMatcher::Prefix(_, prefix), Value::String(value)) => {
+                    value.as_str().starts_with(prefix.as_ref())
+                }
+                (AttributeMatcher::Prefix(..), _) => false,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::{Sampler, SamplingDecision};
+
+    fn decision(
+        sampler: &RuleBasedSampler,
+        name: &str,
+        span_kind: SpanKind,
+        attributes: &[KeyValue],
+    ) -> SamplingDecision {
+        sampler
+            .should_sample(None, TraceId::from(1), name, &span_kind, attributes, &[])
+            .decision
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let sampler = RuleBasedSampler::new(Sampler::AlwaysOn)
+            .with_rule(
+                SamplingRule::new(Sampler::AlwaysOff)
+                    .with_span_kind(SpanKind::Server)
+                    .with_attribute("http.route", "/healthz"),
+            )
+            .with_rule(
+                SamplingRule::new(Sampler::AlwaysOn)
+                    .with_attribute_prefix("http.route", "/payments/"),
+            )
+            .with_rule(SamplingRule::new(Sampler::AlwaysOff).with_span_name("GET /internal/*"));
+
+        let healthz = [KeyValue::new("http.route", "/healthz")];
+        assert_eq!(
+            decision(&sampler, "GET", SpanKind::Server, &healthz),
+            SamplingDecision::Drop
+        );
+        // The span kind doesn't match, so the fallback decides.
+        assert_eq!(
+            decision(&sampler, "GET", SpanKind::Client, &healthz),
+            SamplingDecision::RecordAndSample
+        );
+
+        // The payment rule is evaluated before the internal one.
+        let payments = [KeyValue::new("http.route", "/payments/{id}")];
+        assert_eq!(
+            decision(
+                &sampler,
+                "GET /internal/payments",
+                SpanKind::Server,
+                &payments
+            ),
+            SamplingDecision::RecordAndSample
+        );
+        assert_eq!(
+            decision(&sampler, "GET /internal/stats", SpanKind::Server, &[]),
+            SamplingDecision::Drop
+        );
+        // Span names are case sensitive.
+        assert_eq!(
+            decision(&sampler, "get /internal/stats", SpanKind::Server, &[]),
+            SamplingDecision::RecordAndSample
+        );
+    }
+
+    #[test]
+    fn attribute_criteria() {
+        let rule = SamplingRule::new(Sampler::AlwaysOff)
+            .with_attribute("http.response.status_code", 200)
+            .with_attribute_prefix("url.path", "/api");
+
+        assert!(rule.matches(
+            "span",
+            &SpanKind::Internal,
+            &[
+                KeyValue::new("url.path", "/api/orders"),
+                KeyValue::new("http.response.status_code", 200),
+            ]
+        ));
+        assert!(!rule.matches(
+            "span",
+            &SpanKind::Internal,
+            &[
+                KeyValue::new("url.path", "/api/orders"),
+                KeyValue::new("http.response.status_code", 500),
+            ]
+        ));
+        assert!(!rule.matches(
+            "span",
+            &SpanKind::Internal,
+            &[KeyValue::new("url.path", "/api/orders")]
+        ));
+        // Prefixes only apply to string values.
+        let rule = SamplingRule::new(Sampler::AlwaysOff).with_attribute_prefix("port", "80");
+        assert!(!rule.matches("span", &SpanKind::Internal, &[KeyValue::new("port", 8080)]));
+    }
+
+    #[test]
+    fn custom_span_name_matcher() {
+        let sampler = RuleBasedSampler::new(Sampler::AlwaysOff).with_rule(
+            SamplingRule::new(Sampler::AlwaysOn)
+                .with_span_name_matching(|name| name.split('/').any(|segment| segment == "v2")),
+        );
+
+        assert_eq!(
+            decision(&sampler, "GET /api/v2/orders", SpanKind::Server, &[]),
+            SamplingDecision::RecordAndSample
+        );
+        assert_eq!(
+            decision(&sampler, "GET /api/v1/orders", SpanKind::Server, &[]),
+            SamplingDecision::Drop
+        );
+    }
+}
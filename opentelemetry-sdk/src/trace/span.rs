@@ -8,6 +8,7 @@
 //! start time is set to the current time on span creation. After the `Span` is created, it
 //! is possible to change its name, set its `Attributes`, and add `Links` and `Events`.
 //! These cannot be changed after the `Span`'s end time has been set.
+use crate::attribute_limits::{truncate_attributes, truncate_value};
 use crate::trace::SpanLimits;
 use opentelemetry::trace::{Event, Link, SpanContext, SpanId, SpanKind, Status};
 use opentelemetry::KeyValue;
@@ -21,6 +22,7 @@ pub struct Span {
     data: Option<SpanData>,
     tracer: crate::trace::SdkTracer,
     span_limits: SpanLimits,
+    ending: bool,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -62,6 +64,7 @@ impl Span {
             data,
             tracer,
             span_limits: span_limit,
+            ending: false,
         }
     }
 
@@ -83,6 +86,41 @@ impl Span {
             .as_ref()
             .map(|data| build_export_data(data.clone(), span_context, tracer))
     }
+
+    /// Mutable access to the attributes of this span while it is recording,
+    /// for instance to redact values from [`SpanProcessor::on_ending`].
+    ///
+    /// Attributes pushed here are not subject to the configured
+    /// [`SpanLimits`], prefer [`set_attribute`] to add attributes.
+    ///
+    /// [`SpanProcessor::on_ending`]: crate::trace::SpanProcessor::on_ending
+    /// [`set_attribute`]: opentelemetry::trace::Span::set_attribute
+    pub fn attributes_mut(&mut self) -> Option<&mut Vec<KeyValue>> {
+        self.data.as_mut().map(|data| &mut data.attributes)
+    }
+
+    /// The name of this span while it is recording.
+    pub fn name(&self) -> Option<&str> {
+        self.data.as_ref().map(|data| data.name.as_ref())
+    }
+
+    /// The parent span id of this span while it is recording.
+    pub fn parent_span_id(&self) -> Option<SpanId> {
+        self.data.as_ref().map(|data| data.parent_span_id)
+    }
+
+    /// The start time of this span while it is recording.
+    pub fn start_time(&self) -> Option<SystemTime> {
+        self.data.as_ref().map(|data| data.start_time)
+    }
+
+    /// The attributes of this span while it is recording.
+    ///
+    /// Unlike [`exported_data`](Span::exported_data), this does not copy the
+    /// data of the span.
+    pub fn attributes(&self) -> Option<&[KeyValue]> {
+        self.data.as_ref().map(|data| data.attributes.as_slice())
+    }
 }
 
 impl opentelemetry::trace::Span for Span {
@@ -101,11 +139,13 @@ impl opentelemetry::trace::Span for Span {
     {
         let span_events_limit = self.span_limits.max_events_per_span as usize;
         let event_attributes_limit = self.span_limits.max_attributes_per_event as usize;
+        let max_value_length = self.span_limits.max_attribute_value_length;
         self.with_data(|data| {
             if data.events.len() < span_events_limit {
                 let dropped_attributes_count =
                     attributes.len().saturating_sub(event_attributes_limit);
                 attributes.truncate(event_attributes_limit);
+                truncate_attributes(&mut attributes, max_value_length);
 
                 data.events.add_event(Event::new(
                     name,
@@ -136,10 +176,14 @@ impl opentelemetry::trace::Span for Span {
     /// Note that the OpenTelemetry project documents certain ["standard
     /// attributes"](https://github.com/open-telemetry/opentelemetry-specification/tree/v0.5.0/specification/trace/semantic_conventions/README.md)
     /// that have prescribed semantic meanings.
-    fn set_attribute(&mut self, attribute: KeyValue) {
+    fn set_attribute(&mut self, mut attribute: KeyValue) {
         let span_attribute_limit = self.span_limits.max_attributes_per_span as usize;
+        let max_value_length = self.span_limits.max_attribute_value_length;
         self.with_data(|data| {
             if data.attributes.len() < span_attribute_limit {
+                if let Some(max_value_length) = max_value_length {
+                    truncate_value(&mut attribute.value, max_value_length as usize);
+                }
                 data.attributes.push(attribute);
             } else {
                 data.dropped_attributes_count += 1;
@@ -175,12 +219,14 @@ impl opentelemetry::trace::Span for Span {
     fn add_link(&mut self, span_context: SpanContext, attributes: Vec<KeyValue>) {
         let span_links_limit = self.span_limits.max_links_per_span as usize;
         let link_attributes_limit = self.span_limits.max_attributes_per_link as usize;
+        let max_value_length = self.span_limits.max_attribute_value_length;
         self.with_data(|data| {
             if data.links.links.len() < span_links_limit {
                 let dropped_attributes_count =
                     attributes.len().saturating_sub(link_attributes_limit);
                 let mut attributes = attributes;
                 attributes.truncate(link_attributes_limit);
+                truncate_attributes(&mut attributes, max_value_length);
                 data.links.add_link(Link::new(
                     span_context,
                     attributes,
@@ -200,15 +246,23 @@ impl opentelemetry::trace::Span for Span {
 
 impl Span {
     fn ensure_ended_and_exported(&mut self, timestamp: Option<SystemTime>) {
-        // skip if data has already been exported
-        let mut data = match self.data.take() {
+        // skip if data has already been exported, or if a processor ends the
+        // span again from `on_ending`
+        if self.ending {
+            return;
+        }
+        let data = match self.data.as_mut() {
             Some(data) => data,
             None => return,
         };
 
-        let provider = self.tracer.provider();
+        let provider = self.tracer.provider().clone();
+        provider
+            .span_metrics()
+            .record_ended(self.span_context.is_sampled());
         // skip if provider has been shut down
         if provider.is_shutdown() {
+            self.data = None;
             return;
         }
 
@@ -219,6 +273,17 @@ impl Span {
             data.end_time = opentelemetry::time::now();
         }
 
+        // the span is still recording, every processor gets a chance to
+        // update it before any of them receives the final data
+        self.ending = true;
+        for processor in provider.span_processors() {
+            processor.on_ending(self);
+        }
+        let data = match self.data.take() {
+            Some(data) => data,
+            None => return,
+        };
+
         match provider.span_processors() {
             [] => {}
             [processor] => {
@@ -280,7 +345,7 @@ mod tests {
     };
     use crate::trace::{SpanEvents, SpanLinks};
     use opentelemetry::trace::{self, SpanBuilder, TraceFlags, TraceId, Tracer};
-    use opentelemetry::{trace::Span as _, trace::TracerProvider};
+    use opentelemetry::{trace::Span as _, trace::TracerProvider, Array, Value};
     use std::time::Duration;
     use std::vec;
 
@@ -638,6 +703,62 @@ mod tests {
         assert_eq!(processed_link.attributes.len(), 128);
     }
 
+    #[test]
+    fn exceed_attribute_value_length_limit() {
+        let provider = crate::trace::SdkTracerProvider::builder()
+            .with_simple_exporter(NoopSpanExporter::new())
+            .with_max_attribute_value_length(3)
+            .build();
+        let tracer = provider.tracer("opentelemetry-test");
+
+        let link = Link::new(
+            SpanContext::new(
+                TraceId::from(12),
+                SpanId::from(12),
+                TraceFlags::default(),
+                false,
+                Default::default(),
+            ),
+            vec![KeyValue::new("link", "abcdef")],
+            0,
+        );
+        let span_builder = tracer
+            .span_builder("test")
+            .with_attributes([
+                KeyValue::new("string", "abcdef"),
+                KeyValue::new("int", 123456),
+            ])
+            .with_links(vec![link]);
+        let mut span = tracer.build(span_builder);
+        span.set_attribute(KeyValue::new(
+            "array",
+            Value::Array(Array::String(vec!["abcdef".into(), "ab".into()])),
+        ));
+        span.add_event("event", vec![KeyValue::new("event", "abcdef")]);
+
+        let data = span.data.clone().expect("span should be recording");
+        assert_eq!(
+            data.attributes,
+            vec![
+                KeyValue::new("string", "abc"),
+                KeyValue::new("int", 123456),
+                KeyValue::new(
+                    "array",
+                    Value::Array(Array::String(vec!["abc".into(), "ab".into()]))
+                ),
+            ]
+        );
+        assert_eq!(data.dropped_attributes_count, 0);
+        assert_eq!(
+            data.events.events[0].attributes,
+            vec![KeyValue::new("event", "abc")]
+        );
+        assert_eq!(
+            data.links.links[0].attributes,
+            vec![KeyValue::new("link", "abc")]
+        );
+    }
+
     #[test]
     fn exceed_span_links_limit() {
         let exporter = NoopSpanExporter::new();
@@ -745,6 +866,66 @@ mod tests {
         let _ = provider.shutdown();
     }
 
+    #[test]
+    fn on_ending_updates_span_before_any_on_end() {
+        use crate::error::OTelSdkResult;
+        use crate::trace::{InMemorySpanExporterBuilder, SpanProcessor};
+        use opentelemetry::Context;
+
+        #[derive(Debug)]
+        struct RedactingProcessor;
+
+        impl SpanProcessor for RedactingProcessor {
+            fn on_start(&self, _span: &mut Span, _cx: &Context) {}
+
+            fn on_ending(&self, span: &mut Span) {
+                assert!(span.is_recording());
+                if let Some(attributes) = span.attributes_mut() {
+                    attributes.retain(|kv| kv.key.as_str() != "secret");
+                }
+                span.set_attribute(KeyValue::new("redacted", true));
+                // Ending the span again is a no-op.
+                span.end();
+            }
+
+            fn on_end(&self, span: crate::trace::SpanData) {
+                assert!(span.attributes.contains(&KeyValue::new("redacted", true)));
+            }
+
+            fn force_flush(&self) -> OTelSdkResult {
+                Ok(())
+            }
+
+            fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+                Ok(())
+            }
+        }
+
+        let exporter = InMemorySpanExporterBuilder::new().build();
+        // The exporting processor is registered first, it still receives the
+        // span updated by the processors registered after it.
+        let provider = crate::trace::SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .with_span_processor(RedactingProcessor)
+            .build();
+
+        let mut span = provider.tracer("test").start("span");
+        span.set_attribute(KeyValue::new("secret", "hunter2"));
+        span.set_attribute(KeyValue::new("key", "value"));
+        span.end();
+        assert!(!span.is_recording());
+
+        let spans = exporter.get_finished_spans().unwrap();
+        assert_eq!(spans.len(), 1);
+        assert_eq!(
+            spans[0].attributes,
+            vec![
+                KeyValue::new("key", "value"),
+                KeyValue::new("redacted", true)
+            ]
+        );
+    }
+
     #[test]
     fn test_span_exported_data() {
         let provider = crate::trace::SdkTracerProvider::builder()
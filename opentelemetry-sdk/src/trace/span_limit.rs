@@ -9,9 +9,11 @@
 ///  - Maximum allowed span link count
 ///  - Maximum allowed attribute per span event count
 ///  - Maximum allowed attribute per span link count
+///  - Maximum allowed length of attribute values
 ///
 /// If the limit has been breached. The attributes, events or links will be dropped based on their
-/// index in the collection. The one added to collections later will be dropped first.
+/// index in the collection. The one added to collections later will be dropped first. Attribute
+/// values longer than the limit are truncated instead.
 pub(crate) const DEFAULT_MAX_EVENT_PER_SPAN: u32 = 128;
 pub(crate) const DEFAULT_MAX_ATTRIBUTES_PER_SPAN: u32 = 128;
 pub(crate) const DEFAULT_MAX_LINKS_PER_SPAN: u32 = 128;
@@ -31,6 +33,10 @@ pub struct SpanLimits {
     pub max_attributes_per_event: u32,
     /// The max attributes that can be added into a `Link`
     pub max_attributes_per_link: u32,
+    /// The max length of the string values of span, event and link
+    /// attributes, in characters. Strings, and each string of an array, that
+    /// are longer are truncated. `None` means values are not truncated.
+    pub max_attribute_value_length: Option<u32>,
 }
 
 impl Default for SpanLimits {
@@ -41,6 +47,7 @@ impl Default for SpanLimits {
             max_links_per_span: DEFAULT_MAX_LINKS_PER_SPAN,
             max_attributes_per_link: DEFAULT_MAX_ATTRIBUTES_PER_LINK,
             max_attributes_per_event: DEFAULT_MAX_ATTRIBUTES_PER_EVENT,
+            max_attribute_value_length: None,
         }
     }
 }
@@ -36,11 +36,10 @@
 
 use crate::error::{OTelSdkError, OTelSdkResult};
 use crate::resource::Resource;
+use crate::self_diagnostics::{ComponentType, ProcessorMetrics, SdkMeter};
 use crate::trace::Span;
 use crate::trace::{SpanData, SpanExporter};
 use opentelemetry::Context;
-#[cfg(feature = "experimental_metrics_bound_instruments")]
-use opentelemetry::KeyValue;
 use opentelemetry::{otel_debug, otel_error, otel_warn};
 use std::cmp::min;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -80,6 +79,16 @@ pub trait SpanProcessor: Send + Sync + std::fmt::Debug {
     /// not block or throw exceptions.
     fn on_start(&self, span: &mut Span, cx: &Context);
 
+    /// `on_ending` is called when a `Span` is ending, after its end timestamp
+    /// is set but while it is still mutable. It is called for every processor
+    /// before `on_end` is called for any of them, so processors can add final
+    /// attributes or redact values before any processor sees the [`SpanData`].
+    ///
+    /// This method is called synchronously within the `Span::end` API,
+    /// therefore it should not block or throw an exception. Ending the span
+    /// again from this method has no effect.
+    fn on_ending(&self, _span: &mut Span) {}
+
     /// `on_end` is called after a `Span` is ended (i.e., the end timestamp is
     /// already set). This method is called synchronously within the `Span::end`
     /// API, therefore it should not block or throw an exception.
@@ -160,63 +169,40 @@ pub struct SimpleSpanProcessor<T: SpanExporter> {
     exporter: Mutex<T>,
     is_shutdown: AtomicBool,
 
-    // Self-diagnostics: otel.sdk.processor.span.processed counter, gated behind
-    // experimental_metrics_bound_instruments. The SimpleSpanProcessor exports
-    // each span synchronously and has no queue, so the only processor-side drop
-    // is `already_shutdown`.
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    processed_success: opentelemetry::metrics::BoundCounter<u64>,
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    processed_after_shutdown: opentelemetry::metrics::BoundCounter<u64>,
+    // Self-diagnostics. The SimpleSpanProcessor exports each span
+    // synchronously and has no queue, so the only processor-side drop is
+    // `already_shutdown`.
+    metrics: Arc<ProcessorMetrics>,
 }
 
 impl<T: SpanExporter> SimpleSpanProcessor<T> {
     /// Create a new [SimpleSpanProcessor] using the provided exporter.
     pub fn new(exporter: T) -> Self {
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        let (processed_success, processed_after_shutdown) = {
-            static INSTANCE_COUNTER: AtomicUsize = AtomicUsize::new(0);
-            let instance_id = INSTANCE_COUNTER.fetch_add(1, Ordering::Relaxed);
-            let component_name = format!("simple_span_processor/{instance_id}");
-
-            let meter = opentelemetry::global::meter("otel.sdk");
-            let counter = meter
-                .u64_counter("otel.sdk.processor.span.processed")
-                .with_description(
-                    "The number of spans for which the processing has finished, \
-                     either successful or failed.",
-                )
-                .with_unit("{span}")
-                .build();
-
-            // Attribute values follow the OTel semantic conventions for SDK metrics:
-            // https://github.com/open-telemetry/semantic-conventions/blob/main/docs/otel/sdk-metrics.md#metric-otelsdkprocessorspanprocessed
-            // https://github.com/open-telemetry/semantic-conventions/blob/main/docs/registry/attributes/otel.md#otel-component-attributes
-            let success_attrs = [
-                KeyValue::new("otel.component.type", "simple_span_processor"),
-                KeyValue::new("otel.component.name", component_name.clone()),
-            ];
-            let after_shutdown_attrs = [
-                KeyValue::new("error.type", "already_shutdown"),
-                KeyValue::new("otel.component.type", "simple_span_processor"),
-                KeyValue::new("otel.component.name", component_name),
-            ];
-
-            (
-                counter.bind(&success_attrs),
-                counter.bind(&after_shutdown_attrs),
-            )
-        };
-
         Self {
             exporter: Mutex::new(exporter),
             is_shutdown: AtomicBool::new(false),
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            processed_success,
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            processed_after_shutdown,
+            metrics: ProcessorMetrics::new(
+                &SdkMeter::default(),
+                ComponentType::SimpleSpanProcessor,
+                None,
+            ),
         }
     }
+
+    /// Report the self-diagnostics metrics of the processor,
+    /// `otel.sdk.processor.span.processed`, to `meter_provider`.
+    ///
+    /// By default, they are reported to the global meter provider set when
+    /// the processor receives its first span.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn with_meter_provider(
+        mut self,
+        meter_provider: &impl opentelemetry::metrics::MeterProvider,
+    ) -> Self {
+        self.metrics = self.metrics.with_meter(&SdkMeter::new(meter_provider));
+        self
+    }
 }
 
 impl<T: SpanExporter> SpanProcessor for SimpleSpanProcessor<T> {
@@ -232,8 +218,7 @@ impl<T: SpanExporter> SpanProcessor for SimpleSpanProcessor<T> {
         // noop after shutdown
         if self.is_shutdown.load(Ordering::Relaxed) {
             // Record the post-shutdown drop in self-diagnostics before returning.
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            self.processed_after_shutdown.add(1);
+            self.metrics.record_already_shutdown();
             otel_warn!(
                 name: "SimpleSpanProcessor.OnEnd.AfterShutdown",
                 message = "Spans are being emitted even after Shutdown. This indicates incorrect lifecycle management of TracerProvider in application. Spans will not be exported."
@@ -245,8 +230,7 @@ impl<T: SpanExporter> SpanProcessor for SimpleSpanProcessor<T> {
             Ok(exporter) => {
                 // Count the span as processed right before submitting it to the
                 // exporter, independent of the export outcome, per semconv.
-                #[cfg(feature = "experimental_metrics_bound_instruments")]
-                self.processed_success.add(1);
+                self.metrics.record_success(1);
                 futures_executor::block_on(exporter.export(vec![span]))
             }
             Err(_) => Err(OTelSdkError::InternalFailure(
@@ -414,26 +398,28 @@ pub struct BatchSpanProcessor {
     dropped_spans_count: AtomicUsize,
     max_queue_size: usize,
 
-    // Self-diagnostics: otel.sdk.processor.span.processed counter, gated behind
-    // experimental_metrics_bound_instruments so the hot-path `add` is a single
-    // atomic increment with no per-call attribute resolution. The success count
-    // is recorded in the worker thread when a batch is submitted to the
-    // exporter; the drop counts are recorded here at enqueue time.
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    processed_queue_full: opentelemetry::metrics::BoundCounter<u64>,
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    processed_after_shutdown: opentelemetry::metrics::BoundCounter<u64>,
+    // Self-diagnostics. The success count is recorded in the worker thread
+    // when a batch is submitted to the exporter; the drop counts are recorded
+    // here at enqueue time.
+    metrics: Arc<ProcessorMetrics>,
 }
 
 impl BatchSpanProcessor {
     /// Creates a new instance of `BatchSpanProcessor`.
     pub fn new<E>(
-        mut exporter: E,
+        exporter: E,
         config: BatchConfig,
         //max_queue_size: usize,
         //scheduled_delay: Duration,
         //shutdown_timeout: Duration,
     ) -> Self
+    where
+        E: SpanExporter + Send + 'static,
+    {
+        Self::new_with_meter(exporter, config, SdkMeter::default())
+    }
+
+    fn new_with_meter<E>(mut exporter: E, config: BatchConfig, meter: SdkMeter) -> Self
     where
         E: SpanExporter + Send + 'static,
     {
@@ -444,49 +430,12 @@ impl BatchSpanProcessor {
         let current_batch_size = Arc::new(AtomicUsize::new(0));
         let current_batch_size_for_thread = current_batch_size.clone();
 
-        // Self-diagnostics: create the otel.sdk.processor.span.processed counter.
-        // Created before the worker thread is spawned so the success counter can
-        // be moved into the worker and incremented when a batch is exported.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        let (processed_success, processed_queue_full, processed_after_shutdown) = {
-            static INSTANCE_COUNTER: AtomicUsize = AtomicUsize::new(0);
-            let instance_id = INSTANCE_COUNTER.fetch_add(1, Ordering::Relaxed);
-            let component_name = format!("batching_span_processor/{instance_id}");
-
-            let meter = opentelemetry::global::meter("otel.sdk");
-            let counter = meter
-                .u64_counter("otel.sdk.processor.span.processed")
-                .with_description(
-                    "The number of spans for which the processing has finished, \
-                     either successful or failed.",
-                )
-                .with_unit("{span}")
-                .build();
-
-            // Attribute values follow the OTel semantic conventions for SDK metrics:
-            // https://github.com/open-telemetry/semantic-conventions/blob/main/docs/otel/sdk-metrics.md#metric-otelsdkprocessorspanprocessed
-            // https://github.com/open-telemetry/semantic-conventions/blob/main/docs/registry/attributes/otel.md#otel-component-attributes
-            let success_attrs = [
-                KeyValue::new("otel.component.type", "batching_span_processor"),
-                KeyValue::new("otel.component.name", component_name.clone()),
-            ];
-            let queue_full_attrs = [
-                KeyValue::new("error.type", "queue_full"),
-                KeyValue::new("otel.component.type", "batching_span_processor"),
-                KeyValue::new("otel.component.name", component_name.clone()),
-            ];
-            let after_shutdown_attrs = [
-                KeyValue::new("error.type", "already_shutdown"),
-                KeyValue::new("otel.component.type", "batching_span_processor"),
-                KeyValue::new("otel.component.name", component_name),
-            ];
-
-            (
-                counter.bind(&success_attrs),
-                counter.bind(&queue_full_attrs),
-                counter.bind(&after_shutdown_attrs),
-            )
-        };
+        let metrics = ProcessorMetrics::new(
+            &meter,
+            ComponentType::BatchingSpanProcessor,
+            Some((max_queue_size, current_batch_size.clone())),
+        );
+        let metrics_for_thread = metrics.clone();
 
         let handle = thread::Builder::new()
             .name("OpenTelemetry.Traces.BatchProcessor".to_string())
@@ -502,12 +451,7 @@ impl BatchSpanProcessor {
                 let mut last_export_time = Instant::now();
                 let current_batch_size = current_batch_size_for_thread;
 
-                // Counts spans for the otel.sdk.processor.span.processed metric;
-                // a no-op when the self-diagnostics feature is disabled.
-                #[cfg(feature = "experimental_metrics_bound_instruments")]
-                let record_processed_success = move |count: u64| processed_success.add(count);
-                #[cfg(not(feature = "experimental_metrics_bound_instruments"))]
-                let record_processed_success = |_count: u64| {};
+                let metrics = metrics_for_thread;
                 loop {
                     let remaining_time_option = config
                         .scheduled_delay
@@ -531,7 +475,7 @@ impl BatchSpanProcessor {
                                     &mut last_export_time,
                                     &current_batch_size,
                                     &config,
-                                    &record_processed_success,
+                                    &metrics,
                                 );
                             }
                             BatchMessage::ForceFlush(sender) => {
@@ -543,7 +487,7 @@ impl BatchSpanProcessor {
                                     &mut last_export_time,
                                     &current_batch_size,
                                     &config,
-                                    &record_processed_success,
+                                    &metrics,
                                 );
                                 let _ = sender.send(result);
                             }
@@ -556,7 +500,7 @@ impl BatchSpanProcessor {
                                     &mut last_export_time,
                                     &current_batch_size,
                                     &config,
-                                    &record_processed_success,
+                                    &metrics,
                                 );
                                 let _ = exporter.shutdown();
                                 let _ = sender.send(result);
@@ -586,7 +530,7 @@ impl BatchSpanProcessor {
                                 &mut last_export_time,
                                 &current_batch_size,
                                 &config,
-                                &record_processed_success,
+                                &metrics,
                             );
                         }
                         Err(RecvTimeoutError::Disconnected) => {
@@ -616,10 +560,7 @@ impl BatchSpanProcessor {
             export_span_message_sent: Arc::new(AtomicBool::new(false)),
             current_batch_size,
             max_export_batch_size,
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            processed_queue_full,
-            #[cfg(feature = "experimental_metrics_bound_instruments")]
-            processed_after_shutdown,
+            metrics,
         }
     }
 
@@ -631,6 +572,7 @@ impl BatchSpanProcessor {
         BatchSpanProcessorBuilder {
             exporter,
             config: BatchConfig::default(),
+            meter: SdkMeter::default(),
         }
     }
 
@@ -638,18 +580,17 @@ impl BatchSpanProcessor {
     // It returns the result of the export operation.
     // It expects the spans vec to be empty when it's called.
     #[inline]
-    fn get_spans_and_export<E, F>(
+    fn get_spans_and_export<E>(
         spans_receiver: &Receiver<SpanData>,
         exporter: &E,
         spans: &mut Vec<SpanData>,
         last_export_time: &mut Instant,
         current_batch_size: &AtomicUsize,
         config: &BatchConfig,
-        record_processed_success: &F,
+        metrics: &ProcessorMetrics,
     ) -> OTelSdkResult
     where
         E: SpanExporter + Send + Sync + 'static,
-        F: Fn(u64),
     {
         let target = current_batch_size.load(Ordering::Acquire); // `target` is used to determine the stopping criteria for exporting spans.
         let mut result = OTelSdkResult::Ok(());
@@ -673,14 +614,16 @@ impl BatchSpanProcessor {
                 break;
             }
             total_exported_spans += count_of_spans;
+            // The spans left the queue, which is reported as
+            // `otel.sdk.processor.span.queue.size`.
+            current_batch_size.fetch_sub(count_of_spans, Ordering::AcqRel);
 
             // Count the batch as processed before invoking the exporter,
             // regardless of the export outcome.
-            record_processed_success(count_of_spans as u64);
-
-            result = Self::export_batch_sync(exporter, spans, last_export_time); // This method clears the spans vec after exporting
+            metrics.record_success(count_of_spans);
 
-            current_batch_size.fetch_sub(count_of_spans, Ordering::AcqRel);
+            result = Self::export_batch_sync(exporter, spans, last_export_time);
+            // This method clears the spans vec after exporting
         }
         result
     }
@@ -730,6 +673,7 @@ impl SpanProcessor for BatchSpanProcessor {
 
     /// Handles span end.
     fn on_end(&self, span: SpanData) {
+        self.metrics.register_callbacks();
         // Count the span before enqueueing it so that a concurrent
         // force_flush()/shutdown() drain never observes an
         // enqueued-but-uncounted span and misses it (issue #3453). If the
@@ -783,8 +727,7 @@ impl SpanProcessor for BatchSpanProcessor {
                 // The span never entered the channel; revert the increment.
                 self.current_batch_size.fetch_sub(1, Ordering::AcqRel);
                 // Record queue-full drop in self-diagnostics.
-                #[cfg(feature = "experimental_metrics_bound_instruments")]
-                self.processed_queue_full.add(1);
+                self.metrics.record_queue_full();
                 // Increment dropped spans count. The first time we have to drop
                 // a span, emit a warning.
                 if self.dropped_spans_count.fetch_add(1, Ordering::Relaxed) == 0 {
@@ -796,8 +739,7 @@ impl SpanProcessor for BatchSpanProcessor {
                 // The span never entered the channel; revert the increment.
                 self.current_batch_size.fetch_sub(1, Ordering::AcqRel);
                 // Record after-shutdown drop in self-diagnostics.
-                #[cfg(feature = "experimental_metrics_bound_instruments")]
-                self.processed_after_shutdown.add(1);
+                self.metrics.record_already_shutdown();
                 // Given background thread is the only receiver, and it's
                 // disconnected, it indicates the thread is shutdown
                 otel_warn!(
@@ -847,6 +789,7 @@ impl SpanProcessor for BatchSpanProcessor {
 
     /// Shuts down the processor.
     fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.metrics.unregister_callbacks();
         let dropped_spans = self.dropped_spans_count.load(Ordering::Relaxed);
         let max_queue_size = self.max_queue_size;
         if dropped_spans > 0 {
@@ -926,6 +869,7 @@ where
 {
     exporter: E,
     config: BatchConfig,
+    meter: SdkMeter,
 }
 
 impl<E> BatchSpanProcessorBuilder<E>
@@ -937,9 +881,26 @@ where
         BatchSpanProcessorBuilder { config, ..self }
     }
 
+    /// Report the self-diagnostics metrics of the processor, such as
+    /// `otel.sdk.processor.span.processed`, to `meter_provider`.
+    ///
+    /// By default, they are reported to the global meter provider set when
+    /// the processor receives its first span.
+    #[cfg(feature = "metrics")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+    pub fn with_meter_provider(
+        self,
+        meter_provider: &impl opentelemetry::metrics::MeterProvider,
+    ) -> Self {
+        BatchSpanProcessorBuilder {
+            meter: SdkMeter::new(meter_provider),
+            ..self
+        }
+    }
+
     /// Build a new instance of `BatchSpanProcessor`.
     pub fn build(self) -> BatchSpanProcessor {
-        BatchSpanProcessor::new(self.exporter, self.config)
+        BatchSpanProcessor::new_with_meter(self.exporter, self.config, self.meter)
     }
 }
 
@@ -1155,6 +1116,7 @@ mod tests {
         OTEL_BSP_SCHEDULE_DELAY, OTEL_BSP_SCHEDULE_DELAY_DEFAULT,
     };
     use crate::error::OTelSdkResult;
+    use crate::self_diagnostics::{ComponentType, ProcessorMetrics, SdkMeter};
     use crate::testing::trace::new_test_export_span_data;
     use crate::trace::span_processor::{
         OTEL_BSP_EXPORT_TIMEOUT_DEFAULT, OTEL_BSP_MAX_CONCURRENT_EXPORTS,
@@ -1476,7 +1438,11 @@ mod tests {
             &mut last_export_time,
             &current_batch_size,
             &config,
-            &|_count: u64| {},
+            &ProcessorMetrics::new(
+                &SdkMeter::default(),
+                ComponentType::BatchingSpanProcessor,
+                None,
+            ),
         );
 
         assert!(result.is_ok(), "export should succeed");
@@ -1524,7 +1490,11 @@ mod tests {
             &mut last_export_time,
             &current_batch_size,
             &config,
-            &|_count: u64| {},
+            &ProcessorMetrics::new(
+                &SdkMeter::default(),
+                ComponentType::BatchingSpanProcessor,
+                None,
+            ),
         );
 
         assert!(result.is_ok(), "export should succeed");
@@ -1549,7 +1519,11 @@ mod tests {
             &mut last_export_time,
             &current_batch_size,
             &config,
-            &|_count: u64| {},
+            &ProcessorMetrics::new(
+                &SdkMeter::default(),
+                ComponentType::BatchingSpanProcessor,
+                None,
+            ),
         );
 
         assert!(result.is_ok(), "export should succeed");
@@ -1618,11 +1592,11 @@ mod tests {
         }
 
         assert_eq!(processor.dropped_spans_count.load(Ordering::Relaxed), 2);
-        // 4 spans in-flight in the blocked export (not yet subtracted) plus
-        // 4 spans queued. Without the queue-full revert this would read 10.
+        // The 4 spans in-flight in the blocked export left the queue, 4 spans
+        // are queued. Without the queue-full revert this would read 6.
         assert_eq!(
             processor.current_batch_size.load(Ordering::Relaxed),
-            8,
+            4,
             "dropped spans must not remain counted as pending"
         );
 
@@ -2009,7 +1983,6 @@ mod tests {
     /// Sums the values of `otel.sdk.processor.span.processed` data points whose
     /// `error.type` attribute equals `error_type` (or that have no `error.type`
     /// attribute when `error_type` is `None`).
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
     fn sum_processed_spans(
         metric_exporter: &crate::metrics::InMemoryMetricExporter,
         error_type: Option<&str>,
@@ -2044,166 +2017,178 @@ mod tests {
         total
     }
 
-    #[cfg(feature = "experimental_metrics_bound_instruments")]
-    mod self_obs {
-        use super::*;
+    /// Verifies that `otel.sdk.processor.span.processed` counts spans (with no
+    /// `error.type`) when the processor submits a batch to the exporter.
+    #[test]
+    fn self_diagnostics_counter_records_success() {
+        use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
 
-        /// Verifies that `otel.sdk.processor.span.processed` counts spans (with no
-        /// `error.type`) when the processor submits a batch to the exporter.
-        ///
-        /// `#[ignore]`d because it mutates process-wide state via
-        /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        #[test]
-        #[ignore]
-        fn self_diagnostics_counter_records_success() {
-            use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter.clone())
+            .build();
 
-            let metric_exporter = InMemoryMetricExporter::default();
-            let meter_provider = SdkMeterProvider::builder()
-                .with_periodic_exporter(metric_exporter.clone())
-                .build();
-            opentelemetry::global::set_meter_provider(meter_provider.clone());
+        let span_exporter = InMemorySpanExporterBuilder::new().build();
+        let config = BatchConfigBuilder::default()
+            .with_max_queue_size(256)
+            .with_max_export_batch_size(64)
+            .with_scheduled_delay(Duration::from_secs(60))
+            .build();
+        let processor = BatchSpanProcessor::builder(span_exporter)
+            .with_batch_config(config)
+            .with_meter_provider(&meter_provider)
+            .build();
 
-            let span_exporter = InMemorySpanExporterBuilder::new().build();
-            let config = BatchConfigBuilder::default()
-                .with_max_queue_size(256)
-                .with_max_export_batch_size(64)
-                .with_scheduled_delay(Duration::from_secs(60))
-                .build();
-            let processor = BatchSpanProcessor::new(span_exporter, config);
+        for _ in 0..10 {
+            processor.on_end(create_test_span("success"));
+        }
 
-            for _ in 0..10 {
-                processor.on_end(create_test_span("success"));
-            }
+        // Flush so the batch is submitted to the exporter, which is when the
+        // counter is incremented.
+        processor.force_flush().unwrap();
+        meter_provider.force_flush().unwrap();
 
-            // Flush so the batch is submitted to the exporter, which is when the
-            // counter is incremented.
-            processor.force_flush().unwrap();
-            meter_provider.force_flush().unwrap();
+        let processed = sum_processed_spans(&metric_exporter, None);
+        assert_eq!(
+            processed, 10,
+            "expected 10 processed spans, got {processed}"
+        );
 
-            let processed = sum_processed_spans(&metric_exporter, None);
-            assert_eq!(
-                processed, 10,
-                "expected 10 processed spans, got {processed}"
-            );
+        processor.shutdown().unwrap();
+        meter_provider.shutdown().unwrap();
+    }
 
-            processor.shutdown().unwrap();
-            meter_provider.shutdown().unwrap();
-        }
+    /// Verifies that `otel.sdk.processor.span.processed` records queue-full drops
+    /// with `error.type = queue_full` when spans overflow the queue while the
+    /// worker is blocked exporting, and that the queue gauges report the
+    /// capacity and the queued spans.
+    #[test]
+    fn self_diagnostics_counter_records_queue_full_drops() {
+        use crate::metrics::data::{AggregatedMetrics, MetricData};
+        use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
 
-        /// Verifies that `otel.sdk.processor.span.processed` records queue-full drops
-        /// with `error.type = queue_full` when spans overflow the queue while the
-        /// worker is blocked exporting.
-        ///
-        /// `#[ignore]`d because it mutates process-wide state via
-        /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        #[test]
-        #[ignore]
-        fn self_diagnostics_counter_records_queue_full_drops() {
-            use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter.clone())
+            .build();
 
-            let metric_exporter = InMemoryMetricExporter::default();
-            let meter_provider = SdkMeterProvider::builder()
-                .with_periodic_exporter(metric_exporter.clone())
-                .build();
-            opentelemetry::global::set_meter_provider(meter_provider.clone());
+        let (started_sender, started_receiver) = std::sync::mpsc::sync_channel(8);
+        let (release_sender, release_receiver) = std::sync::mpsc::sync_channel(8);
+        let exported_count = Arc::new(AtomicUsize::new(0));
+        let exporter = BlockingExporter {
+            exported_count: exported_count.clone(),
+            export_started: started_sender,
+            release: Arc::new(Mutex::new(release_receiver)),
+        };
+        let config = BatchConfigBuilder::default()
+            .with_max_queue_size(4)
+            .with_max_export_batch_size(4)
+            .with_scheduled_delay(Duration::from_secs(60))
+            .build();
+        let processor = BatchSpanProcessor::builder(exporter)
+            .with_batch_config(config)
+            .with_meter_provider(&meter_provider)
+            .build();
+
+        // Fill the queue to the export threshold; the worker drains all four
+        // spans and blocks inside export().
+        for _ in 0..4 {
+            processor.on_end(create_test_span("first_batch"));
+        }
+        started_receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("worker should start exporting the first batch");
 
-            let (started_sender, started_receiver) = std::sync::mpsc::sync_channel(8);
-            let (release_sender, release_receiver) = std::sync::mpsc::sync_channel(8);
-            let exported_count = Arc::new(AtomicUsize::new(0));
-            let exporter = BlockingExporter {
-                exported_count: exported_count.clone(),
-                export_started: started_sender,
-                release: Arc::new(Mutex::new(release_receiver)),
+        // While the worker is blocked, refill the queue (4) and overflow it by
+        // two spans, which must be dropped and counted as queue_full.
+        for _ in 0..4 {
+            processor.on_end(create_test_span("second_batch"));
+        }
+        for _ in 0..2 {
+            processor.on_end(create_test_span("overflow"));
+        }
+
+        meter_provider.force_flush().unwrap();
+        let queue_gauge = |name: &str| {
+            let metrics = metric_exporter.get_finished_metrics().unwrap();
+            let metric = metrics
+                .last()
+                .unwrap()
+                .scope_metrics
+                .iter()
+                .flat_map(|sm| &sm.metrics)
+                .find(|metric| metric.name == name)
+                .unwrap();
+            let AggregatedMetrics::I64(MetricData::Sum(sum)) = &metric.data else {
+                panic!("unexpected data for {name}");
             };
-            let config = BatchConfigBuilder::default()
-                .with_max_queue_size(4)
-                .with_max_export_batch_size(4)
-                .with_scheduled_delay(Duration::from_secs(60))
-                .build();
-            let processor = BatchSpanProcessor::new(exporter, config);
+            let value = sum.data_points().next().unwrap().value();
+            value
+        };
+        assert_eq!(queue_gauge("otel.sdk.processor.span.queue.capacity"), 4);
+        assert_eq!(queue_gauge("otel.sdk.processor.span.queue.size"), 4);
 
-            // Fill the queue to the export threshold; the worker drains all four
-            // spans and blocks inside export().
-            for _ in 0..4 {
-                processor.on_end(create_test_span("first_batch"));
-            }
-            started_receiver
-                .recv_timeout(Duration::from_secs(5))
-                .expect("worker should start exporting the first batch");
-
-            // While the worker is blocked, refill the queue (4) and overflow it by
-            // two spans, which must be dropped and counted as queue_full.
-            for _ in 0..4 {
-                processor.on_end(create_test_span("second_batch"));
-            }
-            for _ in 0..2 {
-                processor.on_end(create_test_span("overflow"));
-            }
+        // Release the in-flight export and the one triggered by force_flush.
+        release_sender.send(()).unwrap();
+        release_sender.send(()).unwrap();
+        processor.force_flush().unwrap();
+        metric_exporter.reset();
+        meter_provider.force_flush().unwrap();
 
-            // Release the in-flight export and the one triggered by force_flush.
-            release_sender.send(()).unwrap();
-            release_sender.send(()).unwrap();
-            processor.force_flush().unwrap();
-            meter_provider.force_flush().unwrap();
+        let queue_full = sum_processed_spans(&metric_exporter, Some("queue_full"));
+        assert_eq!(
+            queue_full, 2,
+            "expected 2 queue_full drops, got {queue_full}"
+        );
+        assert_eq!(queue_gauge("otel.sdk.processor.span.queue.size"), 0);
 
-            let queue_full = sum_processed_spans(&metric_exporter, Some("queue_full"));
-            assert_eq!(
-                queue_full, 2,
-                "expected 2 queue_full drops, got {queue_full}"
-            );
+        processor.shutdown().unwrap();
+        meter_provider.shutdown().unwrap();
+    }
 
-            processor.shutdown().unwrap();
-            meter_provider.shutdown().unwrap();
-        }
+    /// Verifies that `otel.sdk.processor.span.processed` records post-shutdown
+    /// emits with `error.type = already_shutdown`.
+    #[test]
+    fn self_diagnostics_counter_records_already_shutdown_drops() {
+        use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
 
-        /// Verifies that `otel.sdk.processor.span.processed` records post-shutdown
-        /// emits with `error.type = already_shutdown`.
-        ///
-        /// `#[ignore]`d because it mutates process-wide state via
-        /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
-        #[test]
-        #[ignore]
-        fn self_diagnostics_counter_records_already_shutdown_drops() {
-            use crate::metrics::{InMemoryMetricExporter, SdkMeterProvider};
+        let metric_exporter = InMemoryMetricExporter::default();
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter.clone())
+            .build();
 
-            let metric_exporter = InMemoryMetricExporter::default();
-            let meter_provider = SdkMeterProvider::builder()
-                .with_periodic_exporter(metric_exporter.clone())
-                .build();
-            opentelemetry::global::set_meter_provider(meter_provider.clone());
+        let span_exporter = InMemorySpanExporterBuilder::new().build();
+        let processor = BatchSpanProcessor::builder(span_exporter)
+            .with_meter_provider(&meter_provider)
+            .build();
 
-            let span_exporter = InMemorySpanExporterBuilder::new().build();
-            let processor = BatchSpanProcessor::new(span_exporter, BatchConfig::default());
+        // Shut the processor down so the worker thread (the only receiver)
+        // disconnects; subsequent on_end calls hit the already_shutdown branch.
+        processor.shutdown().unwrap();
 
-            // Shut the processor down so the worker thread (the only receiver)
-            // disconnects; subsequent on_end calls hit the already_shutdown branch.
-            processor.shutdown().unwrap();
+        for _ in 0..7 {
+            processor.on_end(create_test_span("after_shutdown"));
+        }
 
-            for _ in 0..7 {
-                processor.on_end(create_test_span("after_shutdown"));
-            }
+        meter_provider.force_flush().unwrap();
 
-            meter_provider.force_flush().unwrap();
+        let already_shutdown = sum_processed_spans(&metric_exporter, Some("already_shutdown"));
+        assert_eq!(
+            already_shutdown, 7,
+            "expected 7 already_shutdown drops, got {already_shutdown}"
+        );
 
-            let already_shutdown = sum_processed_spans(&metric_exporter, Some("already_shutdown"));
-            assert_eq!(
-                already_shutdown, 7,
-                "expected 7 already_shutdown drops, got {already_shutdown}"
-            );
+        meter_provider.shutdown().unwrap();
+    }
 
-            meter_provider.shutdown().unwrap();
-        }
+    mod self_obs {
+        use super::*;
 
         /// Verifies that `otel.sdk.processor.span.processed` counts spans (with no
         /// `error.type`) when `SimpleSpanProcessor` submits them to the exporter.
         ///
         /// `#[ignore]`d because it mutates process-wide state via
         /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
         #[test]
         #[ignore]
         fn simple_self_diagnostics_counter_records_success() {
@@ -2239,7 +2224,6 @@ mod tests {
         ///
         /// `#[ignore]`d because it mutates process-wide state via
         /// `global::set_meter_provider()`. CI runs it in isolation via `test.sh`.
-        #[cfg(feature = "experimental_metrics_bound_instruments")]
         #[test]
         #[ignore]
         fn simple_self_diagnostics_counter_records_already_shutdown_drops() {
@@ -0,0 +1,666 @@
+//! # Tail Sampling Span Processor
+//!
+//! Head sampling, done by [`ShouldSample`] implementations, decides whether a
+//! trace is sampled before its spans are created, so it cannot keep traces
+//! based on their outcome. The [`TailSamplingProcessor`] buffers the finished
+//! spans of each trace for a while, then decides whether to keep the whole
+//! trace by applying [`TailSamplingPolicy`]s, like keeping traces with errors
+//! or slow traces, and forwards the spans of the sampled traces to another
+//! [`SpanProcessor`].
+//!
+//! Spans are only buffered in memory, by a single process, so traces spanning
+//! several services are sampled independently by each of them. Use the
+//! tail-sampling processor of the OpenTelemetry Collector to sample such
+//! traces consistently.
+//!
+//! [`ShouldSample`]: crate::trace::ShouldSample
+use crate::error::{OTelSdkError, OTelSdkResult};
+use crate::resource::Resource;
+use crate::trace::sampler::sample_based_on_probability;
+use crate::trace::{SamplingDecision, Span, SpanData, SpanProcessor};
+use opentelemetry::trace::{SpanId, Status, TraceId};
+use opentelemetry::{otel_debug, otel_warn, Context, KeyValue};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{sync_channel, RecvTimeoutError, SyncSender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The default time spans of a trace are buffered before deciding whether to
+/// sample it.
+const DEFAULT_DECISION_WAIT: Duration = Duration::from_secs(30);
+/// The default maximum number of traces buffered at once.
+const DEFAULT_MAX_TRACES: usize = 10_000;
+/// The default maximum number of spans buffered per trace.
+const DEFAULT_MAX_SPANS_PER_TRACE: usize = 1_000;
+
+/// A policy deciding whether a trace buffered by a [`TailSamplingProcessor`]
+/// is sampled. A trace is sampled if any of the policies samples it.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum TailSamplingPolicy {
+    /// Sample traces containing a span with an error status.
+    ErrorStatus,
+    /// Sample traces whose local root span lasted longer than the threshold.
+    /// If the root span isn't buffered, the latency of the trace is measured
+    /// from the start of its first span to the end of its last span.
+    Latency(Duration),
+    /// Sample traces containing a span with the given attribute.
+    Attribute(KeyValue),
+    /// Sample the given fraction of traces, based on their trace id like
+    /// [`Sampler::TraceIdRatioBased`]. Usually the last policy, sampling a
+    /// fraction of the traces the other policies drop.
+    ///
+    /// [`Sampler::TraceIdRatioBased`]: crate::trace::Sampler::TraceIdRatioBased
+    Probabilistic(f64),
+}
+
+impl TailSamplingPolicy {
+    fn should_sample(&self, trace_id: TraceId, spans: &[SpanData]) -> bool {
+        match self {
+            TailSamplingPolicy::ErrorStatus => spans
+                .iter()
+                .any(|span| matches!(span.status, Status::Error { .. })),
+            TailSamplingPolicy::Latency(threshold) => trace_latency(spans) > *threshold,
+            TailSamplingPolicy::Attribute(attribute) => {
+                spans.iter().any(|span| span.attributes.contains(attribute))
+            }
+            TailSamplingPolicy::Probabilistic(ratio) => {
+                sample_based_on_probability(ratio, trace_id) == SamplingDecision::RecordAndSample
+            }
+        }
+    }
+}
+
+fn trace_latency(spans: &[SpanData]) -> Duration {
+    let root = spans
+        .iter()
+        .find(|span| span.parent_span_id == SpanId::INVALID || span.parent_span_is_remote);
+    let (start, end) = match root {
+        Some(root) => (Some(root.start_time), Some(root.end_time)),
+        None => (
+            spans.iter().map(|span| span.start_time).min(),
+            spans.iter().map(|span| span.end_time).max(),
+        ),
+    };
+    match (start, end) {
+        (Some(start), Some(end)) => end.duration_since(start).unwrap_or_default(),
+        _ => Duration::ZERO,
+    }
+}
+
+/// A [`SpanProcessor`] buffering the spans of each trace, and forwarding the
+/// spans of the traces sampled by its [`TailSamplingPolicy`]s to another
+/// processor.
+///
+/// The spans of a trace are buffered for the decision wait, starting when its
+/// first span ends, then the policies decide whether the whole trace is
+/// sampled. The decision is remembered for a while, so spans ending after it
+/// are forwarded or dropped like the rest of their trace. A [`force_flush`] or
+/// a [`shutdown`] decides on all the buffered traces right away.
+///
+/// Memory is bounded by the maximum number of buffered traces, above which
+/// the oldest trace is dropped without decision, and by the maximum number of
+/// spans buffered per trace, above which spans are dropped. Dropped traces and
+/// spans are reported in the internal logs.
+///
+/// Only spans sampled by the head sampler reach the processor, which is
+/// usually configured with [`Sampler::AlwaysOn`]. Other processors registered
+/// on the tracer provider still see all spans.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "testing")]
+/// # {
+/// use opentelemetry::KeyValue;
+/// use opentelemetry_sdk::trace::{
+///     BatchSpanProcessor, InMemorySpanExporter, SdkTracerProvider, TailSamplingPolicy,
+///     TailSamplingProcessor,
+/// };
+/// use std::time::Duration;
+///
+/// let exporter = InMemorySpanExporter::default();
+/// let processor = TailSamplingProcessor::builder(BatchSpanProcessor::builder(exporter).build())
+///     .with_policy(TailSamplingPolicy::ErrorStatus)
+///     .with_policy(TailSamplingPolicy::Latency(Duration::from_millis(500)))
+///     .with_policy(TailSamplingPolicy::Attribute(KeyValue::new("tenant.tier", "premium")))
+///     .with_policy(TailSamplingPolicy::Probabilistic(0.01))
+///     .with_decision_wait(Duration::from_secs(10))
+///     .build();
+///
+/// let provider = SdkTracerProvider::builder()
+///     .with_span_processor(processor)
+///     .build();
+/// # }
+/// ```
+///
+/// [`force_flush`]: SpanProcessor::force_flush
+/// [`shutdown`]: SpanProcessor::shutdown
+/// [`Sampler::AlwaysOn`]: crate::trace::Sampler::AlwaysOn
+#[derive(Debug)]
+pub struct TailSamplingProcessor {
+    shared: Arc<Shared>,
+    // Dropping the sender stops the worker thread.
+    shutdown_sender: Mutex<Option<SyncSender<()>>>,
+    handle: Mutex<Option<thread::JoinHandle<()>>>,
+    is_shutdown: AtomicBool,
+}
+
+#[derive(Debug)]
+struct Shared {
+    processor: RwLock<Box<dyn SpanProcessor>>,
+    policies: Vec<TailSamplingPolicy>,
+    decision_wait: Duration,
+    max_traces: usize,
+    max_spans_per_trace: usize,
+    state: Mutex<State>,
+    evicted_traces_count: AtomicUsize,
+    dropped_spans_count: AtomicUsize,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    pending: HashMap<TraceId, PendingTrace>,
+    // Ids of the pending traces, in the order of their deadline.
+    pending_order: VecDeque<TraceId>,
+    decisions: HashMap<TraceId, bool>,
+    decisions_order: VecDeque<TraceId>,
+}
+
+#[derive(Debug)]
+struct PendingTrace {
+    spans: Vec<SpanData>,
+    deadline: Instant,
+}
+
+impl TailSamplingProcessor {
+    /// Create a builder for a processor forwarding the spans of the sampled
+    /// traces to `processor`.
+    ///
+    /// To export them, use a [`BatchSpanProcessor`] or a
+    /// [`SimpleSpanProcessor`] wrapping the exporter.
+    ///
+    /// [`BatchSpanProcessor`]: crate::trace::BatchSpanProcessor
+    /// [`SimpleSpanProcessor`]: crate::trace::SimpleSpanProcessor
+    pub fn builder<P>(processor: P) -> TailSamplingProcessorBuilder
+    where
+        P: SpanProcessor + 'static,
+    {
+        TailSamplingProcessorBuilder {
+            processor: Box::new(processor),
+            policies: Vec::new(),
+            decision_wait: DEFAULT_DECISION_WAIT,
+            max_traces: DEFAULT_MAX_TRACES,
+            max_spans_per_trace: DEFAULT_MAX_SPANS_PER_TRACE,
+        }
+    }
+}
+
+impl Shared {
+    fn buffer(&self, state: &mut State, span: SpanData) {
+        let trace_id = span.span_context.trace_id();
+        if let Some(trace) = state.pending.get_mut(&trace_id) {
+            if trace.spans.len() < self.max_spans_per_trace {
+                trace.spans.push(span);
+            } else if self.dropped_spans_count.fetch_add(1, Ordering::Relaxed) == 0 {
+                otel_warn!(
+                    name: "TailSamplingProcessor.SpanDroppingStarted",
+                    message = "TailSamplingProcessor dropped a Span as its trace reached the maximum number of buffered spans. No further log will be emitted for further drops until Shutdown. During Shutdown time, a log will be emitted with exact count of total spans dropped."
+                );
+            }
+            return;
+        }
+
+        if state.pending.len() >= self.max_traces {
+            if let Some(evicted) = state.pending_order.pop_front() {
+                state.pending.remove(&evicted);
+                // Drop the spans of the evicted trace ending later as well.
+                state.record_decision(evicted, false, self.max_traces);
+                if self.evicted_traces_count.fetch_add(1, Ordering::Relaxed) == 0 {
+                    otel_warn!(
+                        name: "TailSamplingProcessor.TraceDroppingStarted",
+                        message = "TailSamplingProcessor dropped a trace without decision as the maximum number of buffered traces was reached. No further log will be emitted for further drops until Shutdown. During Shutdown time, a log will be emitted with exact count of total traces dropped."
+                    );
+                }
+            }
+        }
+        state.pending.insert(
+            trace_id,
+            PendingTrace {
+                spans: vec![span],
+                deadline: Instant::now() + self.decision_wait,
+            },
+        );
+        state.pending_order.push_back(trace_id);
+    }
+
+    /// Decides on the traces whose decision wait elapsed, or on all pending
+    /// traces if `all` is true, and forwards the sampled spans.
+    fn decide(&self, all: bool) {
+        let now = Instant::now();
+        let mut sampled = Vec::new();
+        if let Ok(mut state) = self.state.lock() {
+            while let Some(&trace_id) = state.pending_order.front() {
+                let due = all
+                    || state
+                        .pending
+                        .get(&trace_id)
+                        .map_or(true, |trace| trace.deadline <= now);
+                if !due {
+                    break;
+                }
+                state.pending_order.pop_front();
+                if let Some(trace) = state.pending.remove(&trace_id) {
+                    let sample = self
+                        .policies
+                        .iter()
+                        .any(|policy| policy.should_sample(trace_id, &trace.spans));
+                    state.record_decision(trace_id, sample, self.max_traces);
+                    if sample {
+                        sampled.extend(trace.spans);
+                    }
+                }
+            }
+        }
+
+        if !sampled.is_empty() {
+            if let Ok(processor) = self.processor.read() {
+                for span in sampled {
+                    processor.on_end(span);
+                }
+            }
+        }
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        let state = self.state.lock().ok()?;
+        let trace_id = state.pending_order.front()?;
+        state.pending.get(trace_id).map(|trace| trace.deadline)
+    }
+}
+
+impl State {
+    fn record_decision(&mut self, trace_id: TraceId, sampled: bool, max_decisions: usize) {
+        if self.decisions.len() >= max_decisions {
+            if let Some(oldest) = self.decisions_order.pop_front() {
+                self.decisions.remove(&oldest);
+            }
+        }
+        if self.decisions.insert(trace_id, sampled).is_none() {
+            self.decisions_order.push_back(trace_id);
+        }
+    }
+}
+
+impl SpanProcessor for TailSamplingProcessor {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        if let Ok(processor) = self.shared.processor.read() {
+            processor.on_start(span, cx);
+        }
+    }
+
+    fn on_ending(&self, span: &mut Span) {
+        if let Ok(processor) = self.shared.processor.read() {
+            processor.on_ending(span);
+        }
+    }
+
+    fn on_end(&self, span: SpanData) {
+        if !span.span_context.is_sampled() {
+            return;
+        }
+
+        if self.is_shutdown.load(Ordering::Relaxed) {
+            otel_warn!(
+                name: "TailSamplingProcessor.OnEnd.AfterShutdown",
+                message = "Spans are being emitted even after Shutdown. This indicates incorrect lifecycle management of TracerProvider in application. Spans will not be exported."
+            );
+            return;
+        }
+
+        let forward = match self.shared.state.lock() {
+            Ok(mut state) => match state.decisions.get(&span.span_context.trace_id()) {
+                Some(true) => Some(span),
+                Some(false) => None,
+                None => {
+                    self.shared.buffer(&mut state, span);
+                    None
+                }
+            },
+            Err(_) => None,
+        };
+
+        if let Some(span) = forward {
+            if let Ok(processor) = self.shared.processor.read() {
+                processor.on_end(span);
+            }
+        }
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.shared.decide(true);
+        self.shared
+            .processor
+            .read()
+            .map_err(|_| OTelSdkError::InternalFailure("TailSamplingProcessor lock poison".into()))?
+            .force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        if self.is_shutdown.swap(true, Ordering::Relaxed) {
+            return Err(OTelSdkError::AlreadyShutdown);
+        }
+
+        let evicted_traces = self.shared.evicted_traces_count.load(Ordering::Relaxed);
+        if evicted_traces > 0 {
+            otel_warn!(
+                name: "TailSamplingProcessor.TracesDropped",
+                dropped_trace_count = evicted_traces,
+                max_traces = self.shared.max_traces,
+                message = "Traces were dropped without decision as the maximum number of buffered traces was reached. The count represents the total count of traces dropped in the lifetime of this TailSamplingProcessor. Consider increasing the maximum number of traces and/or decreasing the decision wait."
+            );
+        }
+        let dropped_spans = self.shared.dropped_spans_count.load(Ordering::Relaxed);
+        if dropped_spans > 0 {
+            otel_warn!(
+                name: "TailSamplingProcessor.SpansDropped",
+                dropped_span_count = dropped_spans,
+                max_spans_per_trace = self.shared.max_spans_per_trace,
+                message = "Spans were dropped as their trace reached the maximum number of buffered spans. The count represents the total count of spans dropped in the lifetime of this TailSamplingProcessor."
+            );
+        }
+
+        if let Ok(mut sender) = self.shutdown_sender.lock() {
+            sender.take();
+        }
+        if let Some(handle) = self.handle.lock().ok().and_then(|mut handle| handle.take()) {
+            let _ = handle.join();
+        }
+
+        self.shared.decide(true);
+        self.shared
+            .processor
+            .read()
+            .map_err(|_| OTelSdkError::InternalFailure("TailSamplingProcessor lock poison".into()))?
+            .shutdown_with_timeout(timeout)
+    }
+
+    fn set_resource(&mut self, resource: &Resource) {
+        if let Ok(mut processor) = self.shared.processor.write() {
+            processor.set_resource(resource);
+        }
+    }
+}
+
+/// Builder for [`TailSamplingProcessor`].
+#[derive(Debug)]
+pub struct TailSamplingProcessorBuilder {
+    processor: Box<dyn SpanProcessor>,
+    policies: Vec<TailSamplingPolicy>,
+    decision_wait: Duration,
+    max_traces: usize,
+    max_spans_per_trace: usize,
+}
+
+impl TailSamplingProcessorBuilder {
+    /// Add a policy. Traces matching none of the policies are dropped.
+    pub fn with_policy(mut self, policy: TailSamplingPolicy) -> Self {
+        self.policies.push(policy);
+        self
+    }
+
+    /// Set the time the spans of a trace are buffered, starting when its first
+    /// span ends, before deciding whether the trace is sampled. The default
+    /// value is 30 seconds.
+    pub fn with_decision_wait(mut self, decision_wait: Duration) -> Self {
+        self.decision_wait = decision_wait;
+        self
+    }
+
+    /// Set the maximum number of traces buffered at once. When it is reached,
+    /// the oldest trace is dropped. The default value is 10,000.
+    pub fn with_max_traces(mut self, max_traces: usize) -> Self {
+        self.max_traces = max_traces.max(1);
+        self
+    }
+
+    /// Set the maximum number of spans buffered per trace. When it is reached,
+    /// the spans of the trace ending later are dropped. The default value is
+    /// 1,000.
+    pub fn with_max_spans_per_trace(mut self, max_spans_per_trace: usize) -> Self {
+        self.max_spans_per_trace = max_spans_per_trace.max(1);
+        self
+    }
+
+    /// Build a new instance of [`TailSamplingProcessor`], starting the thread
+    /// deciding on the traces whose decision wait elapsed.
+    pub fn build(self) -> TailSamplingProcessor {
+        let shared = Arc::new(Shared {
+            processor: RwLock::new(self.processor),
+            policies: self.policies,
+            decision_wait: self.decision_wait,
+            max_traces: self.max_traces,
+            max_spans_per_trace: self.max_spans_per_trace,
+            state: Mutex::new(State::default()),
+            evicted_traces_count: AtomicUsize::new(0),
+            dropped_spans_count: AtomicUsize::new(0),
+        });
+        let (shutdown_sender, shutdown_receiver) = sync_channel::<()>(1);
+
+        let worker_shared = shared.clone();
+        let handle = thread::Builder::new()
+            .name("OpenTelemetry.Traces.TailSamplingProcessor".to_string())
+            .spawn(move || {
+                let _suppress_guard = Context::enter_telemetry_suppressed_scope();
+                otel_debug!(
+                    name: "TailSamplingProcessor.ThreadStarted",
+                    decision_wait_in_millisecs = worker_shared.decision_wait.as_millis(),
+                    max_traces = worker_shared.max_traces,
+                );
+                loop {
+                    // Deadlines are never further than the decision wait, so
+                    // sleeping at most that long doesn't delay new traces.
+                    let timeout = worker_shared
+                        .next_deadline()
+                        .map_or(worker_shared.decision_wait, |deadline| {
+                            deadline.saturating_duration_since(Instant::now())
+                        });
+                    match shutdown_receiver.recv_timeout(timeout) {
+                        Err(RecvTimeoutError::Timeout) => worker_shared.decide(false),
+                        Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                otel_debug!(name: "TailSamplingProcessor.ThreadStopped");
+            })
+            .expect("Failed to spawn thread");
+
+        TailSamplingProcessor {
+            shared,
+            shutdown_sender: Mutex::new(Some(shutdown_sender)),
+            handle: Mutex::new(Some(handle)),
+            is_shutdown: AtomicBool::new(false),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::trace::new_test_export_span_data;
+    use crate::trace::{InMemorySpanExporter, SimpleSpanProcessor};
+    use opentelemetry::trace::{SpanContext, TraceFlags, TraceState};
+    use std::time::SystemTime;
+
+    fn span(trace_id: u128, span_id: u64, parent_span_id: u64) -> SpanData {
+        let mut span = new_test_export_span_data();
+        span.span_context = SpanContext::new(
+            TraceId::from(trace_id),
+            SpanId::from(span_id),
+            TraceFlags::SAMPLED,
+            false,
+            TraceState::default(),
+        );
+        span.parent_span_id = SpanId::from(parent_span_id);
+        span
+    }
+
+    fn processor(exporter: &InMemorySpanExporter) -> TailSamplingProcessorBuilder {
+        TailSamplingProcessor::builder(SimpleSpanProcessor::new(exporter.clone()))
+            // Decisions are only made by force_flush unless tests say otherwise.
+            .with_decision_wait(Duration::from_secs(3600))
+    }
+
+    fn exported_span_ids(exporter: &InMemorySpanExporter) -> Vec<SpanId> {
+        exporter
+            .get_finished_spans()
+            .unwrap()
+            .iter()
+            .map(|span| span.span_context.span_id())
+            .collect()
+    }
+
+    #[test]
+    fn samples_whole_traces_matching_a_policy() {
+        let exporter = InMemorySpanExporter::default();
+        let processor = processor(&exporter)
+            .with_policy(TailSamplingPolicy::ErrorStatus)
+            .build();
+
+        let mut failed = span(1, 2, 1);
+        failed.status = Status::error("failure");
+        processor.on_end(failed);
+        processor.on_end(span(1, 1, 0));
+        processor.on_end(span(2, 3, 0));
+        assert!(exporter.get_finished_spans().unwrap().is_empty());
+
+        processor.force_flush().unwrap();
+        assert_eq!(
+            exported_span_ids(&exporter),
+            vec![SpanId::from(2), SpanId::from(1)]
+        );
+
+        // Late spans follow the decision made for their trace.
+        exporter.reset();
+        processor.on_end(span(1, 4, 1));
+        processor.on_end(span(2, 5, 3));
+        assert_eq!(exported_span_ids(&exporter), vec![SpanId::from(4)]);
+        processor.force_flush().unwrap();
+        assert_eq!(exported_span_ids(&exporter), vec![SpanId::from(4)]);
+        processor.shutdown().unwrap();
+    }
+
+    #[test]
+    fn policies() {
+        let now = SystemTime::now();
+        let mut root = span(1, 1, 0);
+        root.start_time = now;
+        root.end_time = now + Duration::from_millis(200);
+        let mut child = span(1, 2, 1);
+        child.start_time = now;
+        child.end_time = now + Duration::from_millis(600);
+        child.attributes = vec![KeyValue::new("tenant.tier", "premium")];
+
+        let trace_id = TraceId::from(1);
+        let spans = [root, child];
+        assert!(!TailSamplingPolicy::ErrorStatus.should_sample(trace_id, &spans));
+        // The root span decides the latency when it's buffered.
+        assert!(
+            TailSamplingPolicy::Latency(Duration::from_millis(100)).should_sample(trace_id, &spans)
+        );
+        assert!(!TailSamplingPolicy::Latency(Duration::from_millis(300))
+            .should_sample(trace_id, &spans));
+        assert!(TailSamplingPolicy::Latency(Duration::from_millis(300))
+            .should_sample(trace_id, &spans[1..]));
+        assert!(
+            TailSamplingPolicy::Attribute(KeyValue::new("tenant.tier", "premium"))
+                .should_sample(trace_id, &spans)
+        );
+        assert!(
+            !TailSamplingPolicy::Attribute(KeyValue::new("tenant.tier", "free"))
+                .should_sample(trace_id, &spans)
+        );
+        assert!(TailSamplingPolicy::Probabilistic(1.0).should_sample(trace_id, &spans));
+        assert!(!TailSamplingPolicy::Probabilistic(0.0).should_sample(trace_id, &spans));
+    }
+
+    #[test]
+    fn decides_after_decision_wait() {
+        let exporter = InMemorySpanExporter::default();
+        let processor = processor(&exporter)
+            .with_policy(TailSamplingPolicy::Probabilistic(1.0))
+            .with_decision_wait(Duration::from_millis(50))
+            .build();
+
+        processor.on_end(span(1, 1, 0));
+        processor.on_end(span(1, 2, 1));
+        assert!(exporter.get_finished_spans().unwrap().is_empty());
+
+        let start = Instant::now();
+        while exporter.get_finished_spans().unwrap().len() < 2 {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "trace not exported"
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+        processor.shutdown().unwrap();
+    }
+
+    #[test]
+    fn evicts_by_count() {
+        let exporter = InMemorySpanExporter::default();
+        let processor = processor(&exporter)
+            .with_policy(TailSamplingPolicy::Probabilistic(1.0))
+            .with_max_traces(2)
+            .with_max_spans_per_trace(2)
+            .build();
+
+        for span_id in 1..=3 {
+            processor.on_end(span(1, span_id, 0));
+        }
+        processor.on_end(span(2, 4, 0));
+        // Evicts trace 1, whose later spans are dropped.
+        processor.on_end(span(3, 5, 0));
+        processor.on_end(span(1, 6, 0));
+        assert_eq!(
+            processor
+                .shared
+                .evicted_traces_count
+                .load(Ordering::Relaxed),
+            1
+        );
+        assert_eq!(
+            processor.shared.dropped_spans_count.load(Ordering::Relaxed),
+            1
+        );
+
+        processor.force_flush().unwrap();
+        assert_eq!(
+            exported_span_ids(&exporter),
+            vec![SpanId::from(4), SpanId::from(5)]
+        );
+        processor.shutdown().unwrap();
+    }
+
+    #[test]
+    fn skips_unsampled_spans_and_spans_after_shutdown() {
+        let exporter = InMemorySpanExporter::default();
+        let processor = processor(&exporter)
+            .with_policy(TailSamplingPolicy::Probabilistic(1.0))
+            .build();
+
+        let mut unsampled = span(1, 1, 0);
+        unsampled.span_context = SpanContext::empty_context();
+        processor.on_end(unsampled);
+        processor.shutdown().unwrap();
+        processor.on_end(span(2, 2, 0));
+
+        assert!(exporter.get_finished_spans().unwrap().is_empty());
+        assert!(matches!(
+            processor.shutdown(),
+            Err(OTelSdkError::AlreadyShutdown)
+        ));
+    }
+}
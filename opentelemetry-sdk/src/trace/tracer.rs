@@ -7,6 +7,7 @@
 //! and exposes methods for creating and activating new `Spans`.
 //!
 //! Docs: <https://github.com/open-telemetry/opentelemetry-specification/blob/v1.3.0/specification/trace/api.md#tracer>
+use crate::attribute_limits::truncate_attributes;
 use crate::trace::{
     provider::SdkTracerProvider,
     span::{Span, SpanData},
@@ -24,6 +25,7 @@ use std::sync::Arc;
 pub struct SdkTracer {
     scope: Arc<InstrumentationScope>,
     provider: SdkTracerProvider,
+    disabled: bool,
 }
 
 impl fmt::Debug for SdkTracer {
@@ -43,6 +45,16 @@ impl SdkTracer {
         SdkTracer {
             scope: Arc::new(scope),
             provider,
+            disabled: false,
+        }
+    }
+
+    /// Create a tracer disabled by the `TracerConfig` of its scope.
+    pub(crate) fn disabled(scope: InstrumentationScope, provider: SdkTracerProvider) -> Self {
+        SdkTracer {
+            scope: Arc::new(scope),
+            provider,
+            disabled: true,
         }
     }
 
@@ -74,6 +86,8 @@ impl SdkTracer {
             .saturating_sub(span_attributes_limit);
         attribute_options.truncate(span_attributes_limit);
         let dropped_attributes_count = dropped_attributes_count as u32;
+        let max_value_length = span_limits.max_attribute_value_length;
+        truncate_attributes(&mut attribute_options, max_value_length);
 
         // Links are available as Option<Vec<Link>> in the builder
         // If it is None, then there are no links to process.
@@ -93,6 +107,7 @@ impl SdkTracer {
                 let dropped_attributes_count =
                     link.attributes.len().saturating_sub(link_attributes_limit);
                 link.attributes.truncate(link_attributes_limit);
+                truncate_attributes(&mut link.attributes, max_value_length);
                 link.dropped_attributes_count = dropped_attributes_count as u32;
             }
             SpanLinks {
@@ -122,6 +137,7 @@ impl SdkTracer {
                     .len()
                     .saturating_sub(event_attributes_limit);
                 event.attributes.truncate(event_attributes_limit);
+                truncate_attributes(&mut event.attributes, max_value_length);
                 event.dropped_attributes_count = dropped_attributes_count as u32;
             }
             SpanEvents {
@@ -173,6 +189,17 @@ impl opentelemetry::trace::Tracer for SdkTracer {
             );
         }
 
+        // a disabled tracer behaves like the no-op tracer, propagating the
+        // span context of the parent
+        if self.disabled {
+            return Span::new(
+                parent_cx.span().span_context().clone(),
+                None,
+                self.clone(),
+                SpanLimits::default(),
+            );
+        }
+
         let provider = self.provider();
         // no point start a span if the tracer provider has already being shutdown
         if provider.is_shutdown() {
@@ -212,6 +239,10 @@ impl opentelemetry::trace::Tracer for SdkTracer {
             builder.links.as_deref().unwrap_or(&[]),
         );
 
+        provider
+            .span_metrics()
+            .record_started(psc, &samplings_result.decision);
+
         let trace_flags = parent_cx.span().span_context().trace_flags();
         let trace_state = samplings_result.trace_state;
         let span_limits = config.span_limits;
@@ -265,6 +296,18 @@ impl opentelemetry::trace::Tracer for SdkTracer {
 
         span
     }
+
+    /// Returns `false` when the spans started by this tracer can't be recorded:
+    /// the tracer is disabled by the `TracerConfig` of its scope, the tracer
+    /// provider is shut down or has no span processor, or its sampler always
+    /// drops.
+    fn enabled(&self) -> bool {
+        let provider = self.provider();
+        !self.disabled
+            && !provider.is_shutdown()
+            && !provider.span_processors().is_empty()
+            && !provider.config().sampler.always_drops()
+    }
 }
 
 #[cfg(all(test, feature = "testing", feature = "trace"))]
@@ -617,4 +660,61 @@ mod tests {
         assert_ne!(child.parent_span_id, active_span_id);
         assert_ne!(child.span_context.trace_id(), active_trace_id);
     }
+
+    #[test]
+    fn tracer_enabled() {
+        let exporter = crate::trace::InMemorySpanExporter::default();
+        let tracer_provider = crate::trace::SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let tracer = tracer_provider.tracer("test");
+        assert!(tracer.enabled());
+
+        let _ = tracer_provider.shutdown();
+        assert!(!tracer.enabled());
+    }
+
+    #[test]
+    fn tracer_disabled_without_processor() {
+        let tracer_provider = crate::trace::SdkTracerProvider::builder().build();
+        assert!(!tracer_provider.tracer("test").enabled());
+    }
+
+    #[test]
+    fn tracer_disabled_with_always_off_sampler() {
+        use crate::trace::{RuleBasedSampler, SamplingRule};
+
+        let parent_based = |root: Sampler| Sampler::ParentBased(Box::new(root));
+        let rule_based = |rule: Sampler, fallback: Sampler| {
+            Sampler::ParentBased(Box::new(
+                RuleBasedSampler::new(fallback).with_rule(SamplingRule::new(rule)),
+            ))
+        };
+        let samplers = [
+            (Sampler::AlwaysOff, false),
+            (Sampler::AlwaysOn, true),
+            (Sampler::TraceIdRatioBased(0.0), false),
+            (Sampler::TraceIdRatioBased(-1.0), false),
+            (Sampler::TraceIdRatioBased(0.5), true),
+            (parent_based(Sampler::AlwaysOff), false),
+            (parent_based(Sampler::TraceIdRatioBased(0.0)), false),
+            (parent_based(Sampler::AlwaysOn), true),
+            (parent_based(Sampler::TraceIdRatioBased(0.5)), true),
+            (rule_based(Sampler::AlwaysOff, Sampler::AlwaysOff), false),
+            (rule_based(Sampler::AlwaysOn, Sampler::AlwaysOff), true),
+            (rule_based(Sampler::AlwaysOff, Sampler::AlwaysOn), true),
+        ];
+        for (sampler, enabled) in samplers {
+            let description = format!("{sampler:?}");
+            let tracer_provider = crate::trace::SdkTracerProvider::builder()
+                .with_simple_exporter(crate::trace::InMemorySpanExporter::default())
+                .with_sampler(sampler)
+                .build();
+            assert_eq!(
+                tracer_provider.tracer("test").enabled(),
+                enabled,
+                "{description}"
+            );
+        }
+    }
 }
@@ -0,0 +1,68 @@
+use crate::scope_config::ScopeConfigurator;
+use opentelemetry::InstrumentationScope;
+use std::fmt;
+
+/// The configuration of the tracers of an instrumentation scope, returned by
+/// a [`TracerConfigurator`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TracerConfig {
+    /// Whether the tracers record spans. A disabled tracer starts
+    /// non-recording spans propagating the span context of their parent.
+    pub enabled: bool,
+}
+
+impl Default for TracerConfig {
+    /// Create an enabled tracer config.
+    fn default() -> Self {
+        TracerConfig { enabled: true }
+    }
+}
+
+impl TracerConfig {
+    /// Create a disabled tracer config.
+    pub fn disabled() -> Self {
+        TracerConfig { enabled: false }
+    }
+
+    /// Set whether the tracers record spans.
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+/// Computes the [`TracerConfig`] of the tracers of each instrumentation scope.
+///
+/// It is called every time a tracer is obtained from a provider that is not
+/// shut down, without caching the result, so it should be cheap, see
+/// [`TracerProviderBuilder::with_tracer_configurator`]. It is implemented by
+/// [`ScopeConfigurator<TracerConfig>`] and by closures taking an
+/// [`InstrumentationScope`] and returning a [`TracerConfig`].
+///
+/// [`TracerProviderBuilder::with_tracer_configurator`]: crate::trace::TracerProviderBuilder::with_tracer_configurator
+pub trait TracerConfigurator: Send + Sync {
+    /// Returns the config of the tracers of `scope`.
+    fn tracer_config(&self, scope: &InstrumentationScope) -> TracerConfig;
+}
+
+impl<T> TracerConfigurator for T
+where
+    T: Fn(&InstrumentationScope) -> TracerConfig + Send + Sync,
+{
+    fn tracer_config(&self, scope: &InstrumentationScope) -> TracerConfig {
+        self(scope)
+    }
+}
+
+impl TracerConfigurator for ScopeConfigurator<TracerConfig> {
+    fn tracer_config(&self, scope: &InstrumentationScope) -> TracerConfig {
+        self.config(scope)
+    }
+}
+
+impl fmt::Debug for dyn TracerConfigurator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TracerConfigurator")
+    }
+}
@@ -7,3 +7,90 @@ pub fn tokio_interval_stream(
 ) -> tokio_stream::wrappers::IntervalStream {
     tokio_stream::wrappers::IntervalStream::new(tokio::time::interval(period))
 }
+
+/// Matches `name` against `pattern`, where `*` matches any sequence of
+/// characters and `?` matches a single character. The comparison is ASCII
+/// case-insensitive if `ignore_ascii_case` is set.
+#[cfg(any(feature = "metrics", feature = "trace", feature = "logs"))]
+pub(crate) fn wildcard_match(pattern: &str, name: &str, ignore_ascii_case: bool) -> bool {
+    // Byte offsets in `pattern` and `name`, always on character boundaries, so
+    // matching does not allocate.
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and the name position it was
+    // matched against, to backtrack to when the rest of the pattern fails.
+    let mut star: Option<(usize, usize)> = None;
+
+    while let Some(c) = name[n..].chars().next() {
+        let pattern_char = pattern[p..].chars().next();
+        match pattern_char {
+            Some(pc)
+                if pc == '?' || pc == c || (ignore_ascii_case && pc.eq_ignore_ascii_case(&c)) =>
+            {
+                p += pc.len_utf8();
+                n += c.len_utf8();
+            }
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    // Let the `*` match one more character of the name.
+                    p = star_p + 1;
+                    n = star_n + name[star_n..].chars().next().map_or(0, char::len_utf8);
+                    star = Some((star_p, n));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].chars().all(|c| c == '*')
+}
+
+#[cfg(all(test, any(feature = "metrics", feature = "trace")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcard_matching() {
+        let cases = [
+            ("*", "anything", true),
+            ("*", "", true),
+            ("http.*", "http.server.duration", true),
+            ("http.*", "rpc.server.duration", false),
+            ("*.duration", "http.server.duration", true),
+            ("http.*.duration", "http.client.duration", true),
+            ("http.?erver.duration", "http.server.duration", true),
+            ("http.?erver.duration", "http.erver.duration", false),
+            ("requests", "requests", true),
+            ("requests", "requests_total", false),
+            ("a*b*c", "axxbyyc", true),
+            ("a*b*c", "axxbyy", false),
+            ("caf?.*", "café.latte", true),
+            ("*é", "café", true),
+            ("?", "é", true),
+        ];
+
+        for (pattern, name, expected) in cases {
+            for ignore_ascii_case in [true, false] {
+                assert_eq!(
+                    wildcard_match(pattern, name, ignore_ascii_case),
+                    expected,
+                    "pattern {pattern:?} against {name:?}"
+                );
+            }
+        }
+
+        assert!(wildcard_match(
+            "HTTP.Server.*",
+            "http.server.duration",
+            true
+        ));
+        assert!(!wildcard_match(
+            "HTTP.Server.*",
+            "http.server.duration",
+            false
+        ));
+    }
+}
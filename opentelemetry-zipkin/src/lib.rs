@@ -263,4 +263,4 @@ mod exporter;
 mod propagator;
 
 pub use exporter::{ExporterBuildError, ZipkinExporter, ZipkinExporterBuilder};
-pub use propagator::{B3Encoding, Propagator};
+pub use propagator::{register_propagators, B3Encoding, Propagator};
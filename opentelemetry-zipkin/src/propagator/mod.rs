@@ -303,6 +303,29 @@ impl TextMapPropagator for Propagator {
     }
 }
 
+/// Registers the B3 propagators with the SDK so they can be selected with the
+/// `OTEL_PROPAGATORS` environment variable: `b3` for the single header and
+/// `b3multi` for the multiple header encoding.
+///
+/// # Examples
+///
+/// ```
+/// use opentelemetry::global;
+/// use opentelemetry_sdk::propagation::text_map_propagator_from_env;
+///
+/// opentelemetry_zipkin::register_propagators();
+/// // With OTEL_PROPAGATORS=tracecontext,b3
+/// global::set_text_map_propagator(text_map_propagator_from_env());
+/// ```
+pub fn register_propagators() {
+    opentelemetry_sdk::propagation::register_text_map_propagator("b3", || {
+        Propagator::with_encoding(B3Encoding::SingleHeader)
+    });
+    opentelemetry_sdk::propagation::register_text_map_propagator("b3multi", || {
+        Propagator::with_encoding(B3Encoding::MultipleHeader)
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -680,4 +703,30 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_registered_propagators() {
+        use opentelemetry_sdk::propagation::text_map_propagator_from_names;
+
+        register_propagators();
+
+        assert_eq!(
+            text_map_propagator_from_names(["b3"])
+                .fields()
+                .collect::<Vec<&str>>(),
+            vec![B3_SINGLE_HEADER]
+        );
+        let multi_header_propagator = text_map_propagator_from_names(["B3Multi"]);
+        let mut fields = multi_header_propagator.fields().collect::<Vec<&str>>();
+        fields.sort_unstable();
+        assert_eq!(
+            fields,
+            vec![
+                B3_DEBUG_FLAG_HEADER,
+                B3_SAMPLED_HEADER,
+                B3_SPAN_ID_HEADER,
+                B3_TRACE_ID_HEADER
+            ]
+        );
+    }
 }
@@ -0,0 +1,43 @@
+//! # OpenTelemetry zPages
+//!
+//! In-process web pages showing live telemetry data of the process, as
+//! described by the [zPages] experimental specification. Unlike exported
+//! telemetry, they don't depend on any collector or backend being reachable.
+//!
+//! Only the `/tracez` page is implemented. The [`ZPagesSpanProcessor`] keeps,
+//! for each span name, the running spans, the number of ended spans by latency
+//! bucket along with the most recent samples of each bucket, and the most
+//! recent spans that ended with an error. [`Tracez`] reads that data and
+//! serves it, in HTML and JSON, from a handler independent of any HTTP server
+//! framework.
+//!
+//! # Example
+//!
+//! ```
+//! use opentelemetry::trace::{Tracer, TracerProvider};
+//! use opentelemetry_sdk::trace::SdkTracerProvider;
+//! use opentelemetry_zpages::ZPagesSpanProcessor;
+//!
+//! let processor = ZPagesSpanProcessor::new();
+//! let tracez = processor.tracez();
+//! let provider = SdkTracerProvider::builder()
+//!     .with_span_processor(processor)
+//!     .build();
+//!
+//! provider.tracer("my-component").in_span("do-work", |_cx| {});
+//!
+//! // Called by the HTTP server for the requests to `/tracez` and `/tracez/*`.
+//! let request = http::Request::get("/tracez").body(()).unwrap();
+//! let response = tracez.handle(&request);
+//! assert!(response.body().contains("do-work"));
+//! ```
+//!
+//! [zPages]: https://github.com/open-telemetry/opentelemetry-specification/blob/main/experimental/trace/zpages.md
+#![warn(missing_debug_implementations, missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg), deny(rustdoc::broken_intra_doc_links))]
+
+mod processor;
+mod tracez;
+
+pub use processor::ZPagesSpanProcessor;
+pub use tracez::{Tracez, LATENCY_BUCKETS};
@@ -0,0 +1,345 @@
+use crate::tracez::Tracez;
+use opentelemetry::trace::{Span as _, SpanId, Status, TraceId};
+use opentelemetry::{Context, KeyValue};
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::trace::{Span, SpanData, SpanProcessor};
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+/// The lower bounds of the latency buckets: 0, 10µs, 100µs, 1ms, 10ms,
+/// 100ms, 1s, 10s and 100s.
+pub(crate) const LATENCY_BUCKET_BOUNDARIES: [Duration; 9] = [
+    Duration::ZERO,
+    Duration::from_micros(10),
+    Duration::from_micros(100),
+    Duration::from_millis(1),
+    Duration::from_millis(10),
+    Duration::from_millis(100),
+    Duration::from_secs(1),
+    Duration::from_secs(10),
+    Duration::from_secs(100),
+];
+
+const DEFAULT_SAMPLES_PER_BUCKET: usize = 10;
+const DEFAULT_MAX_SPAN_NAMES: usize = 1000;
+
+/// The number of maps the running spans are spread over, by span id, so that
+/// starting and ending spans on different threads rarely contend.
+const RUNNING_SHARDS: usize = 16;
+
+/// A [`SpanProcessor`] keeping the data shown by the `/tracez` zPage.
+///
+/// For each span name, the processor keeps the spans that are running, the
+/// number of ended spans by latency bucket along with samples of the most
+/// recent ones, and the number of spans that ended with an error along with
+/// samples of the most recent ones. A running span is shown with the name,
+/// parent and attributes it had when it started.
+///
+/// The ended spans are kept for at most 1000 span names, the spans of other
+/// names are only counted, see [`Tracez::untracked_spans`]. The running spans
+/// are forgotten when the processor is shut down.
+///
+/// The data is read with the [`Tracez`] returned by
+/// [`ZPagesSpanProcessor::tracez`]. Only the spans that are recorded reach
+/// the processor, see [`Sampler`].
+///
+/// [`Sampler`]: opentelemetry_sdk::trace::Sampler
+pub struct ZPagesSpanProcessor {
+    store: Arc<SpanStore>,
+}
+
+impl fmt::Debug for ZPagesSpanProcessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZPagesSpanProcessor")
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ZPagesSpanProcessor {
+    fn default() -> Self {
+        ZPagesSpanProcessor::new()
+    }
+}
+
+impl ZPagesSpanProcessor {
+    /// Create a processor keeping 10 samples per latency bucket and for
+    /// errors, for each of at most 1000 span names.
+    pub fn new() -> Self {
+        ZPagesSpanProcessor {
+            store: Arc::new(SpanStore::new()),
+        }
+    }
+
+    /// Keep `samples` samples per latency bucket and for errors, for each span
+    /// name, instead of 10.
+    pub fn with_samples_per_bucket(self, samples: usize) -> Self {
+        lock(&self.store.ended).samples_per_bucket = samples;
+        self
+    }
+
+    /// Keep the ended spans of at most `max_span_names` span names, instead of
+    /// 1000. The spans ended with another name once the limit is reached are
+    /// only counted.
+    pub fn with_max_span_names(self, max_span_names: usize) -> Self {
+        lock(&self.store.ended).max_span_names = max_span_names;
+        self
+    }
+
+    /// Returns the [`Tracez`] reading the data of this processor, which can
+    /// be kept after the processor is moved into a tracer provider.
+    pub fn tracez(&self) -> Tracez {
+        Tracez::new(self.store.clone())
+    }
+}
+
+impl SpanProcessor for ZPagesSpanProcessor {
+    fn on_start(&self, span: &mut Span, _cx: &Context) {
+        let (Some(name), Some(parent_span_id), Some(start_time), Some(attributes)) = (
+            span.name(),
+            span.parent_span_id(),
+            span.start_time(),
+            span.attributes(),
+        ) else {
+            return;
+        };
+        let span_context = span.span_context();
+        let running = RunningSpan {
+            name: name.to_string(),
+            trace_id: span_context.trace_id(),
+            span_id: span_context.span_id(),
+            parent_span_id,
+            start_time,
+            attributes: attributes.to_vec(),
+        };
+        lock(self.store.running_shard(running.span_id))
+            .insert((running.trace_id, running.span_id), running);
+    }
+
+    fn on_end(&self, span: SpanData) {
+        let span_id = span.span_context.span_id();
+        lock(self.store.running_shard(span_id)).remove(&(span.span_context.trace_id(), span_id));
+        lock(&self.store.ended).end(span);
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        Ok(())
+    }
+
+    fn shutdown_with_timeout(&self, _timeout: Duration) -> OTelSdkResult {
+        // The spans still running never reach `on_end` once the provider is
+        // shut down.
+        for shard in &self.store.running {
+            lock(shard).clear();
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // The store is left consistent by every operation, so a poisoned lock is
+    // still usable.
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The running and ended spans of all the span names.
+#[derive(Debug)]
+pub(crate) struct SpanStore {
+    /// The running spans, keyed by id since the name of a span can change
+    /// before it ends, spread over shards by span id.
+    pub(crate) running: [Mutex<HashMap<(TraceId, SpanId), RunningSpan>>; RUNNING_SHARDS],
+    pub(crate) ended: Mutex<EndedStore>,
+}
+
+/// The data of a running span rendered by the `/tracez` zPage, as it was
+/// when the span started.
+#[derive(Clone, Debug)]
+pub(crate) struct RunningSpan {
+    pub(crate) name: String,
+    pub(crate) trace_id: TraceId,
+    pub(crate) span_id: SpanId,
+    pub(crate) parent_span_id: SpanId,
+    pub(crate) start_time: SystemTime,
+    pub(crate) attributes: Vec<KeyValue>,
+}
+
+/// The ended spans, by span name.
+#[derive(Debug)]
+pub(crate) struct EndedStore {
+    samples_per_bucket: usize,
+    max_span_names: usize,
+    pub(crate) by_name: HashMap<Cow<'static, str>, EndedSpans>,
+    /// The number of ended spans whose name was not kept because
+    /// `max_span_names` names were already kept.
+    pub(crate) untracked_spans: u64,
+}
+
+/// The ended spans of a span name.
+#[derive(Debug, Default)]
+pub(crate) struct EndedSpans {
+    pub(crate) latency_counts: [u32; LATENCY_BUCKET_BOUNDARIES.len()],
+    pub(crate) latency_samples: [VecDeque<SpanData>; LATENCY_BUCKET_BOUNDARIES.len()],
+    pub(crate) error_count: u32,
+    pub(crate) error_samples: VecDeque<SpanData>,
+}
+
+impl SpanStore {
+    fn new() -> Self {
+        SpanStore {
+            running: std::array::from_fn(|_| Mutex::default()),
+            ended: Mutex::new(EndedStore {
+                samples_per_bucket: DEFAULT_SAMPLES_PER_BUCKET,
+                max_span_names: DEFAULT_MAX_SPAN_NAMES,
+                by_name: HashMap::new(),
+                untracked_spans: 0,
+            }),
+        }
+    }
+
+    fn running_shard(&self, span_id: SpanId) -> &Mutex<HashMap<(TraceId, SpanId), RunningSpan>> {
+        let index = u64::from_be_bytes(span_id.to_bytes()) as usize % RUNNING_SHARDS;
+        &self.running[index]
+    }
+}
+
+impl EndedStore {
+    fn end(&mut self, span: SpanData) {
+        if !self.by_name.contains_key(span.name.as_ref())
+            && self.by_name.len() >= self.max_span_names
+        {
+            self.untracked_spans = self.untracked_spans.saturating_add(1);
+            return;
+        }
+
+        let samples_per_bucket = self.samples_per_bucket;
+        let ended = self.by_name.entry(span.name.clone()).or_default();
+        let (count, samples) = if matches!(span.status, Status::Error { .. }) {
+            (&mut ended.error_count, &mut ended.error_samples)
+        } else {
+            let bucket = latency_bucket(
+                span.end_time
+                    .duration_since(span.start_time)
+                    .unwrap_or_default(),
+            );
+            (
+                &mut ended.latency_counts[bucket],
+                &mut ended.latency_samples[bucket],
+            )
+        };
+        *count = count.saturating_add(1);
+        if samples_per_bucket > 0 {
+            if samples.len() == samples_per_bucket {
+                samples.pop_front();
+            }
+            samples.push_back(span);
+        }
+    }
+}
+
+/// Returns the index of the latency bucket of `latency`.
+fn latency_bucket(latency: Duration) -> usize {
+    LATENCY_BUCKET_BOUNDARIES
+        .iter()
+        .rposition(|boundary| latency >= *boundary)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{Tracer, TracerProvider};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+
+    #[test]
+    fn latency_buckets() {
+        assert_eq!(latency_bucket(Duration::ZERO), 0);
+        assert_eq!(latency_bucket(Duration::from_micros(9)), 0);
+        assert_eq!(latency_bucket(Duration::from_micros(10)), 1);
+        assert_eq!(latency_bucket(Duration::from_millis(50)), 4);
+        assert_eq!(latency_bucket(Duration::from_secs(1000)), 8);
+    }
+
+    fn running_count(store: &SpanStore) -> usize {
+        store.running.iter().map(|shard| lock(shard).len()).sum()
+    }
+
+    #[test]
+    fn spans_are_tracked_by_name() {
+        let processor = ZPagesSpanProcessor::new().with_samples_per_bucket(2);
+        let store = processor.store.clone();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(processor)
+            .build();
+        let tracer = provider.tracer("test");
+
+        let mut running = tracer.start("running");
+        for _ in 0..3 {
+            tracer.in_span("ok", |_| {});
+        }
+        let mut failed = tracer.start("failed");
+        failed.set_status(Status::error("boom"));
+        failed.end();
+        let mut renamed = tracer.start("before");
+        renamed.update_name("after");
+
+        assert_eq!(running_count(&store), 2);
+        {
+            let ended = lock(&store.ended);
+            let ok = &ended.by_name["ok"];
+            assert_eq!(ok.latency_counts.iter().sum::<u32>(), 3);
+            for (count, samples) in ok.latency_counts.iter().zip(&ok.latency_samples) {
+                assert_eq!(samples.len(), (*count as usize).min(2));
+            }
+            assert_eq!(ok.error_count, 0);
+            let failed = &ended.by_name["failed"];
+            assert_eq!(failed.error_count, 1);
+            assert_eq!(failed.latency_counts.iter().sum::<u32>(), 0);
+            assert_eq!(failed.error_samples.len(), 1);
+        }
+
+        running.end();
+        renamed.end();
+        assert_eq!(running_count(&store), 0);
+        let ended = lock(&store.ended);
+        assert_eq!(ended.by_name["after"].latency_counts.iter().sum::<u32>(), 1);
+    }
+
+    #[test]
+    fn span_names_are_capped() {
+        let processor = ZPagesSpanProcessor::new().with_max_span_names(2);
+        let store = processor.store.clone();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(processor)
+            .build();
+        let tracer = provider.tracer("test");
+
+        for name in ["a", "b", "c", "a", "d"] {
+            tracer.in_span(name, |_| {});
+        }
+
+        let ended = lock(&store.ended);
+        let mut names: Vec<&str> = ended.by_name.keys().map(|name| name.as_ref()).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(ended.by_name["a"].latency_counts.iter().sum::<u32>(), 2);
+        assert_eq!(ended.untracked_spans, 2);
+    }
+
+    #[test]
+    fn running_spans_are_cleared_on_shutdown() {
+        let processor = ZPagesSpanProcessor::new();
+        let store = processor.store.clone();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(processor)
+            .build();
+        let tracer = provider.tracer("test");
+
+        let _never_ended = tracer.start("never-ended");
+        assert_eq!(running_count(&store), 1);
+
+        provider.shutdown().unwrap();
+        assert_eq!(running_count(&store), 0);
+    }
+}
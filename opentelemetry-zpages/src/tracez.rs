@@ -0,0 +1,573 @@
+use crate::processor::{lock, RunningSpan, SpanStore, LATENCY_BUCKET_BOUNDARIES};
+use chrono::{DateTime, SecondsFormat, Utc};
+use http::{header, Request, Response, StatusCode};
+use opentelemetry::trace::{SpanId, Status, TraceId};
+use opentelemetry::KeyValue;
+use opentelemetry_proto::tonic::tracez::v1::{ErrorData, LatencyData, RunningData, TracezCounts};
+use opentelemetry_proto::transform::common::tonic::Attributes;
+use opentelemetry_sdk::trace::SpanData;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The number of latency buckets, see [`Tracez::latency`].
+pub const LATENCY_BUCKETS: usize = LATENCY_BUCKET_BOUNDARIES.len();
+
+const LATENCY_BUCKET_NAMES: [&str; LATENCY_BUCKETS] = [
+    "&gt;0s",
+    "&gt;10µs",
+    "&gt;100µs",
+    "&gt;1ms",
+    "&gt;10ms",
+    "&gt;100ms",
+    "&gt;1s",
+    "&gt;10s",
+    "&gt;100s",
+];
+
+/// Reads the data kept by a [`ZPagesSpanProcessor`] and serves it as the
+/// `/tracez` zPage.
+///
+/// [`ZPagesSpanProcessor`]: crate::ZPagesSpanProcessor
+#[derive(Clone)]
+pub struct Tracez {
+    store: Arc<SpanStore>,
+}
+
+impl fmt::Debug for Tracez {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tracez").finish_non_exhaustive()
+    }
+}
+
+impl Tracez {
+    pub(crate) fn new(store: Arc<SpanStore>) -> Self {
+        Tracez { store }
+    }
+
+    /// Returns the number of running spans, of ended spans by latency bucket
+    /// and of spans ended with an error, for each span name, sorted by name.
+    pub fn summary(&self) -> Vec<TracezCounts> {
+        let mut counts: BTreeMap<String, TracezCounts> = BTreeMap::new();
+        let new_counts = |name: &str| TracezCounts {
+            spanname: name.to_string(),
+            latency: vec![0; LATENCY_BUCKETS],
+            running: 0,
+            error: 0,
+        };
+        for shard in &self.store.running {
+            for span in lock(shard).values() {
+                if let Some(counts) = counts.get_mut(&span.name) {
+                    counts.running += 1;
+                } else {
+                    let mut running = new_counts(&span.name);
+                    running.running = 1;
+                    counts.insert(span.name.clone(), running);
+                }
+            }
+        }
+        for (name, ended) in &lock(&self.store.ended).by_name {
+            let counts = counts
+                .entry(name.to_string())
+                .or_insert_with(|| new_counts(name));
+            counts.latency = ended.latency_counts.to_vec();
+            counts.error = ended.error_count;
+        }
+        counts.into_values().collect()
+    }
+
+    /// Returns the number of ended spans that are not in the
+    /// [`summary`](Tracez::summary) because the processor already kept the
+    /// maximum number of span names, see
+    /// [`ZPagesSpanProcessor::with_max_span_names`].
+    ///
+    /// [`ZPagesSpanProcessor::with_max_span_names`]: crate::ZPagesSpanProcessor::with_max_span_names
+    pub fn untracked_spans(&self) -> u64 {
+        lock(&self.store.ended).untracked_spans
+    }
+
+    /// Returns the running spans named `span_name`, with the data they had
+    /// when they started.
+    pub fn running(&self, span_name: &str) -> Vec<RunningData> {
+        self.running_spans(span_name)
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    /// Returns the samples of the spans named `span_name` whose latency is in
+    /// `bucket`, from 0 to [`LATENCY_BUCKETS`] excluded, with the buckets
+    /// starting at 0, 10µs, 100µs, 1ms, 10ms, 100ms, 1s, 10s and 100s.
+    pub fn latency(&self, span_name: &str, bucket: usize) -> Vec<LatencyData> {
+        self.latency_samples(span_name, bucket)
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    /// Returns the samples of the spans named `span_name` that ended with an
+    /// error.
+    pub fn errors(&self, span_name: &str) -> Vec<ErrorData> {
+        self.error_samples(span_name)
+            .into_iter()
+            .map(Into::into)
+            .collect()
+    }
+
+    fn running_spans(&self, span_name: &str) -> Vec<RunningSpan> {
+        let mut spans = Vec::new();
+        for shard in &self.store.running {
+            spans.extend(
+                lock(shard)
+                    .values()
+                    .filter(|span| span.name == span_name)
+                    .cloned(),
+            );
+        }
+        spans.sort_by_key(|span| span.start_time);
+        spans
+    }
+
+    fn latency_samples(&self, span_name: &str, bucket: usize) -> Vec<SpanData> {
+        lock(&self.store.ended)
+            .by_name
+            .get(span_name)
+            .and_then(|ended| ended.latency_samples.get(bucket))
+            .map(|samples| samples.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn error_samples(&self, span_name: &str) -> Vec<SpanData> {
+        lock(&self.store.ended)
+            .by_name
+            .get(span_name)
+            .map(|ended| ended.error_samples.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Serves the `/tracez` zPage, which can be mounted under any path prefix
+    /// of an HTTP server, e.g. `/debug/tracez`.
+    ///
+    /// | Path | Response |
+    /// |------|----------|
+    /// | `/tracez` | The summary, in HTML. The `zspanname`, `ztype` (`running`, `latency` or `error`) and `zlatencybucket` query parameters add the samples of a span name. |
+    /// | `/tracez/api/summary` | [`Tracez::summary`], in JSON |
+    /// | `/tracez/api/running/{name}` | [`Tracez::running`], in JSON |
+    /// | `/tracez/api/latency/{bucket}/{name}` | [`Tracez::latency`], in JSON |
+    /// | `/tracez/api/error/{name}` | [`Tracez::errors`], in JSON |
+    ///
+    /// Any other request is answered with `404 Not Found`.
+    pub fn handle<B>(&self, request: &Request<B>) -> Response<String> {
+        let uri = request.uri();
+        let Some(path) = uri
+            .path()
+            .find("/tracez")
+            .map(|start| &uri.path()[start + "/tracez".len()..])
+        else {
+            return not_found();
+        };
+        let path = path.trim_end_matches('/');
+        if path.is_empty() {
+            return html(self.render_page(uri.query().unwrap_or_default()));
+        }
+        let Some(api_path) = path.strip_prefix("/api/") else {
+            return not_found();
+        };
+        let (kind, rest) = api_path.split_once('/').unwrap_or((api_path, ""));
+        let name = percent_decode_str(rest).decode_utf8_lossy();
+        let body = match kind {
+            "summary" if rest.is_empty() => serde_json::to_string(&self.summary()),
+            "running" => serde_json::to_string(&self.running(&name)),
+            "error" => serde_json::to_string(&self.errors(&name)),
+            "latency" => {
+                let Some((bucket, name)) = rest.split_once('/') else {
+                    return not_found();
+                };
+                let Some(bucket) = bucket.parse().ok().filter(|b| *b < LATENCY_BUCKETS) else {
+                    return not_found();
+                };
+                let name = percent_decode_str(name).decode_utf8_lossy();
+                serde_json::to_string(&self.latency(&name, bucket))
+            }
+            _ => return not_found(),
+        };
+        match body {
+            Ok(body) => response(StatusCode::OK, "application/json", body),
+            Err(e) => response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "text/plain; charset=utf-8",
+                e.to_string(),
+            ),
+        }
+    }
+
+    fn render_page(&self, query: &str) -> String {
+        let mut span_name = None;
+        let mut sample_type = None;
+        let mut bucket = 0;
+        for (key, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+            let value = percent_decode_str(&value.replace('+', " "))
+                .decode_utf8_lossy()
+                .into_owned();
+            match key {
+                "zspanname" => span_name = Some(value),
+                "ztype" => sample_type = Some(value),
+                "zlatencybucket" => bucket = value.parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+
+        let mut page = String::from(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>TraceZ</title>\n\
+             <style>table{border-collapse:collapse}th,td{border:1px solid #ccc;padding:2px 8px}\
+             td{text-align:right}td.name,td.text{text-align:left}</style>\n</head>\n<body>\n\
+             <h1>TraceZ Summary</h1>\n",
+        );
+        self.render_summary(&mut page);
+        if let (Some(span_name), Some(sample_type)) = (span_name, sample_type) {
+            let running_spans;
+            let ended_spans;
+            let (title, rows): (_, Vec<SampleRow<'_>>) = match sample_type.as_str() {
+                "running" => {
+                    running_spans = self.running_spans(&span_name);
+                    ("Running", running_spans.iter().map(Into::into).collect())
+                }
+                "latency" if bucket < LATENCY_BUCKETS => {
+                    ended_spans = self.latency_samples(&span_name, bucket);
+                    (
+                        LATENCY_BUCKET_NAMES[bucket],
+                        ended_spans.iter().map(Into::into).collect(),
+                    )
+                }
+                "error" => {
+                    ended_spans = self.error_samples(&span_name);
+                    ("Error", ended_spans.iter().map(Into::into).collect())
+                }
+                _ => ("Unknown", Vec::new()),
+            };
+            render_samples(&mut page, &span_name, title, &rows);
+        }
+        page.push_str("</body>\n</html>\n");
+        page
+    }
+
+    fn render_summary(&self, page: &mut String) {
+        page.push_str("<table>\n<tr><th>Span Name</th><th>Running</th>");
+        for name in LATENCY_BUCKET_NAMES {
+            let _ = write!(page, "<th>{name}</th>");
+        }
+        page.push_str("<th>Error</th></tr>\n");
+        for counts in self.summary() {
+            let name = utf8_percent_encode(&counts.spanname, NON_ALPHANUMERIC).to_string();
+            let _ = write!(
+                page,
+                "<tr><td class=\"name\">{}</td>",
+                escape(&counts.spanname)
+            );
+            render_count(page, counts.running, &name, "running", None);
+            for (bucket, count) in counts.latency.iter().enumerate() {
+                render_count(page, *count, &name, "latency", Some(bucket));
+            }
+            render_count(page, counts.error, &name, "error", None);
+            page.push_str("</tr>\n");
+        }
+        page.push_str("</table>\n");
+        let untracked_spans = self.untracked_spans();
+        if untracked_spans > 0 {
+            let _ = writeln!(
+                page,
+                "<p>{untracked_spans} ended spans of other span names were not kept.</p>"
+            );
+        }
+    }
+}
+
+impl From<RunningSpan> for RunningData {
+    fn from(span: RunningSpan) -> Self {
+        RunningData {
+            traceid: span.trace_id.to_bytes().to_vec(),
+            spanid: span.span_id.to_bytes().to_vec(),
+            parentid: span.parent_span_id.to_bytes().to_vec(),
+            starttime: span
+                .start_time
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_nanos() as u64),
+            attributes: Attributes::from(span.attributes).0,
+            events: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+}
+
+/// A row of the table of sample spans.
+struct SampleRow<'a> {
+    start_time: SystemTime,
+    /// The latency of the span, not known yet if it is running.
+    latency: Option<Duration>,
+    trace_id: TraceId,
+    span_id: SpanId,
+    parent_span_id: SpanId,
+    status: Option<&'a Status>,
+    attributes: &'a [KeyValue],
+}
+
+impl<'a> From<&'a SpanData> for SampleRow<'a> {
+    fn from(span: &'a SpanData) -> Self {
+        SampleRow {
+            start_time: span.start_time,
+            latency: Some(
+                span.end_time
+                    .duration_since(span.start_time)
+                    .unwrap_or(Duration::ZERO),
+            ),
+            trace_id: span.span_context.trace_id(),
+            span_id: span.span_context.span_id(),
+            parent_span_id: span.parent_span_id,
+            status: Some(&span.status),
+            attributes: &span.attributes,
+        }
+    }
+}
+
+impl<'a> From<&'a RunningSpan> for SampleRow<'a> {
+    fn from(span: &'a RunningSpan) -> Self {
+        SampleRow {
+            start_time: span.start_time,
+            latency: None,
+            trace_id: span.trace_id,
+            span_id: span.span_id,
+            parent_span_id: span.parent_span_id,
+            status: None,
+            attributes: &span.attributes,
+        }
+    }
+}
+
+fn render_count(
+    page: &mut String,
+    count: u32,
+    encoded_name: &str,
+    sample_type: &str,
+    bucket: Option<usize>,
+) {
+    if count == 0 {
+        page.push_str("<td>0</td>");
+        return;
+    }
+    let _ = write!(
+        page,
+        "<td><a href=\"?zspanname={encoded_name}&amp;ztype={sample_type}"
+    );
+    if let Some(bucket) = bucket {
+        let _ = write!(page, "&amp;zlatencybucket={bucket}");
+    }
+    let _ = write!(page, "\">{count}</a></td>");
+}
+
+/// Renders the sample spans of `rows`.
+fn render_samples(page: &mut String, span_name: &str, title: &str, rows: &[SampleRow<'_>]) {
+    let _ = write!(
+        page,
+        "<h2>{}: {title}</h2>\n<table>\n<tr><th>Start Time</th><th>Latency</th>\
+         <th>Trace ID</th><th>Span ID</th><th>Parent Span ID</th><th>Status</th>\
+         <th>Attributes</th></tr>\n",
+        escape(span_name)
+    );
+    for row in rows {
+        let start_time: DateTime<Utc> = row.start_time.into();
+        let latency = row
+            .latency
+            .map(|latency| format!("{latency:?}"))
+            .unwrap_or_default();
+        let status = match row.status {
+            None | Some(Status::Unset) => String::new(),
+            Some(Status::Ok) => "Ok".to_string(),
+            Some(Status::Error { description }) => format!("Error: {description}"),
+        };
+        let attributes = row
+            .attributes
+            .iter()
+            .map(|kv| format!("{}={}", kv.key, kv.value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(
+            page,
+            "<tr><td class=\"text\">{}</td><td>{latency}</td><td class=\"text\">{}</td>\
+             <td class=\"text\">{}</td><td class=\"text\">{}</td><td class=\"text\">{}</td>\
+             <td class=\"text\">{}</td></tr>",
+            start_time.to_rfc3339_opts(SecondsFormat::Micros, true),
+            row.trace_id,
+            row.span_id,
+            row.parent_span_id,
+            escape(&status),
+            escape(&attributes),
+        );
+    }
+    page.push_str("</table>\n");
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn html(body: String) -> Response<String> {
+    response(StatusCode::OK, "text/html; charset=utf-8", body)
+}
+
+fn not_found() -> Response<String> {
+    response(
+        StatusCode::NOT_FOUND,
+        "text/plain; charset=utf-8",
+        "not found".to_string(),
+    )
+}
+
+fn response(status: StatusCode, content_type: &'static str, body: String) -> Response<String> {
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static(content_type),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ZPagesSpanProcessor;
+    use opentelemetry::trace::{Span as _, Tracer, TracerProvider};
+    use opentelemetry::KeyValue;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+
+    fn get(tracez: &Tracez, uri: &str) -> Response<String> {
+        tracez.handle(&Request::get(uri).body(()).unwrap())
+    }
+
+    fn tracez_with_spans() -> (SdkTracerProvider, Tracez) {
+        let processor = ZPagesSpanProcessor::new();
+        let tracez = processor.tracez();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(processor)
+            .build();
+        let tracer = provider.tracer("test");
+        tracer.in_span("GET /users/<id>", |_| {});
+        let mut span = tracer.start("failing");
+        span.set_attribute(KeyValue::new("user", "<script>"));
+        span.set_status(Status::error("boom"));
+        span.end();
+        (provider, tracez)
+    }
+
+    #[test]
+    fn summary_is_served_as_html() {
+        let (provider, tracez) = tracez_with_spans();
+        let _running = provider.tracer("test").start("running");
+
+        let response = get(&tracez, "http://localhost/debug/tracez");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        let body = response.body();
+        assert!(body.contains("<td class=\"name\">GET /users/&lt;id&gt;</td>"));
+        assert!(body.contains("?zspanname=failing&amp;ztype=error\">1</a>"));
+        assert!(body.contains("?zspanname=running&amp;ztype=running\">1</a>"));
+    }
+
+    #[test]
+    fn samples_are_served_as_html() {
+        let (_provider, tracez) = tracez_with_spans();
+
+        let body = get(&tracez, "/tracez?zspanname=failing&ztype=error").into_body();
+        assert!(body.contains("<h2>failing: Error</h2>"));
+        assert!(body.contains("Error: boom"));
+        assert!(body.contains("user=&lt;script&gt;"));
+        assert!(!body.contains("<script>"));
+
+        let body = get(
+            &tracez,
+            "/tracez?zspanname=GET+%2Fusers%2F%3Cid%3E&ztype=latency&zlatencybucket=0",
+        )
+        .into_body();
+        assert!(body.contains("<h2>GET /users/&lt;id&gt;: "));
+    }
+
+    #[test]
+    fn api_is_served_as_json() {
+        let (_provider, tracez) = tracez_with_spans();
+
+        let response = get(&tracez, "/tracez/api/summary");
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        let summary: Vec<TracezCounts> = serde_json::from_str(response.body()).unwrap();
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].spanname, "GET /users/<id>");
+        assert_eq!(summary[0].latency.iter().sum::<u32>(), 1);
+        assert_eq!(summary[1].spanname, "failing");
+        assert_eq!(summary[1].error, 1);
+
+        let errors: Vec<ErrorData> =
+            serde_json::from_str(get(&tracez, "/tracez/api/error/failing").body()).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].status.as_ref().unwrap().message, "boom");
+
+        let bucket = summary[0].latency.iter().position(|count| *count > 0);
+        let uri = format!(
+            "/tracez/api/latency/{}/GET%20%2Fusers%2F%3Cid%3E",
+            bucket.unwrap()
+        );
+        let latency: Vec<LatencyData> = serde_json::from_str(get(&tracez, &uri).body()).unwrap();
+        assert_eq!(latency.len(), 1);
+
+        let running: Vec<RunningData> =
+            serde_json::from_str(get(&tracez, "/tracez/api/running/failing").body()).unwrap();
+        assert!(running.is_empty());
+    }
+
+    #[test]
+    fn untracked_spans_are_shown_in_the_summary() {
+        let processor = ZPagesSpanProcessor::new().with_max_span_names(1);
+        let tracez = processor.tracez();
+        let provider = SdkTracerProvider::builder()
+            .with_span_processor(processor)
+            .build();
+        let tracer = provider.tracer("test");
+        for name in ["kept", "dropped", "dropped"] {
+            tracer.in_span(name, |_| {});
+        }
+
+        assert_eq!(tracez.untracked_spans(), 2);
+        assert_eq!(tracez.summary().len(), 1);
+        let body = get(&tracez, "/tracez").into_body();
+        assert!(body.contains("<p>2 ended spans of other span names were not kept.</p>"));
+    }
+
+    #[test]
+    fn unknown_paths_are_not_found() {
+        let (_provider, tracez) = tracez_with_spans();
+
+        for uri in [
+            "/other",
+            "/tracez/other",
+            "/tracez/api/unknown",
+            "/tracez/api/latency/9/failing",
+            "/tracez/api/latency/failing",
+        ] {
+            assert_eq!(get(&tracez, uri).status(), StatusCode::NOT_FOUND, "{uri}");
+        }
+    }
+}
@@ -0,0 +1,494 @@
+use crate::logs::{
+    self, AnyValue, LogRecord, Logger, LoggerProvider, NoopLoggerProvider, Severity,
+};
+use crate::{otel_error, otel_info, InstrumentationScope, Key, SpanId, TraceFlags, TraceId};
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::SystemTime;
+
+/// Allows a specific [`LogRecord`] to be used generically by [`BoxedLogRecord`]
+/// instances by mirroring the interface without generic parameters.
+pub trait ObjectSafeLogRecord {
+    /// Sets the `event_name` of a record
+    fn set_event_name(&mut self, name: &'static str);
+
+    /// Sets the `target` of a record.
+    fn set_target(&mut self, target: Cow<'static, str>);
+
+    /// Sets the time when the event occurred measured by the origin clock.
+    fn set_timestamp(&mut self, timestamp: SystemTime);
+
+    /// Sets the observed event timestamp.
+    fn set_observed_timestamp(&mut self, timestamp: SystemTime);
+
+    /// Sets severity as text.
+    fn set_severity_text(&mut self, text: &'static str);
+
+    /// Sets severity as a numeric value.
+    fn set_severity_number(&mut self, number: Severity);
+
+    /// Sets the message body of the log.
+    fn set_body(&mut self, body: AnyValue);
+
+    /// Adds a single attribute.
+    fn add_attribute(&mut self, key: Key, value: AnyValue);
+
+    /// Sets the trace context of the log.
+    fn set_trace_context(
+        &mut self,
+        trace_id: TraceId,
+        span_id: SpanId,
+        trace_flags: Option<TraceFlags>,
+    );
+
+    /// Converts the boxed record into [`Any`] so that the logger which created
+    /// it can recover the concrete record type when emitting.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: LogRecord + 'static> ObjectSafeLogRecord for T {
+    fn set_event_name(&mut self, name: &'static str) {
+        LogRecord::set_event_name(self, name)
+    }
+
+    fn set_target(&mut self, target: Cow<'static, str>) {
+        LogRecord::set_target(self, target)
+    }
+
+    fn set_timestamp(&mut self, timestamp: SystemTime) {
+        LogRecord::set_timestamp(self, timestamp)
+    }
+
+    fn set_observed_timestamp(&mut self, timestamp: SystemTime) {
+        LogRecord::set_observed_timestamp(self, timestamp)
+    }
+
+    fn set_severity_text(&mut self, text: &'static str) {
+        LogRecord::set_severity_text(self, text)
+    }
+
+    fn set_severity_number(&mut self, number: Severity) {
+        LogRecord::set_severity_number(self, number)
+    }
+
+    fn set_body(&mut self, body: AnyValue) {
+        LogRecord::set_body(self, body)
+    }
+
+    fn add_attribute(&mut self, key: Key, value: AnyValue) {
+        LogRecord::add_attribute(self, key, value)
+    }
+
+    fn set_trace_context(
+        &mut self,
+        trace_id: TraceId,
+        span_id: SpanId,
+        trace_flags: Option<TraceFlags>,
+    ) {
+        LogRecord::set_trace_context(self, trace_id, span_id, trace_flags)
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Wraps the [`BoxedLogger`]'s [`LogRecord`] so it can be used generically by
+/// applications without knowing the underlying type.
+pub struct BoxedLogRecord(Box<dyn ObjectSafeLogRecord + Send + Sync>);
+
+impl fmt::Debug for BoxedLogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BoxedLogRecord")
+    }
+}
+
+impl LogRecord for BoxedLogRecord {
+    fn set_event_name(&mut self, name: &'static str) {
+        self.0.set_event_name(name)
+    }
+
+    fn set_target<T>(&mut self, target: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.0.set_target(target.into())
+    }
+
+    fn set_timestamp(&mut self, timestamp: SystemTime) {
+        self.0.set_timestamp(timestamp)
+    }
+
+    fn set_observed_timestamp(&mut self, timestamp: SystemTime) {
+        self.0.set_observed_timestamp(timestamp)
+    }
+
+    fn set_severity_text(&mut self, text: &'static str) {
+        self.0.set_severity_text(text)
+    }
+
+    fn set_severity_number(&mut self, number: Severity) {
+        self.0.set_severity_number(number)
+    }
+
+    fn set_body(&mut self, body: AnyValue) {
+        self.0.set_body(body)
+    }
+
+    fn add_attributes<I, K, V>(&mut self, attributes: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<Key>,
+        V: Into<AnyValue>,
+    {
+        for (key, value) in attributes {
+            self.0.add_attribute(key.into(), value.into());
+        }
+    }
+
+    fn add_attribute<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<Key>,
+        V: Into<AnyValue>,
+    {
+        self.0.add_attribute(key.into(), value.into())
+    }
+
+    fn set_trace_context(
+        &mut self,
+        trace_id: TraceId,
+        span_id: SpanId,
+        trace_flags: Option<TraceFlags>,
+    ) {
+        self.0.set_trace_context(trace_id, span_id, trace_flags)
+    }
+}
+
+/// Allows a specific [`Logger`] to be used generically by [`BoxedLogger`]
+/// instances by mirroring the interface and boxing the return types.
+pub trait ObjectSafeLogger {
+    /// Creates a new boxed log record.
+    fn create_log_record_boxed(&self) -> Box<dyn ObjectSafeLogRecord + Send + Sync>;
+
+    /// Emits a log record previously created by [`create_log_record_boxed`].
+    ///
+    /// Records created by a different logger are dropped.
+    ///
+    /// [`create_log_record_boxed`]: ObjectSafeLogger::create_log_record_boxed
+    fn emit_boxed(&self, record: Box<dyn ObjectSafeLogRecord + Send + Sync>);
+
+    /// Check if the given log is enabled.
+    fn event_enabled(&self, level: Severity, target: &str, name: Option<&str>) -> bool;
+}
+
+impl<R, L> ObjectSafeLogger for L
+where
+    R: LogRecord + Send + Sync + 'static,
+    L: Logger<LogRecord = R>,
+{
+    fn create_log_record_boxed(&self) -> Box<dyn ObjectSafeLogRecord + Send + Sync> {
+        Box::new(self.create_log_record())
+    }
+
+    fn emit_boxed(&self, record: Box<dyn ObjectSafeLogRecord + Send + Sync>) {
+        if let Ok(record) = record.into_any().downcast::<R>() {
+            self.emit(*record)
+        }
+    }
+
+    fn event_enabled(&self, level: Severity, target: &str, name: Option<&str>) -> bool {
+        Logger::event_enabled(self, level, target, name)
+    }
+}
+
+/// Wraps the [`GlobalLoggerProvider`]'s [`Logger`] so it can be used generically by
+/// applications without knowing the underlying type.
+pub struct BoxedLogger(Box<dyn ObjectSafeLogger + Send + Sync>);
+
+impl BoxedLogger {
+    /// Create a `BoxedLogger` from an object-safe logger.
+    pub fn new(logger: Box<dyn ObjectSafeLogger + Send + Sync>) -> Self {
+        BoxedLogger(logger)
+    }
+}
+
+impl fmt::Debug for BoxedLogger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BoxedLogger")
+    }
+}
+
+impl Logger for BoxedLogger {
+    /// Global logger uses `BoxedLogRecord`s so that it can be a global singleton,
+    /// which is not possible if it takes generic type parameters.
+    type LogRecord = BoxedLogRecord;
+
+    fn create_log_record(&self) -> Self::LogRecord {
+        BoxedLogRecord(self.0.create_log_record_boxed())
+    }
+
+    fn emit(&self, record: Self::LogRecord) {
+        self.0.emit_boxed(record.0)
+    }
+
+    fn event_enabled(&self, level: Severity, target: &str, name: Option<&str>) -> bool {
+        self.0.event_enabled(level, target, name)
+    }
+}
+
+/// Allows a specific [`LoggerProvider`] to be used generically by the
+/// [`GlobalLoggerProvider`] by mirroring the interface and boxing the return types.
+pub trait ObjectSafeLoggerProvider {
+    /// Creates a logger with the given instrumentation scope that is a trait
+    /// object through the underlying `LoggerProvider`.
+    fn boxed_logger(&self, scope: InstrumentationScope) -> Box<dyn ObjectSafeLogger + Send + Sync>;
+}
+
+impl<R, L, P> ObjectSafeLoggerProvider for P
+where
+    R: LogRecord + Send + Sync + 'static,
+    L: Logger<LogRecord = R> + Send + Sync + 'static,
+    P: LoggerProvider<Logger = L>,
+{
+    fn boxed_logger(&self, scope: InstrumentationScope) -> Box<dyn ObjectSafeLogger + Send + Sync> {
+        Box::new(self.logger_with_scope(scope))
+    }
+}
+
+/// Represents the globally configured [`LoggerProvider`] instance for this
+/// application. This allows generic logging through the returned
+/// [`BoxedLogger`] instances.
+#[derive(Clone)]
+pub struct GlobalLoggerProvider {
+    provider: Arc<dyn ObjectSafeLoggerProvider + Send + Sync>,
+}
+
+impl fmt::Debug for GlobalLoggerProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GlobalLoggerProvider")
+    }
+}
+
+impl GlobalLoggerProvider {
+    /// Create a new GlobalLoggerProvider instance from a struct that implements `LoggerProvider`.
+    fn new<P, L, R>(provider: P) -> Self
+    where
+        R: LogRecord + Send + Sync + 'static,
+        L: Logger<LogRecord = R> + Send + Sync + 'static,
+        P: LoggerProvider<Logger = L> + Send + Sync + 'static,
+    {
+        GlobalLoggerProvider {
+            provider: Arc::new(provider),
+        }
+    }
+}
+
+impl LoggerProvider for GlobalLoggerProvider {
+    type Logger = BoxedLogger;
+
+    /// Create a logger using the global provider.
+    fn logger_with_scope(&self, scope: InstrumentationScope) -> Self::Logger {
+        BoxedLogger(self.provider.boxed_logger(scope))
+    }
+}
+
+/// The global `Logger` provider singleton.
+static GLOBAL_LOGGER_PROVIDER: OnceLock<RwLock<GlobalLoggerProvider>> = OnceLock::new();
+
+#[inline]
+fn global_logger_provider() -> &'static RwLock<GlobalLoggerProvider> {
+    GLOBAL_LOGGER_PROVIDER
+        .get_or_init(|| RwLock::new(GlobalLoggerProvider::new(NoopLoggerProvider::new())))
+}
+
+/// Returns an instance of the currently configured global [`LoggerProvider`] through
+/// [`GlobalLoggerProvider`].
+pub fn logger_provider() -> GlobalLoggerProvider {
+    // Try to get the global logger provider. If the RwLock is poisoned, we'll log an error and return a NoopLoggerProvider.
+    let global_provider = global_logger_provider().read();
+    if let Ok(provider) = global_provider {
+        provider.clone()
+    } else {
+        otel_error!(name: "LoggerProvider.GlobalGetFailed", message = "Getting global logger provider failed. Loggers created using global::logger() or global::logger_with_scope() will not function. Report this issue in OpenTelemetry repo.");
+        GlobalLoggerProvider::new(NoopLoggerProvider::new())
+    }
+}
+
+/// Creates a named instance of [`Logger`] via the configured [`GlobalLoggerProvider`].
+///
+/// This is a more convenient way of expressing `global::logger_provider().logger(name)`.
+///
+/// **NOTE:** Calls to [`logger()`] return a [`Logger`] backed by the global [`LoggerProvider`] configured during the method invocation.
+/// If the global [`LoggerProvider`] is changed after getting [`Logger`] instances from these calls, the [`Logger`] instances returned will not reflect the change.
+pub fn logger(name: impl Into<Cow<'static, str>>) -> BoxedLogger {
+    logger_provider().logger(name)
+}
+
+/// Creates a [`Logger`] with the given instrumentation scope
+/// via the configured [`GlobalLoggerProvider`].
+///
+/// This is a simpler alternative to `global::logger_provider().logger_with_scope(...)`
+///
+/// # Example
+///
+/// ```
+/// use opentelemetry::global::logger_with_scope;
+/// use opentelemetry::InstrumentationScope;
+/// use opentelemetry::KeyValue;
+///
+/// let scope = InstrumentationScope::builder("io.opentelemetry")
+///     .with_version("0.17")
+///     .with_schema_url("https://opentelemetry.io/schemas/1.2.0")
+///     .with_attributes(vec![(KeyValue::new("key", "value"))])
+///     .build();
+///
+/// let logger = logger_with_scope(scope);
+/// ```
+pub fn logger_with_scope(scope: InstrumentationScope) -> BoxedLogger {
+    logger_provider().logger_with_scope(scope)
+}
+
+/// Sets the given [`LoggerProvider`] instance as the current global provider.
+///
+/// Libraries should NOT call this function. It is intended for applications/executables.
+///
+/// **NOTE:** This function should be called before getting [`Logger`] instances via [`logger()`] or [`logger_with_scope()`]. Otherwise, you could get no-op [`Logger`] instances.
+pub fn set_logger_provider<P, L, R>(new_provider: P)
+where
+    R: logs::LogRecord + Send + Sync + 'static,
+    L: logs::Logger<LogRecord = R> + Send + Sync + 'static,
+    P: logs::LoggerProvider<Logger = L> + Send + Sync + 'static,
+{
+    let mut global_provider = global_logger_provider().write();
+    if let Ok(ref mut provider) = global_provider {
+        **provider = GlobalLoggerProvider::new(new_provider);
+        otel_info!(name: "LoggerProvider.GlobalSet", message = "Global logger provider is set. Loggers can now be created using global::logger() or global::logger_with_scope().");
+    } else {
+        otel_error!(name: "LoggerProvider.GlobalSetFailed", message = "Setting global logger provider failed. Loggers created using global::logger() or global::logger_with_scope() will not function. Report this issue in OpenTelemetry repo.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{logger, GlobalLoggerProvider};
+    use crate::logs::{AnyValue, LogRecord, Logger, LoggerProvider, Severity};
+    use crate::{InstrumentationScope, Key};
+    use std::borrow::Cow;
+    use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
+
+    #[derive(Debug, Default)]
+    struct TestRecord {
+        body: Option<AnyValue>,
+        attributes: Vec<(Key, AnyValue)>,
+    }
+
+    impl LogRecord for TestRecord {
+        fn set_event_name(&mut self, _name: &'static str) {}
+        fn set_target<T>(&mut self, _target: T)
+        where
+            T: Into<Cow<'static, str>>,
+        {
+        }
+        fn set_timestamp(&mut self, _timestamp: SystemTime) {}
+        fn set_observed_timestamp(&mut self, _timestamp: SystemTime) {}
+        fn set_severity_text(&mut self, _text: &'static str) {}
+        fn set_severity_number(&mut self, _number: Severity) {}
+        fn set_body(&mut self, body: AnyValue) {
+            self.body = Some(body);
+        }
+        fn add_attributes<I, K, V>(&mut self, attributes: I)
+        where
+            I: IntoIterator<Item = (K, V)>,
+            K: Into<Key>,
+            V: Into<AnyValue>,
+        {
+            for (k, v) in attributes {
+                self.add_attribute(k, v);
+            }
+        }
+        fn add_attribute<K, V>(&mut self, key: K, value: V)
+        where
+            K: Into<Key>,
+            V: Into<AnyValue>,
+        {
+            self.attributes.push((key.into(), value.into()));
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct TestLogger {
+        scope: InstrumentationScope,
+        emitted: Arc<Mutex<Vec<(String, TestRecord)>>>,
+    }
+
+    impl Logger for TestLogger {
+        type LogRecord = TestRecord;
+
+        fn create_log_record(&self) -> Self::LogRecord {
+            TestRecord::default()
+        }
+
+        fn emit(&self, record: Self::LogRecord) {
+            self.emitted
+                .lock()
+                .unwrap()
+                .push((self.scope.name().to_string(), record));
+        }
+
+        fn event_enabled(&self, level: Severity, _target: &str, _name: Option<&str>) -> bool {
+            level >= Severity::Info
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct TestLoggerProvider {
+        emitted: Arc<Mutex<Vec<(String, TestRecord)>>>,
+    }
+
+    impl LoggerProvider for TestLoggerProvider {
+        type Logger = TestLogger;
+
+        fn logger_with_scope(&self, scope: InstrumentationScope) -> Self::Logger {
+            TestLogger {
+                scope,
+                emitted: self.emitted.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn boxed_logger_emits_through_underlying_logger() {
+        let provider = TestLoggerProvider::default();
+        let emitted = provider.emitted.clone();
+        let global = GlobalLoggerProvider::new(provider);
+
+        let logger = global.logger("test-logger");
+        assert!(logger.event_enabled(Severity::Error, "target", None));
+        assert!(!logger.event_enabled(Severity::Debug, "target", None));
+
+        let mut record = logger.create_log_record();
+        record.set_body("hello".into());
+        record.add_attributes([("key1", "value1"), ("key2", "value2")]);
+        logger.emit(record);
+
+        let emitted = emitted.lock().unwrap();
+        assert_eq!(emitted.len(), 1);
+        let (scope_name, record) = &emitted[0];
+        assert_eq!(scope_name, "test-logger");
+        assert_eq!(record.body, Some(AnyValue::from("hello")));
+        assert_eq!(record.attributes.len(), 2);
+        assert_eq!(record.attributes[0].0, Key::new("key1"));
+    }
+
+    #[test]
+    fn default_global_logger_is_noop() {
+        let logger = logger("noop");
+        assert!(!logger.event_enabled(Severity::Fatal, "target", None));
+        let mut record = logger.create_log_record();
+        record.set_body("ignored".into());
+        logger.emit(record);
+    }
+}
@@ -129,8 +129,39 @@
 //!
 //! [`MeterProvider`]: crate::metrics::MeterProvider
 //! [`set_meter_provider`]: crate::global::set_meter_provider
+//!
+//! ## Global Logs API
+//!
+//! The global logs API **provides log appenders and libraries access to the
+//! configured [`LoggerProvider`] instance from anywhere in the codebase**,
+//! without requiring the SDK's logger provider to be passed around. Like the
+//! rest of the Logs Bridge API, it is intended for authors of log appenders
+//! rather than for application code emitting logs directly.
+//!
+//! ```
+//! # #[cfg(feature="logs")]
+//! # {
+//! use opentelemetry::logs::{Logger, LogRecord, Severity};
+//! use opentelemetry::global;
+//!
+//! fn emit_from_appender() {
+//!     // Until the application calls `global::set_logger_provider`, this is a no-op logger.
+//!     let logger = global::logger("my-appender");
+//!     if logger.event_enabled(Severity::Info, "my-target", None) {
+//!         let mut record = logger.create_log_record();
+//!         record.set_severity_number(Severity::Info);
+//!         record.set_body("hello from the global logger".into());
+//!         logger.emit(record);
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! [`LoggerProvider`]: crate::logs::LoggerProvider
 
 mod internal_logging;
+#[cfg(feature = "logs")]
+mod logs;
 #[cfg(feature = "metrics")]
 mod metrics;
 #[cfg(feature = "trace")]
@@ -138,6 +169,9 @@ mod propagation;
 #[cfg(feature = "trace")]
 mod trace;
 
+#[cfg(feature = "logs")]
+#[cfg_attr(docsrs, doc(cfg(feature = "logs")))]
+pub use logs::*;
 #[cfg(feature = "metrics")]
 #[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
 pub use metrics::*;
@@ -263,6 +263,10 @@ impl trace::Tracer for BoxedTracer {
     fn build_with_context(&self, builder: trace::SpanBuilder, parent_cx: &Context) -> Self::Span {
         BoxedSpan(self.0.build_with_context_boxed(builder, parent_cx))
     }
+
+    fn enabled(&self) -> bool {
+        self.0.enabled()
+    }
 }
 
 /// Allows a specific [`Tracer`] to be used generically by [`BoxedTracer`]
@@ -277,6 +281,11 @@ pub trait ObjectSafeTracer {
         builder: trace::SpanBuilder,
         parent_cx: &Context,
     ) -> Box<dyn ObjectSafeSpan + Send + Sync>;
+
+    /// Returns whether the spans started by the tracer may be recorded.
+    fn enabled(&self) -> bool {
+        true
+    }
 }
 
 impl<S, T> ObjectSafeTracer for T
@@ -293,6 +302,10 @@ where
     ) -> Box<dyn ObjectSafeSpan + Send + Sync> {
         Box::new(self.build_with_context(builder, parent_cx))
     }
+
+    fn enabled(&self) -> bool {
+        trace::Tracer::enabled(self)
+    }
 }
 
 /// Allows a specific [`TracerProvider`] to be used generically by the
@@ -4,7 +4,9 @@ use std::sync::Arc;
 
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 use super::BoundSyncInstrument;
-use super::SyncInstrument;
+use super::{
+    instrument_id, AsyncInstrument, ObservableInstrument, ObservableInstrumentOf, SyncInstrument,
+};
 
 /// An instrument that records increasing values.
 ///
@@ -35,6 +37,18 @@ impl<T> Counter<T> {
         self.0.measure(value, attributes)
     }
 
+    /// Returns whether the measurements of the counter are recorded.
+    ///
+    /// It returns `false` when the SDK ignores them, e.g. when no reader is
+    /// registered or the aggregation of every stream of the counter is
+    /// [`Drop`], so that the computation of the value or of the attributes of
+    /// a measurement can be skipped.
+    ///
+    /// [`Drop`]: https://opentelemetry.io/docs/specs/otel/metrics/sdk/#drop-aggregation
+    pub fn enabled(&self) -> bool {
+        self.0.enabled()
+    }
+
     /// Binds this counter to a fixed set of attributes.
     ///
     /// Corresponds to the `Bind` capability in the OpenTelemetry spec (status:
@@ -49,16 +63,39 @@ impl<T> Counter<T> {
 #[derive(Clone)]
 #[non_exhaustive]
 pub struct ObservableCounter<T> {
-    _marker: std::marker::PhantomData<T>,
+    inner: Option<Arc<dyn AsyncInstrument<T>>>,
 }
 
 impl<T> ObservableCounter<T> {
     /// Create a new observable counter.
+    ///
+    /// Its measurements are only observed by the callbacks it was built with,
+    /// callbacks registered with [`Meter::register_callback`] can't observe it.
+    ///
+    /// [`Meter::register_callback`]: crate::metrics::Meter::register_callback
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        ObservableCounter {
-            _marker: std::marker::PhantomData,
-        }
+        ObservableCounter { inner: None }
+    }
+
+    /// Create a new observable counter recording the measurements observed by the
+    /// callbacks registered with [`Meter::register_callback`] to `inner`.
+    ///
+    /// [`Meter::register_callback`]: crate::metrics::Meter::register_callback
+    pub fn with_instrument(inner: Arc<dyn AsyncInstrument<T>>) -> Self {
+        ObservableCounter { inner: Some(inner) }
+    }
+}
+
+impl<T> ObservableInstrument for ObservableCounter<T> {
+    fn instrument_id(&self) -> Option<usize> {
+        self.inner.as_deref().map(instrument_id)
+    }
+}
+
+impl<T> ObservableInstrumentOf<T> for ObservableCounter<T> {
+    fn async_instrument(&self) -> Option<&dyn AsyncInstrument<T>> {
+        self.inner.as_deref()
     }
 }
 
@@ -4,7 +4,9 @@ use std::sync::Arc;
 
 #[cfg(feature = "experimental_metrics_bound_instruments")]
 use super::BoundSyncInstrument;
-use super::SyncInstrument;
+use super::{
+    instrument_id, AsyncInstrument, ObservableInstrument, ObservableInstrumentOf, SyncInstrument,
+};
 
 /// An instrument that records independent values
 ///
@@ -35,6 +37,18 @@ impl<T> Gauge<T> {
         self.0.measure(value, attributes)
     }
 
+    /// Returns whether the measurements of the gauge are recorded.
+    ///
+    /// It returns `false` when the SDK ignores them, e.g. when no reader is
+    /// registered or the aggregation of every stream of the gauge is
+    /// [`Drop`], so that the computation of the value or of the attributes of
+    /// a measurement can be skipped.
+    ///
+    /// [`Drop`]: https://opentelemetry.io/docs/specs/otel/metrics/sdk/#drop-aggregation
+    pub fn enabled(&self) -> bool {
+        self.0.enabled()
+    }
+
     /// Binds this gauge to a fixed set of attributes.
     ///
     /// Corresponds to the `Bind` capability in the OpenTelemetry spec (status:
@@ -78,7 +92,7 @@ impl<T> BoundGauge<T> {
 #[derive(Clone)]
 #[non_exhaustive]
 pub struct ObservableGauge<T> {
-    _marker: std::marker::PhantomData<T>,
+    inner: Option<Arc<dyn AsyncInstrument<T>>>,
 }
 
 impl<T> fmt::Debug for ObservableGauge<T>
@@ -95,10 +109,33 @@ where
 
 impl<T> ObservableGauge<T> {
     /// Create a new gauge
+    ///
+    /// Its measurements are only observed by the callbacks it was built with,
+    /// callbacks registered with [`Meter::register_callback`] can't observe it.
+    ///
+    /// [`Meter::register_callback`]: crate::metrics::Meter::register_callback
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        ObservableGauge {
-            _marker: std::marker::PhantomData,
-        }
+        ObservableGauge { inner: None }
+    }
+
+    /// Create a new observable gauge recording the measurements observed by the
+    /// callbacks registered with [`Meter::register_callback`] to `inner`.
+    ///
+    /// [`Meter::register_callback`]: crate::metrics::Meter::register_callback
+    pub fn with_instrument(inner: Arc<dyn AsyncInstrument<T>>) -> Self {
+        ObservableGauge { inner: Some(inner) }
+    }
+}
+
+impl<T> ObservableInstrument for ObservableGauge<T> {
+    fn instrument_id(&self) -> Option<usize> {
+        self.inner.as_deref().map(instrument_id)
+    }
+}
+
+impl<T> ObservableInstrumentOf<T> for ObservableGauge<T> {
+    fn async_instrument(&self) -> Option<&dyn AsyncInstrument<T>> {
+        self.inner.as_deref()
     }
 }
@@ -35,6 +35,18 @@ impl<T> Histogram<T> {
         self.0.measure(value, attributes)
     }
 
+    /// Returns whether the measurements of the histogram are recorded.
+    ///
+    /// It returns `false` when the SDK ignores them, e.g. when no reader is
+    /// registered or the aggregation of every stream of the histogram is
+    /// [`Drop`], so that the computation of the value or of the attributes of
+    /// a measurement can be skipped.
+    ///
+    /// [`Drop`]: https://opentelemetry.io/docs/specs/otel/metrics/sdk/#drop-aggregation
+    pub fn enabled(&self) -> bool {
+        self.0.enabled()
+    }
+
     /// Binds this histogram to a fixed set of attributes.
     ///
     /// Corresponds to the `Bind` capability in the OpenTelemetry spec (status:
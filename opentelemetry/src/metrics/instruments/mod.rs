@@ -29,6 +29,13 @@ pub trait SyncInstrument<T>: Send + Sync {
     /// Records a measurement synchronously.
     fn measure(&self, measurement: T, attributes: &[KeyValue]);
 
+    /// Returns whether the measurements are recorded.
+    ///
+    /// The default implementation returns `true`.
+    fn enabled(&self) -> bool {
+        true
+    }
+
     /// Binds this instrument to a fixed set of attributes, returning a handle
     /// that records measurements without per-call attribute lookup.
     ///
@@ -264,6 +271,103 @@ impl<T> fmt::Debug for HistogramBuilder<'_, T> {
 /// The function needs to complete in a finite amount of time.
 pub type Callback<T> = Box<dyn Fn(&dyn AsyncInstrument<T>) + Send + Sync>;
 
+/// An asynchronous instrument that a callback registered with
+/// [`Meter::register_callback`] can observe: an [`ObservableCounter`], an
+/// [`ObservableUpDownCounter`] or an [`ObservableGauge`].
+pub trait ObservableInstrument {
+    /// Identifies the SDK instrument recording the measurements, `None` if
+    /// the measurements are ignored.
+    #[doc(hidden)]
+    fn instrument_id(&self) -> Option<usize>;
+}
+
+/// An [`ObservableInstrument`] of measurements of type `T`.
+pub trait ObservableInstrumentOf<T>: ObservableInstrument {
+    /// Returns the SDK instrument recording the measurements, `None` if the
+    /// measurements are ignored.
+    #[doc(hidden)]
+    fn async_instrument(&self) -> Option<&dyn AsyncInstrument<T>>;
+}
+
+pub(crate) fn instrument_id<T>(instrument: &dyn AsyncInstrument<T>) -> usize {
+    instrument as *const dyn AsyncInstrument<T> as *const () as usize
+}
+
+/// Records the measurements made by a callback registered with
+/// [`Meter::register_callback`].
+pub struct Observer<'a> {
+    instruments: &'a [usize],
+}
+
+impl<'a> Observer<'a> {
+    pub(crate) fn new(instruments: &'a [usize]) -> Self {
+        Observer { instruments }
+    }
+
+    /// Records a measurement of `instrument`.
+    ///
+    /// Only the instruments the callback was registered with can be observed,
+    /// the measurements of other instruments are ignored.
+    pub fn observe<T>(
+        &self,
+        instrument: &impl ObservableInstrumentOf<T>,
+        measurement: T,
+        attributes: &[KeyValue],
+    ) {
+        let Some(async_instrument) = instrument.async_instrument() else {
+            return;
+        };
+        if self.instruments.contains(&instrument_id(async_instrument)) {
+            async_instrument.observe(measurement, attributes);
+        } else {
+            crate::otel_debug!(
+                name: "Observer.UnregisteredInstrument",
+                message = "The measurement is ignored because the callback was not registered with the instrument."
+            );
+        }
+    }
+}
+
+impl fmt::Debug for Observer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Observer")
+            .field("instruments", &self.instruments.len())
+            .finish()
+    }
+}
+
+/// An SDK implemented handle of a callback registered with
+/// [`Meter::register_callback`].
+pub trait CallbackRegistration: Send + Sync {
+    /// Unregisters the callback, which isn't invoked by the following
+    /// collections.
+    fn unregister(&self);
+}
+
+/// The handle of a callback registered with [`Meter::register_callback`].
+///
+/// Dropping the handle doesn't unregister the callback.
+pub struct Registration(Box<dyn CallbackRegistration>);
+
+impl Registration {
+    /// Create a handle unregistering the callback with `registration`.
+    pub fn new(registration: impl CallbackRegistration + 'static) -> Self {
+        Registration(Box::new(registration))
+    }
+
+    /// Unregisters the callback, which isn't invoked by the following
+    /// collections.
+    pub fn unregister(self) {
+        self.0.unregister()
+    }
+}
+
+impl fmt::Debug for Registration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Registration")
+    }
+}
+
 /// Configuration for building an async instrument.
 #[must_use = "Callbacks will not be invoked unless you call .build() on this async instrument builder."]
 #[non_exhaustive] // We expect to add more configuration fields in the future
@@ -4,7 +4,8 @@ use std::sync::Arc;
 
 use crate::metrics::{
     AsyncInstrumentBuilder, Gauge, InstrumentBuilder, InstrumentProvider, ObservableCounter,
-    ObservableGauge, ObservableUpDownCounter, UpDownCounter,
+    ObservableGauge, ObservableInstrument, ObservableUpDownCounter, Observer, Registration,
+    UpDownCounter,
 };
 use crate::InstrumentationScope;
 
@@ -472,6 +473,57 @@ impl Meter {
     ) -> HistogramBuilder<'_, Histogram<u64>> {
         HistogramBuilder::new(self, name.into())
     }
+
+    /// Registers a callback observing the measurements of several observable
+    /// instruments at each collection.
+    ///
+    /// The callback can only observe the `instruments` it is registered with,
+    /// through the [`Observer`] it is passed. It is invoked until it is
+    /// unregistered with the returned [`Registration`].
+    ///
+    /// The function needs to complete in a finite amount of time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use opentelemetry::{global, KeyValue};
+    ///
+    /// let meter = global::meter("my-meter");
+    /// let connections = meter.u64_observable_gauge("db.client.connections").build();
+    /// let pending = meter.u64_observable_gauge("db.client.pending_requests").build();
+    ///
+    /// let registration = meter.register_callback(&[&connections, &pending], {
+    ///     let (connections, pending) = (connections.clone(), pending.clone());
+    ///     move |observer| {
+    ///         // A single read of the pool statistics feeds both instruments.
+    ///         let (used, idle, waiting) = (3, 7, 1);
+    ///         observer.observe(&connections, used, &[KeyValue::new("state", "used")]);
+    ///         observer.observe(&connections, idle, &[KeyValue::new("state", "idle")]);
+    ///         observer.observe(&pending, waiting, &[]);
+    ///     }
+    /// });
+    ///
+    /// // Stop observing the instruments.
+    /// registration.unregister();
+    /// ```
+    pub fn register_callback<F>(
+        &self,
+        instruments: &[&dyn ObservableInstrument],
+        callback: F,
+    ) -> Registration
+    where
+        F: Fn(&Observer<'_>) + Send + Sync + 'static,
+    {
+        let instruments: Vec<usize> = instruments
+            .iter()
+            .filter_map(|instrument| instrument.instrument_id())
+            .collect();
+        if instruments.is_empty() {
+            return Registration::new(super::noop::NoopRegistration::new());
+        }
+        self.instrument_provider
+            .register_callback(Box::new(move || callback(&Observer::new(&instruments))))
+    }
 }
 
 impl fmt::Debug for Meter {
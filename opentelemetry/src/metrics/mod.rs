@@ -15,7 +15,8 @@ pub use instruments::{
     gauge::{Gauge, ObservableGauge},
     histogram::Histogram,
     up_down_counter::{ObservableUpDownCounter, UpDownCounter},
-    AsyncInstrument, AsyncInstrumentBuilder, Callback, HistogramBuilder, InstrumentBuilder,
+    AsyncInstrument, AsyncInstrumentBuilder, Callback, CallbackRegistration, HistogramBuilder,
+    InstrumentBuilder, ObservableInstrument, ObservableInstrumentOf, Observer, Registration,
     SyncInstrument,
 };
 pub use meter::{Meter, MeterProvider};
@@ -129,4 +130,10 @@ pub trait InstrumentProvider {
     fn u64_histogram(&self, _builder: HistogramBuilder<'_, Histogram<u64>>) -> Histogram<u64> {
         Histogram::new(Arc::new(noop::NoopSyncInstrument::new()))
     }
+
+    /// Registers a callback invoked at each collection, observing the
+    /// measurements of several observable instruments.
+    fn register_callback(&self, _callback: Box<dyn Fn() + Send + Sync>) -> Registration {
+        Registration::new(noop::NoopRegistration::new())
+    }
 }
@@ -4,7 +4,7 @@
 //! has been set. It is expected to have minimal resource utilization and
 //! runtime impact.
 use crate::{
-    metrics::{InstrumentProvider, Meter, MeterProvider},
+    metrics::{CallbackRegistration, InstrumentProvider, Meter, MeterProvider},
     otel_debug, KeyValue,
 };
 use std::sync::Arc;
@@ -48,6 +48,23 @@ impl NoopMeter {
 
 impl InstrumentProvider for NoopMeter {}
 
+/// A no-op callback registration
+#[derive(Debug, Default)]
+pub(crate) struct NoopRegistration {
+    _private: (),
+}
+
+impl NoopRegistration {
+    /// Create a new no-op callback registration
+    pub(crate) fn new() -> Self {
+        NoopRegistration { _private: () }
+    }
+}
+
+impl CallbackRegistration for NoopRegistration {
+    fn unregister(&self) {}
+}
+
 /// A no-op sync instrument
 #[derive(Debug, Default)]
 pub(crate) struct NoopSyncInstrument {
@@ -66,6 +83,10 @@ impl<T: Send + Sync + 'static> SyncInstrument<T> for NoopSyncInstrument {
         // Ignored
     }
 
+    fn enabled(&self) -> bool {
+        false
+    }
+
     #[cfg(feature = "experimental_metrics_bound_instruments")]
     fn bind(&self, _attributes: &[KeyValue]) -> Box<dyn BoundSyncInstrument<T> + Send + Sync> {
         Box::new(NoopBoundSyncInstrument::new())
@@ -129,6 +129,10 @@ impl trace::Tracer for NoopTracer {
             span_context: parent_cx.span().span_context().clone(),
         }
     }
+
+    fn enabled(&self) -> bool {
+        false
+    }
 }
 
 /// A no-op instance of an [`TextMapPropagator`].
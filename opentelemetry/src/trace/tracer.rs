@@ -174,6 +174,19 @@ pub trait Tracer {
     /// Start a span from a [`SpanBuilder`] with a parent context.
     fn build_with_context(&self, builder: SpanBuilder, parent_cx: &Context) -> Self::Span;
 
+    /// Returns whether the spans started by this tracer may be recorded.
+    ///
+    /// It returns `false` when every span started by the tracer is known to be
+    /// non-recording, e.g. when the SDK has no span processor or its sampler
+    /// drops every span, so that the computation of the span attributes can be
+    /// skipped. The answer may change over time, so it should be checked every
+    /// time a span is about to be started.
+    ///
+    /// The default implementation returns `true`.
+    fn enabled(&self) -> bool {
+        true
+    }
+
     /// Start a new span and execute the given closure with reference to the context
     /// in which the span is active.
     ///
@@ -34,6 +34,11 @@ if rustup component add clippy && \
 
   cargo_feature opentelemetry-jaeger-propagator "default"
 
+  cargo_feature opentelemetry-autoconfigure "default"
+  cargo_feature opentelemetry-autoconfigure "default,otlp-grpc"
+
+  cargo_feature opentelemetry-zpages "default"
+
   cargo_feature opentelemetry-proto "default"
   cargo_feature opentelemetry-proto "full"
   cargo_feature opentelemetry-proto "gen-tonic,trace"
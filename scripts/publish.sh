@@ -13,6 +13,8 @@ packages=(
     "opentelemetry-stdout"
     "opentelemetry-appender-log"
     "opentelemetry-prometheus"
+    "opentelemetry-autoconfigure"
+    "opentelemetry-zpages"
 
     # Add more packages as needed, in the right order. A package should only be published after all its dependencies (including dev-dependencies) have been published.
 )